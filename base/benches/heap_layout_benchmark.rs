@@ -0,0 +1,75 @@
+use std::{collections::BinaryHeap, hint::black_box};
+
+use base::{BHeap, Heap, MaxHeap, generate};
+
+use criterion::{Criterion, criterion_group, criterion_main};
+
+/// 堆中的元素数量，u64 共 32 MiB，远大于 CPU 缓存
+const N: usize = 4_000_000;
+/// 每次迭代替换堆顶的次数
+const OPS: usize = 10_000;
+
+/// 稳定状态下反复取出堆顶并插入新元素，每次操作都从堆顶堆化到堆底附近
+fn replace_top<H: Heap<u64>>(heap: &mut H, keys: &[u64]) -> u64 {
+    let mut sum = 0;
+    for &key in keys {
+        sum ^= heap.pop().unwrap_or_default();
+        heap.push(key);
+    }
+
+    sum
+}
+
+fn replace_top_benchmark(c: &mut Criterion) {
+    let init = generate::uniform(N, 5);
+    let keys = generate::uniform(OPS, 6);
+
+    let mut array = MaxHeap::from(init.clone());
+    c.bench_function("数组堆替换堆顶", |b| {
+        b.iter(|| replace_top(&mut array, black_box(&keys)))
+    });
+
+    let mut paged = BHeap::<_, 9>::from(init.clone());
+    c.bench_function("B-heap 替换堆顶（每页 511 个结点）", |b| {
+        b.iter(|| replace_top(&mut paged, black_box(&keys)))
+    });
+
+    let mut small_paged = BHeap::<_, 3>::from(init.clone());
+    c.bench_function("B-heap 替换堆顶（每页 7 个结点）", |b| {
+        b.iter(|| replace_top(&mut small_paged, black_box(&keys)))
+    });
+
+    let mut std_heap = BinaryHeap::from(init);
+    c.bench_function("标准库 BinaryHeap 替换堆顶", |b| {
+        b.iter(|| {
+            let mut sum = 0;
+            for &key in black_box(&keys) {
+                sum ^= std_heap.pop().unwrap_or_default();
+                std_heap.push(key);
+            }
+            sum
+        })
+    });
+}
+
+fn build_benchmark(c: &mut Criterion) {
+    let init = generate::uniform(N, 5);
+
+    c.bench_function("数组堆建堆", |b| {
+        b.iter_batched(
+            || init.clone(),
+            MaxHeap::from_vec,
+            criterion::BatchSize::LargeInput,
+        )
+    });
+    c.bench_function("B-heap 建堆", |b| {
+        b.iter_batched(
+            || init.clone(),
+            BHeap::<_, 9>::from_vec,
+            criterion::BatchSize::LargeInput,
+        )
+    });
+}
+
+criterion_group!(benches, replace_top_benchmark, build_benchmark);
+criterion_main!(benches);
@@ -0,0 +1,75 @@
+use std::hint::black_box;
+
+use base::LinkedList;
+
+use criterion::{Criterion, criterion_group, criterion_main};
+
+/// 队列中保持的元素数量
+const LEN: usize = 64;
+/// 每轮入队、出队的次数
+const ROUNDS: usize = 10_000;
+
+/// 保持队列长度不变，反复从尾部插入、从头部弹出
+fn churn(list: &mut LinkedList<u64>) -> u64 {
+    let mut sum = 0;
+
+    for i in 0..ROUNDS as u64 {
+        list.push_back(black_box(i));
+        sum += list.pop_front().unwrap_or_default();
+    }
+
+    sum
+}
+
+fn push_pop_benchmark(c: &mut Criterion) {
+    let mut plain = (0..LEN as u64).collect::<LinkedList<_>>();
+    c.bench_function("链表入队出队（不使用结点池）", |b| {
+        b.iter(|| churn(&mut plain))
+    });
+
+    let mut pooled = LinkedList::with_pool_capacity(LEN);
+    pooled.extend(0..LEN as u64);
+    c.bench_function("链表入队出队（使用结点池）", |b| {
+        b.iter(|| churn(&mut pooled))
+    });
+}
+
+/// 反复填满再清空，每轮都要重新分配全部结点
+fn fill_clear_benchmark(c: &mut Criterion) {
+    let mut plain = LinkedList::new();
+    c.bench_function("链表填满再清空（不使用结点池）", |b| {
+        b.iter(|| {
+            plain.extend(0..ROUNDS as u64);
+            plain.clear();
+        })
+    });
+
+    let mut pooled = LinkedList::with_pool_capacity(ROUNDS);
+    c.bench_function("链表填满再清空（使用结点池）", |b| {
+        b.iter(|| {
+            pooled.extend(0..ROUNDS as u64);
+            pooled.clear();
+        })
+    });
+}
+
+/// 反复把同一个链表复制到已有的链表中
+fn clone_benchmark(c: &mut Criterion) {
+    let source = (0..ROUNDS as u64).collect::<LinkedList<_>>();
+
+    let mut target = source.clone();
+    c.bench_function("链表复制（clone）", |b| {
+        b.iter(|| target = black_box(&source).clone())
+    });
+    c.bench_function("链表复制（clone_from）", |b| {
+        b.iter(|| target.clone_from(black_box(&source)))
+    });
+}
+
+criterion_group!(
+    benches,
+    push_pop_benchmark,
+    fill_clear_benchmark,
+    clone_benchmark
+);
+criterion_main!(benches);
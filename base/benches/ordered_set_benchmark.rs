@@ -0,0 +1,115 @@
+use std::{collections::BTreeSet, hint::black_box};
+
+use base::{
+    AvlTree, LlrbTree, OrderedSet, ScapegoatTree, SkipListMap, WeightBalancedTree, generate,
+};
+
+use criterion::{Criterion, criterion_group, criterion_main};
+
+const N: usize = 10_000;
+
+/// 随机键和有序键，有序插入是不平衡的二叉搜索树的最坏情况
+fn key_sets() -> [(&'static str, Vec<u64>); 2] {
+    [
+        ("随机键", generate::uniform(N, 1)),
+        ("有序键", (0..N as u64).collect()),
+    ]
+}
+
+/// 对一种有序集合依次测试插入、查找和删除
+fn bench_set<S: OrderedSet<u64> + Default>(c: &mut Criterion, name: &str) {
+    for (keys_name, keys) in key_sets() {
+        let build = || {
+            let mut set = S::default();
+            for &key in &keys {
+                set.insert(key);
+            }
+            set
+        };
+
+        c.bench_function(&format!("{name}插入（{keys_name}）"), |b| b.iter(build));
+
+        let set = build();
+        c.bench_function(&format!("{name}查找（{keys_name}）"), |b| {
+            b.iter(|| {
+                keys.iter()
+                    .filter(|key| set.contains(black_box(key)))
+                    .count()
+            })
+        });
+
+        c.bench_function(&format!("{name}删除（{keys_name}）"), |b| {
+            b.iter_batched(
+                build,
+                |mut set| {
+                    for key in &keys {
+                        set.remove(key);
+                    }
+                    set
+                },
+                criterion::BatchSize::LargeInput,
+            )
+        });
+    }
+}
+
+fn avl_benchmark(c: &mut Criterion) {
+    bench_set::<AvlTree<u64>>(c, "AVL 树");
+}
+
+fn weight_balanced_benchmark(c: &mut Criterion) {
+    bench_set::<WeightBalancedTree<u64>>(c, "重量平衡树");
+}
+
+fn scapegoat_benchmark(c: &mut Criterion) {
+    bench_set::<ScapegoatTree<u64>>(c, "替罪羊树");
+}
+
+fn llrb_benchmark(c: &mut Criterion) {
+    bench_set::<LlrbTree<u64>>(c, "左偏红黑树");
+}
+
+fn skip_list_benchmark(c: &mut Criterion) {
+    bench_set::<SkipListMap<u64, ()>>(c, "跳表");
+}
+
+fn btree_set_benchmark(c: &mut Criterion) {
+    bench_set::<BTreeSet<u64>>(c, "标准库 BTreeSet ");
+}
+
+/// 按升序遍历全部元素，AVL 树和替罪羊树没有迭代器，不参与比较
+fn iteration_benchmark(c: &mut Criterion) {
+    let keys = generate::uniform(N, 1);
+    let weight_balanced = keys.iter().copied().collect::<WeightBalancedTree<_>>();
+    let llrb = keys.iter().copied().collect::<LlrbTree<_>>();
+    let skip_list = keys
+        .iter()
+        .map(|&key| (key, ()))
+        .collect::<SkipListMap<_, _>>();
+    let btree_set = keys.iter().copied().collect::<BTreeSet<_>>();
+
+    c.bench_function("重量平衡树遍历", |b| {
+        b.iter(|| weight_balanced.iter().sum::<u64>())
+    });
+    c.bench_function("左偏红黑树遍历", |b| {
+        b.iter(|| llrb.iter().sum::<u64>())
+    });
+    c.bench_function("跳表遍历", |b| {
+        b.iter(|| skip_list.iter().map(|(key, _)| key).sum::<u64>())
+    });
+    c.bench_function("标准库 BTreeSet 遍历", |b| {
+        b.iter(|| btree_set.iter().sum::<u64>())
+    });
+}
+
+criterion_group!(
+    benches,
+    avl_benchmark,
+    weight_balanced_benchmark,
+    scapegoat_benchmark,
+    llrb_benchmark,
+    skip_list_benchmark,
+    btree_set_benchmark,
+    iteration_benchmark
+);
+criterion_main!(benches);
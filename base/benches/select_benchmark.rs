@@ -0,0 +1,53 @@
+use std::hint::black_box;
+
+use base::{generate, select};
+
+use criterion::{BatchSize, Criterion, criterion_group, criterion_main};
+
+fn new_vec() -> Vec<u64> {
+    generate::uniform(10_000, 0)
+}
+
+fn quick_select_benchmark(c: &mut Criterion) {
+    let v = new_vec();
+
+    c.bench_function("随机快速选择", |b| {
+        b.iter_batched(
+            || v.clone(),
+            |mut v| *select::quick_select(&mut v, black_box(5_000)),
+            BatchSize::SmallInput,
+        )
+    });
+}
+
+fn median_of_medians_benchmark(c: &mut Criterion) {
+    let v = new_vec();
+
+    c.bench_function("中位数的中位数", |b| {
+        b.iter_batched(
+            || v.clone(),
+            |mut v| *select::median_of_medians(&mut v, black_box(5_000)),
+            BatchSize::SmallInput,
+        )
+    });
+}
+
+fn std_select_benchmark(c: &mut Criterion) {
+    let v = new_vec();
+
+    c.bench_function("标准库 select_nth_unstable", |b| {
+        b.iter_batched(
+            || v.clone(),
+            |mut v| *v.select_nth_unstable(black_box(5_000)).1,
+            BatchSize::SmallInput,
+        )
+    });
+}
+
+criterion_group!(
+    benches,
+    quick_select_benchmark,
+    median_of_medians_benchmark,
+    std_select_benchmark
+);
+criterion_main!(benches);
@@ -0,0 +1,109 @@
+use base::{
+    generate::{self, Rng},
+    sort::{self, network},
+};
+
+use criterion::{BatchSize, Criterion, criterion_group, criterion_main};
+
+/// 基本有序的数据：由若干较长的有序段拼接而成，并随机交换少量元素
+fn partially_sorted() -> Vec<u64> {
+    let mut rng = Rng::default();
+
+    let mut v = (0..8).flat_map(|_| 0..1_250).collect::<Vec<u64>>();
+    for _ in 0..20 {
+        let (a, b) = (rng.below(v.len() as u64), rng.below(v.len() as u64));
+        v.swap(a as usize, b as usize);
+    }
+
+    v
+}
+
+fn merge_sort_benchmark(c: &mut Criterion) {
+    let v = partially_sorted();
+
+    c.bench_function("归并排序（基本有序）", |b| {
+        b.iter_batched(
+            || v.clone(),
+            |mut v| sort::merge_sort(&mut v),
+            BatchSize::SmallInput,
+        )
+    });
+}
+
+fn natural_merge_sort_benchmark(c: &mut Criterion) {
+    let v = partially_sorted();
+
+    c.bench_function("自然归并排序（基本有序）", |b| {
+        b.iter_batched(
+            || v.clone(),
+            |mut v| sort::natural_merge_sort(&mut v),
+            BatchSize::SmallInput,
+        )
+    });
+}
+
+/// 1000 个长度为 16 的随机数组
+fn small_arrays() -> Vec<[u64; 16]> {
+    let v = generate::uniform(16_000, 0);
+
+    v.chunks_exact(16)
+        .map(|chunk| chunk.try_into().unwrap())
+        .collect()
+}
+
+fn insertion_sort(v: &mut [u64]) {
+    for i in 1..v.len() {
+        let mut j = i;
+        while j > 0 && v[j] < v[j - 1] {
+            v.swap(j, j - 1);
+            j -= 1;
+        }
+    }
+}
+
+fn sorting_network_benchmark(c: &mut Criterion) {
+    let arrays = small_arrays();
+
+    c.bench_function("排序网络（长度 16）", |b| {
+        b.iter_batched(
+            || arrays.clone(),
+            |mut arrays| arrays.iter_mut().for_each(network::sort_n),
+            BatchSize::SmallInput,
+        )
+    });
+}
+
+fn insertion_sort_benchmark(c: &mut Criterion) {
+    let arrays = small_arrays();
+
+    c.bench_function("插入排序（长度 16）", |b| {
+        b.iter_batched(
+            || arrays.clone(),
+            |mut arrays| arrays.iter_mut().for_each(|v| insertion_sort(v)),
+            BatchSize::SmallInput,
+        )
+    });
+}
+
+/// 对比基本情形的整体效果：分别以默认特性和 `--features sorting-network` 运行
+fn intro_sort_benchmark(c: &mut Criterion) {
+    let v = small_arrays().concat();
+
+    c.bench_function("内省排序（随机）", |b| {
+        b.iter_batched(
+            || v.clone(),
+            |mut v| sort::intro_sort(&mut v),
+            BatchSize::SmallInput,
+        )
+    });
+}
+
+criterion_group!(
+    benches,
+    merge_sort_benchmark,
+    natural_merge_sort_benchmark,
+    sorting_network_benchmark,
+    insertion_sort_benchmark,
+    intro_sort_benchmark
+);
+criterion_main!(benches);
@@ -0,0 +1,54 @@
+use base::{SuffixArray, SuffixSort, generate::Rng, sort};
+
+use criterion::{BatchSize, Criterion, criterion_group, criterion_main};
+
+/// 10000 个随机单词，每个单词以给定前缀的一部分开头
+fn dictionary(prefix: &str) -> Vec<String> {
+    let mut rng = Rng::default();
+
+    (0..10_000)
+        .map(|_| {
+            let shared = rng.below(prefix.len() as u64 + 1) as usize;
+            let suffix = (0..rng.below(8)).map(|_| (b'a' + rng.below(26) as u8) as char);
+            prefix[..shared].chars().chain(suffix).collect()
+        })
+        .collect()
+}
+
+type StringSort = fn(&mut [String]);
+
+fn string_sort_benchmark(c: &mut Criterion) {
+    let sorts: [(&str, StringSort); 3] = [
+        ("模式消除快速排序", sort::pdq_sort),
+        ("MSD 基数排序", sort::msd_radix_sort),
+        ("三路字符串快速排序", sort::three_way_string_quicksort),
+    ];
+
+    for (shared, prefix) in [("无公共前缀", ""), ("长公共前缀", &"abcdefgh".repeat(8))] {
+        let v = dictionary(prefix);
+
+        for (name, sort) in sorts {
+            c.bench_function(&format!("{name}（{shared}）"), |b| {
+                b.iter_batched(|| v.clone(), |mut v| sort(&mut v), BatchSize::SmallInput)
+            });
+        }
+    }
+}
+
+fn suffix_array_benchmark(c: &mut Criterion) {
+    let text = dictionary("abcdefgh").concat();
+    let backends = [
+        ("比较排序", SuffixSort::Comparison),
+        ("MSD 基数排序", SuffixSort::MsdRadix),
+        ("三路字符串快速排序", SuffixSort::ThreeWayQuicksort),
+    ];
+
+    for (name, backend) in backends {
+        c.bench_function(&format!("后缀数组（{name}）"), |b| {
+            b.iter(|| SuffixArray::with_backend(&text, backend))
+        });
+    }
+}
+
+criterion_group!(benches, string_sort_benchmark, suffix_array_benchmark);
+criterion_main!(benches);
@@ -0,0 +1,120 @@
+use std::{cmp::Reverse, collections::BinaryHeap, hint::black_box};
+
+use base::{TimerWheel, generate::Rng};
+
+use criterion::{Criterion, criterion_group, criterion_main};
+
+/// 定时器数量
+const TIMERS: usize = 100_000;
+/// 定时器的最大延迟
+const MAX_DELAY: u64 = 10_000;
+
+/// 每个定时器的延迟
+fn delays() -> Vec<u64> {
+    let mut rng = Rng::new(3);
+
+    (0..TIMERS).map(|_| rng.below(MAX_DELAY) + 1).collect()
+}
+
+/// 基于堆的事件队列：按 (到期时刻, 编号) 排序，取消时只做标记，出堆时跳过
+struct HeapTimers {
+    heap: BinaryHeap<Reverse<(u64, usize)>>,
+    cancelled: Vec<bool>,
+    now: u64,
+}
+
+impl HeapTimers {
+    fn new() -> Self {
+        Self {
+            heap: BinaryHeap::new(),
+            cancelled: Vec::new(),
+            now: 0,
+        }
+    }
+
+    fn schedule(&mut self, after: u64) -> usize {
+        let id = self.cancelled.len();
+
+        self.cancelled.push(false);
+        self.heap.push(Reverse((self.now + after, id)));
+        id
+    }
+
+    fn cancel(&mut self, id: usize) {
+        self.cancelled[id] = true;
+    }
+
+    fn advance(&mut self, ticks: u64) -> Vec<usize> {
+        self.now += ticks;
+
+        let mut expired = Vec::new();
+        while let Some(&Reverse((deadline, id))) = self.heap.peek() {
+            if deadline > self.now {
+                break;
+            }
+            self.heap.pop();
+            if !self.cancelled[id] {
+                expired.push(id);
+            }
+        }
+
+        expired
+    }
+}
+
+/// 添加全部定时器，每次推进一个刻度直到全部到期，cancel_every 不为 0 时每隔几个定时器取消一个
+fn run_wheel(delays: &[u64], cancel_every: usize) -> usize {
+    let mut wheel = TimerWheel::new();
+    let handles = delays
+        .iter()
+        .enumerate()
+        .map(|(id, &after)| wheel.schedule(after, id))
+        .collect::<Vec<_>>();
+    if cancel_every > 0 {
+        for &handle in handles.iter().step_by(cancel_every) {
+            wheel.cancel(handle);
+        }
+    }
+
+    (0..MAX_DELAY).map(|_| wheel.advance(1).len()).sum()
+}
+
+fn run_heap(delays: &[u64], cancel_every: usize) -> usize {
+    let mut timers = HeapTimers::new();
+    let ids = delays
+        .iter()
+        .map(|&after| timers.schedule(after))
+        .collect::<Vec<_>>();
+    if cancel_every > 0 {
+        for &id in ids.iter().step_by(cancel_every) {
+            timers.cancel(id);
+        }
+    }
+
+    (0..MAX_DELAY).map(|_| timers.advance(1).len()).sum()
+}
+
+fn expire_benchmark(c: &mut Criterion) {
+    let delays = delays();
+
+    c.bench_function("时间轮全部到期", |b| {
+        b.iter(|| run_wheel(black_box(&delays), 0))
+    });
+    c.bench_function("堆定时器全部到期", |b| {
+        b.iter(|| run_heap(black_box(&delays), 0))
+    });
+}
+
+fn cancel_benchmark(c: &mut Criterion) {
+    let delays = delays();
+
+    c.bench_function("时间轮取消一半", |b| {
+        b.iter(|| run_wheel(black_box(&delays), 2))
+    });
+    c.bench_function("堆定时器取消一半", |b| {
+        b.iter(|| run_heap(black_box(&delays), 2))
+    });
+}
+
+criterion_group!(benches, expire_benchmark, cancel_benchmark);
+criterion_main!(benches);
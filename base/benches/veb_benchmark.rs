@@ -0,0 +1,92 @@
+use std::{collections::BTreeSet, hint::black_box};
+
+use base::{AvlTree, VebSet, generate::Rng};
+
+use criterion::{Criterion, criterion_group, criterion_main};
+
+/// 值域的位数
+const BITS: u32 = 16;
+
+/// 稠密的整数键：从 2^16 的值域中随机取 40000 个
+fn dense_keys() -> Vec<u64> {
+    let mut rng = Rng::new(7);
+
+    (0..40_000).map(|_| rng.below(1 << BITS)).collect()
+}
+
+fn insert_benchmark(c: &mut Criterion) {
+    let keys = dense_keys();
+
+    c.bench_function("vEB 树插入（稠密整数）", |b| {
+        b.iter(|| {
+            let mut set = VebSet::new(BITS);
+            set.extend(keys.iter().copied());
+            set
+        })
+    });
+
+    c.bench_function("AVL 树插入（稠密整数）", |b| {
+        b.iter(|| {
+            let mut tree = AvlTree::new();
+            for &key in &keys {
+                tree.try_insert(key);
+            }
+            tree
+        })
+    });
+}
+
+fn contains_benchmark(c: &mut Criterion) {
+    let keys = dense_keys();
+    let mut set = VebSet::new(BITS);
+    set.extend(keys.iter().copied());
+    let tree = AvlTree::from_iter_dedup(keys.iter().copied()).0;
+
+    c.bench_function("vEB 树查找（稠密整数）", |b| {
+        b.iter(|| {
+            (0..1 << BITS)
+                .filter(|&x| set.contains(black_box(x)))
+                .count()
+        })
+    });
+
+    c.bench_function("AVL 树查找（稠密整数）", |b| {
+        b.iter(|| {
+            (0..1 << BITS)
+                .filter(|x| tree.search(black_box(x)).is_some())
+                .count()
+        })
+    });
+}
+
+fn successor_benchmark(c: &mut Criterion) {
+    let keys = dense_keys();
+    let mut set = VebSet::new(BITS);
+    set.extend(keys.iter().copied());
+    let btree = keys.iter().copied().collect::<BTreeSet<_>>();
+
+    // AVL 树没有后继查询，与标准库的 B 树比较
+    c.bench_function("vEB 树查找后继（稠密整数）", |b| {
+        b.iter(|| {
+            (0..1 << BITS)
+                .filter_map(|x| set.successor(black_box(x)))
+                .sum::<u64>()
+        })
+    });
+
+    c.bench_function("标准库 BTreeSet 查找后继（稠密整数）", |b| {
+        b.iter(|| {
+            (0..1 << BITS)
+                .filter_map(|x| btree.range(black_box(x) + 1..).next())
+                .sum::<u64>()
+        })
+    });
+}
+
+criterion_group!(
+    benches,
+    insert_benchmark,
+    contains_benchmark,
+    successor_benchmark
+);
+criterion_main!(benches);
@@ -0,0 +1,23 @@
+//! 逐个插入元素，观察 AVL 树通过旋转保持平衡的过程
+//!
+//! 运行：cargo run --example avl_rotations 5 3 8 1
+
+mod common;
+
+use base::{AvlTree, BinarySearchTree, bt};
+
+fn main() {
+    let values = common::parse_args_or(|| (1..=7).collect());
+    let mut avl = AvlTree::new();
+    let mut bst = BinarySearchTree::new();
+
+    for val in values {
+        avl.insert(val);
+        bst.insert(val);
+        println!("插入 {val} 后的 AVL 树：");
+        println!("{}", bt::render(&avl.to_tree().root));
+    }
+
+    println!("相同插入顺序下的普通二叉搜索树（可能退化为链表）：");
+    println!("{}", bt::render(&bst.to_tree().root));
+}
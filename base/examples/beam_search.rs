@@ -0,0 +1,94 @@
+//! 束搜索寻路：在迷宫中从 S 走到 G，参数为要尝试的束宽度
+//!
+//! 向下和向右一样接近终点，但向下是死胡同：束宽度为 1 时退化为贪心搜索而走进死胡同，
+//! 束宽度增大后才能同时保留两个方向，找到路径
+//!
+//! 运行：cargo run --example beam_search 1 2 4
+
+mod common;
+
+use std::cmp::Reverse;
+
+use base::search;
+
+const GRID: [&str; 7] = [
+    "S...........",
+    ".##########.",
+    ".#........#.",
+    ".#.######.#.",
+    ".#......#.#.",
+    ".########.#.",
+    "..........#G",
+];
+
+type Pos = (usize, usize);
+
+fn main() {
+    let widths: Vec<usize> = common::parse_args_or(|| vec![1, 2, 4]);
+    let goal = (GRID.len() - 1, GRID[0].len() - 1);
+
+    for width in widths.into_iter().filter(|&width| width > 0) {
+        let path = search::beam_search(
+            vec![(0, 0)],
+            |path: &Vec<Pos>| successors(path, goal),
+            |path: &Vec<Pos>| {
+                // 离终点越近越好，距离相同时路径越短越好
+                let &(r, c) = path.last().unwrap();
+                Reverse((r.abs_diff(goal.0) + c.abs_diff(goal.1), path.len()))
+            },
+            width,
+        );
+
+        let reached = path.last() == Some(&goal);
+        println!(
+            "束宽度 {width}：{}，路径长度 {}",
+            if reached {
+                "到达终点"
+            } else {
+                "未到达终点"
+            },
+            path.len() - 1
+        );
+        print_path(&path);
+        println!();
+    }
+}
+
+/// 向四个方向走一步，不穿墙，也不重复经过同一个格子
+fn successors(path: &[Pos], goal: Pos) -> Vec<Vec<Pos>> {
+    let &(r, c) = path.last().unwrap();
+    if (r, c) == goal {
+        return vec![];
+    }
+
+    [
+        (r.wrapping_sub(1), c),
+        (r + 1, c),
+        (r, c.wrapping_sub(1)),
+        (r, c + 1),
+    ]
+    .into_iter()
+    .filter(|&(r, c)| r < GRID.len() && c < GRID[0].len())
+    .filter(|&(r, c)| GRID[r].as_bytes()[c] != b'#')
+    .filter(|next| !path.contains(next))
+    .map(|next| {
+        let mut path = path.to_vec();
+        path.push(next);
+        path
+    })
+    .collect()
+}
+
+fn print_path(path: &[Pos]) {
+    for (r, row) in GRID.iter().enumerate() {
+        let line = row
+            .chars()
+            .enumerate()
+            .map(|(c, ch)| match ch {
+                '.' if path.contains(&(r, c)) => '*',
+                _ => ch,
+            })
+            .collect::<String>();
+        println!("{line}");
+    }
+}
@@ -0,0 +1,47 @@
+//! 二分答案：将任务按顺序分给 k 个工人，每人负责连续的一段，求最大负载的最小值
+//!
+//! 第一个参数为工人数量 k，其余参数为各任务的耗时
+//!
+//! 运行：cargo run --example binary_search_answer 2 7 2 5 10 8
+
+mod common;
+
+use base::search;
+
+fn main() {
+    let args: Vec<i64> = common::parse_args_or(|| vec![2, 7, 2, 5, 10, 8]);
+    let Some((&k, tasks)) = args
+        .split_first()
+        .filter(|(k, tasks)| **k > 0 && !tasks.is_empty())
+    else {
+        eprintln!("用法：binary_search_answer <工人数量> <任务耗时>...");
+        std::process::exit(1);
+    };
+
+    // 最大负载不超过 limit 时，贪心地计算至少需要多少个工人
+    let workers = |limit: i64| {
+        let (mut count, mut load) = (1, 0);
+        for &task in tasks {
+            if load + task > limit {
+                count += 1;
+                load = 0;
+            }
+            load += task;
+        }
+        count
+    };
+
+    let lo = *tasks.iter().max().unwrap();
+    let hi = tasks.iter().sum::<i64>() + 1;
+    let answer = search::binary_search_predicate(lo, hi, |limit| {
+        let feasible = workers(limit) <= k;
+        println!(
+            "尝试最大负载 {limit}：需要 {} 个工人，{}",
+            workers(limit),
+            if feasible { "可行" } else { "不可行" }
+        );
+        feasible
+    });
+
+    println!("{k} 个工人时最大负载的最小值为 {answer}");
+}
@@ -0,0 +1,20 @@
+//! 示例程序共用的命令行参数解析
+
+use std::{env, process, str::FromStr};
+
+/// 将命令行参数解析为整数列表，没有参数时返回默认值，解析失败时退出程序
+pub fn parse_args_or<T: FromStr>(default: impl FnOnce() -> Vec<T>) -> Vec<T> {
+    let args = env::args().skip(1).collect::<Vec<_>>();
+    if args.is_empty() {
+        return default();
+    }
+
+    args.iter()
+        .map(|arg| {
+            arg.parse().unwrap_or_else(|_| {
+                eprintln!("无法解析参数：{arg}");
+                process::exit(1);
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,29 @@
+//! 建堆与出堆：依次弹出堆顶元素，得到有序序列（即堆排序）
+//!
+//! 运行：cargo run --example heap 4 1 7 3 8 5
+
+mod common;
+
+use base::{Heap, MaxHeap, MinHeap};
+
+fn main() {
+    let values: Vec<i64> = common::parse_args_or(|| vec![4, 1, 7, 3, 8, 5]);
+
+    let mut max_heap = MaxHeap::from_vec(values.clone());
+    println!(
+        "大顶堆，堆顶：{:?}，元素数量：{}",
+        max_heap.peek(),
+        max_heap.len()
+    );
+    while let Some(top) = max_heap.pop() {
+        println!("  弹出 {top}，新的堆顶：{:?}", max_heap.peek());
+    }
+
+    let mut min_heap = MinHeap::new();
+    for val in values {
+        min_heap.push(val);
+        println!("小顶堆压入 {val}，堆顶：{:?}", min_heap.peek());
+    }
+    let sorted = std::iter::from_fn(|| min_heap.pop()).collect::<Vec<_>>();
+    println!("小顶堆依次出堆：{sorted:?}");
+}
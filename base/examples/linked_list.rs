@@ -0,0 +1,34 @@
+//! 双向链表的基本操作
+//!
+//! 运行：cargo run --example linked_list 1 2 3 4 5
+
+mod common;
+
+use base::LinkedList;
+
+fn main() {
+    let values = common::parse_args_or(|| vec![1, 2, 3, 4, 5]);
+    let mut list = values.into_iter().collect::<LinkedList<_>>();
+    println!("构建链表：{}", show(&list));
+
+    list.push_front(0);
+    list.push_back(99);
+    println!("头部插入 0、尾部插入 99：{}", show(&list));
+
+    list.reverse();
+    println!("反转：{}", show(&list));
+
+    let at = list.len() / 2;
+    let tail = list.split_off(at);
+    println!("在下标 {at} 处拆分：{} 和 {}", show(&list), show(&tail));
+
+    while let Some(front) = list.pop_front() {
+        println!("弹出头部元素 {front}，剩余：{}", show(&list));
+    }
+}
+
+fn show(list: &LinkedList<i64>) -> String {
+    let items = list.iter().map(i64::to_string).collect::<Vec<_>>();
+
+    format!("[{}]", items.join(" <-> "))
+}
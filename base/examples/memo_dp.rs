@@ -0,0 +1,52 @@
+//! 记忆化搜索：两个字符串之间的编辑距离，统计实际计算的子问题数量
+//!
+//! 运行：cargo run --example memo_dp kitten sitting
+
+mod common;
+
+use std::cell::Cell;
+
+use base::memo;
+
+fn main() {
+    let words: Vec<String> =
+        common::parse_args_or(|| vec!["kitten".to_string(), "sitting".to_string()]);
+    let [a, b] = words.as_slice() else {
+        eprintln!("需要恰好两个字符串参数");
+        std::process::exit(1);
+    };
+    let (a, b) = (a.chars().collect::<Vec<_>>(), b.chars().collect::<Vec<_>>());
+
+    // 子问题 (i, j) 为 a[i..] 与 b[j..] 之间的编辑距离
+    let computed = Cell::new(0);
+    let distance = memo::memoize(
+        |d: &dyn Fn((usize, usize)) -> usize, (i, j): (usize, usize)| {
+            computed.set(computed.get() + 1);
+            if i == a.len() || j == b.len() {
+                return (a.len() - i) + (b.len() - j);
+            }
+            if a[i] == b[j] {
+                return d((i + 1, j + 1));
+            }
+            1 + d((i + 1, j)).min(d((i, j + 1))).min(d((i + 1, j + 1)))
+        },
+    );
+
+    println!("编辑距离：{}", distance((0, 0)));
+    println!(
+        "计算的子问题数量：{}，全部子问题数量：{}",
+        computed.get(),
+        (a.len() + 1) * (b.len() + 1)
+    );
+
+    // 缓存已经保存了所有子问题，再次查询不会重新计算
+    let before = computed.get();
+    for i in (0..=a.len()).rev() {
+        let row = (0..=b.len())
+            .map(|j| format!("{:>2}", distance((i, j))))
+            .collect::<Vec<_>>()
+            .join(" ");
+        println!("  a[{i}..]：{row}");
+    }
+    println!("打印上表新计算的子问题数量：{}", computed.get() - before);
+}
@@ -0,0 +1,50 @@
+//! PageRank：幂迭代的每一轮分数，以及收敛后的结果
+//!
+//! 参数依次两两组成有向边 u -> v，结点编号从 0 开始
+//!
+//! 运行：cargo run --example pagerank 1 0 2 0 3 0 0 1
+
+mod common;
+
+use base::{SparseMatrix, graph, sparse};
+
+const DAMPING: f64 = 0.85;
+
+fn main() {
+    let ids: Vec<usize> = common::parse_args_or(|| vec![1, 0, 2, 0, 3, 0, 0, 1]);
+    if !ids.len().is_multiple_of(2) {
+        eprintln!("参数数量应为偶数，每两个结点编号组成一条边");
+        std::process::exit(1);
+    }
+
+    let n = ids.iter().max().map_or(0, |&max| max + 1);
+    let mut adj = vec![Vec::new(); n];
+    for edge in ids.chunks(2) {
+        adj[edge[0]].push(edge[1]);
+    }
+
+    let matrix = SparseMatrix::from_adjacency(&adj);
+    println!("结点数量：{n}，边数：{}", matrix.nnz());
+    for (u, neighbors) in adj.iter().enumerate() {
+        println!("  {u} -> {neighbors:?}");
+    }
+
+    for iters in 1..=5 {
+        let rank = sparse::pagerank(&adj, DAMPING, iters);
+        println!("第 {iters} 轮：{}", format_rank(&rank));
+    }
+
+    let rank = graph::pagerank(&adj, DAMPING, 1e-10);
+    println!("收敛后：{}", format_rank(&rank));
+
+    let mut order = (0..n).collect::<Vec<_>>();
+    order.sort_by(|&a, &b| rank[b].total_cmp(&rank[a]));
+    println!("按分数从高到低：{order:?}");
+}
+
+fn format_rank(rank: &[f64]) -> String {
+    rank.iter()
+        .map(|r| format!("{r:.4}"))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
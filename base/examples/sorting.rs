@@ -0,0 +1,49 @@
+//! 对比各种排序算法的比较次数，并展示煎饼排序的翻转过程和圈排序的写入次数
+//!
+//! 运行：cargo run --example sorting 5 2 9 1 5 6
+
+mod common;
+
+use std::cmp::Ordering;
+
+use base::sort;
+
+type SortBy = fn(&mut [i64], &mut dyn FnMut(&i64, &i64) -> Ordering);
+
+fn main() {
+    let values: Vec<i64> = common::parse_args_or(|| vec![5, 2, 9, 1, 5, 6, 3, 8, 7, 4]);
+    println!("输入：{values:?}");
+
+    let sorts: [(&str, SortBy); 6] = [
+        ("朴素快速排序", |v, f| sort::quick_sort_by(v, f)),
+        ("内省排序", |v, f| sort::intro_sort_by(v, f)),
+        ("模式消除快速排序", |v, f| sort::pdq_sort_by(v, f)),
+        ("归并排序", |v, f| sort::merge_sort_by(v, f)),
+        ("自然归并排序", |v, f| {
+            sort::natural_merge_sort_by(v, f)
+        }),
+        ("标准库排序", |v, f| v.sort_by(f)),
+    ];
+    for (name, sort) in sorts {
+        let mut v = values.clone();
+        let mut comparisons = 0;
+        sort(&mut v, &mut |a, b| {
+            comparisons += 1;
+            a.cmp(b)
+        });
+        println!("{name}：{v:?}，比较 {comparisons} 次");
+    }
+
+    let mut v = values.clone();
+    let flips = sort::pancake_sort(&mut v);
+    println!("煎饼排序：{v:?}");
+    let mut pancakes = values.clone();
+    for k in flips {
+        pancakes[..k].reverse();
+        println!("  翻转前 {k} 个：{pancakes:?}");
+    }
+
+    let mut v = values;
+    let writes = sort::cycle_sort(&mut v);
+    println!("圈排序：{v:?}，写入 {writes} 次");
+}
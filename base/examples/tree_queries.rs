@@ -0,0 +1,45 @@
+//! 树上查询：由父结点数组构建有根树，计算各结点深度、两两之间的最近公共祖先和距离
+//!
+//! 参数为每个结点的父结点编号，根结点用 - 表示
+//!
+//! 运行：cargo run --example tree_queries -- - 0 0 1 1 2
+
+mod common;
+
+use base::LcaIndex;
+
+fn main() {
+    let parents: Vec<String> = common::parse_args_or(|| {
+        ["-", "0", "0", "1", "1", "2", "3"]
+            .map(String::from)
+            .to_vec()
+    });
+    let parents = parents
+        .iter()
+        .map(|p| match p.as_str() {
+            "-" => None,
+            p => Some(p.parse().unwrap_or_else(|_| {
+                eprintln!("无法解析父结点：{p}");
+                std::process::exit(1);
+            })),
+        })
+        .collect::<Vec<_>>();
+
+    let index = LcaIndex::from_parents(&parents);
+    for (node, parent) in parents.iter().enumerate() {
+        let parent = parent.map_or(String::from("-"), |p| p.to_string());
+        println!("结点 {node}：父结点 {parent}，深度 {}", index.depth(node));
+    }
+
+    println!();
+    for a in 0..parents.len() {
+        for b in a + 1..parents.len() {
+            match (index.lca(a, b), index.distance(a, b)) {
+                (Some(lca), Some(distance)) => {
+                    println!("lca({a}, {b}) = {lca}，距离 {distance}");
+                }
+                _ => println!("{a} 和 {b} 不在同一棵树中"),
+            }
+        }
+    }
+}
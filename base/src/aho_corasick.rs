@@ -0,0 +1,337 @@
+//! Aho-Corasick 自动机：同时查找多个模式串
+//!
+//! 把所有模式串插入字典树，再为每个结点计算失配指针（fail link）：
+//! 指向该结点所表示字符串的最长真后缀中、同样出现在字典树里的那个结点，与 KMP 的前缀函数类似。
+//! 按广度优先的顺序计算失配指针时，顺便把每个结点缺失的转移补全为失配指针结点的转移，
+//! 字典树就变成了确定有限状态自动机（DFA）：每读入一个字节只需一次查表，文本只扫描一遍，
+//! 时间复杂度为 O(n + m + z)，n 为文本长度，m 为模式串总长，z 为匹配数量。
+//!
+//! 自动机的全部状态只有“当前结点”一个整数，因此很容易改造为流式处理：
+//! [`StreamFilter`] 在多次 `feed` 之间保存当前结点和已读入的字节数，
+//! 跨越数据块边界的匹配也能被找到，适合处理文件、网络等无法一次读入内存的数据。
+
+use std::{
+    collections::VecDeque,
+    io::{self, Read},
+};
+
+/// 字节表的大小
+const ALPHABET: usize = 256;
+/// 字典树中不存在的转移
+const NONE: u32 = u32::MAX;
+
+/// 一次匹配，start..end 为匹配在文本（流式处理时为整个数据流）中的位置
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Match {
+    /// 模式串的编号，即构建时的顺序
+    pub pattern: usize,
+    pub start: usize,
+    pub end: usize,
+}
+
+/// Aho-Corasick 自动机
+///
+/// # Examples
+///
+/// ```rust
+/// use base::AhoCorasick;
+///
+/// let automaton = AhoCorasick::new(["he", "she", "his", "hers"]);
+/// let found = automaton
+///     .find_all(b"ushers")
+///     .iter()
+///     .map(|m| (m.pattern, m.start, m.end))
+///     .collect::<Vec<_>>();
+///
+/// // 同一位置结束的匹配中，较长的排在前面
+/// assert_eq!(found, [(1, 1, 4), (0, 2, 4), (3, 2, 6)]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct AhoCorasick {
+    /// 补全后的转移表，goto[s][b] 为状态 s 读入字节 b 后的状态，0 为初始状态
+    goto: Vec<[u32; ALPHABET]>,
+    /// 到达每个状态时匹配的模式串，包括沿失配指针可以到达的所有状态的输出
+    outputs: Vec<Vec<usize>>,
+    /// 每个模式串的长度
+    lengths: Vec<usize>,
+}
+
+impl AhoCorasick {
+    /// 由模式串构建自动机，时间复杂度：O(m * 256)，m 为模式串总长
+    ///
+    /// # Panics
+    ///
+    /// Panics if a pattern is empty.
+    pub fn new<I, P>(patterns: I) -> Self
+    where
+        I: IntoIterator<Item = P>,
+        P: AsRef<[u8]>,
+    {
+        let mut goto = vec![[NONE; ALPHABET]];
+        let mut outputs = vec![Vec::new()];
+        let mut lengths = Vec::new();
+
+        // 构建字典树
+        for (id, pattern) in patterns.into_iter().enumerate() {
+            let pattern = pattern.as_ref();
+            assert!(!pattern.is_empty(), "Pattern {id} is empty");
+
+            let mut state = 0;
+            for &byte in pattern {
+                if goto[state][byte as usize] == NONE {
+                    goto[state][byte as usize] = goto.len() as u32;
+                    goto.push([NONE; ALPHABET]);
+                    outputs.push(Vec::new());
+                }
+                state = goto[state][byte as usize] as usize;
+            }
+            outputs[state].push(id);
+            lengths.push(pattern.len());
+        }
+
+        // 按广度优先的顺序计算失配指针，并补全转移
+        let mut fail = vec![0; goto.len()];
+        let mut queue = VecDeque::new();
+        for next in goto[0].iter_mut() {
+            if *next == NONE {
+                *next = 0;
+            } else {
+                queue.push_back(*next as usize);
+            }
+        }
+        while let Some(state) = queue.pop_front() {
+            // 失配指针的深度更小，其转移已经补全
+            let fallbacks = goto[fail[state]];
+            for (next, &fallback) in goto[state].iter_mut().zip(&fallbacks) {
+                if *next == NONE {
+                    *next = fallback;
+                } else {
+                    let next = *next as usize;
+                    fail[next] = fallback as usize;
+                    let inherited = outputs[fail[next]].clone();
+                    outputs[next].extend(inherited);
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        Self {
+            goto,
+            outputs,
+            lengths,
+        }
+    }
+
+    /// 模式串的数量
+    pub fn patterns(&self) -> usize {
+        self.lengths.len()
+    }
+
+    /// 查找文本中所有模式串的所有出现（可以重叠），按结束位置排序
+    pub fn find_all(&self, haystack: &[u8]) -> Vec<Match> {
+        let mut matches = Vec::new();
+        self.scan(0, 0, haystack, &mut matches);
+
+        matches
+    }
+
+    /// 判断文本中是否出现了任意一个模式串
+    pub fn is_match(&self, haystack: &[u8]) -> bool {
+        let mut state = 0;
+
+        haystack.iter().any(|&byte| {
+            state = self.goto[state][byte as usize] as usize;
+            !self.outputs[state].is_empty()
+        })
+    }
+
+    /// 从状态 state 开始扫描 chunk，chunk 在整个数据流中的起始位置为 offset，返回结束时的状态
+    fn scan(
+        &self,
+        mut state: usize,
+        offset: usize,
+        chunk: &[u8],
+        matches: &mut Vec<Match>,
+    ) -> usize {
+        for (i, &byte) in chunk.iter().enumerate() {
+            state = self.goto[state][byte as usize] as usize;
+
+            let end = offset + i + 1;
+            matches.extend(self.outputs[state].iter().map(|&pattern| Match {
+                pattern,
+                start: end - self.lengths[pattern],
+                end,
+            }));
+        }
+
+        state
+    }
+}
+
+/// 流式匹配：分块读入数据，在块之间保存自动机的状态
+///
+/// # Examples
+///
+/// ```rust
+/// use base::{AhoCorasick, StreamFilter};
+///
+/// let mut filter = StreamFilter::new(AhoCorasick::new(["needle"]));
+///
+/// // 模式串被拆分在两个数据块中
+/// assert!(filter.feed(b"hay hay nee").is_empty());
+/// let found = filter.feed(b"dle hay");
+///
+/// assert_eq!((found[0].start, found[0].end), (8, 14));
+/// assert_eq!(filter.position(), 18);
+/// ```
+#[derive(Debug, Clone)]
+pub struct StreamFilter {
+    automaton: AhoCorasick,
+    state: usize,
+    position: usize,
+}
+
+impl StreamFilter {
+    /// 创建流式匹配器，从数据流的开头开始
+    pub fn new(automaton: AhoCorasick) -> Self {
+        Self {
+            automaton,
+            state: 0,
+            position: 0,
+        }
+    }
+
+    /// 使用的自动机
+    pub fn automaton(&self) -> &AhoCorasick {
+        &self.automaton
+    }
+
+    /// 已经读入的字节数
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// 读入下一块数据，返回在这一块中结束的所有匹配，位置相对于整个数据流
+    pub fn feed(&mut self, chunk: &[u8]) -> Vec<Match> {
+        let mut matches = Vec::new();
+
+        self.state = self
+            .automaton
+            .scan(self.state, self.position, chunk, &mut matches);
+        self.position += chunk.len();
+
+        matches
+    }
+
+    /// 读完 reader 中剩余的全部数据，返回所有匹配
+    pub fn feed_reader<R: Read>(&mut self, mut reader: R) -> io::Result<Vec<Match>> {
+        let mut buf = [0; 8192];
+        let mut matches = Vec::new();
+
+        loop {
+            let n = match reader.read(&mut buf) {
+                Ok(0) => return Ok(matches),
+                Ok(n) => n,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(e),
+            };
+            matches.extend(self.feed(&buf[..n]));
+        }
+    }
+
+    /// 回到数据流的开头，丢弃已读入数据的状态
+    pub fn reset(&mut self) {
+        self.state = 0;
+        self.position = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generate::Rng;
+
+    fn random_bytes(rng: &mut Rng, len: usize, alphabet: u64) -> Vec<u8> {
+        (0..len).map(|_| b'a' + rng.below(alphabet) as u8).collect()
+    }
+
+    /// 逐个位置比较每个模式串，按 (结束位置, 长度降序) 排序
+    fn brute_force(patterns: &[Vec<u8>], haystack: &[u8]) -> Vec<Match> {
+        let mut matches = Vec::new();
+        for end in 1..=haystack.len() {
+            let mut here = patterns
+                .iter()
+                .enumerate()
+                .filter(|(_, p)| haystack[..end].ends_with(p))
+                .map(|(pattern, p)| Match {
+                    pattern,
+                    start: end - p.len(),
+                    end,
+                })
+                .collect::<Vec<_>>();
+            here.sort_by_key(|m| (m.start, m.pattern));
+            matches.extend(here);
+        }
+
+        matches
+    }
+
+    /// 按 (结束位置, 起始位置, 编号) 排序，比较时不依赖同一位置的输出顺序
+    fn normalized(mut matches: Vec<Match>) -> Vec<Match> {
+        matches.sort_by_key(|m| (m.end, m.start, m.pattern));
+        matches
+    }
+
+    #[test]
+    fn aho_corasick_should_match_brute_force() {
+        let mut rng = Rng::new(12);
+
+        for _ in 0..20 {
+            let patterns = (0..1 + rng.below(8))
+                .map(|_| {
+                    let len = 1 + rng.below(4) as usize;
+                    random_bytes(&mut rng, len, 3)
+                })
+                .collect::<Vec<_>>();
+            let haystack = random_bytes(&mut rng, 300, 3);
+            let automaton = AhoCorasick::new(&patterns);
+
+            let expected = brute_force(&patterns, &haystack);
+            assert_eq!(
+                normalized(automaton.find_all(&haystack)),
+                normalized(expected.clone())
+            );
+            assert_eq!(automaton.is_match(&haystack), !expected.is_empty());
+        }
+    }
+
+    #[test]
+    fn stream_filter_should_match_across_chunks() {
+        let mut rng = Rng::new(4);
+        let patterns = ["abca", "bc", "cab", "aaaa", "b"];
+        let automaton = AhoCorasick::new(patterns);
+        let haystack = random_bytes(&mut rng, 2000, 3);
+        let expected = automaton.find_all(&haystack);
+
+        // 随机切分为若干块，包括空块
+        let mut filter = StreamFilter::new(automaton.clone());
+        let mut found = Vec::new();
+        let mut rest = &haystack[..];
+        while !rest.is_empty() {
+            let len = (rng.below(6) as usize).min(rest.len());
+            found.extend(filter.feed(&rest[..len]));
+            rest = &rest[len..];
+        }
+        assert_eq!(found, expected);
+        assert_eq!(filter.position(), haystack.len());
+
+        filter.reset();
+        assert_eq!(filter.feed_reader(&haystack[..]).unwrap(), expected);
+    }
+
+    #[test]
+    #[should_panic(expected = "Pattern 1 is empty")]
+    fn aho_corasick_should_reject_empty_pattern() {
+        AhoCorasick::new(["a", ""]);
+    }
+}
@@ -0,0 +1,185 @@
+//! 统计内存分配的全局分配器，需要启用 `alloc-counter` 特性
+//!
+//! 启用后 [`CountingAllocator`] 被注册为全局分配器：所有分配仍转发给系统分配器，
+//! 同时在线程局部变量中记录分配次数和字节数。统计按线程进行，并行运行的测试互不干扰。
+//!
+//! 注意：全局分配器对整个程序生效，依赖本库并启用该特性的程序也会使用它。
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+};
+
+/// 线程局部的分配统计
+struct Counters {
+    allocations: Cell<usize>,
+    deallocations: Cell<usize>,
+    reallocations: Cell<usize>,
+    allocated_bytes: Cell<usize>,
+    /// 当前仍未释放的字节数，可能因释放其他线程分配的内存而为负
+    live_bytes: Cell<isize>,
+    peak_bytes: Cell<isize>,
+}
+
+thread_local! {
+    // 使用 const 初始化，访问时不会触发分配，可以在分配器内部使用
+    static COUNTERS: Counters = const {
+        Counters {
+            allocations: Cell::new(0),
+            deallocations: Cell::new(0),
+            reallocations: Cell::new(0),
+            allocated_bytes: Cell::new(0),
+            live_bytes: Cell::new(0),
+            peak_bytes: Cell::new(0),
+        }
+    };
+}
+
+/// 记录分配与释放，delta 为未释放字节数的变化量
+fn record(allocations: usize, deallocations: usize, reallocations: usize, delta: isize) {
+    // 线程退出、线程局部变量已销毁时忽略统计
+    let _ = COUNTERS.try_with(|c| {
+        c.allocations.set(c.allocations.get() + allocations);
+        c.deallocations.set(c.deallocations.get() + deallocations);
+        c.reallocations.set(c.reallocations.get() + reallocations);
+        if delta > 0 {
+            c.allocated_bytes
+                .set(c.allocated_bytes.get() + delta as usize);
+        }
+        c.live_bytes.set(c.live_bytes.get() + delta);
+        c.peak_bytes.set(c.peak_bytes.get().max(c.live_bytes.get()));
+    });
+}
+
+/// 统计内存分配的全局分配器
+pub struct CountingAllocator;
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc(layout) };
+        if !ptr.is_null() {
+            record(1, 0, 0, layout.size() as isize);
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc_zeroed(layout) };
+        if !ptr.is_null() {
+            record(1, 0, 0, layout.size() as isize);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) };
+        record(0, 1, 0, -(layout.size() as isize));
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = unsafe { System.realloc(ptr, layout, new_size) };
+        if !new_ptr.is_null() {
+            record(0, 0, 1, new_size as isize - layout.size() as isize);
+        }
+        new_ptr
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+/// 一段代码的内存分配统计
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct AllocStats {
+    /// 分配次数
+    pub allocations: usize,
+    /// 释放次数
+    pub deallocations: usize,
+    /// 重新分配（扩容或缩容）次数
+    pub reallocations: usize,
+    /// 累计分配的字节数，重新分配时只计入增加的部分
+    pub allocated_bytes: usize,
+    /// 运行期间未释放字节数的峰值（相对于开始时）
+    pub peak_bytes: usize,
+}
+
+/// 运行 f，返回其结果和当前线程在运行期间的内存分配统计
+///
+/// # Examples
+///
+/// ```rust
+/// use base::alloc_counter;
+///
+/// let (v, stats) = alloc_counter::measure_allocations(|| vec![0u64; 100]);
+///
+/// assert_eq!(stats.allocations, 1);
+/// assert_eq!(stats.peak_bytes, 800);
+/// drop(v);
+/// ```
+pub fn measure_allocations<R>(f: impl FnOnce() -> R) -> (R, AllocStats) {
+    let snapshot = || {
+        COUNTERS.with(|c| {
+            (
+                c.allocations.get(),
+                c.deallocations.get(),
+                c.reallocations.get(),
+                c.allocated_bytes.get(),
+                c.live_bytes.get(),
+            )
+        })
+    };
+
+    let before = snapshot();
+    // 以当前的未释放字节数为起点重新记录峰值
+    let outer_peak = COUNTERS.with(|c| c.peak_bytes.replace(c.live_bytes.get()));
+
+    let result = f();
+
+    let after = snapshot();
+    let peak = COUNTERS.with(|c| {
+        let peak = c.peak_bytes.get();
+        c.peak_bytes.set(peak.max(outer_peak));
+        peak
+    });
+
+    let stats = AllocStats {
+        allocations: after.0 - before.0,
+        deallocations: after.1 - before.1,
+        reallocations: after.2 - before.2,
+        allocated_bytes: after.3 - before.3,
+        peak_bytes: (peak - before.4).max(0) as usize,
+    };
+
+    (result, stats)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn measure_allocations_should_work() {
+        let ((), stats) = measure_allocations(|| {});
+        assert_eq!(stats, AllocStats::default());
+
+        let ((), stats) = measure_allocations(|| {
+            let mut v = Vec::<u8>::with_capacity(10);
+            v.extend([0; 100]);
+            let b = Box::new([0u8; 1000]);
+            drop(b);
+        });
+        assert_eq!(stats.allocations, 2);
+        assert_eq!(stats.deallocations, 2);
+        assert!(stats.reallocations >= 1);
+        assert!(stats.peak_bytes >= 1100);
+
+        // 嵌套测量不影响外层的峰值
+        let ((), outer) = measure_allocations(|| {
+            let big = vec![0u8; 4096];
+            let ((), inner) = measure_allocations(|| drop(vec![0u8; 16]));
+            assert_eq!(inner.peak_bytes, 16);
+            drop(big);
+        });
+        assert_eq!(outer.peak_bytes, 4096 + 16);
+    }
+}
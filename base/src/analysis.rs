@@ -0,0 +1,245 @@
+//! 复杂度实验
+//!
+//! 倍率实验（doubling experiment）：让输入规模依次翻倍，测量每个规模下的运行时间。
+//! 若运行时间 T(n) ≈ a·n^k，则 log T = log a + k·log n，
+//! 对 (log n, log T) 做最小二乘直线拟合，斜率即增长指数 k：
+//! O(n) 约为 1，O(n²) 约为 2，O(nlogn) 略大于 1。
+//!
+//! 启用 `alloc-counter` 特性时还会记录每个规模下的内存峰值，用于观察空间复杂度。
+//!
+//! 实验结果受缓存、编译优化和机器负载影响，应在 release 模式下运行，并只作为对理论分析的直观印证。
+
+use std::{
+    fmt,
+    time::{Duration, Instant},
+};
+
+/// 每个规模至少累计运行的时间，运行时间过短时重复运行取平均值以减小误差
+const MIN_TIME: Duration = Duration::from_millis(5);
+/// 每个规模最多重复运行的次数
+const MAX_REPEATS: u32 = 1000;
+
+/// 一个规模下的测量结果
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Sample {
+    /// 输入规模
+    pub size: usize,
+    /// 平均运行时间
+    pub elapsed: Duration,
+    /// 运行期间新分配且未释放的内存峰值（字节），仅在启用 `alloc-counter` 特性时记录
+    pub peak_bytes: Option<usize>,
+}
+
+/// 倍率实验的结果
+#[derive(Debug, Clone, PartialEq)]
+pub struct GrowthReport {
+    samples: Vec<Sample>,
+}
+
+impl GrowthReport {
+    /// 各规模的测量结果，规模依次翻倍
+    pub fn samples(&self) -> &[Sample] {
+        &self.samples
+    }
+
+    /// 拟合的增长指数，少于两个有效样本时返回 None
+    pub fn exponent(&self) -> Option<f64> {
+        // 运行时间为 0 时无法取对数，忽略这些样本
+        let points = self
+            .samples
+            .iter()
+            .filter(|sample| sample.size > 0 && !sample.elapsed.is_zero())
+            .map(|sample| {
+                let x = (sample.size as f64).ln();
+                let y = sample.elapsed.as_secs_f64().ln();
+                (x, y)
+            })
+            .collect::<Vec<_>>();
+        if points.len() < 2 {
+            return None;
+        }
+
+        // 最小二乘法：斜率 = Σ(x - x̄)(y - ȳ) / Σ(x - x̄)²
+        let n = points.len() as f64;
+        let mean_x = points.iter().map(|(x, _)| x).sum::<f64>() / n;
+        let mean_y = points.iter().map(|(_, y)| y).sum::<f64>() / n;
+        let (covariance, variance) = points.iter().fold((0.0, 0.0), |(c, v), (x, y)| {
+            (c + (x - mean_x) * (y - mean_y), v + (x - mean_x).powi(2))
+        });
+
+        Some(covariance / variance)
+    }
+}
+
+impl fmt::Display for GrowthReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:>12} {:>16} {:>8}", "规模", "耗时", "倍率")?;
+        let show_peak = self.samples.iter().any(|s| s.peak_bytes.is_some());
+        if show_peak {
+            write!(f, " {:>12}", "内存峰值")?;
+        }
+        writeln!(f)?;
+
+        let mut prev: Option<Duration> = None;
+        for sample in &self.samples {
+            let ratio = prev
+                .filter(|prev| !prev.is_zero())
+                .map_or(String::from("-"), |prev| {
+                    format!("{:.2}", sample.elapsed.as_secs_f64() / prev.as_secs_f64())
+                });
+            let elapsed = format!("{:.3?}", sample.elapsed);
+            write!(f, "{:>12} {elapsed:>16} {ratio:>8}", sample.size)?;
+            if show_peak {
+                let peak = sample
+                    .peak_bytes
+                    .map_or(String::from("-"), |b| b.to_string());
+                write!(f, " {peak:>12}")?;
+            }
+            writeln!(f)?;
+            prev = Some(sample.elapsed);
+        }
+
+        match self.exponent() {
+            Some(exponent) => write!(f, "增长指数：{exponent:.2}"),
+            None => write!(f, "增长指数：样本不足"),
+        }
+    }
+}
+
+/// 倍率实验：规模从 start 开始翻倍 rounds 次，测量 routine 的运行时间
+///
+/// 每次运行前先调用 setup 生成该规模的输入，setup 的耗时不计入结果。
+///
+/// # Examples
+///
+/// ```rust
+/// use base::{analysis, generate};
+///
+/// let report = analysis::doubling(
+///     1000,
+///     4,
+///     |n| generate::uniform(n, 0),
+///     |mut v| v.sort(),
+/// );
+///
+/// assert_eq!(report.samples().len(), 4);
+/// assert_eq!(report.samples()[3].size, 8000);
+/// println!("{report}");
+/// ```
+pub fn doubling<I, S, R>(start: usize, rounds: usize, mut setup: S, mut routine: R) -> GrowthReport
+where
+    S: FnMut(usize) -> I,
+    R: FnMut(I),
+{
+    let samples = (0..rounds)
+        .map(|round| {
+            let size = start << round;
+
+            let (mut total, mut repeats, mut peak_bytes) = (Duration::ZERO, 0, None);
+            while total < MIN_TIME && repeats < MAX_REPEATS {
+                let input = setup(size);
+                let now = Instant::now();
+                let peak = run_once(&mut routine, input);
+                total += now.elapsed();
+                repeats += 1;
+                peak_bytes = peak_bytes.max(peak);
+            }
+
+            Sample {
+                size,
+                elapsed: total / repeats.max(1),
+                peak_bytes,
+            }
+        })
+        .collect();
+
+    GrowthReport { samples }
+}
+
+/// 运行一次 routine，启用 `alloc-counter` 特性时返回运行期间的内存峰值
+fn run_once<I>(routine: &mut impl FnMut(I), input: I) -> Option<usize> {
+    #[cfg(feature = "alloc-counter")]
+    {
+        let ((), stats) = crate::alloc_counter::measure_allocations(|| routine(input));
+        Some(stats.peak_bytes)
+    }
+
+    #[cfg(not(feature = "alloc-counter"))]
+    {
+        routine(input);
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report(samples: &[(usize, u64)]) -> GrowthReport {
+        GrowthReport {
+            samples: samples
+                .iter()
+                .map(|&(size, nanos)| Sample {
+                    size,
+                    elapsed: Duration::from_nanos(nanos),
+                    peak_bytes: None,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn exponent_should_fit_power_law() {
+        let linear = report(&[(100, 1_000), (200, 2_000), (400, 4_000), (800, 8_000)]);
+        assert!((linear.exponent().unwrap() - 1.0).abs() < 1e-9);
+
+        let quadratic = report(&[(100, 1_000), (200, 4_000), (400, 16_000)]);
+        assert!((quadratic.exponent().unwrap() - 2.0).abs() < 1e-9);
+
+        assert_eq!(report(&[(100, 1_000)]).exponent(), None);
+        assert_eq!(report(&[(100, 0), (200, 1_000)]).exponent(), None);
+    }
+
+    #[test]
+    fn report_should_display_table() {
+        let text = report(&[(100, 1_000), (200, 4_000)]).to_string();
+
+        assert!(text.contains("4.00"));
+        assert!(text.ends_with("增长指数：2.00"));
+    }
+
+    #[test]
+    fn doubling_should_measure_each_size() {
+        let mut sizes = Vec::new();
+        let report = doubling(
+            8,
+            3,
+            |n| {
+                sizes.push(n);
+                vec![0u8; n]
+            },
+            |v| {
+                std::hint::black_box(v);
+            },
+        );
+
+        let measured = report.samples().iter().map(|s| s.size).collect::<Vec<_>>();
+        assert_eq!(measured, [8, 16, 32]);
+        // 运行时间很短，每个规模都会重复运行多次
+        assert!(sizes.len() > 3);
+    }
+
+    #[cfg(feature = "alloc-counter")]
+    #[test]
+    fn doubling_should_record_peak_bytes() {
+        let report = doubling(1024, 3, |n| n, |n| drop(vec![0u8; n]));
+
+        let peaks = report
+            .samples()
+            .iter()
+            .map(|s| s.peak_bytes)
+            .collect::<Vec<_>>();
+        assert_eq!(peaks, [Some(1024), Some(2048), Some(4096)]);
+        assert!(report.to_string().contains("内存峰值"));
+    }
+}
@@ -0,0 +1,120 @@
+//! 自动补全
+//!
+//! 组合三个模块实现按词频排序的输入建议：
+//! * [`Trie`] 找出所有以给定前缀开头的单词
+//! * [`ChainedHashMap`] 记录每个单词的出现次数
+//! * [`top_k_heap`] 从候选单词中选出出现次数最多的 k 个
+
+use std::cmp::Reverse;
+
+use crate::{ChainedHashMap, Trie, top_k_heap};
+
+/// 按词频排序的自动补全
+///
+/// # Examples
+///
+/// ```rust
+/// use base::Autocomplete;
+///
+/// let mut autocomplete = Autocomplete::new();
+/// for word in "the then they the them then the".split(' ') {
+///     autocomplete.record(word);
+/// }
+///
+/// assert_eq!(autocomplete.suggest("the", 2), ["the", "then"]);
+/// assert_eq!(autocomplete.frequency("then"), 2);
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct Autocomplete {
+    trie: Trie,
+    counts: ChainedHashMap<String, u64>,
+}
+
+impl Autocomplete {
+    /// 创建空的自动补全
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 不同单词的数量
+    pub fn len(&self) -> usize {
+        self.trie.len()
+    }
+
+    /// 判断是否为空
+    pub fn is_empty(&self) -> bool {
+        self.trie.is_empty()
+    }
+
+    /// 记录单词出现一次
+    pub fn record(&mut self, word: &str) {
+        self.record_n(word, 1);
+    }
+
+    /// 记录单词出现 n 次
+    pub fn record_n(&mut self, word: &str, n: u64) {
+        self.trie.insert(word);
+        *self.counts.entry(word.to_string()).or_default() += n;
+    }
+
+    /// 单词出现的次数
+    pub fn frequency(&self, word: &str) -> u64 {
+        self.counts.get(word).copied().unwrap_or(0)
+    }
+
+    /// 返回以 prefix 开头、出现次数最多的 k 个单词，按次数降序排列，次数相同时按字典序排列
+    ///
+    /// 时间复杂度：O(m + s + clogk)，m 为前缀长度，s 为前缀对应子树的大小，c 为候选单词数量
+    pub fn suggest(&self, prefix: &str, k: usize) -> Vec<String> {
+        if k == 0 {
+            return Vec::new();
+        }
+
+        let candidates = self
+            .trie
+            .words_with_prefix(prefix)
+            .into_iter()
+            .map(|word| (self.frequency(&word), Reverse(word)));
+
+        // 堆中的元素是 Reverse，into_sorted_vec 的升序即为次数的降序
+        top_k_heap(candidates, k)
+            .into_sorted_vec()
+            .into_iter()
+            .map(|Reverse((_, Reverse(word)))| word)
+            .collect()
+    }
+}
+
+impl<'a> FromIterator<&'a str> for Autocomplete {
+    fn from_iter<I: IntoIterator<Item = &'a str>>(iter: I) -> Self {
+        let mut autocomplete = Autocomplete::new();
+
+        iter.into_iter().for_each(|word| autocomplete.record(word));
+
+        autocomplete
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn autocomplete_should_rank_by_frequency() {
+        let mut autocomplete = Autocomplete::from_iter(["car", "cat", "cart", "care", "dog"]);
+        autocomplete.record_n("cart", 5);
+        autocomplete.record_n("care", 3);
+        autocomplete.record("cat");
+
+        assert_eq!(autocomplete.len(), 5);
+        assert_eq!(autocomplete.frequency("cart"), 6);
+        assert_eq!(autocomplete.frequency("cow"), 0);
+
+        assert_eq!(autocomplete.suggest("ca", 3), ["cart", "care", "cat"]);
+        assert_eq!(autocomplete.suggest("car", 10), ["cart", "care", "car"]);
+        // 次数相同时按字典序
+        assert_eq!(autocomplete.suggest("", 5)[3..], ["car", "dog"]);
+        assert!(autocomplete.suggest("x", 3).is_empty());
+        assert!(autocomplete.suggest("c", 0).is_empty());
+    }
+}
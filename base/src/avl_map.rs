@@ -0,0 +1,600 @@
+//! 基于 AVL 树的有序映射
+//!
+//! 与 [`AvlTree`](crate::AvlTree) 使用 `Rc<RefCell<_>>` 连接结点不同，AvlMap 把结点保存在 Vec 中，
+//! 用下标表示父子关系。旋转只修改下标，结点在 Vec 中的位置不会改变，
+//! 因此可以在插入后直接返回新值的可变借用，支持 [`entry`](AvlMap::entry) 这样的接口。
+
+use std::{cmp::Ordering, collections::BTreeMap, mem};
+
+#[derive(Debug, Clone)]
+struct AvlNode<K, V> {
+    key: K,
+    value: V,
+    /// 叶结点高度为 0
+    height: i32,
+    left: Option<usize>,
+    right: Option<usize>,
+}
+
+/// 基于 AVL 树的有序映射，插入、删除、查找的时间复杂度均为 O(logn)
+#[derive(Debug, Clone)]
+pub struct AvlMap<K, V> {
+    /// 已删除的结点为 None
+    nodes: Vec<Option<AvlNode<K, V>>>,
+    /// 已删除、可复用的结点下标
+    free: Vec<usize>,
+    root: Option<usize>,
+    len: usize,
+}
+
+impl<K, V> AvlMap<K, V> {
+    /// 创建空的 AvlMap
+    pub fn new() -> Self {
+        Self {
+            nodes: Vec::new(),
+            free: Vec::new(),
+            root: None,
+            len: 0,
+        }
+    }
+
+    /// 键值对数量
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// 判断是否为空
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// 树的高度，空树的高度为 -1
+    pub fn height(&self) -> i32 {
+        self.height_of(self.root)
+    }
+
+    /// 按键升序遍历所有键值对（中序遍历）
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> {
+        let mut stack = Vec::new();
+        let mut current = self.root;
+
+        std::iter::from_fn(move || {
+            while let Some(index) = current {
+                stack.push(index);
+                current = self.node(index).left;
+            }
+
+            let node = self.node(stack.pop()?);
+            current = node.right;
+            Some((&node.key, &node.value))
+        })
+    }
+
+    fn node(&self, index: usize) -> &AvlNode<K, V> {
+        // Safety: 树中的下标一定指向未删除的结点
+        self.nodes[index].as_ref().unwrap()
+    }
+
+    fn node_mut(&mut self, index: usize) -> &mut AvlNode<K, V> {
+        // Safety: 同上
+        self.nodes[index].as_mut().unwrap()
+    }
+
+    /// 创建新结点，优先复用已删除结点的位置
+    fn alloc(&mut self, key: K, value: V) -> usize {
+        let node = Some(AvlNode {
+            key,
+            value,
+            height: 0,
+            left: None,
+            right: None,
+        });
+
+        match self.free.pop() {
+            Some(free) => {
+                self.nodes[free] = node;
+                free
+            }
+            None => {
+                self.nodes.push(node);
+                self.nodes.len() - 1
+            }
+        }
+    }
+
+    fn height_of(&self, link: Option<usize>) -> i32 {
+        link.map_or(-1, |index| self.node(index).height)
+    }
+
+    fn update_height(&mut self, index: usize) {
+        let node = self.node(index);
+        let height = self.height_of(node.left).max(self.height_of(node.right)) + 1;
+
+        self.node_mut(index).height = height;
+    }
+
+    /// 平衡因子：左子树高度减去右子树高度
+    fn balance_factor(&self, index: usize) -> i32 {
+        let node = self.node(index);
+
+        self.height_of(node.left) - self.height_of(node.right)
+    }
+
+    /// 右旋，返回旋转后子树的根结点
+    fn right_rotate(&mut self, index: usize) -> usize {
+        // Safety: 只有左子树更高时才右旋，左子结点一定存在
+        let child = self.node(index).left.unwrap();
+
+        self.node_mut(index).left = self.node(child).right;
+        self.node_mut(child).right = Some(index);
+        self.update_height(index);
+        self.update_height(child);
+
+        child
+    }
+
+    /// 左旋，返回旋转后子树的根结点
+    fn left_rotate(&mut self, index: usize) -> usize {
+        // Safety: 只有右子树更高时才左旋，右子结点一定存在
+        let child = self.node(index).right.unwrap();
+
+        self.node_mut(index).right = self.node(child).left;
+        self.node_mut(child).left = Some(index);
+        self.update_height(index);
+        self.update_height(child);
+
+        child
+    }
+
+    /// 更新结点高度并通过旋转恢复平衡，返回子树新的根结点
+    fn rebalance(&mut self, index: usize) -> usize {
+        self.update_height(index);
+
+        let factor = self.balance_factor(index);
+        if factor > 1 {
+            // Safety: 平衡因子大于 1，左子结点一定存在
+            let left = self.node(index).left.unwrap();
+            if self.balance_factor(left) < 0 {
+                // 先左旋后右旋
+                let left = self.left_rotate(left);
+                self.node_mut(index).left = Some(left);
+            }
+            self.right_rotate(index)
+        } else if factor < -1 {
+            // Safety: 平衡因子小于 -1，右子结点一定存在
+            let right = self.node(index).right.unwrap();
+            if self.balance_factor(right) > 0 {
+                // 先右旋后左旋
+                let right = self.right_rotate(right);
+                self.node_mut(index).right = Some(right);
+            }
+            self.left_rotate(index)
+        } else {
+            index
+        }
+    }
+
+    /// 删除以 index 为根的子树中的最小结点，返回子树新的根结点和被删除的结点
+    fn remove_min(&mut self, index: usize) -> (Option<usize>, usize) {
+        match self.node(index).left {
+            None => (self.node(index).right, index),
+            Some(left) => {
+                let (left, min) = self.remove_min(left);
+                self.node_mut(index).left = left;
+                (Some(self.rebalance(index)), min)
+            }
+        }
+    }
+}
+
+impl<K: Ord, V> AvlMap<K, V> {
+    /// 查找键所在的结点
+    fn find(&self, key: &K) -> Option<usize> {
+        let mut current = self.root;
+
+        while let Some(index) = current {
+            let node = self.node(index);
+            current = match key.cmp(&node.key) {
+                Ordering::Equal => break,
+                Ordering::Less => node.left,
+                Ordering::Greater => node.right,
+            };
+        }
+
+        current
+    }
+
+    /// 获取键对应的值
+    pub fn get(&self, key: &K) -> Option<&V> {
+        self.find(key).map(|index| &self.node(index).value)
+    }
+
+    /// 获取键对应值的可变借用
+    pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        let index = self.find(key)?;
+
+        Some(&mut self.node_mut(index).value)
+    }
+
+    /// 判断是否包含指定的键
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.find(key).is_some()
+    }
+
+    /// 不大于 key 的最大键及其值，时间复杂度：O(logn)
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use base::AvlMap;
+    ///
+    /// let map = AvlMap::from([(1, 'a'), (5, 'b'), (9, 'c')]);
+    ///
+    /// assert_eq!(map.floor(&5), Some((&5, &'b')));
+    /// assert_eq!(map.floor(&8), Some((&5, &'b')));
+    /// assert_eq!(map.floor(&0), None);
+    /// assert_eq!(map.ceiling(&6), Some((&9, &'c')));
+    /// assert_eq!(map.ceiling(&10), None);
+    /// ```
+    pub fn floor(&self, key: &K) -> Option<(&K, &V)> {
+        self.bound(key, Ordering::Less)
+    }
+
+    /// 不小于 key 的最小键及其值，时间复杂度：O(logn)
+    pub fn ceiling(&self, key: &K) -> Option<(&K, &V)> {
+        self.bound(key, Ordering::Greater)
+    }
+
+    /// 查找等于 key 的结点，不存在时返回在 side 一侧离 key 最近的结点
+    fn bound(&self, key: &K, side: Ordering) -> Option<(&K, &V)> {
+        let mut current = self.root;
+        let mut candidate = None;
+
+        while let Some(index) = current {
+            let node = self.node(index);
+            match node.key.cmp(key) {
+                Ordering::Equal => return Some((&node.key, &node.value)),
+                // 结点位于 side 一侧，记为候选后向 key 靠近
+                ordering if ordering == side => {
+                    candidate = Some(index);
+                    current = if side == Ordering::Less {
+                        node.right
+                    } else {
+                        node.left
+                    };
+                }
+                _ => {
+                    current = if side == Ordering::Less {
+                        node.left
+                    } else {
+                        node.right
+                    }
+                }
+            }
+        }
+
+        candidate.map(|index| {
+            let node = self.node(index);
+            (&node.key, &node.value)
+        })
+    }
+
+    /// 插入键值对，键已存在时替换其值并返回旧值
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        match self.entry(key) {
+            Entry::Occupied(mut entry) => Some(entry.insert(value)),
+            Entry::Vacant(entry) => {
+                entry.insert(value);
+                None
+            }
+        }
+    }
+
+    /// 删除键对应的键值对，返回被删除的值
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        let (root, removed) = self.remove_at(self.root, key);
+        self.root = root;
+
+        let removed = removed?;
+        self.free.push(removed);
+        self.len -= 1;
+
+        self.nodes[removed].take().map(|node| node.value)
+    }
+
+    /// 获取键对应的条目，用于原地修改或按需插入，只需查找一次
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use base::AvlMap;
+    ///
+    /// let mut counts = AvlMap::new();
+    /// for word in ["a", "b", "a", "c", "a"] {
+    ///     *counts.entry(word).or_insert(0) += 1;
+    /// }
+    ///
+    /// assert_eq!(counts.get(&"a"), Some(&3));
+    /// assert_eq!(counts.len(), 3);
+    /// ```
+    pub fn entry(&mut self, key: K) -> Entry<'_, K, V> {
+        match self.find(&key) {
+            Some(index) => Entry::Occupied(OccupiedEntry { map: self, index }),
+            None => Entry::Vacant(VacantEntry { map: self, key }),
+        }
+    }
+
+    /// 把结点 index 插入以 link 为根的子树，返回子树新的根结点。调用者保证键不存在
+    fn insert_at(&mut self, link: Option<usize>, index: usize) -> usize {
+        let Some(root) = link else {
+            return index;
+        };
+
+        if self.node(index).key < self.node(root).key {
+            let left = self.insert_at(self.node(root).left, index);
+            self.node_mut(root).left = Some(left);
+        } else {
+            let right = self.insert_at(self.node(root).right, index);
+            self.node_mut(root).right = Some(right);
+        }
+
+        self.rebalance(root)
+    }
+
+    /// 从以 link 为根的子树中摘除键所在的结点，返回子树新的根结点和被摘除的结点
+    fn remove_at(&mut self, link: Option<usize>, key: &K) -> (Option<usize>, Option<usize>) {
+        let Some(index) = link else {
+            return (None, None);
+        };
+
+        let node = self.node(index);
+        match key.cmp(&node.key) {
+            Ordering::Less => {
+                let (left, removed) = self.remove_at(node.left, key);
+                self.node_mut(index).left = left;
+                (Some(self.rebalance(index)), removed)
+            }
+            Ordering::Greater => {
+                let (right, removed) = self.remove_at(node.right, key);
+                self.node_mut(index).right = right;
+                (Some(self.rebalance(index)), removed)
+            }
+            Ordering::Equal => {
+                let root = match (node.left, node.right) {
+                    // 子结点数量为 0 或 1，用子结点代替当前结点
+                    (None, child) | (child, None) => child,
+                    // 子结点数量为 2，用右子树的最小结点代替当前结点
+                    (Some(left), Some(right)) => {
+                        let (right, min) = self.remove_min(right);
+                        let min_node = self.node_mut(min);
+                        min_node.left = Some(left);
+                        min_node.right = right;
+                        Some(self.rebalance(min))
+                    }
+                };
+                (root, Some(index))
+            }
+        }
+    }
+}
+
+impl<K, V> Default for AvlMap<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Ord, V> FromIterator<(K, V)> for AvlMap<K, V> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut map = AvlMap::new();
+
+        iter.into_iter().for_each(|(key, value)| {
+            map.insert(key, value);
+        });
+
+        map
+    }
+}
+
+impl<K: Ord, V, const N: usize> From<[(K, V); N]> for AvlMap<K, V> {
+    fn from(v: [(K, V); N]) -> Self {
+        v.into_iter().collect()
+    }
+}
+
+impl<K: Ord, V> From<BTreeMap<K, V>> for AvlMap<K, V> {
+    fn from(map: BTreeMap<K, V>) -> Self {
+        map.into_iter().collect()
+    }
+}
+
+impl<K: Ord, V> From<AvlMap<K, V>> for BTreeMap<K, V> {
+    fn from(map: AvlMap<K, V>) -> Self {
+        map.nodes
+            .into_iter()
+            .flatten()
+            .map(|node| (node.key, node.value))
+            .collect()
+    }
+}
+
+/// AvlMap 中的一个条目，由 [`AvlMap::entry`] 返回
+pub enum Entry<'a, K, V> {
+    /// 键已存在
+    Occupied(OccupiedEntry<'a, K, V>),
+    /// 键不存在
+    Vacant(VacantEntry<'a, K, V>),
+}
+
+/// 已存在的条目
+pub struct OccupiedEntry<'a, K, V> {
+    map: &'a mut AvlMap<K, V>,
+    index: usize,
+}
+
+/// 不存在的条目
+pub struct VacantEntry<'a, K, V> {
+    map: &'a mut AvlMap<K, V>,
+    key: K,
+}
+
+impl<'a, K: Ord, V> Entry<'a, K, V> {
+    /// 条目的键
+    pub fn key(&self) -> &K {
+        match self {
+            Entry::Occupied(entry) => entry.key(),
+            Entry::Vacant(entry) => entry.key(),
+        }
+    }
+
+    /// 键不存在时插入 default，返回值的可变借用
+    pub fn or_insert(self, default: V) -> &'a mut V {
+        self.or_insert_with(|| default)
+    }
+
+    /// 键不存在时插入 f 的返回值，返回值的可变借用
+    pub fn or_insert_with<F: FnOnce() -> V>(self, f: F) -> &'a mut V {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(f()),
+        }
+    }
+
+    /// 键存在时用 f 修改其值
+    pub fn and_modify<F: FnOnce(&mut V)>(mut self, f: F) -> Self {
+        if let Entry::Occupied(entry) = &mut self {
+            f(entry.get_mut());
+        }
+
+        self
+    }
+}
+
+impl<'a, K: Ord, V: Default> Entry<'a, K, V> {
+    /// 键不存在时插入默认值，返回值的可变借用
+    pub fn or_default(self) -> &'a mut V {
+        self.or_insert_with(V::default)
+    }
+}
+
+impl<'a, K, V> OccupiedEntry<'a, K, V> {
+    /// 条目的键
+    pub fn key(&self) -> &K {
+        &self.map.node(self.index).key
+    }
+
+    /// 条目的值
+    pub fn get(&self) -> &V {
+        &self.map.node(self.index).value
+    }
+
+    /// 条目的值的可变借用
+    pub fn get_mut(&mut self) -> &mut V {
+        &mut self.map.node_mut(self.index).value
+    }
+
+    /// 转换为值的可变借用，其生命周期与 AvlMap 的借用相同
+    pub fn into_mut(self) -> &'a mut V {
+        &mut self.map.node_mut(self.index).value
+    }
+
+    /// 替换条目的值，返回旧值
+    pub fn insert(&mut self, value: V) -> V {
+        mem::replace(self.get_mut(), value)
+    }
+}
+
+impl<'a, K: Ord, V> VacantEntry<'a, K, V> {
+    /// 条目的键
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+
+    /// 取回条目的键
+    pub fn into_key(self) -> K {
+        self.key
+    }
+
+    /// 插入值，返回值的可变借用
+    pub fn insert(self, value: V) -> &'a mut V {
+        let map = self.map;
+        let index = map.alloc(self.key, value);
+
+        map.root = Some(map.insert_at(map.root, index));
+        map.len += 1;
+
+        // 旋转只修改结点之间的链接，新结点的下标不变
+        &mut map.node_mut(index).value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generate::{self, Operation};
+
+    #[test]
+    fn avl_map_should_match_btree_map() {
+        let mut map = AvlMap::new();
+        let mut expected = BTreeMap::new();
+
+        for (i, op) in generate::operations(5000, 200, 2).into_iter().enumerate() {
+            match op {
+                Operation::Insert(key) => assert_eq!(map.insert(key, i), expected.insert(key, i)),
+                Operation::Remove(key) => assert_eq!(map.remove(&key), expected.remove(&key)),
+                Operation::Get(key) => {
+                    assert_eq!(map.get(&key), expected.get(&key));
+                    assert_eq!(map.floor(&key), expected.range(..=key).next_back());
+                    assert_eq!(map.ceiling(&key), expected.range(key..).next());
+                }
+            }
+        }
+
+        assert_eq!(map.len(), expected.len());
+        assert!(map.iter().eq(expected.iter()));
+        // AVL 树的高度不超过 1.44 * log2(n + 2)
+        let bound = 1.44 * ((map.len() + 2) as f64).log2();
+        assert!((map.height() as f64) < bound);
+    }
+
+    #[test]
+    fn avl_map_should_stay_balanced() {
+        let map = (0..1023).map(|i| (i, ())).collect::<AvlMap<_, _>>();
+
+        assert_eq!(map.height(), 9);
+        assert!(AvlMap::<i32, ()>::new().height() < 0);
+    }
+
+    #[test]
+    fn avl_map_entry_should_work() {
+        let mut map = AvlMap::from([("a", 1)]);
+
+        map.entry("a").and_modify(|v| *v += 10).or_insert(0);
+        map.entry("b").and_modify(|v| *v += 10).or_insert(0);
+        *map.entry("c").or_default() += 5;
+        assert_eq!(map.entry("d").key(), &"d");
+
+        match map.entry("a") {
+            Entry::Occupied(mut entry) => {
+                assert_eq!(entry.get(), &11);
+                assert_eq!(entry.insert(12), 11);
+            }
+            Entry::Vacant(_) => unreachable!(),
+        }
+        match map.entry("e") {
+            Entry::Occupied(_) => unreachable!(),
+            Entry::Vacant(entry) => assert_eq!(entry.into_key(), "e"),
+        }
+
+        let mut calls = 0;
+        map.entry("b").or_insert_with(|| {
+            calls += 1;
+            100
+        });
+        assert_eq!(calls, 0);
+
+        let map = BTreeMap::from(map);
+        assert_eq!(map, BTreeMap::from([("a", 12), ("b", 0), ("c", 5)]));
+        assert_eq!(AvlMap::from(map).len(), 3);
+    }
+}
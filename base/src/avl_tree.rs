@@ -1,9 +1,9 @@
 //! AVL 树是一种平衡搜索二叉树，它能够在不影响二叉树的中序遍历序列的前提下，
 //! 通过旋转操作，使失衡节点重新恢复平衡。
 
-use std::{cell::RefCell, cmp::Ordering, rc::Rc};
+use std::{cell::RefCell, cmp::Ordering, collections::BTreeSet, rc::Rc};
 
-use crate::binary_tree;
+use crate::binary_tree::{self, DedupStats};
 
 type NodeRc<T> = Rc<RefCell<AvlTreeNode<T>>>;
 type OptionNodeRc<T> = Option<NodeRc<T>>;
@@ -75,6 +75,24 @@ impl<T: Ord + Clone, const N: usize> From<[T; N]> for AvlTree<T> {
     }
 }
 
+impl<T: Ord + Clone> From<BTreeSet<T>> for AvlTree<T> {
+    fn from(set: BTreeSet<T>) -> Self {
+        let mut tree = AvlTree::new();
+
+        set.into_iter().for_each(|val| {
+            tree.insert(val);
+        });
+
+        tree
+    }
+}
+
+impl<T: Ord + Clone> From<AvlTree<T>> for BTreeSet<T> {
+    fn from(tree: AvlTree<T>) -> Self {
+        tree.to_tree().to_vec().into_iter().collect()
+    }
+}
+
 impl<T> AvlTree<T> {
     pub fn new() -> Self {
         Self { root: None }
@@ -111,6 +129,40 @@ impl<T: Clone + Ord> AvlTree<T> {
         self.root = insert_recursive(self.root.clone(), val);
     }
 
+    /// 插入节点，返回是否插入成功，树中已存在相同的值时返回 false
+    pub fn try_insert(&mut self, val: T) -> bool {
+        // 先查找一次，树的高度为 O(logn)，额外的开销可以接受
+        if self.search(&val).is_some() {
+            return false;
+        }
+
+        self.insert(val);
+        true
+    }
+
+    /// 依次插入迭代器中的元素构建 AVL 树，同时统计跳过的重复元素
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use base::AvlTree;
+    ///
+    /// let (tree, stats) = AvlTree::from_iter_dedup([5, 5, 5, 1]);
+    ///
+    /// assert_eq!(tree.to_tree().to_vec(), [1, 5]);
+    /// assert_eq!((stats.inserted, stats.duplicates), (2, 2));
+    /// ```
+    pub fn from_iter_dedup(iter: impl IntoIterator<Item = T>) -> (Self, DedupStats) {
+        let mut tree = Self::new();
+        let mut stats = DedupStats::default();
+
+        for val in iter {
+            stats.record(tree.try_insert(val));
+        }
+
+        (tree, stats)
+    }
+
     /// 删除节点
     pub fn remove(&mut self, val: &T) {
         self.root = remove_recursive(self.root.clone(), val);
@@ -360,6 +412,30 @@ mod tests {
         assert_eq!(tree.to_vec(), vec![3, 4, 5, 7]);
     }
 
+    #[test]
+    fn avl_dedup_should_work() {
+        let mut avl_tree = AvlTree::new();
+        assert!(avl_tree.try_insert(1));
+        assert!(!avl_tree.try_insert(1));
+
+        let (avl_tree, stats) = AvlTree::from_iter_dedup([4, 2, 4, 6, 2, 8]);
+        assert_eq!(avl_tree.to_tree().to_vec(), [2, 4, 6, 8]);
+        assert_eq!(
+            stats,
+            DedupStats {
+                inserted: 4,
+                duplicates: 2
+            }
+        );
+    }
+
+    #[test]
+    fn avl_std_conversions_should_work() {
+        let avl_tree = AvlTree::from(BTreeSet::from([5, 1, 9, 3]));
+        assert!(avl_tree.search(&9).is_some());
+        assert_eq!(BTreeSet::from(avl_tree), BTreeSet::from([1, 3, 5, 9]));
+    }
+
     #[test]
     fn avl_convert_should_work() {
         let avl_tree = AvlTree::from([1, 3, 4, 5, 7]);
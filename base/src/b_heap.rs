@@ -0,0 +1,266 @@
+//! B-heap：按页组织内存布局的大顶堆
+//!
+//! 普通的数组堆按层序存放结点，结点 i 的子结点位于 2i + 1 和 2i + 2，越往下父子结点在内存中相距越远，
+//! 堆很大时一次从顶至底堆化的每一步几乎都会访问新的缓存行和内存页。
+//! B-heap（Poul-Henning Kamp, "You're Doing It Wrong", 2010）把堆切分为高度为 H 的完全二叉子树，
+//! 每棵子树的 2^H - 1 个结点连续存放为一页，页内仍按层序排列：
+//! * 页内非叶结点的子结点在同一页内
+//! * 页内第 l 个叶结点的两个子结点分别是第 2l 和第 2l + 1 个子页的根结点，每页有 2^H 个子页，
+//!   页之间按 2^H 叉树的层序编号
+//!
+//! 从堆顶到堆底的路径只经过约 log(n) / H 个页，其余访问都落在已经载入的页中。
+//! 元素总是占用物理下标 0..n，父结点的下标总小于子结点，因此插入时追加到末尾、
+//! 删除时用末尾的元素替换堆顶，与普通的数组堆完全相同，只是父子关系的下标计算不同。
+//! H = 1 时每页只有一个结点，布局退化为普通的数组堆。
+
+use std::fmt;
+
+use crate::Heap;
+
+/// 按页组织内存布局的大顶堆，每页是一棵高度为 PAGE_HEIGHT 的完全二叉子树
+///
+/// # Examples
+///
+/// ```rust
+/// use base::{BHeap, Heap};
+///
+/// let mut heap = BHeap::<_, 2>::from_vec(vec![3, 1, 4, 1, 5, 9, 2, 6]);
+///
+/// // 每页 3 个结点：第 0 页是堆顶和它的两个子结点
+/// assert_eq!(heap.as_slice()[0], 9);
+/// assert_eq!(heap.pop(), Some(9));
+/// assert_eq!(heap.pop(), Some(6));
+/// heap.push(7);
+/// assert_eq!(heap.peek(), Some(&7));
+/// ```
+pub struct BHeap<T, const PAGE_HEIGHT: u32 = 9>(Vec<T>);
+
+impl<T: PartialOrd, const PAGE_HEIGHT: u32> BHeap<T, PAGE_HEIGHT> {
+    /// 每页的结点数
+    const PAGE: usize = {
+        assert!(PAGE_HEIGHT >= 1 && PAGE_HEIGHT < usize::BITS / 2);
+        (1 << PAGE_HEIGHT) - 1
+    };
+    /// 页内第一个叶结点的偏移
+    const FIRST_LEAF: usize = (1 << (PAGE_HEIGHT - 1)) - 1;
+    /// 每页的子页数
+    const FANOUT: usize = 1 << PAGE_HEIGHT;
+
+    /// 创建一个空的 BHeap
+    pub fn new() -> Self {
+        Self(Vec::new())
+    }
+
+    /// 堆的物理布局
+    pub fn as_slice(&self) -> &[T] {
+        &self.0
+    }
+
+    /// 转换为堆的物理布局
+    pub fn into_vec(self) -> Vec<T> {
+        self.0
+    }
+
+    /// 结点 i 的左右子结点的下标，溢出时饱和为 usize::MAX，必然越界
+    fn children(i: usize) -> (usize, usize) {
+        let (page, offset) = (i / Self::PAGE, i % Self::PAGE);
+
+        if offset < Self::FIRST_LEAF {
+            let left = page * Self::PAGE + 2 * offset + 1;
+            (left, left + 1)
+        } else {
+            // 第 leaf 个叶结点的子结点是第 2 * leaf 和 2 * leaf + 1 个子页的根结点
+            let leaf = offset - Self::FIRST_LEAF;
+            let left_page = page
+                .saturating_mul(Self::FANOUT)
+                .saturating_add(1 + 2 * leaf);
+            let left = left_page.saturating_mul(Self::PAGE);
+            (left, left.saturating_add(Self::PAGE))
+        }
+    }
+
+    /// 结点 i 的父结点的下标，堆顶结点没有父结点
+    fn parent(i: usize) -> Option<usize> {
+        let (page, offset) = (i / Self::PAGE, i % Self::PAGE);
+
+        if offset > 0 {
+            return Some(page * Self::PAGE + (offset - 1) / 2);
+        }
+        // 页的根结点，父结点是父页中的叶结点
+        let child = page.checked_sub(1)?;
+        let (parent_page, nth) = (child / Self::FANOUT, child % Self::FANOUT);
+
+        Some(parent_page * Self::PAGE + Self::FIRST_LEAF + nth / 2)
+    }
+
+    /// 从结点 i 开始，从底至顶堆化
+    fn sift_up(&mut self, mut i: usize) {
+        while let Some(p) = Self::parent(i) {
+            if self.0[i] <= self.0[p] {
+                break;
+            }
+            self.0.swap(i, p);
+            i = p;
+        }
+    }
+
+    /// 从结点 i 开始，从顶至底堆化
+    fn sift_down(&mut self, mut i: usize) {
+        let len = self.0.len();
+
+        loop {
+            let (l, r) = Self::children(i);
+            let mut max = i;
+            if l < len && self.0[l] > self.0[max] {
+                max = l;
+            }
+            if r < len && self.0[r] > self.0[max] {
+                max = r;
+            }
+            if max == i {
+                break;
+            }
+            self.0.swap(i, max);
+            i = max;
+        }
+    }
+}
+
+impl<T: PartialOrd, const PAGE_HEIGHT: u32> Heap<T> for BHeap<T, PAGE_HEIGHT> {
+    type HeapTp = BHeap<T, PAGE_HEIGHT>;
+
+    // 时间复杂度：O(n)
+    fn from_vec<I>(v: I) -> Self::HeapTp
+    where
+        I: Into<Vec<T>>,
+    {
+        let mut heap = BHeap(v.into());
+        // 父结点的下标总小于子结点，从后往前堆化即可，叶结点会立即返回
+        for i in (0..heap.0.len()).rev() {
+            heap.sift_down(i);
+        }
+
+        heap
+    }
+
+    fn peek(&self) -> Option<&T> {
+        self.0.first()
+    }
+
+    // 时间复杂度：O(logn)
+    fn push(&mut self, val: T) {
+        self.0.push(val);
+        self.sift_up(self.0.len() - 1);
+    }
+
+    // 时间复杂度：O(logn)
+    fn pop(&mut self) -> Option<T> {
+        if self.0.is_empty() {
+            return None;
+        }
+        let val = self.0.swap_remove(0);
+        self.sift_down(0);
+
+        Some(val)
+    }
+
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl<T: PartialOrd, const PAGE_HEIGHT: u32> Default for BHeap<T, PAGE_HEIGHT> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: PartialOrd, const PAGE_HEIGHT: u32> From<Vec<T>> for BHeap<T, PAGE_HEIGHT> {
+    fn from(v: Vec<T>) -> Self {
+        BHeap::from_vec(v)
+    }
+}
+
+impl<T: fmt::Debug, const PAGE_HEIGHT: u32> fmt::Debug for BHeap<T, PAGE_HEIGHT> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("BHeap").field(&self.0).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{MaxHeap, generate};
+
+    /// 所有结点都不大于父结点，且父子关系互逆
+    fn check<const H: u32>(heap: &BHeap<u64, H>) {
+        for i in 1..heap.len() {
+            let p = BHeap::<u64, H>::parent(i).unwrap();
+            assert!(p < i);
+            assert!(heap.0[i] <= heap.0[p]);
+
+            let (l, r) = BHeap::<u64, H>::children(p);
+            assert!(i == l || i == r);
+        }
+    }
+
+    fn heap_should_match_max_heap<const H: u32>() {
+        let keys = generate::few_distinct(3000, 500, u64::from(H));
+        let mut heap = BHeap::<_, H>::from(keys[..1000].to_vec());
+        let mut expected = MaxHeap::from(keys[..1000].to_vec());
+        check(&heap);
+
+        for (i, &key) in keys[1000..].iter().enumerate() {
+            if i % 3 == 0 {
+                assert_eq!(heap.pop(), expected.pop());
+            } else {
+                heap.push(key);
+                expected.push(key);
+            }
+            assert_eq!(heap.peek(), expected.peek());
+        }
+        check(&heap);
+
+        while let Some(top) = expected.pop() {
+            assert_eq!(heap.pop(), Some(top));
+        }
+        assert!(heap.is_empty());
+    }
+
+    #[test]
+    fn b_heap_should_match_max_heap() {
+        heap_should_match_max_heap::<1>();
+        heap_should_match_max_heap::<2>();
+        heap_should_match_max_heap::<3>();
+        heap_should_match_max_heap::<9>();
+    }
+
+    #[test]
+    fn b_heap_with_height_one_should_be_array_heap() {
+        for i in 0..1000 {
+            assert_eq!(BHeap::<u64, 1>::children(i), (2 * i + 1, 2 * i + 2));
+            assert_eq!(BHeap::<u64, 1>::parent(i), i.checked_sub(1).map(|i| i / 2));
+        }
+
+        let keys = generate::uniform(100, 4);
+        let heap = BHeap::<_, 1>::from(keys.clone());
+        assert_eq!(heap.as_slice(), MaxHeap::from(keys).as_slice());
+    }
+
+    #[test]
+    fn b_heap_pages_should_be_contiguous_subtrees() {
+        // H = 2：每页 3 个结点，第 0 页的两个叶结点各有两个子页
+        type H2 = BHeap<u64, 2>;
+        assert_eq!(H2::children(0), (1, 2));
+        assert_eq!(H2::children(1), (3, 6));
+        assert_eq!(H2::children(2), (9, 12));
+        assert_eq!(H2::children(3), (4, 5));
+        assert_eq!(H2::parent(12), Some(2));
+        assert_eq!(H2::parent(5), Some(3));
+        assert_eq!(H2::parent(0), None);
+    }
+}
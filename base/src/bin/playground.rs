@@ -0,0 +1,195 @@
+//! 交互式演示程序：在命令行中操作各种数据结构，每条命令执行后打印结构的当前状态
+//!
+//! 运行：cargo run --features playground --bin playground
+//!
+//! ```text
+//! > bst insert 5
+//! > heap push 3
+//! > list reverse
+//! ```
+
+use std::io::{self, BufRead, Write};
+
+use base::{AvlTree, BinarySearchTree, Heap, LinkedList, MaxHeap, bt};
+
+const HELP: &str = "\
+可用命令：
+  bst insert <n> | bst remove <n> | bst search <n> | bst print
+  avl insert <n> | avl remove <n> | avl search <n> | avl print
+  heap push <n>  | heap pop       | heap peek      | heap print
+  list push_front <n> | list push_back <n> | list pop_front | list pop_back
+  list reverse   | list print
+  help | quit";
+
+#[derive(Default)]
+struct Playground {
+    bst: BinarySearchTree<i64>,
+    avl: AvlTree<i64>,
+    heap: MaxHeap<i64>,
+    list: LinkedList<i64>,
+}
+
+impl Playground {
+    /// 执行一条命令，返回要打印的内容
+    fn execute(&mut self, line: &str) -> Result<String, String> {
+        let words = line.split_whitespace().collect::<Vec<_>>();
+        let arg = || -> Result<i64, String> {
+            let arg = words.get(2).ok_or("缺少参数")?;
+            arg.parse().map_err(|_| format!("参数必须是整数：{arg}"))
+        };
+
+        let message = match words.as_slice() {
+            [] => return Ok(String::new()),
+            ["help"] => return Ok(HELP.to_string()),
+
+            ["bst", "insert", _] => {
+                self.bst.insert(arg()?);
+                None
+            }
+            ["bst", "remove", _] => {
+                self.bst.remove(&arg()?);
+                None
+            }
+            ["bst", "search", _] => Some(found(self.bst.search(&arg()?).is_some())),
+            ["bst", "print"] => None,
+
+            ["avl", "insert", _] => {
+                self.avl.insert(arg()?);
+                None
+            }
+            ["avl", "remove", _] => {
+                self.avl.remove(&arg()?);
+                None
+            }
+            ["avl", "search", _] => Some(found(self.avl.search(&arg()?).is_some())),
+            ["avl", "print"] => None,
+
+            ["heap", "push", _] => {
+                self.heap.push(arg()?);
+                None
+            }
+            ["heap", "pop"] => Some(format!("弹出：{:?}", self.heap.pop())),
+            ["heap", "peek"] => Some(format!("堆顶：{:?}", self.heap.peek())),
+            ["heap", "print"] => None,
+
+            ["list", "push_front", _] => {
+                self.list.push_front(arg()?);
+                None
+            }
+            ["list", "push_back", _] => {
+                self.list.push_back(arg()?);
+                None
+            }
+            ["list", "pop_front"] => Some(format!("弹出：{:?}", self.list.pop_front())),
+            ["list", "pop_back"] => Some(format!("弹出：{:?}", self.list.pop_back())),
+            ["list", "reverse"] => {
+                self.list.reverse();
+                None
+            }
+            ["list", "print"] => None,
+
+            _ => return Err(format!("未知命令：{line}，输入 help 查看可用命令")),
+        };
+
+        let state = self.render(words[0]);
+        Ok(match message {
+            Some(message) => format!("{message}\n{state}"),
+            None => state,
+        })
+    }
+
+    /// 打印结构的当前状态
+    fn render(&self, structure: &str) -> String {
+        match structure {
+            "bst" => render_tree(bt::render(&self.bst.to_tree().root)),
+            "avl" => render_tree(bt::render(&self.avl.to_tree().root)),
+            "heap" => format!("{:?}", self.heap.as_slice()),
+            "list" => {
+                let items = self.list.iter().map(i64::to_string).collect::<Vec<_>>();
+                format!("[{}]", items.join(" <-> "))
+            }
+            _ => unreachable!(),
+        }
+    }
+}
+
+fn found(found: bool) -> String {
+    String::from(if found { "找到" } else { "未找到" })
+}
+
+fn render_tree(rendered: String) -> String {
+    if rendered.is_empty() {
+        String::from("（空树）")
+    } else {
+        rendered.trim_end().to_string()
+    }
+}
+
+fn main() -> io::Result<()> {
+    let mut playground = Playground::default();
+    println!("{HELP}");
+
+    let stdin = io::stdin();
+    let mut lines = stdin.lock().lines();
+    loop {
+        print!("> ");
+        io::stdout().flush()?;
+
+        let Some(line) = lines.next() else {
+            break;
+        };
+        let line = line?;
+        if line.trim() == "quit" {
+            break;
+        }
+
+        match playground.execute(&line) {
+            Ok(output) if output.is_empty() => {}
+            Ok(output) => println!("{output}"),
+            Err(e) => eprintln!("{e}"),
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn playground_should_execute_commands() {
+        let mut playground = Playground::default();
+
+        playground.execute("bst insert 2").unwrap();
+        let output = playground.execute("bst insert 1").unwrap();
+        assert_eq!(output, "2\n├── 1\n└── ∅");
+        assert_eq!(
+            playground.execute("bst search 3").unwrap(),
+            "未找到\n2\n├── 1\n└── ∅"
+        );
+
+        playground.execute("heap push 1").unwrap();
+        assert_eq!(playground.execute("heap push 3").unwrap(), "[3, 1]");
+        assert_eq!(
+            playground.execute("heap pop").unwrap(),
+            "弹出：Some(3)\n[1]"
+        );
+
+        playground.execute("list push_back 1").unwrap();
+        playground.execute("list push_back 2").unwrap();
+        assert_eq!(playground.execute("list reverse").unwrap(), "[2 <-> 1]");
+
+        assert_eq!(playground.execute("avl print").unwrap(), "（空树）");
+        assert_eq!(playground.execute("  ").unwrap(), "");
+    }
+
+    #[test]
+    fn playground_should_report_errors() {
+        let mut playground = Playground::default();
+
+        assert!(playground.execute("tree insert 1").is_err());
+        assert!(playground.execute("bst insert").is_err());
+        assert!(playground.execute("bst insert x").is_err());
+    }
+}
@@ -1,4 +1,12 @@
-use std::{cell::RefCell, cmp::Ordering, collections::VecDeque, rc::Rc};
+use std::{
+    cell::RefCell,
+    cmp::Ordering,
+    collections::{BTreeSet, VecDeque},
+    error::Error,
+    fmt,
+    rc::Rc,
+    str::FromStr,
+};
 
 use crate::bt;
 
@@ -75,6 +83,26 @@ impl<T> BinaryTree<T> {
             }
         }
     }
+
+    /// 树高：叶结点高度为 0，空树高度为 -1
+    pub fn height(&self) -> i32 {
+        bt::height(&self.root)
+    }
+
+    /// 结点总数
+    pub fn count_nodes(&self) -> usize {
+        bt::count_nodes(&self.root)
+    }
+
+    /// 叶结点的数量
+    pub fn count_leaves(&self) -> usize {
+        bt::count_leaves(&self.root)
+    }
+
+    /// 深度为 k 的结点数量，根结点深度为 0
+    pub fn count_at_level(&self, k: usize) -> usize {
+        bt::count_at_level(&self.root, k)
+    }
 }
 
 impl<T: Clone> BinaryTree<T> {
@@ -82,6 +110,181 @@ impl<T: Clone> BinaryTree<T> {
     pub fn to_vec(&self) -> Vec<T> {
         bt::in_order(&self.root)
     }
+
+    /// 转换为层序数组，空位为 None，省略末尾的 None，与 LeetCode 的 `[1,null,2,3]` 格式相同
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use base::{BinaryTree, tree};
+    ///
+    /// let tree = tree! {1, right: tree! {2, left: 3}};
+    ///
+    /// assert_eq!(tree.to_level_vec(), [Some(1), None, Some(2), Some(3)]);
+    /// assert_eq!(tree.to_string(), "[1,null,2,3]");
+    /// ```
+    pub fn to_level_vec(&self) -> Vec<Option<T>> {
+        level_slots(&self.root)
+            .iter()
+            .map(|slot| slot.as_ref().map(|node| node.borrow().value.clone()))
+            .collect()
+    }
+}
+
+impl<T> BinaryTree<T> {
+    /// 由层序数组构建二叉树，空位为 None，可以表示任意形状的树
+    ///
+    /// 依次为队列中的每个结点取出两个位置作为左右子结点，空位的子结点不占位置；
+    /// 数组在中途结束时其余子结点均为空，多余的位置被忽略。首个位置为 None 时得到空树，时间复杂度：O(n)
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use base::{BinaryTree, bt};
+    ///
+    /// let tree = BinaryTree::from_level_vec(vec![Some(1), None, Some(2), Some(3)]);
+    ///
+    /// assert_eq!(bt::pre_order(&tree.root), [1, 2, 3]);
+    /// assert_eq!(bt::in_order(&tree.root), [1, 3, 2]);
+    /// ```
+    pub fn from_level_vec(slots: Vec<Option<T>>) -> Self {
+        let mut slots = slots.into_iter();
+        let Some(Some(value)) = slots.next() else {
+            return Self::new();
+        };
+        let root = TreeNode::new_node_rc(value);
+
+        let mut queue = VecDeque::from([root.clone()]);
+        'outer: while let Some(node) = queue.pop_front() {
+            let mut node = node.borrow_mut();
+            let node = &mut *node;
+            for child in [&mut node.left, &mut node.right] {
+                match slots.next() {
+                    Some(Some(value)) => {
+                        let new_node = TreeNode::new_node_rc(value);
+                        queue.push_back(new_node.clone());
+                        *child = Some(new_node);
+                    }
+                    Some(None) => {}
+                    None => break 'outer,
+                }
+            }
+        }
+
+        Self { root: Some(root) }
+    }
+}
+
+/// 层序遍历时的所有位置，空子树也占一个位置，省略末尾的空位
+pub(crate) fn level_slots<T>(root: &OptionNodeRc<T>) -> Vec<OptionNodeRc<T>> {
+    let mut slots = Vec::new();
+    let mut queue = VecDeque::from([root.clone()]);
+    while let Some(slot) = queue.pop_front() {
+        if let Some(node) = &slot {
+            queue.push_back(node.borrow().left.clone());
+            queue.push_back(node.borrow().right.clone());
+        }
+        slots.push(slot);
+    }
+    while slots.last().is_some_and(Option::is_none) {
+        slots.pop();
+    }
+
+    slots
+}
+
+impl<T: fmt::Display> fmt::Display for BinaryTree<T> {
+    /// 格式化为层序数组，如 `[1,null,2,3]`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[")?;
+        for (i, slot) in level_slots(&self.root).iter().enumerate() {
+            if i > 0 {
+                write!(f, ",")?;
+            }
+            match slot {
+                Some(node) => write!(f, "{}", node.borrow().value)?,
+                None => write!(f, "null")?,
+            }
+        }
+        write!(f, "]")
+    }
+}
+
+/// 解析层序数组格式的二叉树失败
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseTreeError {
+    /// 没有用方括号括起来
+    MissingBrackets,
+    /// 第 index 个位置（从 0 开始）既不是 null 也不能解析为结点的值
+    InvalidValue { index: usize, token: String },
+}
+
+impl fmt::Display for ParseTreeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingBrackets => write!(f, "tree must be enclosed in brackets"),
+            Self::InvalidValue { index, token } => {
+                write!(f, "invalid value {token:?} at position {index}")
+            }
+        }
+    }
+}
+
+impl Error for ParseTreeError {}
+
+impl<T: FromStr> FromStr for BinaryTree<T> {
+    type Err = ParseTreeError;
+
+    /// 解析层序数组，如 `[1, null, 2, 3]`，允许空白，结点的值中不能包含逗号
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use base::{BinaryTree, ParseTreeError};
+    ///
+    /// let tree = "[1, 2, 3, null, 4]".parse::<BinaryTree<i32>>().unwrap();
+    /// assert_eq!(tree.to_string(), "[1,2,3,null,4]");
+    ///
+    /// assert!("[]".parse::<BinaryTree<i32>>().unwrap().root.is_none());
+    /// assert_eq!(
+    ///     "[1,x]".parse::<BinaryTree<i32>>().err(),
+    ///     Some(ParseTreeError::InvalidValue { index: 1, token: "x".into() })
+    /// );
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let inner = s
+            .trim()
+            .strip_prefix('[')
+            .and_then(|s| s.strip_suffix(']'))
+            .ok_or(ParseTreeError::MissingBrackets)?;
+        if inner.trim().is_empty() {
+            return Ok(Self::new());
+        }
+
+        let slots = inner
+            .split(',')
+            .map(str::trim)
+            .enumerate()
+            .map(|(index, token)| match token {
+                "null" => Ok(None),
+                _ => token
+                    .parse()
+                    .map(Some)
+                    .map_err(|_| ParseTreeError::InvalidValue {
+                        index,
+                        token: token.to_string(),
+                    }),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self::from_level_vec(slots))
+    }
+}
+
+impl<T> Drop for BinaryTree<T> {
+    fn drop(&mut self) {
+        drop_nodes(self.root.take());
+    }
 }
 
 impl<T> Default for BinaryTree<T> {
@@ -91,26 +294,203 @@ impl<T> Default for BinaryTree<T> {
 }
 
 impl<T, const N: usize> From<[T; N]> for BinaryTree<T> {
+    /// 按层序构建完全二叉树，结果与依次调用 insert 相同，时间复杂度：O(n)
+    ///
+    /// 下标 i 的结点的左右子结点下标分别为 2i + 1 和 2i + 2，从后往前创建结点，
+    /// 创建每个结点时其子结点都已创建完毕，除 n 个结点外不需要额外的堆内存。
     fn from(v: [T; N]) -> Self {
-        let mut tree = BinaryTree::new();
+        let mut nodes: [OptionNodeRc<T>; N] = std::array::from_fn(|_| None);
 
-        v.into_iter().for_each(|val| {
-            tree.insert(val);
-        });
+        for (i, val) in v.into_iter().enumerate().rev() {
+            let node = TreeNode::new_node_rc(val);
+            {
+                let mut node = node.borrow_mut();
+                node.left = nodes.get_mut(2 * i + 1).and_then(Option::take);
+                node.right = nodes.get_mut(2 * i + 2).and_then(Option::take);
+            }
+            nodes[i] = Some(node);
+        }
 
-        tree
+        Self {
+            root: nodes.first_mut().and_then(Option::take),
+        }
+    }
+}
+
+/// 按指定形状逐个结点构建二叉树
+///
+/// [`BinaryTree::from`] 和 [`BinaryTree::insert`] 只能得到完全二叉树，
+/// 遍历、平衡性相关的测试常常需要链状或单侧偏斜等特定形状，可以用 TreeBuilder 或 [`tree!`](crate::tree) 直接写出。
+/// 子树可以是另一个 TreeBuilder、一棵 [`BinaryTree`] 或单个值（叶结点）。
+///
+/// # Examples
+///
+/// ```rust
+/// use base::{TreeBuilder, bt};
+///
+/// //     1
+/// //    /
+/// //   2
+/// //    \
+/// //     3
+/// let tree = TreeBuilder::new(1)
+///     .left(TreeBuilder::new(2).right(3))
+///     .build();
+///
+/// assert_eq!(bt::pre_order(&tree.root), [1, 2, 3]);
+/// assert_eq!(bt::in_order(&tree.root), [2, 3, 1]);
+/// ```
+pub struct TreeBuilder<T> {
+    root: OptionNodeRc<T>,
+}
+
+impl<T> TreeBuilder<T> {
+    /// 以 value 为根结点开始构建
+    pub fn new(value: T) -> Self {
+        Self {
+            root: Some(TreeNode::new_node_rc(value)),
+        }
+    }
+
+    /// 设置根结点的左子树，替换已有的左子树
+    pub fn left(self, child: impl Into<TreeBuilder<T>>) -> Self {
+        if let Some(root) = self.root.as_ref() {
+            root.borrow_mut().left = child.into().root;
+        }
+
+        self
+    }
+
+    /// 设置根结点的右子树，替换已有的右子树
+    pub fn right(self, child: impl Into<TreeBuilder<T>>) -> Self {
+        if let Some(root) = self.root.as_ref() {
+            root.borrow_mut().right = child.into().root;
+        }
+
+        self
+    }
+
+    /// 完成构建
+    pub fn build(self) -> BinaryTree<T> {
+        BinaryTree { root: self.root }
+    }
+}
+
+/// 单个值作为叶结点
+impl<T> From<T> for TreeBuilder<T> {
+    fn from(value: T) -> Self {
+        Self::new(value)
+    }
+}
+
+/// 已有的树作为子树，空树表示没有子结点
+impl<T> From<BinaryTree<T>> for TreeBuilder<T> {
+    fn from(mut tree: BinaryTree<T>) -> Self {
+        Self {
+            root: tree.root.take(),
+        }
+    }
+}
+
+impl<T> From<TreeBuilder<T>> for BinaryTree<T> {
+    fn from(builder: TreeBuilder<T>) -> Self {
+        builder.build()
+    }
+}
+
+/// 按指定形状构建 [`BinaryTree`]，是 [`TreeBuilder`] 的简写
+///
+/// 第一个表达式是根结点的值，之后可选地依次给出 `left:` 和 `right:` 子树，
+/// 子树可以是嵌套的 `tree!`、[`TreeBuilder`] 或单个值。
+///
+/// # Examples
+///
+/// ```rust
+/// use base::{bt, tree};
+///
+/// //     1
+/// //    / \
+/// //   2   3
+/// //      /
+/// //     4
+/// let tree = tree! {1, left: tree! {2}, right: tree! {3, left: 4}};
+///
+/// assert_eq!(bt::pre_order(&tree.root), [1, 2, 3, 4]);
+/// assert_eq!(bt::in_order(&tree.root), [2, 1, 4, 3]);
+/// ```
+#[macro_export]
+macro_rules! tree {
+    ($value:expr $(, left: $left:expr)? $(, right: $right:expr)? $(,)?) => {
+        $crate::TreeBuilder::new($value)
+            $(.left($left))?
+            $(.right($right))?
+            .build()
+    };
+}
+
+/// 批量构建二叉搜索树时的去重统计
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct DedupStats {
+    /// 插入的元素数量
+    pub inserted: usize,
+    /// 因重复而跳过的元素数量
+    pub duplicates: usize,
+}
+
+impl DedupStats {
+    /// 记录一次插入的结果
+    pub(crate) fn record(&mut self, inserted: bool) {
+        if inserted {
+            self.inserted += 1;
+        } else {
+            self.duplicates += 1;
+        }
     }
 }
 
 // 二叉搜索树
 pub struct BinarySearchTree<T> {
-    root: OptionNodeRc<T>,
+    pub(crate) root: OptionNodeRc<T>,
 }
 
 impl<T> BinarySearchTree<T> {
     pub fn new() -> Self {
         Self { root: None }
     }
+
+    /// 原地把二叉搜索树调整为高度最小的二叉搜索树（Day–Stout–Warren 算法）
+    ///
+    /// 1. 不断右旋根结点的左子结点，把整棵树变成只有右子结点的链（vine），同时统计结点数 n
+    /// 2. 沿着链每隔一个结点左旋一次，把链压缩为一半长；先压缩多出满二叉树的那部分叶结点，
+    ///    再重复压缩 n/2、n/4、... 次，最终得到除最后一层外都是满的二叉树
+    ///
+    /// 只通过旋转改变结点间的链接，不复制、不重新分配结点。
+    /// 时间复杂度：O(n)，空间复杂度：O(1)
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use base::{BinarySearchTree, bt};
+    ///
+    /// // 有序插入会退化成链表
+    /// let mut tree = BinarySearchTree::from([1, 2, 3, 4, 5, 6, 7]);
+    /// tree.rebalance();
+    ///
+    /// assert_eq!(bt::pre_order(&tree.to_tree().root), [4, 2, 1, 3, 6, 5, 7]);
+    /// ```
+    pub fn rebalance(&mut self) {
+        let size = tree_to_vine(&mut self.root);
+
+        // 最后一层的叶结点数量 = n + 1 - 不超过 n + 1 的最大的 2 的幂
+        let leaves = size + 1 - (1 << (size + 1).ilog2());
+        compress(&mut self.root, leaves);
+
+        let mut size = size - leaves;
+        while size > 1 {
+            size /= 2;
+            compress(&mut self.root, size);
+        }
+    }
 }
 
 impl<T: Clone + Ord> BinarySearchTree<T> {
@@ -128,11 +508,28 @@ impl<T: Clone + Ord> BinarySearchTree<T> {
         current
     }
 
+    /// 插入节点，树中已存在相同的值时不插入
     pub fn insert(&mut self, val: T) {
+        self.try_insert(val);
+    }
+
+    /// 插入节点，返回是否插入成功，树中已存在相同的值时返回 false
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use base::BinarySearchTree;
+    ///
+    /// let mut tree = BinarySearchTree::new();
+    ///
+    /// assert!(tree.try_insert(1));
+    /// assert!(!tree.try_insert(1));
+    /// ```
+    pub fn try_insert(&mut self, val: T) -> bool {
         // 若树为空，则初始化根节点
         if self.root.is_none() {
             self.root = Some(TreeNode::new_node_rc(val));
-            return;
+            return true;
         }
 
         let mut current = self.root.clone();
@@ -141,7 +538,7 @@ impl<T: Clone + Ord> BinarySearchTree<T> {
         while let Some(node) = current.clone() {
             match val.cmp(&node.borrow().value) {
                 // 找到重复节点直接返回
-                Ordering::Equal => return,
+                Ordering::Equal => return false,
                 Ordering::Less => {
                     previous = current;
                     current = node.borrow().left.clone();
@@ -160,6 +557,31 @@ impl<T: Clone + Ord> BinarySearchTree<T> {
         } else {
             previous.borrow_mut().left = Some(TreeNode::new_node_rc(val));
         }
+
+        true
+    }
+
+    /// 依次插入迭代器中的元素构建二叉搜索树，同时统计跳过的重复元素
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use base::{BinarySearchTree, DedupStats};
+    ///
+    /// let (tree, stats) = BinarySearchTree::from_iter_dedup([3, 1, 3, 2, 1]);
+    ///
+    /// assert_eq!(tree.to_vec(), [1, 2, 3]);
+    /// assert_eq!(stats, DedupStats { inserted: 3, duplicates: 2 });
+    /// ```
+    pub fn from_iter_dedup(iter: impl IntoIterator<Item = T>) -> (Self, DedupStats) {
+        let mut tree = Self::new();
+        let mut stats = DedupStats::default();
+
+        for val in iter {
+            stats.record(tree.try_insert(val));
+        }
+
+        (tree, stats)
     }
 
     pub fn remove(&mut self, val: &T) {
@@ -236,6 +658,53 @@ impl<T: Clone + Ord> BinarySearchTree<T> {
     pub fn to_vec(&self) -> Vec<T> {
         bt::in_order(&self.root)
     }
+
+    /// 转换为普通二叉树，复制所有结点
+    pub fn to_tree(&self) -> BinaryTree<T> {
+        BinaryTree {
+            root: clone_tree(&self.root),
+        }
+    }
+}
+
+/// 复制整棵树，用栈代替递归，树退化成链时也不会栈溢出
+fn clone_tree<T: Clone>(root: &OptionNodeRc<T>) -> OptionNodeRc<T> {
+    let copy = |node: &NodeRc<T>| TreeNode::new_node_rc(node.borrow().value.clone());
+    let new_root = root.as_ref().map(copy);
+    // 栈中的元素为 (原结点, 复制出的结点)，复制出的结点还没有连接子结点
+    let mut stack = Vec::from_iter(root.clone().zip(new_root.clone()));
+
+    while let Some((node, new_node)) = stack.pop() {
+        let node = node.borrow();
+        let mut new_node = new_node.borrow_mut();
+        new_node.left = node.left.as_ref().map(copy);
+        new_node.right = node.right.as_ref().map(copy);
+        stack.extend(node.left.clone().zip(new_node.left.clone()));
+        stack.extend(node.right.clone().zip(new_node.right.clone()));
+    }
+
+    new_root
+}
+
+/// 逐个拆除只被这棵树引用的结点，避免很深的树递归 drop 导致栈溢出
+///
+/// 仍被其他地方引用的结点原样保留，它的子树随最后一个引用一起释放。
+fn drop_nodes<T>(root: OptionNodeRc<T>) {
+    let mut stack = Vec::from_iter(root);
+
+    while let Some(node) = stack.pop() {
+        if let Ok(node) = Rc::try_unwrap(node) {
+            let node = node.into_inner();
+            stack.extend(node.left);
+            stack.extend(node.right);
+        }
+    }
+}
+
+impl<T> Drop for BinarySearchTree<T> {
+    fn drop(&mut self) {
+        drop_nodes(self.root.take());
+    }
 }
 
 impl<T> Default for BinarySearchTree<T> {
@@ -256,10 +725,153 @@ impl<T: Ord + Clone, const N: usize> From<[T; N]> for BinarySearchTree<T> {
     }
 }
 
+impl<T: Ord> From<BTreeSet<T>> for BinarySearchTree<T> {
+    /// 有序地逐个插入会使二叉搜索树退化为链表，因此直接构建平衡的二叉搜索树，时间复杂度：O(n)
+    fn from(set: BTreeSet<T>) -> Self {
+        let len = set.len();
+
+        Self {
+            root: build_balanced(&mut set.into_iter(), len),
+        }
+    }
+}
+
+impl<T: Ord + Clone> From<BinarySearchTree<T>> for BTreeSet<T> {
+    fn from(tree: BinarySearchTree<T>) -> Self {
+        tree.to_vec().into_iter().collect()
+    }
+}
+
+/// 把 parent 的右子结点替换为 node，parent 为 None 时替换根结点
+fn set_right<T>(root: &mut OptionNodeRc<T>, parent: &OptionNodeRc<T>, node: OptionNodeRc<T>) {
+    match parent {
+        Some(parent) => parent.borrow_mut().right = node,
+        None => *root = node,
+    }
+}
+
+/// 通过右旋把树变成只有右子结点的链，返回结点数量
+fn tree_to_vine<T>(root: &mut OptionNodeRc<T>) -> usize {
+    // 链的尾部，其右子结点是还未展开的子树
+    let mut parent: OptionNodeRc<T> = None;
+    let mut current = root.clone();
+    let mut size = 0;
+
+    while let Some(node) = current {
+        let left = node.borrow_mut().left.take();
+        match left {
+            // 右旋：左子结点取代 node，node 成为其右子结点
+            Some(left) => {
+                node.borrow_mut().left = left.borrow_mut().right.take();
+                left.borrow_mut().right = Some(node);
+                set_right(root, &parent, Some(left.clone()));
+                current = Some(left);
+            }
+            // 没有左子结点，node 已经在链上
+            None => {
+                size += 1;
+                current = node.borrow().right.clone();
+                parent = Some(node);
+            }
+        }
+    }
+
+    size
+}
+
+/// 从链的顶部开始左旋 count 次，每次把一个结点变成其右子结点的左子结点
+fn compress<T>(root: &mut OptionNodeRc<T>, count: usize) {
+    let mut parent: OptionNodeRc<T> = None;
+
+    for _ in 0..count {
+        // Safety: 调用者保证链上还有至少 2 * count 个结点
+        let child = match &parent {
+            Some(parent) => parent.borrow().right.clone(),
+            None => root.clone(),
+        }
+        .unwrap();
+        let grandchild = child.borrow_mut().right.take().unwrap();
+
+        child.borrow_mut().right = grandchild.borrow_mut().left.take();
+        grandchild.borrow_mut().left = Some(child);
+        set_right(root, &parent, Some(grandchild.clone()));
+        parent = Some(grandchild);
+    }
+}
+
+/// 由升序迭代器的前 len 个元素构建平衡的二叉搜索树
+///
+/// 按中序遍历的顺序消耗元素：先构建左半部分，再取出根结点，最后构建右半部分
+pub(crate) fn build_balanced<T>(iter: &mut impl Iterator<Item = T>, len: usize) -> OptionNodeRc<T> {
+    if len == 0 {
+        return None;
+    }
+
+    let left = build_balanced(iter, len / 2);
+    // Safety: 调用者保证迭代器中至少还有 len 个元素
+    let node = TreeNode::new_node_rc(iter.next().unwrap());
+    let right = build_balanced(iter, len - len / 2 - 1);
+
+    {
+        let mut node = node.borrow_mut();
+        node.left = left;
+        node.right = right;
+    }
+
+    Some(node)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn level_vec_should_round_trip() {
+        for text in [
+            "[]",
+            "[1]",
+            "[1,2,3,4,5,6,7]",
+            "[1,null,2,3]",
+            "[5,4,8,11,null,13,4,7,2,null,null,null,1]",
+        ] {
+            let tree = text.parse::<BinaryTree<i32>>().unwrap();
+            assert_eq!(tree.to_string(), text);
+            let rebuilt = BinaryTree::from_level_vec(tree.to_level_vec());
+            assert_eq!(rebuilt.to_string(), text);
+        }
+
+        // 完全二叉树没有空位
+        let tree = BinaryTree::from([1, 2, 3, 4, 5]);
+        assert_eq!(tree.to_level_vec(), [1, 2, 3, 4, 5].map(Some));
+
+        // 末尾的 null 和多余的位置被忽略
+        let tree = BinaryTree::from_level_vec(vec![Some(1), None, None, Some(9)]);
+        assert_eq!(tree.to_level_vec(), [Some(1)]);
+        assert!(
+            BinaryTree::from_level_vec(vec![None, Some(1)])
+                .root
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn level_string_should_reject_malformed_input() {
+        assert_eq!(
+            "1,2".parse::<BinaryTree<i32>>().err(),
+            Some(ParseTreeError::MissingBrackets)
+        );
+        let err = "[1, nil]".parse::<BinaryTree<i32>>().err().unwrap();
+        assert_eq!(err.to_string(), r#"invalid value "nil" at position 1"#);
+        assert!("[1,,2]".parse::<BinaryTree<i32>>().is_err());
+        assert_eq!(
+            " [ 1 , null , 2 ] "
+                .parse::<BinaryTree<i32>>()
+                .unwrap()
+                .to_string(),
+            "[1,null,2]"
+        );
+    }
+
     #[test]
     fn tree_basics_should_work() {
         let mut tree = BinaryTree::new();
@@ -309,6 +921,123 @@ mod tests {
         assert_eq!(bt::post_order(&tree.root), vec![4, 5, 2, 6, 3, 1]);
     }
 
+    #[test]
+    fn tree_from_should_match_insert() {
+        let mut tree = BinaryTree::new();
+        (0..20).for_each(|i| tree.insert(i));
+
+        let from = BinaryTree::from(std::array::from_fn::<_, 20, _>(|i| i));
+        assert_eq!(bt::pre_order(&from.root), bt::pre_order(&tree.root));
+        assert_eq!(bt::in_order(&from.root), bt::in_order(&tree.root));
+        assert!(BinaryTree::<i32>::from([]).root.is_none());
+    }
+
+    #[test]
+    fn tree_builder_should_build_exact_shape() {
+        // 1
+        //  \
+        //   2
+        //  /
+        // 3
+        let zigzag = tree! {1, right: tree! {2, left: 3}};
+        assert_eq!(bt::pre_order(&zigzag.root), [1, 2, 3]);
+        assert_eq!(bt::in_order(&zigzag.root), [1, 3, 2]);
+        assert_eq!(bt::post_order(&zigzag.root), [3, 2, 1]);
+
+        // 空树作为子树表示没有子结点，后设置的子树替换先前的子树
+        let tree = TreeBuilder::new(1)
+            .left(BinaryTree::new())
+            .right(new_binary_tree())
+            .left(TreeBuilder::new(0).left(-1))
+            .build();
+        assert_eq!(bt::pre_order(&tree.root), [1, 0, -1, 1, 2, 4, 5, 3, 6]);
+        assert_eq!(bt::in_order(&tree.root), [-1, 0, 1, 4, 2, 5, 1, 6, 3]);
+
+        assert_eq!(tree! {'a',}.to_vec(), ['a']);
+        assert_eq!(BinaryTree::from(TreeBuilder::new('b')).to_vec(), ['b']);
+    }
+
+    #[cfg(feature = "alloc-counter")]
+    #[test]
+    fn tree_from_should_allocate_only_nodes() {
+        use crate::alloc_counter::measure_allocations;
+
+        let (tree, stats) = measure_allocations(|| BinaryTree::from([0; 100]));
+        assert_eq!(stats.allocations, 100);
+        assert_eq!(stats.reallocations, 0);
+        drop(tree);
+    }
+
+    #[test]
+    fn search_tree_std_conversions_should_work() {
+        let tree = BinarySearchTree::from((0..15).collect::<BTreeSet<_>>());
+        // 15 个元素的平衡二叉搜索树恰好是满二叉树
+        assert_eq!(bt::pre_order(&tree.root)[..4], [7, 3, 1, 0]);
+        assert!(tree.search(&14).is_some());
+
+        let set = BTreeSet::from(tree);
+        assert_eq!(set, (0..15).collect());
+        assert!(
+            BinarySearchTree::from(BTreeSet::<i32>::new())
+                .root
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn search_tree_dedup_should_work() {
+        let mut tree = BinarySearchTree::new();
+        assert!(tree.try_insert(2));
+        assert!(tree.try_insert(1));
+        assert!(!tree.try_insert(2));
+
+        let keys = crate::generate::few_distinct(1000, 20, 5);
+        let (tree, stats) = BinarySearchTree::from_iter_dedup(keys);
+        assert_eq!(tree.to_vec(), (0..20).collect::<Vec<_>>());
+        assert_eq!(stats.inserted, 20);
+        assert_eq!(stats.duplicates, 980);
+
+        let (tree, stats) = BinarySearchTree::<i32>::from_iter_dedup([]);
+        assert!(tree.root.is_none());
+        assert_eq!(stats, DedupStats::default());
+    }
+
+    /// 树的层数，空树为 0
+    fn height<T>(root: &OptionNodeRc<T>) -> usize {
+        (bt::height(root) + 1) as usize
+    }
+
+    #[test]
+    fn search_tree_rebalance_should_minimize_height() {
+        for n in [0, 1, 2, 3, 7, 8, 100, 1000] {
+            let mut tree = BinarySearchTree::new();
+            (0..n).for_each(|i| tree.insert(i));
+            assert_eq!(height(&tree.root), n);
+
+            tree.rebalance();
+            // n 个结点的二叉树的最小高度
+            assert_eq!(
+                height(&tree.root),
+                (n + 1).next_power_of_two().ilog2() as usize
+            );
+            assert!(tree.to_vec().into_iter().eq(0..n));
+        }
+
+        let keys = crate::generate::uniform(500, 9);
+        let (mut tree, stats) = BinarySearchTree::from_iter_dedup(keys.iter().copied());
+        let expected = tree.to_vec();
+        tree.rebalance();
+        assert_eq!(height(&tree.root), 9);
+        assert_eq!(stats.inserted, 500);
+        assert_eq!(tree.to_vec(), expected);
+
+        // 调整后仍然可以正常查找、插入和删除
+        tree.insert(u64::MAX);
+        tree.remove(&keys[0]);
+        assert!(tree.search(&u64::MAX).is_some());
+        assert!(tree.search(&keys[0]).is_none());
+    }
+
     #[test]
     fn search_tree_basics_should_work() {
         let mut tree = BinarySearchTree::from([4, 2, 6, 1, 3, 5, 7]);
@@ -0,0 +1,298 @@
+//! 二进制字典树（binary trie），又称 01 字典树
+//!
+//! 把整数看作从最高位到最低位的二进制串插入字典树，每个结点只有 0 和 1 两个子结点。
+//! 异或运算逐位独立，且高位的差异比所有低位加起来都重要，因此可以从根结点开始逐位贪心：
+//! * 与 x 异或的最大值：每一位尽量走与 x 的这一位相反的子结点
+//! * 与 x 异或小于 k 的数量：k 的某一位为 1 时，异或结果这一位为 0 的子树整棵都满足条件
+//!
+//! 每个结点记录子树中键的数量，因此支持重复的键和删除，所有操作的时间复杂度均为 O(w)，w 为键的位数。
+//! 删除时只减少计数，不回收结点，数量为 0 的结点视为不存在。
+
+use std::marker::PhantomData;
+
+/// 可以放入 [`BinaryTrie`] 的无符号整数
+pub trait BitKey: Copy {
+    /// 位数
+    const BITS: u32;
+
+    /// 转换为 u64
+    fn to_u64(self) -> u64;
+
+    /// 从 u64 转换，只会传入不超过 BITS 位的值
+    fn from_u64(bits: u64) -> Self;
+}
+
+macro_rules! impl_bit_key {
+    ($($t:ty),*) => {
+        $(
+            impl BitKey for $t {
+                const BITS: u32 = <$t>::BITS;
+
+                fn to_u64(self) -> u64 {
+                    self as u64
+                }
+
+                fn from_u64(bits: u64) -> Self {
+                    bits as $t
+                }
+            }
+        )*
+    };
+}
+
+impl_bit_key!(u8, u16, u32, u64);
+
+#[derive(Debug, Clone, Default)]
+struct TrieNode {
+    /// 子结点在 nodes 中的下标，0 表示不存在（根结点不会成为子结点）
+    children: [usize; 2],
+    /// 子树中键的数量
+    count: usize,
+}
+
+/// 二进制字典树，可以保存重复的键
+///
+/// # Examples
+///
+/// ```rust
+/// use base::BinaryTrie;
+///
+/// let mut trie = BinaryTrie::<u32>::from_iter([3, 10, 5, 25, 2, 8]);
+///
+/// // 5 ^ 25 = 28
+/// assert_eq!(trie.max_xor_with(5), Some(28));
+/// // 与 5 异或小于 8 的有 3、5、2
+/// assert_eq!(trie.count_less_than_xor(5, 8), 3);
+///
+/// assert!(trie.remove(25));
+/// assert_eq!(trie.max_xor_with(5), Some(15));
+/// ```
+#[derive(Debug, Clone)]
+pub struct BinaryTrie<K> {
+    nodes: Vec<TrieNode>,
+    _marker: PhantomData<K>,
+}
+
+impl<K: BitKey> BinaryTrie<K> {
+    /// 创建空的字典树
+    pub fn new() -> Self {
+        Self {
+            nodes: vec![TrieNode::default()],
+            _marker: PhantomData,
+        }
+    }
+
+    /// 键的数量（重复的键分别计数）
+    pub fn len(&self) -> usize {
+        self.nodes[0].count
+    }
+
+    /// 判断是否为空
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// 插入一个键
+    pub fn insert(&mut self, key: K) {
+        let key = key.to_u64();
+        let mut node = 0;
+
+        self.nodes[0].count += 1;
+        for i in (0..K::BITS).rev() {
+            let bit = (key >> i & 1) as usize;
+            if self.nodes[node].children[bit] == 0 {
+                self.nodes[node].children[bit] = self.nodes.len();
+                self.nodes.push(TrieNode::default());
+            }
+
+            node = self.nodes[node].children[bit];
+            self.nodes[node].count += 1;
+        }
+    }
+
+    /// 删除一个键，键不存在时返回 false
+    pub fn remove(&mut self, key: K) -> bool {
+        if self.count(key) == 0 {
+            return false;
+        }
+
+        let key = key.to_u64();
+        let mut node = 0;
+        self.nodes[0].count -= 1;
+        for i in (0..K::BITS).rev() {
+            node = self.nodes[node].children[(key >> i & 1) as usize];
+            self.nodes[node].count -= 1;
+        }
+
+        true
+    }
+
+    /// 键出现的次数
+    pub fn count(&self, key: K) -> usize {
+        let key = key.to_u64();
+        let mut node = 0;
+
+        for i in (0..K::BITS).rev() {
+            match self.child(node, (key >> i & 1) as usize) {
+                Some(child) => node = child,
+                None => return 0,
+            }
+        }
+
+        self.nodes[node].count
+    }
+
+    /// 判断是否包含键
+    pub fn contains(&self, key: K) -> bool {
+        self.count(key) > 0
+    }
+
+    /// 所有键与 x 异或的最大值，字典树为空时返回 None
+    pub fn max_xor_with(&self, x: K) -> Option<K> {
+        if self.is_empty() {
+            return None;
+        }
+
+        let x = x.to_u64();
+        let (mut node, mut xor) = (0, 0);
+        for i in (0..K::BITS).rev() {
+            let bit = (x >> i & 1) as usize;
+            // 优先走相反的一位，使异或结果的这一位为 1
+            match self.child(node, bit ^ 1) {
+                Some(child) => {
+                    node = child;
+                    xor |= 1 << i;
+                }
+                // Safety: 子树非空，两个子结点中至少有一个非空
+                None => node = self.child(node, bit).unwrap(),
+            }
+        }
+
+        Some(K::from_u64(xor))
+    }
+
+    /// 与 x 异或后小于 k 的键的数量
+    pub fn count_less_than_xor(&self, x: K, k: K) -> usize {
+        let (x, k) = (x.to_u64(), k.to_u64());
+        let mut node = 0;
+        let mut count = 0;
+
+        for i in (0..K::BITS).rev() {
+            let bit = (x >> i & 1) as usize;
+            if k >> i & 1 == 1 {
+                // 异或结果这一位为 0，小于 k 的这一位，整棵子树都满足条件
+                if let Some(child) = self.child(node, bit) {
+                    count += self.nodes[child].count;
+                }
+                // 继续沿着与 k 相等的一侧向下
+                match self.child(node, bit ^ 1) {
+                    Some(child) => node = child,
+                    None => return count,
+                }
+            } else {
+                match self.child(node, bit) {
+                    Some(child) => node = child,
+                    None => return count,
+                }
+            }
+        }
+
+        // 剩下的键与 x 异或恰好等于 k，不计入
+        count
+    }
+
+    /// 非空的子结点
+    fn child(&self, node: usize, bit: usize) -> Option<usize> {
+        let child = self.nodes[node].children[bit];
+
+        (child != 0 && self.nodes[child].count > 0).then_some(child)
+    }
+}
+
+impl<K: BitKey> Default for BinaryTrie<K> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: BitKey> Extend<K> for BinaryTrie<K> {
+    fn extend<I: IntoIterator<Item = K>>(&mut self, iter: I) {
+        for key in iter {
+            self.insert(key);
+        }
+    }
+}
+
+impl<K: BitKey> FromIterator<K> for BinaryTrie<K> {
+    fn from_iter<I: IntoIterator<Item = K>>(iter: I) -> Self {
+        let mut trie = BinaryTrie::new();
+
+        trie.extend(iter);
+        trie
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generate::{self, Operation, Rng};
+
+    #[test]
+    fn binary_trie_should_match_brute_force() {
+        let mut trie = BinaryTrie::<u32>::new();
+        let mut keys: Vec<u32> = Vec::new();
+        let mut rng = Rng::new(11);
+
+        for op in generate::operations(3000, 200, 5) {
+            match op {
+                Operation::Insert(key) => {
+                    trie.insert(key as u32);
+                    keys.push(key as u32);
+                }
+                Operation::Remove(key) => {
+                    let pos = keys.iter().position(|&k| k == key as u32);
+                    assert_eq!(trie.remove(key as u32), pos.is_some());
+                    if let Some(pos) = pos {
+                        keys.swap_remove(pos);
+                    }
+                }
+                Operation::Get(key) => {
+                    let count = keys.iter().filter(|&&k| k == key as u32).count();
+                    assert_eq!(trie.count(key as u32), count);
+                }
+            }
+
+            let x = rng.below(512) as u32;
+            let k = rng.below(512) as u32;
+            assert_eq!(trie.len(), keys.len());
+            assert_eq!(trie.max_xor_with(x), keys.iter().map(|&y| x ^ y).max());
+            assert_eq!(
+                trie.count_less_than_xor(x, k),
+                keys.iter().filter(|&&y| x ^ y < k).count()
+            );
+        }
+    }
+
+    #[test]
+    fn binary_trie_should_use_all_bits() {
+        let keys = generate::uniform(300, 7);
+        let trie = keys.iter().copied().collect::<BinaryTrie<u64>>();
+        let mut rng = Rng::new(3);
+
+        for _ in 0..100 {
+            let x = rng.below(u64::MAX);
+            let k = rng.below(u64::MAX);
+            assert_eq!(trie.max_xor_with(x), keys.iter().map(|&y| x ^ y).max());
+            assert_eq!(
+                trie.count_less_than_xor(x, k),
+                keys.iter().filter(|&&y| x ^ y < k).count()
+            );
+        }
+
+        // 最高位不同的两个键
+        let trie = BinaryTrie::<u64>::from_iter([0, u64::MAX]);
+        assert_eq!(trie.max_xor_with(1), Some(u64::MAX - 1));
+        assert_eq!(trie.count_less_than_xor(0, u64::MAX), 1);
+        assert_eq!(BinaryTrie::<u8>::new().max_xor_with(3), None);
+    }
+}
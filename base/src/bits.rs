@@ -0,0 +1,294 @@
+//! 位运算技巧
+//!
+//! * `x & (x - 1)`：清除最低位的 1
+//! * `x & x.wrapping_neg()`：只保留最低位的 1
+//! * 异或满足交换律和结合律，且 `x ^ x = 0`、`x ^ 0 = x`，成对出现的元素异或后互相抵消
+//! * SWAR（SIMD within a register）：把一个整数看作若干个小的字段，一次运算同时处理所有字段
+
+use std::iter;
+
+/// 统计二进制表示中 1 的个数：每次清除最低位的 1（Brian Kernighan 算法），
+/// 循环次数等于 1 的个数
+///
+/// # Examples
+///
+/// ```rust
+/// use base::bits;
+///
+/// assert_eq!(bits::count_ones_kernighan(0b1011_0000), 3);
+/// assert_eq!(bits::count_ones_kernighan(u64::MAX), 64);
+/// ```
+pub fn count_ones_kernighan(mut x: u64) -> u32 {
+    let mut count = 0;
+
+    while x != 0 {
+        x &= x - 1;
+        count += 1;
+    }
+
+    count
+}
+
+/// 统计二进制表示中 1 的个数：SWAR 并行求和，时间复杂度：O(1)
+///
+/// 依次求出每 2 位、每 4 位、每 8 位中 1 的个数，最后用乘法把 8 个字节的计数累加到最高字节。
+///
+/// # Examples
+///
+/// ```rust
+/// use base::bits;
+///
+/// assert_eq!(bits::count_ones_swar(0b1011_0000), 3);
+/// assert_eq!(bits::count_ones_swar(u64::MAX), 64);
+/// ```
+pub fn count_ones_swar(x: u64) -> u32 {
+    let x = x - ((x >> 1) & 0x5555_5555_5555_5555);
+    let x = (x & 0x3333_3333_3333_3333) + ((x >> 2) & 0x3333_3333_3333_3333);
+    let x = (x + (x >> 4)) & 0x0f0f_0f0f_0f0f_0f0f;
+
+    (x.wrapping_mul(0x0101_0101_0101_0101) >> 56) as u32
+}
+
+/// 翻转二进制位：依次交换相邻的 1 位、2 位、4 位……32 位，时间复杂度：O(log w)
+///
+/// # Examples
+///
+/// ```rust
+/// use base::bits;
+///
+/// assert_eq!(bits::reverse_bits(1), 1 << 63);
+/// assert_eq!(bits::reverse_bits(0b1101), 0b1011 << 60);
+/// ```
+pub fn reverse_bits(x: u64) -> u64 {
+    let x = ((x >> 1) & 0x5555_5555_5555_5555) | ((x & 0x5555_5555_5555_5555) << 1);
+    let x = ((x >> 2) & 0x3333_3333_3333_3333) | ((x & 0x3333_3333_3333_3333) << 2);
+    let x = ((x >> 4) & 0x0f0f_0f0f_0f0f_0f0f) | ((x & 0x0f0f_0f0f_0f0f_0f0f) << 4);
+    let x = ((x >> 8) & 0x00ff_00ff_00ff_00ff) | ((x & 0x00ff_00ff_00ff_00ff) << 8);
+    let x = ((x >> 16) & 0x0000_ffff_0000_ffff) | ((x & 0x0000_ffff_0000_ffff) << 16);
+
+    x.rotate_left(32)
+}
+
+/// 不小于 x 的最小的 2 的幂，结果超出 u64 范围时返回 None
+///
+/// 把最高位的 1 向右“抹开”，使其下方全部变为 1，再加一即可。
+///
+/// # Examples
+///
+/// ```rust
+/// use base::bits;
+///
+/// assert_eq!(bits::next_power_of_two(0), Some(1));
+/// assert_eq!(bits::next_power_of_two(5), Some(8));
+/// assert_eq!(bits::next_power_of_two(8), Some(8));
+/// assert_eq!(bits::next_power_of_two((1 << 63) + 1), None);
+/// ```
+pub fn next_power_of_two(x: u64) -> Option<u64> {
+    if x <= 1 {
+        return Some(1);
+    }
+
+    let mut x = x - 1;
+    for shift in [1, 2, 4, 8, 16, 32] {
+        x |= x >> shift;
+    }
+
+    x.checked_add(1)
+}
+
+/// n 位格雷码序列：相邻两个编码（包括首尾）恰好相差一位，第 i 个编码为 i ^ (i >> 1)
+///
+/// # Panics
+///
+/// Panics if `n` is greater than 63.
+///
+/// # Examples
+///
+/// ```rust
+/// use base::bits;
+///
+/// assert_eq!(bits::gray_code(3), [0b000, 0b001, 0b011, 0b010, 0b110, 0b111, 0b101, 0b100]);
+/// assert_eq!(bits::gray_code(0), [0]);
+/// ```
+pub fn gray_code(n: u32) -> Vec<u64> {
+    assert!(n < 64, "Gray code width {n} is too large");
+
+    (0..1u64 << n).map(|i| i ^ (i >> 1)).collect()
+}
+
+/// 格雷码还原为二进制数：第 i 位为格雷码中第 i 位及更高位的异或
+///
+/// # Examples
+///
+/// ```rust
+/// use base::bits;
+///
+/// assert_eq!(bits::gray_to_binary(0b110), 4);
+/// ```
+pub fn gray_to_binary(mut gray: u64) -> u64 {
+    for shift in [1, 2, 4, 8, 16, 32] {
+        gray ^= gray >> shift;
+    }
+
+    gray
+}
+
+/// 按降序枚举 mask 的所有子集（包括 mask 本身和 0），共 2^k 个，k 为 mask 中 1 的个数
+///
+/// `(s - 1) & mask` 是比 s 小的下一个子集。对所有 mask 枚举子集的总时间复杂度为 O(3^n)。
+///
+/// # Examples
+///
+/// ```rust
+/// use base::bits;
+///
+/// let subsets = bits::subsets_of_mask(0b1010).collect::<Vec<_>>();
+///
+/// assert_eq!(subsets, [0b1010, 0b1000, 0b0010, 0b0000]);
+/// ```
+pub fn subsets_of_mask(mask: u64) -> impl Iterator<Item = u64> {
+    iter::successors(Some(mask), move |&s| (s != 0).then(|| (s - 1) & mask))
+}
+
+/// 除一个元素外其余元素都出现两次，找出只出现一次的元素：全部异或即可
+///
+/// # Examples
+///
+/// ```rust
+/// use base::bits;
+///
+/// assert_eq!(bits::single_number(&[4, 1, 2, 1, 2]), 4);
+/// ```
+pub fn single_number(v: &[i64]) -> i64 {
+    v.iter().fold(0, |acc, x| acc ^ x)
+}
+
+/// 除一个元素外其余元素都出现三次，找出只出现一次的元素
+///
+/// ones 和 twos 记录每一位上 1 出现次数模 3 的结果（分别为 1 和 2），出现三次时两者都清零。
+///
+/// # Examples
+///
+/// ```rust
+/// use base::bits;
+///
+/// assert_eq!(bits::single_number_thrice(&[0, 1, 0, 1, 0, 1, -99]), -99);
+/// ```
+pub fn single_number_thrice(v: &[i64]) -> i64 {
+    let (mut ones, mut twos) = (0, 0);
+
+    for &x in v {
+        ones = (ones ^ x) & !twos;
+        twos = (twos ^ x) & !ones;
+    }
+
+    ones
+}
+
+/// 除两个元素外其余元素都出现两次，找出这两个只出现一次的元素，按升序返回
+///
+/// 全部异或得到 a ^ b，其中任意一位 1 都说明 a 和 b 在该位不同，按该位把元素分为两组分别异或。
+///
+/// # Examples
+///
+/// ```rust
+/// use base::bits;
+///
+/// assert_eq!(bits::two_single_numbers(&[1, 2, 1, 3, 2, 5]), (3, 5));
+/// ```
+pub fn two_single_numbers(v: &[i64]) -> (i64, i64) {
+    let xor = single_number(v);
+    let lowest = xor & xor.wrapping_neg();
+
+    let a = v
+        .iter()
+        .filter(|&&x| x & lowest != 0)
+        .fold(0, |acc, x| acc ^ x);
+    let b = xor ^ a;
+
+    (a.min(b), a.max(b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generate::{self, Rng};
+
+    #[test]
+    fn bit_tricks_should_match_std() {
+        let mut rng = Rng::new(7);
+        let samples =
+            (0..1000)
+                .map(|_| rng.next_u64() >> rng.below(64))
+                .chain([0, 1, u64::MAX, 1 << 63]);
+
+        for x in samples {
+            assert_eq!(count_ones_kernighan(x), x.count_ones());
+            assert_eq!(count_ones_swar(x), x.count_ones());
+            assert_eq!(reverse_bits(x), x.reverse_bits());
+            assert_eq!(next_power_of_two(x), x.checked_next_power_of_two());
+            assert_eq!(gray_to_binary(x ^ (x >> 1)), x);
+        }
+    }
+
+    #[test]
+    fn gray_code_should_differ_by_one_bit() {
+        for n in 0..=10 {
+            let codes = gray_code(n);
+            assert_eq!(codes.len(), 1 << n);
+
+            let mut sorted = codes.clone();
+            sorted.sort();
+            assert!(sorted.iter().copied().eq(0..1 << n));
+
+            if n > 0 {
+                for i in 0..codes.len() {
+                    let next = codes[(i + 1) % codes.len()];
+                    assert_eq!((codes[i] ^ next).count_ones(), 1);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn subsets_of_mask_should_work() {
+        for mask in [0, 1, 0b1011_0110, 0xff] {
+            let subsets = subsets_of_mask(mask).collect::<Vec<_>>();
+            let expected = (0..=mask)
+                .rev()
+                .filter(|s| s & mask == *s)
+                .collect::<Vec<_>>();
+            assert_eq!(subsets, expected);
+        }
+    }
+
+    #[test]
+    fn single_numbers_should_work() {
+        let v = generate::uniform(100, 5)
+            .into_iter()
+            .map(|x| x as i64)
+            .collect::<Vec<_>>();
+        let mut distinct = v.clone();
+        distinct.sort();
+        distinct.dedup();
+        let (a, b) = (distinct[0], distinct[1]);
+
+        let twice = distinct[1..]
+            .iter()
+            .flat_map(|&x| [x, x])
+            .collect::<Vec<_>>();
+        assert_eq!(single_number(&[&twice[..], &[a]].concat()), a);
+
+        let thrice = distinct[1..]
+            .iter()
+            .flat_map(|&x| [x, x, x])
+            .collect::<Vec<_>>();
+        assert_eq!(single_number_thrice(&[&[a][..], &thrice].concat()), a);
+
+        let pairs = distinct[2..]
+            .iter()
+            .flat_map(|&x| [x, x])
+            .collect::<Vec<_>>();
+        assert_eq!(two_single_numbers(&[&pairs[..], &[b, a]].concat()), (a, b));
+    }
+}
@@ -0,0 +1,186 @@
+//! 笛卡尔树（Cartesian tree）
+//!
+//! 由数组构建的二叉树，同时满足：
+//! * 中序遍历恰好是原数组，即下标满足二叉搜索树的性质
+//! * 每个结点的值不大于其子结点的值，即值满足小顶堆的性质
+//!
+//! 使用单调栈可以在 O(n) 时间内构建：栈中保存当前最右侧链上的结点，值自底向上递增。
+//! 加入新元素时弹出所有比它大的结点，最后弹出的结点成为新结点的左子结点，
+//! 新结点成为栈顶结点的右子结点。
+//!
+//! 区间最小值（RMQ）可以转化为最近公共祖先（LCA）：区间 [l, r] 的最小值位于
+//! 结点 l 和 r 的最近公共祖先上。借助 [`LcaIndex`] 单次查询的时间复杂度为 O(logn)。
+
+use std::ops::Range;
+
+use crate::{LcaIndex, StackWithVec};
+
+/// 笛卡尔树，结点编号即元素在原数组中的下标
+#[derive(Debug, Clone)]
+pub struct CartesianTree {
+    root: Option<usize>,
+    parent: Vec<Option<usize>>,
+    left: Vec<Option<usize>>,
+    right: Vec<Option<usize>>,
+    lca: LcaIndex,
+}
+
+impl CartesianTree {
+    /// 使用单调栈构建笛卡尔树，时间复杂度：O(n)
+    ///
+    /// 值相等时靠左的元素作为祖先，因此区间最小值有多个时返回最左侧的下标
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use base::CartesianTree;
+    ///
+    /// let tree = CartesianTree::from_slice(&[3, 1, 4, 1, 5, 9, 2, 6]);
+    ///
+    /// assert_eq!(tree.root(), Some(1));
+    /// assert_eq!(tree.range_min(2..7), Some(3));
+    /// assert_eq!(tree.range_min(4..6), Some(4));
+    /// ```
+    pub fn from_slice<T: Ord>(v: &[T]) -> Self {
+        let n = v.len();
+        let mut parent = vec![None; n];
+        let mut left = vec![None; n];
+        let mut right = vec![None; n];
+        let mut stack = StackWithVec::new();
+
+        for i in 0..n {
+            // 弹出所有比 v[i] 大的结点，最后弹出的结点成为 i 的左子结点
+            let mut last = None;
+            while let Some(&top) = stack.peek()
+                && v[top] > v[i]
+            {
+                last = stack.pop();
+            }
+
+            if let Some(child) = last {
+                left[i] = Some(child);
+                parent[child] = Some(i);
+            }
+            if let Some(&top) = stack.peek() {
+                right[top] = Some(i);
+                parent[i] = Some(top);
+            }
+            stack.push(i);
+        }
+
+        Self {
+            // 栈底的结点即为整个数组的最小值
+            root: stack.first().copied(),
+            lca: LcaIndex::from_parents(&parent),
+            parent,
+            left,
+            right,
+        }
+    }
+
+    /// 结点数量
+    pub fn len(&self) -> usize {
+        self.parent.len()
+    }
+
+    /// 判断树是否为空
+    pub fn is_empty(&self) -> bool {
+        self.parent.is_empty()
+    }
+
+    /// 根结点，即整个数组最小值的下标
+    pub fn root(&self) -> Option<usize> {
+        self.root
+    }
+
+    /// 结点 i 的父结点
+    pub fn parent(&self, i: usize) -> Option<usize> {
+        self.parent[i]
+    }
+
+    /// 结点 i 的左子结点
+    pub fn left(&self, i: usize) -> Option<usize> {
+        self.left[i]
+    }
+
+    /// 结点 i 的右子结点
+    pub fn right(&self, i: usize) -> Option<usize> {
+        self.right[i]
+    }
+
+    /// 区间 [range.start, range.end) 中最小值的下标，空区间返回 None
+    ///
+    /// # Panics
+    ///
+    /// Panics if `range.end > len`.
+    pub fn range_min(&self, range: Range<usize>) -> Option<usize> {
+        let len = self.len();
+        assert!(
+            range.end <= len,
+            "Range end out of bounds: {}, len: {len}",
+            range.end
+        );
+
+        if range.is_empty() {
+            return None;
+        }
+
+        self.lca.lca(range.start, range.end - 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generate;
+
+    #[test]
+    fn cartesian_tree_should_have_heap_and_bst_shape() {
+        let v = [5, 2, 8, 1, 9, 3];
+        let tree = CartesianTree::from_slice(&v);
+
+        assert_eq!(tree.root(), Some(3));
+        assert_eq!(tree.left(3), Some(1));
+        assert_eq!(tree.right(3), Some(5));
+        assert_eq!((tree.left(1), tree.right(1)), (Some(0), Some(2)));
+        assert_eq!(tree.left(5), Some(4));
+        assert_eq!(tree.parent(3), None);
+
+        // 中序遍历恰好是 0..n
+        fn in_order(tree: &CartesianTree, node: Option<usize>, out: &mut Vec<usize>) {
+            if let Some(i) = node {
+                in_order(tree, tree.left(i), out);
+                out.push(i);
+                in_order(tree, tree.right(i), out);
+            }
+        }
+        let mut order = Vec::new();
+        in_order(&tree, tree.root(), &mut order);
+        assert_eq!(order, (0..v.len()).collect::<Vec<_>>());
+
+        let empty = CartesianTree::from_slice::<i32>(&[]);
+        assert!(empty.is_empty());
+        assert_eq!(empty.root(), None);
+    }
+
+    #[test]
+    fn range_min_should_match_brute_force() {
+        let v = generate::few_distinct(200, 30, 4);
+        let tree = CartesianTree::from_slice(&v);
+
+        for l in 0..v.len() {
+            for r in l + 1..=v.len() {
+                // min_by_key 在值相等时返回第一个，与笛卡尔树的约定一致
+                let expected = (l..r).min_by_key(|&i| v[i]);
+                assert_eq!(tree.range_min(l..r), expected);
+            }
+        }
+        assert_eq!(tree.range_min(5..5), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "out of bounds")]
+    fn range_min_should_panic_out_of_bounds() {
+        CartesianTree::from_slice(&[1, 2, 3]).range_min(0..4);
+    }
+}
@@ -0,0 +1,254 @@
+//! LZ77 压缩
+//!
+//! LZ77 在已处理过的数据（滑动窗口）中查找与当前位置最长的重复片段，
+//! 找到时输出（距离，长度）引用，否则原样输出当前字节。
+//! 解码时按引用从已解码的数据中复制即可，引用允许与自身重叠（距离小于长度），
+//! 例如 "aaaaaa" 可编码为字面量 'a' 加上引用（距离 1，长度 5）。
+//!
+//! 查找重复片段使用哈希链：以每个位置起始的 3 个字节计算哈希值，
+//! 相同哈希值的位置串成链表（越靠后越靠近表头），匹配时只需沿链表检查有限个候选位置。
+//! 输出的记号序列还可以再进行熵编码，进一步压缩。
+
+/// 引用的最短长度，更短的重复片段不如直接输出字面量
+const MIN_MATCH: usize = 3;
+/// 引用的最大长度
+const MAX_MATCH: usize = 258;
+/// 每个位置最多检查的候选位置数量，限制最坏情况下的匹配开销
+const MAX_CHAIN: usize = 128;
+/// 哈希表大小为 2^HASH_BITS
+const HASH_BITS: u32 = 15;
+
+/// LZ77 编码输出的记号
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Token {
+    /// 原样输出的字节
+    Literal(u8),
+    /// 引用：从当前位置向前 distance 个字节处开始，复制 length 个字节
+    Match { distance: usize, length: usize },
+}
+
+/// LZ77 编码，window 为滑动窗口的大小，即引用的最大距离
+///
+/// # Examples
+///
+/// ```rust
+/// use base::compress::{self, Token};
+///
+/// let tokens = compress::lz77_encode(b"abcabcabcd", 4096);
+///
+/// assert_eq!(
+///     tokens,
+///     [
+///         Token::Literal(b'a'),
+///         Token::Literal(b'b'),
+///         Token::Literal(b'c'),
+///         Token::Match { distance: 3, length: 6 },
+///         Token::Literal(b'd'),
+///     ]
+/// );
+/// assert_eq!(compress::lz77_decode(&tokens), b"abcabcabcd");
+/// ```
+pub fn lz77_encode(data: &[u8], window: usize) -> Vec<Token> {
+    let mut chains = HashChains::new(data.len());
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < data.len() {
+        let (distance, length) = chains.longest_match(data, i, window);
+
+        if length >= MIN_MATCH {
+            tokens.push(Token::Match { distance, length });
+            // 被引用覆盖的位置也要加入哈希链，供之后的匹配使用
+            for pos in i..i + length {
+                chains.insert(data, pos);
+            }
+            i += length;
+        } else {
+            tokens.push(Token::Literal(data[i]));
+            chains.insert(data, i);
+            i += 1;
+        }
+    }
+
+    tokens
+}
+
+/// LZ77 解码
+///
+/// # Panics
+///
+/// Panics if a match refers to data before the start of the output.
+pub fn lz77_decode(tokens: &[Token]) -> Vec<u8> {
+    let mut data = Vec::new();
+
+    for &token in tokens {
+        match token {
+            Token::Literal(byte) => data.push(byte),
+            Token::Match { distance, length } => {
+                assert!(
+                    (1..=data.len()).contains(&distance),
+                    "Invalid match distance: {distance}, decoded: {}",
+                    data.len()
+                );
+                // 引用可能与正在复制的部分重叠，只能逐字节复制
+                let start = data.len() - distance;
+                for k in 0..length {
+                    data.push(data[start + k]);
+                }
+            }
+        }
+    }
+
+    data
+}
+
+/// 哈希链匹配器
+struct HashChains {
+    /// 每个哈希值最近一次出现的位置
+    head: Vec<Option<usize>>,
+    /// 与该位置哈希值相同的上一个位置
+    prev: Vec<Option<usize>>,
+}
+
+impl HashChains {
+    fn new(len: usize) -> Self {
+        Self {
+            head: vec![None; 1 << HASH_BITS],
+            prev: vec![None; len],
+        }
+    }
+
+    /// 以 pos 起始的 3 个字节的哈希值，调用者保证 pos + MIN_MATCH <= data.len()
+    fn hash(data: &[u8], pos: usize) -> usize {
+        let key = data[pos..pos + MIN_MATCH]
+            .iter()
+            .fold(0u32, |h, &b| (h << 5) ^ b as u32);
+
+        (key.wrapping_mul(0x9e37_79b1) >> (32 - HASH_BITS)) as usize
+    }
+
+    fn insert(&mut self, data: &[u8], pos: usize) {
+        if pos + MIN_MATCH > data.len() {
+            return;
+        }
+
+        let h = Self::hash(data, pos);
+        self.prev[pos] = self.head[h];
+        self.head[h] = Some(pos);
+    }
+
+    /// 在窗口内查找与 pos 处最长的重复片段，返回 (距离, 长度)
+    fn longest_match(&self, data: &[u8], pos: usize, window: usize) -> (usize, usize) {
+        if pos + MIN_MATCH > data.len() {
+            return (0, 0);
+        }
+
+        let max = MAX_MATCH.min(data.len() - pos);
+        let (mut best_distance, mut best_length) = (0, 0);
+        let mut candidate = self.head[Self::hash(data, pos)];
+
+        // 链表中的位置由近及远，超出窗口后即可停止
+        for _ in 0..MAX_CHAIN {
+            let Some(start) = candidate else {
+                break;
+            };
+            if pos - start > window {
+                break;
+            }
+
+            let length = data[start..]
+                .iter()
+                .zip(&data[pos..pos + max])
+                .take_while(|(a, b)| a == b)
+                .count();
+            if length > best_length {
+                (best_distance, best_length) = (pos - start, length);
+                if length == max {
+                    break;
+                }
+            }
+
+            candidate = self.prev[start];
+        }
+
+        (best_distance, best_length)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generate;
+
+    const CORPUS: &str = "\
+        It was the best of times, it was the worst of times, it was the age of wisdom, \
+        it was the age of foolishness, it was the epoch of belief, it was the epoch of incredulity, \
+        it was the season of Light, it was the season of Darkness. \
+        天行健，君子以自强不息；地势坤，君子以厚德载物。天行健，君子以自强不息。";
+
+    #[test]
+    fn lz77_should_round_trip() {
+        let random = generate::uniform(5000, 0)
+            .into_iter()
+            .map(|x| x as u8)
+            .collect::<Vec<_>>();
+
+        let inputs = [
+            Vec::new(),
+            b"a".to_vec(),
+            b"ab".to_vec(),
+            vec![b'z'; 1000],
+            CORPUS.as_bytes().to_vec(),
+            CORPUS.repeat(20).into_bytes(),
+            random,
+        ];
+
+        for input in inputs {
+            for window in [0, 1, 16, 4096, usize::MAX] {
+                let tokens = lz77_encode(&input, window);
+                assert_eq!(lz77_decode(&tokens), input, "window: {window}");
+                assert!(tokens.iter().all(|token| match token {
+                    Token::Literal(_) => true,
+                    Token::Match { distance, length } =>
+                        *distance <= window && (MIN_MATCH..=MAX_MATCH).contains(length),
+                }));
+            }
+        }
+    }
+
+    #[test]
+    fn lz77_should_compress_repetitive_text() {
+        let text = CORPUS.repeat(20);
+        let tokens = lz77_encode(text.as_bytes(), 32 * 1024);
+        assert!(tokens.len() * 10 < text.len());
+
+        // 窗口太小时找不到重复的段落
+        let small = lz77_encode(text.as_bytes(), 8);
+        assert!(small.len() > tokens.len() * 10);
+
+        // 重叠引用
+        let tokens = lz77_encode(&[7; 100], 1);
+        assert_eq!(
+            tokens,
+            [
+                Token::Literal(7),
+                Token::Match {
+                    distance: 1,
+                    length: 99
+                }
+            ]
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn lz77_decode_invalid_distance_should_panic() {
+        lz77_decode(&[
+            Token::Literal(1),
+            Token::Match {
+                distance: 2,
+                length: 1,
+            },
+        ]);
+    }
+}
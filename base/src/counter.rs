@@ -0,0 +1,325 @@
+//! 计数器（counter）：基于哈希表的多重集合
+//!
+//! 多重集合允许元素重复出现，计数器用哈希表记录每个元素出现的次数，次数降为 0 时删除元素。
+//! 哈希表使用有序模式（[`ChainedHashMap::ordered`]），按元素第一次出现的顺序遍历，
+//! 次数相同的元素也按这个顺序排列，输出总是确定的。次数降为 0 后再次加入的元素视为新出现，排在最后。
+//!
+//! 两个计数器之间的运算与集合运算对应：
+//! * 并集：每个元素取两边次数的较大值
+//! * 交集：每个元素取两边次数的较小值
+//! * 和：次数相加
+//! * 差：次数相减，结果不大于 0 的元素被删除
+
+use std::{borrow::Borrow, cmp::Reverse, hash::Hash};
+
+use crate::{ChainedHashMap, top_k_heap};
+
+/// 计数器
+///
+/// # Examples
+///
+/// ```rust
+/// use base::Counter;
+///
+/// let counter = "abracadabra".chars().collect::<Counter<_>>();
+///
+/// assert_eq!(counter.get(&'a'), 5);
+/// assert_eq!(counter.get(&'z'), 0);
+/// assert_eq!(counter.total(), 11);
+/// assert_eq!(counter.most_common(3), [(&'a', 5), (&'b', 2), (&'r', 2)]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Counter<T> {
+    counts: ChainedHashMap<T, usize>,
+    total: usize,
+}
+
+impl<T> Counter<T> {
+    /// 创建空的计数器
+    pub fn new() -> Self {
+        Self {
+            counts: ChainedHashMap::ordered(),
+            total: 0,
+        }
+    }
+
+    /// 不同元素的数量
+    pub fn len(&self) -> usize {
+        self.counts.len()
+    }
+
+    /// 判断是否为空
+    pub fn is_empty(&self) -> bool {
+        self.counts.is_empty()
+    }
+
+    /// 所有元素出现次数之和
+    pub fn total(&self) -> usize {
+        self.total
+    }
+
+    /// 按第一次出现的顺序遍历元素及其次数
+    pub fn iter(&self) -> impl Iterator<Item = (&T, usize)> {
+        self.counts.iter().map(|(item, &count)| (item, count))
+    }
+
+    /// 出现次数最多的 k 个元素，按次数降序排列，次数相同时按第一次出现的顺序排列
+    ///
+    /// 时间复杂度：O(nlogk)，n 为不同元素的数量
+    pub fn most_common(&self, k: usize) -> Vec<(&T, usize)> {
+        if k == 0 {
+            return Vec::new();
+        }
+
+        let items = self.iter().collect::<Vec<_>>();
+        // 按 (次数, 出现顺序的相反数) 选出最大的 k 个，堆中只保存下标
+        let candidates = items
+            .iter()
+            .enumerate()
+            .map(|(i, &(_, count))| (count, Reverse(i)));
+
+        // 堆中的元素是 Reverse，into_sorted_vec 的升序即为降序
+        top_k_heap(candidates, k)
+            .into_sorted_vec()
+            .into_iter()
+            .map(|Reverse((_, Reverse(i)))| items[i])
+            .collect()
+    }
+
+    /// 按次数降序遍历所有元素，次数相同时按第一次出现的顺序排列
+    pub fn sorted_by_count(&self) -> Vec<(&T, usize)> {
+        self.most_common(self.len())
+    }
+}
+
+impl<T: Hash + Eq> Counter<T> {
+    /// 元素出现的次数
+    pub fn get<Q>(&self, item: &Q) -> usize
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.counts.get(item).copied().unwrap_or(0)
+    }
+
+    /// 判断元素是否出现过
+    pub fn contains<Q>(&self, item: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.counts.contains_key(item)
+    }
+
+    /// 记录元素出现一次
+    pub fn add(&mut self, item: T) {
+        self.add_n(item, 1);
+    }
+
+    /// 记录元素出现 n 次
+    pub fn add_n(&mut self, item: T, n: usize) {
+        if n == 0 {
+            return;
+        }
+
+        *self.counts.entry(item).or_default() += n;
+        self.total += n;
+    }
+
+    /// 删除元素的一次出现，元素不存在时返回 false
+    pub fn remove<Q>(&mut self, item: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.remove_n(item, 1) == 1
+    }
+
+    /// 最多删除元素的 n 次出现，返回实际删除的次数
+    pub fn remove_n<Q>(&mut self, item: &Q, n: usize) -> usize
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let Some(count) = self.counts.get_mut(item) else {
+            return 0;
+        };
+
+        let removed = n.min(*count);
+        *count -= removed;
+        if *count == 0 {
+            self.counts.remove(item);
+        }
+        self.total -= removed;
+
+        removed
+    }
+
+    /// 删除元素的所有出现，返回删除的次数
+    pub fn remove_all<Q>(&mut self, item: &Q) -> usize
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let count = self.counts.remove(item).unwrap_or(0);
+        self.total -= count;
+
+        count
+    }
+}
+
+impl<T: Hash + Eq + Clone> Counter<T> {
+    /// 并集：每个元素取两边次数的较大值
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use base::Counter;
+    ///
+    /// let a = Counter::from_iter("aab".chars());
+    /// let b = Counter::from_iter("abbc".chars());
+    ///
+    /// assert!(a.union(&b).iter().eq([(&'a', 2), (&'b', 2), (&'c', 1)]));
+    /// assert!(a.intersection(&b).iter().eq([(&'a', 1), (&'b', 1)]));
+    /// assert!(a.sum(&b).iter().eq([(&'a', 3), (&'b', 3), (&'c', 1)]));
+    /// assert!(a.difference(&b).iter().eq([(&'a', 1)]));
+    /// ```
+    pub fn union(&self, other: &Self) -> Self {
+        let mut result = self.clone();
+        for (item, count) in other.iter() {
+            let extra = count.saturating_sub(self.get(item));
+            result.add_n(item.clone(), extra);
+        }
+
+        result
+    }
+
+    /// 交集：每个元素取两边次数的较小值
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut result = Self::new();
+        for (item, count) in self.iter() {
+            result.add_n(item.clone(), count.min(other.get(item)));
+        }
+
+        result
+    }
+
+    /// 和：次数相加
+    pub fn sum(&self, other: &Self) -> Self {
+        let mut result = self.clone();
+        for (item, count) in other.iter() {
+            result.add_n(item.clone(), count);
+        }
+
+        result
+    }
+
+    /// 差：次数相减，只保留次数仍大于 0 的元素
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut result = Self::new();
+        for (item, count) in self.iter() {
+            result.add_n(item.clone(), count.saturating_sub(other.get(item)));
+        }
+
+        result
+    }
+}
+
+impl<T> Default for Counter<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Hash + Eq> Extend<T> for Counter<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for item in iter {
+            self.add(item);
+        }
+    }
+}
+
+impl<T: Hash + Eq> FromIterator<T> for Counter<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut counter = Counter::new();
+
+        counter.extend(iter);
+        counter
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::generate;
+
+    #[test]
+    fn counter_should_match_std() {
+        let items = generate::few_distinct(2000, 30, 8);
+        let mut counter = items.iter().copied().collect::<Counter<_>>();
+        let mut expected = HashMap::new();
+        for &item in &items {
+            *expected.entry(item).or_insert(0) += 1;
+        }
+
+        for &item in items.iter().step_by(3) {
+            let before = expected[&item];
+            assert_eq!(counter.remove(&item), before > 0);
+            if before > 0 {
+                *expected.get_mut(&item).unwrap() -= 1;
+            }
+        }
+        expected.retain(|_, count| *count > 0);
+
+        assert_eq!(counter.len(), expected.len());
+        assert_eq!(counter.total(), expected.values().sum::<usize>());
+        assert!(counter.iter().all(|(item, count)| expected[item] == count));
+
+        // 按次数降序，次数相同时按第一次出现的顺序
+        let first_seen = |item: &u64| items.iter().position(|x| x == item).unwrap();
+        let mut sorted = expected.into_iter().collect::<Vec<_>>();
+        sorted.sort_by_key(|&(item, count)| (Reverse(count), first_seen(&item)));
+        let sorted = sorted
+            .iter()
+            .map(|(item, count)| (item, *count))
+            .collect::<Vec<_>>();
+        assert_eq!(counter.sorted_by_count(), sorted);
+        assert_eq!(counter.most_common(5), sorted[..5]);
+        assert!(counter.most_common(0).is_empty());
+    }
+
+    #[test]
+    fn counter_arithmetic_should_work() {
+        let a = Counter::from_iter(generate::few_distinct(300, 10, 1));
+        let b = Counter::from_iter(generate::few_distinct(300, 10, 2));
+        let keys = a
+            .iter()
+            .chain(b.iter())
+            .map(|(&item, _)| item)
+            .collect::<Vec<_>>();
+
+        let (union, intersection) = (a.union(&b), a.intersection(&b));
+        let (sum, difference) = (a.sum(&b), a.difference(&b));
+        for item in keys {
+            let (x, y) = (a.get(&item), b.get(&item));
+            assert_eq!(union.get(&item), x.max(y));
+            assert_eq!(intersection.get(&item), x.min(y));
+            assert_eq!(sum.get(&item), x + y);
+            assert_eq!(difference.get(&item), x.saturating_sub(y));
+        }
+
+        // 次数为 0 的元素不会保留
+        assert!(intersection.iter().all(|(_, count)| count > 0));
+        assert!(difference.iter().all(|(_, count)| count > 0));
+        assert_eq!(sum.total(), a.total() + b.total());
+
+        let mut c = Counter::from_iter("mississippi".chars());
+        assert_eq!(c.remove_n(&'s', 3), 3);
+        assert_eq!(c.remove_n(&'s', 3), 1);
+        assert_eq!(c.remove_all(&'i'), 4);
+        assert!(!c.contains(&'s'));
+        assert_eq!(c.total(), 3);
+    }
+}
@@ -0,0 +1,86 @@
+//! 离散化（坐标压缩）
+//!
+//! 很多算法只关心数值之间的大小关系，而不关心数值本身，例如用线段树统计逆序对时，
+//! 下标需要落在 [0, n) 中，而原始数值可能是很大的整数甚至字符串。
+//! 离散化把每个值替换为它在所有不同值中的排名，既保持大小关系，又把取值范围压缩到 [0, m)，m 为不同值的数量。
+//! 排名升序排列的不同值同时作为反向映射：`values[rank]` 即为原来的值。
+
+use crate::sort;
+
+/// 把 values 中的每个值替换为其排名，返回 (排名, 升序排列的不同值)
+///
+/// 相等的值得到相同的排名，a < b 当且仅当 rank(a) < rank(b)。
+/// 时间复杂度：O(nlogn)，查找其他值的排名可以在返回的不同值上二分。
+///
+/// # Examples
+///
+/// ```rust
+/// use base::discretize;
+///
+/// let (ranks, values) = discretize::compress_coordinates(&[1_000_000, -5, 42, -5]);
+///
+/// assert_eq!(ranks, [2, 0, 1, 0]);
+/// assert_eq!(values, [-5, 42, 1_000_000]);
+/// assert_eq!(values[ranks[2]], 42);
+/// ```
+///
+/// 配合线段树统计逆序对：按顺序扫描，查询已出现的值中比当前值大的数量。
+///
+/// ```rust
+/// use base::{SegmentTree, discretize};
+///
+/// let v = ["pear", "apple", "fig", "apple", "banana"];
+/// let (ranks, values) = discretize::compress_coordinates(&v);
+/// let mut seen = SegmentTree::new(vec![0; values.len()], 0, |a, b| a + b);
+///
+/// let mut inversions = 0;
+/// for rank in ranks {
+///     inversions += seen.query(rank + 1..values.len());
+///     seen.update(rank, 1);
+/// }
+/// assert_eq!(inversions, 6);
+/// ```
+pub fn compress_coordinates<T: Ord + Clone>(values: &[T]) -> (Vec<usize>, Vec<T>) {
+    let mut sorted = values.to_vec();
+    sort::pdq_sort(&mut sorted);
+    sorted.dedup();
+
+    let ranks = values
+        .iter()
+        .map(|value| {
+            // Safety: 每个值都在 sorted 中
+            sorted.binary_search(value).unwrap()
+        })
+        .collect();
+
+    (ranks, sorted)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generate;
+
+    #[test]
+    fn compress_coordinates_should_preserve_order() {
+        for values in [
+            generate::uniform(500, 1),
+            generate::few_distinct(500, 7, 2),
+            Vec::new(),
+        ] {
+            let (ranks, distinct) = compress_coordinates(&values);
+
+            assert!(distinct.windows(2).all(|w| w[0] < w[1]));
+            assert_eq!(ranks.len(), values.len());
+            for (i, &rank) in ranks.iter().enumerate() {
+                assert_eq!(distinct[rank], values[i]);
+                assert!(
+                    ranks
+                        .iter()
+                        .zip(&values)
+                        .all(|(&r, v)| (r < rank) == (*v < values[i]))
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,159 @@
+//! 简单编码
+//!
+//! * 游程编码（run-length encoding，RLE）：将连续重复的元素压缩为（元素，重复次数），
+//!   适用于包含大量连续重复元素的数据
+//! * 差分编码（delta encoding）：保存相邻元素的差值，有序或变化平缓的序列差值很小
+//! * 之字形编码（zigzag encoding）：将有符号整数映射为无符号整数，0, -1, 1, -2, 2... 依次映射为
+//!   0, 1, 2, 3, 4...，使绝对值较小的负数也得到较小的编码，常与差分编码、变长整数编码配合使用
+
+/// 游程编码，时间复杂度：O(n)
+///
+/// # Examples
+///
+/// ```rust
+/// use base::encoding;
+///
+/// let runs = encoding::rle_encode(b"aaabccdddd");
+///
+/// assert_eq!(runs, vec![(b'a', 3), (b'b', 1), (b'c', 2), (b'd', 4)]);
+/// assert_eq!(encoding::rle_decode(&runs), b"aaabccdddd");
+/// ```
+pub fn rle_encode<T: PartialEq + Clone>(v: &[T]) -> Vec<(T, usize)> {
+    let mut runs: Vec<(T, usize)> = Vec::new();
+
+    for x in v {
+        match runs.last_mut() {
+            Some((last, count)) if last == x => *count += 1,
+            _ => runs.push((x.clone(), 1)),
+        }
+    }
+
+    runs
+}
+
+/// 游程解码，时间复杂度：O(n)，n 为解码后的长度
+pub fn rle_decode<T: Clone>(runs: &[(T, usize)]) -> Vec<T> {
+    runs.iter()
+        .flat_map(|(x, count)| std::iter::repeat_n(x.clone(), *count))
+        .collect()
+}
+
+/// 差分编码：首个元素原样保存，之后保存与前一个元素的差值，溢出时回绕
+///
+/// # Examples
+///
+/// ```rust
+/// use base::encoding;
+///
+/// let deltas = encoding::delta_encode(&[100, 102, 101, 105]);
+///
+/// assert_eq!(deltas, vec![100, 2, -1, 4]);
+/// assert_eq!(encoding::delta_decode(&deltas), vec![100, 102, 101, 105]);
+/// ```
+pub fn delta_encode(v: &[i64]) -> Vec<i64> {
+    let mut prev = 0;
+
+    v.iter()
+        .map(|&x| {
+            let delta = x.wrapping_sub(prev);
+            prev = x;
+            delta
+        })
+        .collect()
+}
+
+/// 差分解码，即对差值求前缀和
+pub fn delta_decode(deltas: &[i64]) -> Vec<i64> {
+    deltas
+        .iter()
+        .scan(0i64, |prev, &delta| {
+            *prev = prev.wrapping_add(delta);
+            Some(*prev)
+        })
+        .collect()
+}
+
+/// 之字形编码
+///
+/// # Examples
+///
+/// ```rust
+/// use base::encoding;
+///
+/// assert_eq!([0, -1, 1, -2, 2].map(encoding::zigzag_encode), [0, 1, 2, 3, 4]);
+/// assert_eq!(encoding::zigzag_decode(encoding::zigzag_encode(i64::MIN)), i64::MIN);
+/// ```
+pub fn zigzag_encode(x: i64) -> u64 {
+    // 算术右移得到全 0（非负数）或全 1（负数），异或后负数按位取反
+    ((x << 1) ^ (x >> 63)) as u64
+}
+
+/// 之字形解码
+pub fn zigzag_decode(x: u64) -> i64 {
+    ((x >> 1) as i64) ^ -((x & 1) as i64)
+}
+
+/// 先差分编码再之字形编码，将变化平缓的整数序列转换为较小的无符号整数
+///
+/// # Examples
+///
+/// ```rust
+/// use base::encoding;
+///
+/// let encoded = encoding::delta_zigzag_encode(&[7, 5, 6, 6]);
+///
+/// assert_eq!(encoded, vec![14, 3, 2, 0]);
+/// assert_eq!(encoding::delta_zigzag_decode(&encoded), vec![7, 5, 6, 6]);
+/// ```
+pub fn delta_zigzag_encode(v: &[i64]) -> Vec<u64> {
+    delta_encode(v).into_iter().map(zigzag_encode).collect()
+}
+
+/// [`delta_zigzag_encode`] 的逆运算
+pub fn delta_zigzag_decode(v: &[u64]) -> Vec<i64> {
+    let deltas = v.iter().map(|&x| zigzag_decode(x)).collect::<Vec<_>>();
+
+    delta_decode(&deltas)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rle_should_work() {
+        assert!(rle_encode::<u8>(&[]).is_empty());
+        assert!(rle_decode::<u8>(&[]).is_empty());
+
+        let v = vec!["x", "x", "y", "x", "x", "x"];
+        let runs = rle_encode(&v);
+        assert_eq!(runs, vec![("x", 2), ("y", 1), ("x", 3)]);
+        assert_eq!(rle_decode(&runs), v);
+    }
+
+    #[test]
+    fn delta_should_work() {
+        let v = vec![i64::MAX, i64::MIN, 0, -5, 3];
+        let deltas = delta_encode(&v);
+
+        assert_eq!(deltas[2], i64::MIN.wrapping_neg());
+        assert_eq!(delta_decode(&deltas), v);
+        assert!(delta_encode(&[]).is_empty());
+    }
+
+    #[test]
+    fn zigzag_should_work() {
+        for x in [0, 1, -1, 63, -64, i64::MAX, i64::MIN] {
+            assert_eq!(zigzag_decode(zigzag_encode(x)), x);
+        }
+        assert_eq!(zigzag_encode(i64::MAX), u64::MAX - 1);
+        assert_eq!(zigzag_encode(i64::MIN), u64::MAX);
+
+        let v = (0..100)
+            .map(|i| 1_000_000 + (i % 7) - 3)
+            .collect::<Vec<_>>();
+        let encoded = delta_zigzag_encode(&v);
+        assert!(encoded[1..].iter().all(|&x| x < 16));
+        assert_eq!(delta_zigzag_decode(&encoded), v);
+    }
+}
@@ -0,0 +1,496 @@
+//! 精确覆盖（exact cover）与舞蹈链（Dancing Links，DLX）
+//!
+//! 给定一个 0/1 矩阵，选出若干行，使每一列恰好有一个 1，即为精确覆盖问题。
+//! Knuth 的 X 算法是朴素的回溯：选择 1 最少的一列，依次尝试覆盖这一列的每一行，
+//! 删除与该行冲突的所有行和列后递归，失败时恢复。
+//!
+//! 舞蹈链让删除和恢复都是 O(1) 的：矩阵中的每个 1 是一个结点，同一行的结点串成循环双向链表，
+//! 同一列的结点也串成循环双向链表，每列的表头再串成一个循环链表。从双向链表中摘下结点 x 后，
+//! x 自己的左右指针仍指向原来的邻居，按相反的顺序执行 `x.left.right = x; x.right.left = x` 即可放回，
+//! 回溯时不需要保存任何额外的状态。
+//!
+//! 结点保存在 Vec 中，指针用下标表示：0 为总表头，1..=columns 为列表头，之后为矩阵中的 1。
+//!
+//! 数独可以归约为精确覆盖，见 [`solve_sudoku`]；[`solve_sudoku_backtracking`] 为直接回溯的版本，用于对比。
+
+/// 舞蹈链的结点
+#[derive(Debug, Clone)]
+struct DlxNode {
+    left: usize,
+    right: usize,
+    up: usize,
+    down: usize,
+    /// 所在列的表头
+    column: usize,
+    /// 所在行的编号，表头结点为 usize::MAX
+    row: usize,
+}
+
+/// 基于舞蹈链的精确覆盖求解器
+///
+/// # Examples
+///
+/// Knuth 论文中的例子：
+///
+/// ```rust
+/// use base::ExactCover;
+///
+/// let mut problem = ExactCover::new(7);
+/// problem.add_row(&[2, 4, 5]);
+/// problem.add_row(&[0, 3, 6]);
+/// problem.add_row(&[1, 2, 5]);
+/// problem.add_row(&[0, 3]);
+/// problem.add_row(&[1, 6]);
+/// problem.add_row(&[3, 4, 6]);
+///
+/// assert_eq!(problem.solve(), Some(vec![0, 3, 4]));
+/// assert_eq!(problem.count_solutions(), 1);
+/// ```
+#[derive(Debug, Clone)]
+pub struct ExactCover {
+    nodes: Vec<DlxNode>,
+    /// 每列中尚未被删除的结点数量，下标为列表头
+    sizes: Vec<usize>,
+    rows: usize,
+}
+
+impl ExactCover {
+    /// 创建有 columns 列的空矩阵
+    pub fn new(columns: usize) -> Self {
+        let nodes = (0..=columns)
+            .map(|i| DlxNode {
+                left: if i == 0 { columns } else { i - 1 },
+                right: if i == columns { 0 } else { i + 1 },
+                up: i,
+                down: i,
+                column: i,
+                row: usize::MAX,
+            })
+            .collect();
+
+        Self {
+            nodes,
+            sizes: vec![0; columns + 1],
+            rows: 0,
+        }
+    }
+
+    /// 列数
+    pub fn columns(&self) -> usize {
+        self.sizes.len() - 1
+    }
+
+    /// 行数
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    /// 添加一行，columns 为这一行中 1 所在的列，返回行的编号
+    ///
+    /// # Panics
+    ///
+    /// Panics if a column is out of bounds or appears more than once.
+    pub fn add_row(&mut self, columns: &[usize]) -> usize {
+        let row = self.rows;
+        let first = self.nodes.len();
+
+        for (k, &column) in columns.iter().enumerate() {
+            assert!(
+                column < self.columns(),
+                "Column out of bounds: {column}, columns: {}",
+                self.columns()
+            );
+            assert!(
+                !columns[..k].contains(&column),
+                "Column {column} appears more than once in a row"
+            );
+
+            // 插入到列的末尾，即列表头的上方
+            let header = column + 1;
+            let node = self.nodes.len();
+            let up = self.nodes[header].up;
+            self.nodes.push(DlxNode {
+                left: if k == 0 { node } else { node - 1 },
+                right: first,
+                up,
+                down: header,
+                column: header,
+                row,
+            });
+            self.nodes[up].down = node;
+            self.nodes[header].up = node;
+            self.nodes[first].left = node;
+            if k > 0 {
+                self.nodes[node - 1].right = node;
+            }
+            self.sizes[header] += 1;
+        }
+
+        self.rows += 1;
+        row
+    }
+
+    /// 求出一个解，返回选中的行的编号（升序），无解时返回 None
+    ///
+    /// 求解过程中会修改链表，结束时恢复原状，因此需要可变借用。
+    pub fn solve(&mut self) -> Option<Vec<usize>> {
+        let mut solution = None;
+        self.search(&mut Vec::new(), &mut |rows| {
+            let mut rows = rows.to_vec();
+            rows.sort_unstable();
+            solution = Some(rows);
+            true
+        });
+
+        solution
+    }
+
+    /// 解的数量
+    pub fn count_solutions(&mut self) -> usize {
+        let mut count = 0;
+        self.search(&mut Vec::new(), &mut |_| {
+            count += 1;
+            false
+        });
+
+        count
+    }
+
+    /// X 算法，找到解时调用 on_solution，其返回 true 时停止搜索并返回 true
+    fn search(
+        &mut self,
+        partial: &mut Vec<usize>,
+        on_solution: &mut dyn FnMut(&[usize]) -> bool,
+    ) -> bool {
+        // 所有列都已覆盖
+        if self.nodes[0].right == 0 {
+            return on_solution(partial);
+        }
+
+        // 选择剩余结点最少的列，尽早发现无解的分支
+        let mut column = self.nodes[0].right;
+        let mut c = self.nodes[column].right;
+        while c != 0 {
+            if self.sizes[c] < self.sizes[column] {
+                column = c;
+            }
+            c = self.nodes[c].right;
+        }
+        if self.sizes[column] == 0 {
+            return false;
+        }
+
+        self.cover(column);
+        let mut r = self.nodes[column].down;
+        let mut stop = false;
+        while r != column && !stop {
+            partial.push(self.nodes[r].row);
+            // 选中这一行：覆盖这一行涉及的其他列
+            let mut j = self.nodes[r].right;
+            while j != r {
+                self.cover(self.nodes[j].column);
+                j = self.nodes[j].right;
+            }
+
+            stop = self.search(partial, on_solution);
+
+            // 按相反的顺序恢复
+            let mut j = self.nodes[r].left;
+            while j != r {
+                self.uncover(self.nodes[j].column);
+                j = self.nodes[j].left;
+            }
+            partial.pop();
+            r = self.nodes[r].down;
+        }
+        self.uncover(column);
+
+        stop
+    }
+
+    /// 删除列表头 column，以及这一列中所有行的其他结点
+    fn cover(&mut self, column: usize) {
+        let DlxNode { left, right, .. } = self.nodes[column];
+        self.nodes[left].right = right;
+        self.nodes[right].left = left;
+
+        let mut i = self.nodes[column].down;
+        while i != column {
+            let mut j = self.nodes[i].right;
+            while j != i {
+                let DlxNode { up, down, .. } = self.nodes[j];
+                self.nodes[up].down = down;
+                self.nodes[down].up = up;
+                self.sizes[self.nodes[j].column] -= 1;
+                j = self.nodes[j].right;
+            }
+            i = self.nodes[i].down;
+        }
+    }
+
+    /// cover 的逆操作，按与删除相反的顺序把结点放回
+    fn uncover(&mut self, column: usize) {
+        let mut i = self.nodes[column].up;
+        while i != column {
+            let mut j = self.nodes[i].left;
+            while j != i {
+                let DlxNode { up, down, .. } = self.nodes[j];
+                self.nodes[up].down = j;
+                self.nodes[down].up = j;
+                self.sizes[self.nodes[j].column] += 1;
+                j = self.nodes[j].left;
+            }
+            i = self.nodes[i].up;
+        }
+
+        let DlxNode { left, right, .. } = self.nodes[column];
+        self.nodes[left].right = column;
+        self.nodes[right].left = column;
+    }
+}
+
+/// 数独盘面，0 表示空格
+pub type Sudoku = [[u8; 9]; 9];
+
+/// 用舞蹈链求解数独，无解时返回 None
+///
+/// 数独的约束恰好是精确覆盖：每个格子、每行的每个数字、每列的每个数字、每个宫的每个数字都恰好出现一次，
+/// 共 4 * 81 = 324 列；在 (r, c) 填 d 覆盖其中 4 列，作为一行。已知的格子只添加给定数字对应的一行。
+///
+/// # Panics
+///
+/// Panics if a cell is greater than 9.
+///
+/// # Examples
+///
+/// ```rust
+/// use base::exact_cover;
+///
+/// let puzzle = [
+///     [5, 3, 0, 0, 7, 0, 0, 0, 0],
+///     [6, 0, 0, 1, 9, 5, 0, 0, 0],
+///     [0, 9, 8, 0, 0, 0, 0, 6, 0],
+///     [8, 0, 0, 0, 6, 0, 0, 0, 3],
+///     [4, 0, 0, 8, 0, 3, 0, 0, 1],
+///     [7, 0, 0, 0, 2, 0, 0, 0, 6],
+///     [0, 6, 0, 0, 0, 0, 2, 8, 0],
+///     [0, 0, 0, 4, 1, 9, 0, 0, 5],
+///     [0, 0, 0, 0, 8, 0, 0, 7, 9],
+/// ];
+/// let solution = exact_cover::solve_sudoku(&puzzle).unwrap();
+///
+/// assert_eq!(solution[0], [5, 3, 4, 6, 7, 8, 9, 1, 2]);
+/// assert_eq!(Some(solution), exact_cover::solve_sudoku_backtracking(&puzzle));
+/// ```
+pub fn solve_sudoku(puzzle: &Sudoku) -> Option<Sudoku> {
+    let mut problem = ExactCover::new(4 * 81);
+    // 行编号 -> (r, c, d)
+    let mut choices = Vec::new();
+
+    for (r, row) in puzzle.iter().enumerate() {
+        for (c, &cell) in row.iter().enumerate() {
+            assert!(cell <= 9, "Invalid cell at ({r}, {c}): {cell}");
+            let digits = if cell == 0 { 1..=9 } else { cell..=cell };
+            for d in digits {
+                let k = (d - 1) as usize;
+                let b = r / 3 * 3 + c / 3;
+                problem.add_row(&[r * 9 + c, 81 + r * 9 + k, 162 + c * 9 + k, 243 + b * 9 + k]);
+                choices.push((r, c, d));
+            }
+        }
+    }
+
+    let mut solution = [[0; 9]; 9];
+    for row in problem.solve()? {
+        let (r, c, d) = choices[row];
+        solution[r][c] = d;
+    }
+
+    Some(solution)
+}
+
+/// 直接回溯求解数独，无解时返回 None
+///
+/// 用位掩码记录每行、每列、每宫已经使用的数字，每次选择候选数字最少的空格。
+///
+/// # Panics
+///
+/// Panics if a cell is greater than 9.
+pub fn solve_sudoku_backtracking(puzzle: &Sudoku) -> Option<Sudoku> {
+    let mut grid = *puzzle;
+    // used[0] 为行，used[1] 为列，used[2] 为宫，第 d 位表示数字 d 已使用
+    let mut used = [[0_u16; 9]; 3];
+
+    for r in 0..9 {
+        for c in 0..9 {
+            let d = grid[r][c];
+            assert!(d <= 9, "Invalid cell at ({r}, {c}): {d}");
+            if d == 0 {
+                continue;
+            }
+
+            let bit = 1 << d;
+            let b = r / 3 * 3 + c / 3;
+            // 已知数字互相冲突
+            if (used[0][r] | used[1][c] | used[2][b]) & bit != 0 {
+                return None;
+            }
+            used[0][r] |= bit;
+            used[1][c] |= bit;
+            used[2][b] |= bit;
+        }
+    }
+
+    backtrack(&mut grid, &mut used).then_some(grid)
+}
+
+fn backtrack(grid: &mut Sudoku, used: &mut [[u16; 9]; 3]) -> bool {
+    // 候选数字最少的空格：(候选数量, r, c, 候选数字的掩码)
+    let mut best: Option<(u32, usize, usize, u16)> = None;
+    for r in 0..9 {
+        for c in 0..9 {
+            if grid[r][c] != 0 {
+                continue;
+            }
+
+            let b = r / 3 * 3 + c / 3;
+            let candidates = !(used[0][r] | used[1][c] | used[2][b]) & 0b11_1111_1110;
+            let count = candidates.count_ones();
+            if best.is_none_or(|(best_count, ..)| count < best_count) {
+                best = Some((count, r, c, candidates));
+            }
+        }
+    }
+
+    let Some((_, r, c, candidates)) = best else {
+        // 没有空格，已经填满
+        return true;
+    };
+
+    let b = r / 3 * 3 + c / 3;
+    for d in 1..=9 {
+        let bit = 1 << d;
+        if candidates & bit == 0 {
+            continue;
+        }
+
+        grid[r][c] = d;
+        used[0][r] |= bit;
+        used[1][c] |= bit;
+        used[2][b] |= bit;
+        if backtrack(grid, used) {
+            return true;
+        }
+        used[0][r] &= !bit;
+        used[1][c] &= !bit;
+        used[2][b] &= !bit;
+    }
+    grid[r][c] = 0;
+
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generate::Rng;
+
+    /// 检查数独的解是否合法且与题目一致
+    fn is_valid_solution(puzzle: &Sudoku, solution: &Sudoku) -> bool {
+        let mut groups = (0..9).flat_map(|i| {
+            [
+                (0..9).map(|j| (i, j)).collect::<Vec<_>>(),
+                (0..9).map(|j| (j, i)).collect(),
+                (0..9)
+                    .map(|j| (i / 3 * 3 + j / 3, i % 3 * 3 + j % 3))
+                    .collect(),
+            ]
+        });
+
+        groups.all(|cells| {
+            let mask = cells
+                .iter()
+                .fold(0_u16, |mask, &(r, c)| mask | 1 << solution[r][c]);
+            mask == 0b11_1111_1110
+        }) && (0..81).all(|i| {
+            let (r, c) = (i / 9, i % 9);
+            puzzle[r][c] == 0 || puzzle[r][c] == solution[r][c]
+        })
+    }
+
+    #[test]
+    fn exact_cover_should_match_brute_force() {
+        let mut rng = Rng::new(5);
+
+        for _ in 0..30 {
+            let columns = 1 + rng.below(6) as usize;
+            let rows = (0..12)
+                .map(|_| {
+                    (0..columns)
+                        .filter(|_| rng.below(3) == 0)
+                        .collect::<Vec<_>>()
+                })
+                .collect::<Vec<_>>();
+
+            let mut problem = ExactCover::new(columns);
+            for row in &rows {
+                problem.add_row(row);
+            }
+
+            // 枚举所有行的子集
+            let is_cover = |subset: u32| {
+                let mut counts = vec![0; columns];
+                (0..rows.len())
+                    .filter(|i| subset >> i & 1 == 1)
+                    .for_each(|i| rows[i].iter().for_each(|&c| counts[c] += 1));
+                counts.iter().all(|&count| count == 1)
+            };
+            let expected = (0..1_u32 << rows.len())
+                .filter(|&subset| is_cover(subset))
+                .count();
+
+            // 空行不影响覆盖，选与不选是同一个解
+            let empty = rows.iter().filter(|row| row.is_empty()).count();
+            assert_eq!(problem.count_solutions() << empty, expected);
+            match problem.solve() {
+                Some(solution) => {
+                    let subset = solution.iter().fold(0, |subset, &i| subset | 1 << i);
+                    assert!(is_cover(subset));
+                }
+                None => assert_eq!(expected, 0),
+            }
+            // 求解后链表恢复原状，可以重复求解
+            assert_eq!(problem.count_solutions() << empty, expected);
+        }
+    }
+
+    #[test]
+    fn sudoku_backends_should_agree() {
+        // Arto Inkala 设计的难题，只有一个解，需要大量回溯
+        let hard =
+            "8..........36......7..9.2...5...7.......457.....1...3...1....68..85...1..9....4..";
+        let mut puzzle = [[0; 9]; 9];
+        for (i, ch) in hard.chars().enumerate() {
+            puzzle[i / 9][i % 9] = ch.to_digit(10).unwrap_or(0) as u8;
+        }
+
+        let solution = solve_sudoku(&puzzle).unwrap();
+        assert!(is_valid_solution(&puzzle, &solution));
+        assert_eq!(solve_sudoku_backtracking(&puzzle), Some(solution));
+
+        // 空盘面也有解
+        let empty = [[0; 9]; 9];
+        assert!(is_valid_solution(&empty, &solve_sudoku(&empty).unwrap()));
+        assert!(is_valid_solution(
+            &empty,
+            &solve_sudoku_backtracking(&empty).unwrap()
+        ));
+
+        // 同一行出现两个 5
+        let mut conflict = [[0; 9]; 9];
+        conflict[0][0] = 5;
+        conflict[0][8] = 5;
+        assert_eq!(solve_sudoku(&conflict), None);
+        assert_eq!(solve_sudoku_backtracking(&conflict), None);
+    }
+}
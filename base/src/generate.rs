@@ -0,0 +1,151 @@
+//! 可复现的随机数据生成
+//!
+//! 单元测试和基准测试共用这里的生成器：相同的种子总是生成相同的数据，测试失败和性能波动都可以复现。
+//! 随机数由 [`crate::random`] 中的 xorshift64 生成，速度快、实现简单，但不适合密码学用途。
+//!
+//! 模块不能命名为 `gen`，它在 Rust 2024 中是保留关键字。
+
+/// xorshift64 伪随机数生成器，实现见 [`crate::random`]
+pub use crate::random::Xorshift64 as Rng;
+
+/// 均匀分布的随机数组
+///
+/// # Examples
+///
+/// ```rust
+/// use base::generate;
+///
+/// let v = generate::uniform(100, 42);
+///
+/// assert_eq!(v.len(), 100);
+/// assert_eq!(v, generate::uniform(100, 42));
+/// ```
+pub fn uniform(len: usize, seed: u64) -> Vec<u64> {
+    Rng::new(seed).take(len).collect()
+}
+
+/// 只包含 distinct 种不同元素（[0, distinct)）的随机数组，用于测试大量重复元素的情形
+///
+/// # Panics
+///
+/// Panics if `distinct == 0` and `len > 0`.
+pub fn few_distinct(len: usize, distinct: u64, seed: u64) -> Vec<u64> {
+    let mut rng = Rng::new(seed);
+
+    (0..len).map(|_| rng.below(distinct)).collect()
+}
+
+/// 基本有序的数组：在升序数组 0..len 上随机交换 swaps 次
+pub fn nearly_sorted(len: usize, swaps: usize, seed: u64) -> Vec<u64> {
+    let mut rng = Rng::new(seed);
+    let mut v = (0..len as u64).collect::<Vec<_>>();
+
+    if len > 0 {
+        for _ in 0..swaps {
+            let (a, b) = (rng.below(len as u64), rng.below(len as u64));
+            v.swap(a as usize, b as usize);
+        }
+    }
+
+    v
+}
+
+/// 先升后降的“管风琴”数组，如 0, 1, 2, 2, 1, 0
+pub fn organ_pipe(len: usize) -> Vec<u64> {
+    let half = len as u64 / 2;
+
+    (0..half).chain((0..len as u64 - half).rev()).collect()
+}
+
+/// 0..len 的随机排列，用作二叉搜索树的插入顺序
+///
+/// 随机顺序插入的二叉搜索树期望高度为 O(logn)，可与有序插入（退化为链表）对比
+pub fn insertion_order(len: usize, seed: u64) -> Vec<u64> {
+    let mut v = (0..len as u64).collect::<Vec<_>>();
+    Rng::new(seed).shuffle(&mut v);
+
+    v
+}
+
+/// 随机操作脚本中的一步
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operation {
+    Insert(u64),
+    Remove(u64),
+    Get(u64),
+}
+
+/// 随机操作脚本，键均位于 [0, keys) 内，插入、删除、查询的比例约为 2 : 1 : 1
+///
+/// 键的范围较小时同一个键会被反复插入和删除，便于覆盖各种边界情况。
+/// 通常将同一个脚本同时作用于待测数据结构和标准库中的对应实现，比较二者的结果。
+///
+/// # Panics
+///
+/// Panics if `keys == 0` and `len > 0`.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::collections::BTreeSet;
+///
+/// use base::generate::{self, Operation};
+///
+/// let mut set = BTreeSet::new();
+/// for op in generate::operations(1000, 50, 7) {
+///     match op {
+///         Operation::Insert(key) => _ = set.insert(key),
+///         Operation::Remove(key) => _ = set.remove(&key),
+///         Operation::Get(key) => _ = set.contains(&key),
+///     }
+/// }
+///
+/// assert!(set.iter().all(|&key| key < 50));
+/// ```
+pub fn operations(len: usize, keys: u64, seed: u64) -> Vec<Operation> {
+    let mut rng = Rng::new(seed);
+
+    (0..len)
+        .map(|_| {
+            let key = rng.below(keys);
+            match rng.below(4) {
+                0 | 1 => Operation::Insert(key),
+                2 => Operation::Remove(key),
+                _ => Operation::Get(key),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generators_should_be_reproducible() {
+        assert_eq!(uniform(50, 1), uniform(50, 1));
+        assert_ne!(uniform(50, 1), uniform(50, 2));
+        assert_eq!(Rng::new(0).next_u64(), Rng::default().next_u64());
+        assert_eq!(operations(100, 10, 3), operations(100, 10, 3));
+    }
+
+    #[test]
+    fn generators_should_produce_expected_shapes() {
+        assert!(few_distinct(1000, 3, 1).iter().all(|&x| x < 3));
+
+        let mut v = nearly_sorted(1000, 10, 1);
+        let displaced = v.iter().enumerate().filter(|(i, x)| *i as u64 != **x);
+        assert!(displaced.count() <= 20);
+        v.sort();
+        assert_eq!(v, (0..1000).collect::<Vec<_>>());
+
+        assert_eq!(organ_pipe(5), [0, 1, 2, 1, 0]);
+        assert_eq!(organ_pipe(6), [0, 1, 2, 2, 1, 0]);
+        assert!(organ_pipe(0).is_empty());
+
+        let mut order = insertion_order(500, 9);
+        assert_ne!(order, (0..500).collect::<Vec<_>>());
+        order.sort();
+        assert_eq!(order, (0..500).collect::<Vec<_>>());
+    }
+}
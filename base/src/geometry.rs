@@ -0,0 +1,643 @@
+//! 计算几何基础
+//!
+//! 平面上的点与向量都用 [`Point`] 表示，几乎所有判断都归结为两个向量的叉积（cross product）：
+//! * cross(b - a, c - a) > 0：a -> b -> c 逆时针转向
+//! * cross(b - a, c - a) < 0：顺时针转向
+//! * cross(b - a, c - a) = 0：三点共线
+//!
+//! 浮点运算有舍入误差，本该为 0 的叉积可能算出一个极小的非零值。这里把绝对值不超过 [`EPS`] 的结果视为 0：
+//! * 整数坐标（绝对值不超过 2^26）的叉积没有舍入误差，结果与精确计算一致
+//! * 坐标的量级很大或很小时，固定的 EPS 可能过松或过紧，应先把坐标缩放到合适的范围
+//!
+//! 包含凸包（Andrew 单调链）、线段相交判断和点与多边形的位置关系，以及两个扫描线算法：
+//! * [`closest_pair`]：最近点对，用跳表 [`SkipListMap`] 按 y 坐标维护扫描线附近的点
+//! * [`rectangle_union_area`]：矩形面积并，把 y 坐标离散化后用线段树维护扫描线被覆盖的长度
+
+use std::{
+    cmp::Ordering,
+    ops::{Add, Mul, Sub},
+};
+
+use crate::{SkipListMap, sort};
+
+/// 判断浮点数是否为 0 的容差
+pub const EPS: f64 = 1e-9;
+
+/// 平面上的点，也用作向量
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Point {
+    pub x: f64,
+    pub y: f64,
+}
+
+impl Point {
+    /// 创建点 (x, y)
+    pub fn new(x: f64, y: f64) -> Self {
+        Self { x, y }
+    }
+
+    /// 点积：|a||b|cosθ
+    pub fn dot(self, other: Self) -> f64 {
+        self.x * other.x + self.y * other.y
+    }
+
+    /// 叉积：|a||b|sinθ，即两个向量张成的平行四边形的有向面积
+    pub fn cross(self, other: Self) -> f64 {
+        self.x * other.y - self.y * other.x
+    }
+
+    /// 向量的长度
+    pub fn norm(self) -> f64 {
+        self.dot(self).sqrt()
+    }
+
+    /// 两点之间的距离
+    pub fn dist(self, other: Self) -> f64 {
+        (self - other).norm()
+    }
+
+    /// 按 x、y 的顺序比较，用于排序
+    fn cmp_xy(&self, other: &Self) -> Ordering {
+        self.x.total_cmp(&other.x).then(self.y.total_cmp(&other.y))
+    }
+}
+
+impl Add for Point {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl Sub for Point {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl Mul<f64> for Point {
+    type Output = Self;
+
+    fn mul(self, k: f64) -> Self {
+        Self::new(self.x * k, self.y * k)
+    }
+}
+
+impl From<(f64, f64)> for Point {
+    fn from((x, y): (f64, f64)) -> Self {
+        Self::new(x, y)
+    }
+}
+
+/// 三个点的转向
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Orientation {
+    /// 逆时针（左转）
+    CounterClockwise,
+    /// 顺时针（右转）
+    Clockwise,
+    /// 共线
+    Collinear,
+}
+
+/// 按容差判断符号：-1、0 或 1
+fn sign(x: f64) -> i32 {
+    if x > EPS {
+        1
+    } else if x < -EPS {
+        -1
+    } else {
+        0
+    }
+}
+
+/// a -> b -> c 的转向
+///
+/// # Examples
+///
+/// ```rust
+/// use base::geometry::{Orientation, Point, orientation};
+///
+/// let (a, b) = (Point::new(0.0, 0.0), Point::new(1.0, 0.0));
+///
+/// assert_eq!(orientation(a, b, Point::new(1.0, 1.0)), Orientation::CounterClockwise);
+/// assert_eq!(orientation(a, b, Point::new(1.0, -1.0)), Orientation::Clockwise);
+/// // 0.1 + 0.2 != 0.3，但在容差范围内共线
+/// assert_eq!(orientation(a, Point::new(0.1 + 0.2, 0.3), Point::new(0.3, 0.3)), Orientation::Collinear);
+/// ```
+pub fn orientation(a: Point, b: Point, c: Point) -> Orientation {
+    match sign((b - a).cross(c - a)) {
+        1 => Orientation::CounterClockwise,
+        -1 => Orientation::Clockwise,
+        _ => Orientation::Collinear,
+    }
+}
+
+/// 凸包，用 Andrew 单调链算法求出，按逆时针顺序从最左下的点开始，不包含凸包边上共线的点
+///
+/// 先按 (x, y) 排序，再分别扫描出下凸壳和上凸壳：维护一个栈，新点使栈顶的两个点不再左转时弹出栈顶。
+/// 时间复杂度：O(nlogn)，主要开销在排序
+///
+/// # Examples
+///
+/// ```rust
+/// use base::geometry::{self, Point};
+///
+/// let points = [(0.0, 0.0), (2.0, 0.0), (1.0, 1.0), (2.0, 2.0), (0.0, 2.0), (1.0, 0.0)]
+///     .map(Point::from);
+///
+/// assert_eq!(
+///     geometry::convex_hull(&points),
+///     [(0.0, 0.0), (2.0, 0.0), (2.0, 2.0), (0.0, 2.0)].map(Point::from)
+/// );
+/// ```
+pub fn convex_hull(points: &[Point]) -> Vec<Point> {
+    let mut points = points.to_vec();
+    sort::pdq_sort_by(&mut points, Point::cmp_xy);
+    points.dedup_by(|a, b| sign(a.x - b.x) == 0 && sign(a.y - b.y) == 0);
+    if points.len() <= 2 {
+        return points;
+    }
+
+    let mut hull = Vec::with_capacity(points.len() + 1);
+    // 下凸壳从左到右扫描
+    for &p in &points {
+        push_hull_point(&mut hull, p, 1);
+    }
+    // 上凸壳从右到左扫描，不能弹出下凸壳的点
+    let lower = hull.len();
+    for &p in points.iter().rev().skip(1) {
+        push_hull_point(&mut hull, p, lower);
+    }
+    // 最后一个点与起点重合
+    hull.pop();
+
+    hull
+}
+
+/// 把 p 压入凸包栈，先弹出不再左转的栈顶，栈中至少保留 keep 个点
+fn push_hull_point(hull: &mut Vec<Point>, p: Point, keep: usize) {
+    while hull.len() > keep
+        && orientation(hull[hull.len() - 2], hull[hull.len() - 1], p)
+            != Orientation::CounterClockwise
+    {
+        hull.pop();
+    }
+    hull.push(p);
+}
+
+/// 判断点 p 是否在线段 ab 上（包括端点）
+fn on_segment(p: Point, a: Point, b: Point) -> bool {
+    orientation(a, b, p) == Orientation::Collinear && sign((a - p).dot(b - p)) <= 0
+}
+
+/// 判断线段 p1p2 与 q1q2 是否相交，端点接触和共线重叠也算相交
+///
+/// 两条线段相交当且仅当每条线段的两个端点分别位于另一条线段所在直线的两侧（跨立实验），
+/// 或者某个端点恰好落在另一条线段上。
+///
+/// # Examples
+///
+/// ```rust
+/// use base::geometry::{Point, segments_intersect};
+///
+/// let p = |x, y| Point::new(x, y);
+///
+/// assert!(segments_intersect(p(0.0, 0.0), p(2.0, 2.0), p(0.0, 2.0), p(2.0, 0.0)));
+/// assert!(segments_intersect(p(0.0, 0.0), p(1.0, 0.0), p(1.0, 0.0), p(1.0, 5.0)));
+/// assert!(!segments_intersect(p(0.0, 0.0), p(1.0, 0.0), p(2.0, 0.0), p(3.0, 0.0)));
+/// ```
+pub fn segments_intersect(p1: Point, p2: Point, q1: Point, q2: Point) -> bool {
+    let d1 = orientation(q1, q2, p1);
+    let d2 = orientation(q1, q2, p2);
+    let d3 = orientation(p1, p2, q1);
+    let d4 = orientation(p1, p2, q2);
+
+    let straddles = |a: Orientation, b: Orientation| {
+        a != Orientation::Collinear && b != Orientation::Collinear && a != b
+    };
+    if straddles(d1, d2) && straddles(d3, d4) {
+        return true;
+    }
+
+    on_segment(p1, q1, q2)
+        || on_segment(p2, q1, q2)
+        || on_segment(q1, p1, p2)
+        || on_segment(q2, p1, p2)
+}
+
+/// 点与多边形的位置关系
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Location {
+    /// 在多边形内部
+    Inside,
+    /// 在多边形的边上
+    OnBoundary,
+    /// 在多边形外部
+    Outside,
+}
+
+/// 点 p 与简单多边形（顶点按顺序给出，顺时针或逆时针均可，可以是凹多边形）的位置关系
+///
+/// 先检查 p 是否在某条边上；否则从 p 向右作水平射线，与边界相交奇数次时在内部（射线法）。
+/// 每条边按“下端点闭、上端点开”计数，射线恰好经过顶点时不会重复计数。
+///
+/// # Examples
+///
+/// ```rust
+/// use base::geometry::{self, Location, Point};
+///
+/// // 凹多边形：缺了右上角的正方形
+/// let polygon = [(0.0, 0.0), (4.0, 0.0), (4.0, 2.0), (2.0, 2.0), (2.0, 4.0), (0.0, 4.0)]
+///     .map(Point::from);
+///
+/// assert_eq!(geometry::point_in_polygon(Point::new(1.0, 3.0), &polygon), Location::Inside);
+/// assert_eq!(geometry::point_in_polygon(Point::new(3.0, 3.0), &polygon), Location::Outside);
+/// assert_eq!(geometry::point_in_polygon(Point::new(3.0, 2.0), &polygon), Location::OnBoundary);
+/// ```
+pub fn point_in_polygon(p: Point, polygon: &[Point]) -> Location {
+    let n = polygon.len();
+    let mut inside = false;
+
+    for i in 0..n {
+        let (a, b) = (polygon[i], polygon[(i + 1) % n]);
+        if on_segment(p, a, b) {
+            return Location::OnBoundary;
+        }
+
+        // 边跨过射线所在的水平线，且交点在 p 的右侧
+        let (low, high) = if a.y < b.y { (a, b) } else { (b, a) };
+        if low.y <= p.y
+            && p.y < high.y
+            && orientation(low, high, p) == Orientation::CounterClockwise
+        {
+            inside = !inside;
+        }
+    }
+
+    if inside {
+        Location::Inside
+    } else {
+        Location::Outside
+    }
+}
+
+/// 最近点对的下标 (i, j)，i < j，少于两个点时返回 None
+///
+/// 按 x 坐标从左到右扫描，设目前的最近距离为 d，只有与当前点的 x 距离不超过 d 的点才可能更近，
+/// 这些点按 (y, x) 保存在有序集合中。与当前点的 y 距离也不超过 d 的点最多只有常数个，
+/// 用范围查询取出逐一比较即可。时间复杂度：O(nlogn)
+///
+/// # Examples
+///
+/// ```rust
+/// use base::geometry::{self, Point};
+///
+/// let points = [(0.0, 0.0), (5.0, 4.0), (3.0, 1.0), (9.0, 9.0), (5.5, 3.0)].map(Point::from);
+///
+/// assert_eq!(geometry::closest_pair(&points), Some((1, 4)));
+/// assert_eq!(geometry::closest_pair(&points[..1]), None);
+/// ```
+pub fn closest_pair(points: &[Point]) -> Option<(usize, usize)> {
+    let mut order = (0..points.len()).collect::<Vec<_>>();
+    sort::pdq_sort_by(&mut order, |&i, &j| points[i].cmp_xy(&points[j]));
+
+    let mut active = SkipListMap::new();
+    let mut best: Option<(usize, usize)> = None;
+    let mut d = f64::INFINITY;
+    let mut left = 0;
+
+    for &i in &order {
+        let p = points[i];
+        // 删除 x 距离已经超过 d 的点
+        while p.x - points[order[left]].x > d {
+            let q = points[order[left]];
+            active.remove(&SweepKey(q.y, q.x, order[left]));
+            left += 1;
+        }
+
+        let low = SweepKey(p.y - d, f64::NEG_INFINITY, 0);
+        let high = SweepKey(p.y + d, f64::INFINITY, usize::MAX);
+        for (_, &j) in active.range(low..=high) {
+            let dist = p.dist(points[j]);
+            if dist < d {
+                d = dist;
+                best = Some((i.min(j), i.max(j)));
+            }
+        }
+
+        active.insert(SweepKey(p.y, p.x, i), i);
+    }
+
+    best
+}
+
+/// 扫描线上的点按 (y, x, 下标) 排序，下标保证重合的点也有不同的键
+#[derive(Debug, Clone, Copy)]
+struct SweepKey(f64, f64, usize);
+
+impl PartialEq for SweepKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for SweepKey {}
+
+impl PartialOrd for SweepKey {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for SweepKey {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0
+            .total_cmp(&other.0)
+            .then(self.1.total_cmp(&other.1))
+            .then(self.2.cmp(&other.2))
+    }
+}
+
+/// 轴对齐矩形的面积并，每个矩形由两个对角顶点给出
+///
+/// 竖直的扫描线从左到右经过所有矩形的左右边，相邻两条边之间被覆盖的面积为扫描线上被覆盖的长度乘以宽度。
+/// 把所有 y 坐标排序去重后，相邻两个坐标之间的区间成为线段树的叶结点，
+/// 矩形的左边使对应区间的覆盖次数加 1，右边减 1。时间复杂度：O(nlogn)
+///
+/// # Examples
+///
+/// ```rust
+/// use base::geometry::{self, Point};
+///
+/// // 两个 2 x 2 的正方形重叠了 1 x 1
+/// let rects = [
+///     (Point::new(0.0, 0.0), Point::new(2.0, 2.0)),
+///     (Point::new(3.0, 3.0), Point::new(1.0, 1.0)),
+/// ];
+///
+/// assert_eq!(geometry::rectangle_union_area(&rects), 7.0);
+/// ```
+pub fn rectangle_union_area(rects: &[(Point, Point)]) -> f64 {
+    let mut ys = Vec::with_capacity(rects.len() * 2);
+    // 扫描线事件：(x, 下边, 上边, 覆盖次数的变化)
+    let mut events = Vec::with_capacity(rects.len() * 2);
+    for &(a, b) in rects {
+        let (y1, y2) = (a.y.min(b.y), a.y.max(b.y));
+        ys.extend([y1, y2]);
+        events.push((a.x.min(b.x), y1, y2, 1));
+        events.push((a.x.max(b.x), y1, y2, -1));
+    }
+
+    sort::pdq_sort_by(&mut ys, f64::total_cmp);
+    ys.dedup_by(|a, b| a.total_cmp(b) == Ordering::Equal);
+    sort::pdq_sort_by(&mut events, |a, b| a.0.total_cmp(&b.0));
+    if ys.len() < 2 {
+        return 0.0;
+    }
+
+    let mut cover = CoverTree::new(ys);
+    let mut area = 0.0;
+    let mut prev_x = f64::NEG_INFINITY;
+    for (x, y1, y2, delta) in events {
+        if cover.covered() > 0.0 {
+            area += cover.covered() * (x - prev_x);
+        }
+        cover.add(y1, y2, delta);
+        prev_x = x;
+    }
+
+    area
+}
+
+/// 维护扫描线被覆盖长度的线段树
+///
+/// 与 [`SegmentTree`](crate::SegmentTree) 的单点修改不同，这里需要区间修改覆盖次数。
+/// 覆盖只会被对应的移除操作撤销，因此不需要下传标记：
+/// 结点的覆盖次数大于 0 时整段都被覆盖，否则被覆盖的长度为两个子结点之和。
+struct CoverTree {
+    /// 离散化后的 y 坐标，叶结点 i 表示区间 [ys[i], ys[i + 1]]
+    ys: Vec<f64>,
+    /// 结点整段被覆盖的次数
+    count: Vec<i32>,
+    /// 结点范围内被覆盖的长度
+    covered: Vec<f64>,
+}
+
+impl CoverTree {
+    fn new(ys: Vec<f64>) -> Self {
+        let size = 4 * (ys.len() - 1);
+
+        Self {
+            ys,
+            count: vec![0; size],
+            covered: vec![0.0; size],
+        }
+    }
+
+    /// 整条扫描线上被覆盖的长度
+    fn covered(&self) -> f64 {
+        self.covered[1]
+    }
+
+    /// 区间 [y1, y2] 的覆盖次数加上 delta
+    fn add(&mut self, y1: f64, y2: f64, delta: i32) {
+        // Safety: y1 和 y2 都在离散化的坐标中
+        let lo = self.ys.binary_search_by(|y| y.total_cmp(&y1)).unwrap();
+        let hi = self.ys.binary_search_by(|y| y.total_cmp(&y2)).unwrap();
+        if lo < hi {
+            self.update(1, 0, self.ys.len() - 1, lo, hi, delta);
+        }
+    }
+
+    /// 结点 node 表示叶结点 [start, end)，把其中的 [lo, hi) 的覆盖次数加上 delta
+    fn update(&mut self, node: usize, start: usize, end: usize, lo: usize, hi: usize, delta: i32) {
+        if hi <= start || end <= lo {
+            return;
+        }
+        if lo <= start && end <= hi {
+            self.count[node] += delta;
+        } else {
+            let mid = (start + end) / 2;
+            self.update(2 * node, start, mid, lo, hi, delta);
+            self.update(2 * node + 1, mid, end, lo, hi, delta);
+        }
+
+        self.covered[node] = if self.count[node] > 0 {
+            self.ys[end] - self.ys[start]
+        } else if end - start == 1 {
+            0.0
+        } else {
+            self.covered[2 * node] + self.covered[2 * node + 1]
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generate::Rng;
+
+    fn random_points(len: usize, range: u64, seed: u64) -> Vec<Point> {
+        let mut rng = Rng::new(seed);
+        (0..len)
+            .map(|_| Point::new(rng.below(range) as f64, rng.below(range) as f64))
+            .collect()
+    }
+
+    #[test]
+    fn convex_hull_should_contain_all_points() {
+        for seed in 0..10 {
+            let points = random_points(100, 50, seed);
+            let hull = convex_hull(&points);
+            let n = hull.len();
+
+            // 严格凸，且按逆时针排列
+            for i in 0..n {
+                let (a, b, c) = (hull[i], hull[(i + 1) % n], hull[(i + 2) % n]);
+                assert_eq!(orientation(a, b, c), Orientation::CounterClockwise);
+            }
+            // 顶点都来自输入，所有点都在凸包内或边上
+            assert!(hull.iter().all(|p| points.contains(p)));
+            assert!(
+                points
+                    .iter()
+                    .all(|&p| point_in_polygon(p, &hull) != Location::Outside)
+            );
+        }
+
+        // 退化情形：重复点和共线点
+        let p = |x, y| Point::new(x, y);
+        assert_eq!(convex_hull(&[p(1.0, 1.0), p(1.0, 1.0)]), [p(1.0, 1.0)]);
+        assert_eq!(
+            convex_hull(&[p(0.0, 0.0), p(2.0, 2.0), p(1.0, 1.0)]),
+            [p(0.0, 0.0), p(2.0, 2.0)]
+        );
+        assert!(convex_hull(&[]).is_empty());
+    }
+
+    #[test]
+    fn segments_intersect_should_handle_degenerate_cases() {
+        let p = |x, y| Point::new(x, y);
+
+        // 共线且重叠、共线但分离
+        assert!(segments_intersect(
+            p(0.0, 0.0),
+            p(4.0, 0.0),
+            p(2.0, 0.0),
+            p(6.0, 0.0)
+        ));
+        assert!(!segments_intersect(
+            p(0.0, 0.0),
+            p(1.0, 1.0),
+            p(2.0, 2.0),
+            p(3.0, 3.0)
+        ));
+        // T 形接触、平行
+        assert!(segments_intersect(
+            p(0.0, 0.0),
+            p(4.0, 0.0),
+            p(2.0, 0.0),
+            p(2.0, 3.0)
+        ));
+        assert!(!segments_intersect(
+            p(0.0, 0.0),
+            p(4.0, 0.0),
+            p(0.0, 1.0),
+            p(4.0, 1.0)
+        ));
+        // 退化为点的线段
+        assert!(segments_intersect(
+            p(1.0, 1.0),
+            p(1.0, 1.0),
+            p(0.0, 0.0),
+            p(2.0, 2.0)
+        ));
+
+        // 浮点误差：交点坐标无法精确表示，仍判断为相交
+        let (a, b) = (p(0.0, 0.0), p(0.3, 0.1 + 0.2));
+        assert!(segments_intersect(a, b, p(0.3, 0.3), p(1.0, 0.0)));
+    }
+
+    #[test]
+    fn point_in_polygon_should_handle_vertices() {
+        let p = |x, y| Point::new(x, y);
+        // 菱形，射线会恰好经过左右两个顶点
+        let diamond = [p(0.0, -2.0), p(2.0, 0.0), p(0.0, 2.0), p(-2.0, 0.0)];
+
+        assert_eq!(point_in_polygon(p(0.0, 0.0), &diamond), Location::Inside);
+        assert_eq!(point_in_polygon(p(-3.0, 0.0), &diamond), Location::Outside);
+        assert_eq!(point_in_polygon(p(3.0, 0.0), &diamond), Location::Outside);
+        assert_eq!(
+            point_in_polygon(p(2.0, 0.0), &diamond),
+            Location::OnBoundary
+        );
+        assert_eq!(
+            point_in_polygon(p(1.0, 1.0), &diamond),
+            Location::OnBoundary
+        );
+
+        // 顶点顺序反过来结果不变
+        let mut reversed = diamond;
+        reversed.reverse();
+        for q in random_points(50, 6, 4) {
+            let q = q - p(3.0, 3.0);
+            assert_eq!(
+                point_in_polygon(q, &diamond),
+                point_in_polygon(q, &reversed)
+            );
+            let expected = match sign(q.x.abs() + q.y.abs() - 2.0) {
+                -1 => Location::Inside,
+                0 => Location::OnBoundary,
+                _ => Location::Outside,
+            };
+            assert_eq!(point_in_polygon(q, &diamond), expected);
+        }
+    }
+
+    #[test]
+    fn closest_pair_should_match_brute_force() {
+        for (len, range, seed) in [(2, 10, 1), (50, 1000, 2), (300, 100, 3), (300, 20, 4)] {
+            let points = random_points(len, range, seed);
+            let (i, j) = closest_pair(&points).unwrap();
+            assert!(i < j);
+
+            let mut expected = f64::INFINITY;
+            for a in 0..len {
+                for b in a + 1..len {
+                    expected = expected.min(points[a].dist(points[b]));
+                }
+            }
+            assert_eq!(points[i].dist(points[j]), expected);
+        }
+
+        assert_eq!(closest_pair(&[]), None);
+    }
+
+    #[test]
+    fn rectangle_union_area_should_match_grid() {
+        let rects = random_points(60, 20, 17)
+            .chunks(2)
+            .map(|corners| (corners[0], corners[1]))
+            .collect::<Vec<_>>();
+
+        // 整数坐标下逐个单位格子统计被覆盖的数量
+        let covered = (0..20)
+            .flat_map(|x| (0..20).map(move |y| (x as f64 + 0.5, y as f64 + 0.5)))
+            .filter(|&(x, y)| {
+                rects.iter().any(|(a, b)| {
+                    a.x.min(b.x) < x && x < a.x.max(b.x) && a.y.min(b.y) < y && y < a.y.max(b.y)
+                })
+            })
+            .count();
+        assert_eq!(rectangle_union_area(&rects), covered as f64);
+
+        // 退化为线段的矩形面积为 0
+        let p = |x, y| Point::new(x, y);
+        assert_eq!(rectangle_union_area(&[(p(0.0, 0.0), p(0.0, 5.0))]), 0.0);
+        assert_eq!(rectangle_union_area(&[]), 0.0);
+    }
+}
@@ -0,0 +1,208 @@
+//! 图上的迭代算法
+//!
+//! 图用邻接表表示：adj[u] 为结点 u 的所有邻居，无向图的每条边在两端各出现一次。
+//! 与最短路径等一次遍历就能得到答案的算法不同，这里的算法反复更新每个结点的状态，直到收敛：
+//! * [`pagerank`]：幂迭代，分数的变化量小于给定精度时停止
+//! * [`label_propagation_communities`]：标签传播，没有结点改变标签时停止
+//!
+//! 两个算法都按结点编号的顺序处理结点，标签传播的平局也按编号决定，结果是确定的，便于测试。
+
+use crate::{ChainedHashMap, sparse};
+
+/// 标签传播的最大轮数，标签在少数情况下会来回振荡，超过后直接返回当前结果
+const MAX_ROUNDS: usize = 100;
+
+/// 计算有向图中每个结点的 PageRank，直到两轮之间分数变化量的和小于 eps，结果之和为 1
+///
+/// 每一轮的计算与 [`sparse::pagerank`] 相同，变化量每轮至少缩小为原来的 d 倍，因此一定会停止。
+///
+/// # Panics
+///
+/// Panics if `d` is not in `[0, 1)` or `eps` is not positive.
+///
+/// # Examples
+///
+/// ```rust
+/// use base::graph;
+///
+/// // 1、2、3 都指向 0，0 指向 1
+/// let adj = vec![vec![1], vec![0], vec![0], vec![0]];
+/// let rank = graph::pagerank(&adj, 0.85, 1e-10);
+///
+/// assert!(rank[0] > rank[1] && rank[1] > rank[2]);
+/// assert!((rank[2] - rank[3]).abs() < 1e-12);
+/// ```
+pub fn pagerank(adj: &[Vec<usize>], d: f64, eps: f64) -> Vec<f64> {
+    assert!(
+        (0.0..1.0).contains(&d),
+        "Damping factor must be in [0, 1), got {d}"
+    );
+    assert!(eps > 0.0, "Tolerance must be positive, got {eps}");
+
+    let n = adj.len();
+    if n == 0 {
+        return Vec::new();
+    }
+
+    let transition = sparse::transition_matrix(adj);
+    let mut rank = vec![1.0 / n as f64; n];
+    loop {
+        let next = sparse::pagerank_step(&transition, adj, &rank, d);
+        let delta = next
+            .iter()
+            .zip(&rank)
+            .map(|(a, b)| (a - b).abs())
+            .sum::<f64>();
+        rank = next;
+
+        if delta < eps {
+            return rank;
+        }
+    }
+}
+
+/// 用标签传播划分无向图的社区，返回每个结点的社区编号，编号按社区中最小结点的顺序从 0 开始
+///
+/// 初始时每个结点的标签为自己的编号，之后按编号顺序依次把每个结点的标签改为邻居中最常见的标签：
+/// 出现次数相同时，如果当前标签也是最常见的之一则保持不变，否则选择最大的标签。
+/// 一轮中没有结点改变标签时停止。
+///
+/// 按编号顺序处理时，编号较小的结点先更新，如果平局时选择最小的标签，
+/// 刚刚更新的小标签会沿着结点顺序一路传播到其他社区；选择最大的标签则让社区内部先达成一致。
+///
+/// # Panics
+///
+/// Panics if an edge points to a node out of bounds.
+///
+/// # Examples
+///
+/// ```rust
+/// use base::graph;
+///
+/// // 两个三角形 {0, 1, 2} 和 {3, 4, 5}，由边 2 - 3 相连
+/// let adj = vec![
+///     vec![1, 2],
+///     vec![0, 2],
+///     vec![0, 1, 3],
+///     vec![2, 4, 5],
+///     vec![3, 5],
+///     vec![3, 4],
+/// ];
+///
+/// assert_eq!(graph::label_propagation_communities(&adj), [0, 0, 0, 1, 1, 1]);
+/// ```
+pub fn label_propagation_communities(adj: &[Vec<usize>]) -> Vec<usize> {
+    let n = adj.len();
+    let mut labels = (0..n).collect::<Vec<_>>();
+
+    for _ in 0..MAX_ROUNDS {
+        let mut changed = false;
+        for u in 0..n {
+            if let Some(label) = most_common_label(&adj[u], &labels, labels[u]) {
+                changed |= label != labels[u];
+                labels[u] = label;
+            }
+        }
+
+        if !changed {
+            break;
+        }
+    }
+
+    // 按第一次出现的顺序重新编号
+    let mut ids = ChainedHashMap::new();
+    labels
+        .into_iter()
+        .map(|label| {
+            let next_id = ids.len();
+            *ids.entry(label).or_insert(next_id)
+        })
+        .collect()
+}
+
+/// 邻居中最常见的标签，没有邻居时返回 None
+fn most_common_label(neighbors: &[usize], labels: &[usize], current: usize) -> Option<usize> {
+    let mut counts = ChainedHashMap::new();
+    for &v in neighbors {
+        *counts.entry(labels[v]).or_insert(0) += 1;
+    }
+
+    let max = counts.iter().map(|(_, &count)| count).max()?;
+    if counts.get(&current) == Some(&max) {
+        return Some(current);
+    }
+
+    counts
+        .iter()
+        .filter(|&(_, &count)| count == max)
+        .map(|(&label, _)| label)
+        .max()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generate::Rng;
+
+    /// 把有向边补全为无向图
+    fn undirected(n: usize, edges: &[(usize, usize)]) -> Vec<Vec<usize>> {
+        let mut adj = vec![Vec::new(); n];
+        for &(u, v) in edges {
+            adj[u].push(v);
+            adj[v].push(u);
+        }
+
+        adj
+    }
+
+    #[test]
+    fn pagerank_should_converge() {
+        let mut rng = Rng::new(3);
+        let n = 40;
+        let mut adj = vec![Vec::new(); n];
+        for _ in 0..120 {
+            let u = rng.below(n as u64) as usize;
+            adj[u].push(rng.below(n as u64) as usize);
+        }
+
+        let rank = pagerank(&adj, 0.85, 1e-12);
+        let expected = sparse::pagerank(&adj, 0.85, 500);
+        assert!(
+            rank.iter()
+                .zip(&expected)
+                .all(|(a, b)| (a - b).abs() < 1e-10)
+        );
+        assert!((rank.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+
+        // d = 0 时每个结点的分数相同
+        assert!(
+            pagerank(&adj, 0.0, 1e-12)
+                .iter()
+                .all(|r| (r - 1.0 / n as f64).abs() < 1e-15)
+        );
+        assert!(pagerank(&[], 0.85, 1e-6).is_empty());
+    }
+
+    #[test]
+    fn label_propagation_should_find_cliques() {
+        // 三个大小为 5 的完全图，相邻的两个之间只有一条边
+        let mut edges = Vec::new();
+        for group in 0..3 {
+            let base = group * 5;
+            for u in base..base + 5 {
+                edges.extend((u + 1..base + 5).map(|v| (u, v)));
+            }
+        }
+        edges.extend([(4, 5), (9, 10)]);
+        let adj = undirected(15, &edges);
+
+        let labels = label_propagation_communities(&adj);
+        let expected = (0..15).map(|u| u / 5).collect::<Vec<_>>();
+        assert_eq!(labels, expected);
+
+        // 连通分量各自成为社区，孤立结点单独成为一个社区
+        let adj = undirected(6, &[(0, 1), (1, 2), (4, 5)]);
+        assert_eq!(label_propagation_communities(&adj), [0, 0, 0, 1, 2, 2]);
+        assert!(label_propagation_communities(&[]).is_empty());
+    }
+}
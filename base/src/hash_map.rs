@@ -0,0 +1,606 @@
+//! 哈希表（hash table），又称散列表，通过建立键与值之间的映射实现 O(1) 的查询
+//!
+//! 哈希函数把键映射到桶（bucket）的下标，不同的键可能映射到同一个桶，称为哈希冲突。
+//! 链式地址（separate chaining）让每个桶保存一个列表，冲突的键值对都放在同一个列表中。
+//!
+//! 负载因子（load factor）= 键值对数量 / 桶数量，负载因子越大，列表越长，查询越慢。
+//! 负载因子超过阈值时将桶的数量翻倍，并把所有键值对重新放入新的桶中（扩容）。
+//!
+//! 哈希函数使用标准库的 [`DefaultHasher`]，它的密钥固定，同一程序中相同的键总是得到相同的哈希值。
+//! 因此遍历顺序是确定的：相同的操作序列总是得到相同的遍历顺序，但这个顺序由哈希值决定，
+//! 换一个标准库版本就可能改变。
+//!
+//! 有序模式（[`ChainedHashMap::ordered`]）额外用一个双向链表按插入顺序串起所有键值对，
+//! 遍历时按插入顺序输出，适合需要稳定输出的示例和快照测试。链表结点保存在数组中，
+//! 记录键值对所在的桶和位置，键值对在桶内移动或扩容时同步更新，插入和删除仍为 O(1)。
+
+use std::{
+    borrow::Borrow,
+    hash::{DefaultHasher, Hash, Hasher},
+    mem,
+};
+
+/// 初始的桶数量
+const INITIAL_BUCKETS: usize = 4;
+/// 负载因子阈值，超过后扩容
+const LOAD_FACTOR_THRESHOLD: f64 = 2.0 / 3.0;
+/// 扩容倍数
+const EXTEND_RATIO: usize = 2;
+
+fn hash_of<Q: Hash + ?Sized>(key: &Q) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    key.hash(&mut hasher);
+
+    hasher.finish()
+}
+
+/// 桶中的一个键值对
+#[derive(Debug, Clone)]
+struct Slot<K, V> {
+    key: K,
+    value: V,
+    /// 有序模式下在插入顺序链表中的结点下标
+    link: usize,
+}
+
+/// 插入顺序链表的结点，记录键值对所在的桶和在桶中的位置
+#[derive(Debug, Clone)]
+struct Link {
+    bucket: usize,
+    pos: usize,
+    prev: Option<usize>,
+    next: Option<usize>,
+}
+
+/// 按插入顺序串起所有键值对的双向链表
+#[derive(Debug, Clone, Default)]
+struct InsertionOrder {
+    links: Vec<Link>,
+    /// 已删除、可复用的结点下标
+    free: Vec<usize>,
+    head: Option<usize>,
+    tail: Option<usize>,
+}
+
+impl InsertionOrder {
+    /// 在链表尾部添加结点，返回结点下标
+    fn push_back(&mut self, bucket: usize, pos: usize) -> usize {
+        let link = Link {
+            bucket,
+            pos,
+            prev: self.tail,
+            next: None,
+        };
+        let index = match self.free.pop() {
+            Some(index) => {
+                self.links[index] = link;
+                index
+            }
+            None => {
+                self.links.push(link);
+                self.links.len() - 1
+            }
+        };
+
+        match self.tail {
+            Some(tail) => self.links[tail].next = Some(index),
+            None => self.head = Some(index),
+        }
+        self.tail = Some(index);
+
+        index
+    }
+
+    /// 删除结点
+    fn remove(&mut self, index: usize) {
+        let Link { prev, next, .. } = self.links[index];
+
+        match prev {
+            Some(prev) => self.links[prev].next = next,
+            None => self.head = next,
+        }
+        match next {
+            Some(next) => self.links[next].prev = prev,
+            None => self.tail = prev,
+        }
+        self.free.push(index);
+    }
+}
+
+/// 基于链式地址的哈希表
+#[derive(Debug, Clone)]
+pub struct ChainedHashMap<K, V> {
+    buckets: Vec<Vec<Slot<K, V>>>,
+    len: usize,
+    /// 有序模式下的插入顺序
+    order: Option<InsertionOrder>,
+}
+
+impl<K, V> ChainedHashMap<K, V> {
+    /// 创建空的哈希表
+    pub fn new() -> Self {
+        Self {
+            buckets: (0..INITIAL_BUCKETS).map(|_| Vec::new()).collect(),
+            len: 0,
+            order: None,
+        }
+    }
+
+    /// 创建按插入顺序遍历的空哈希表
+    ///
+    /// 替换已有键的值不改变它的位置，删除后重新插入则移到最后。
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use base::ChainedHashMap;
+    ///
+    /// let mut map = ChainedHashMap::ordered();
+    /// for (i, word) in ["pear", "apple", "fig", "kiwi"].into_iter().enumerate() {
+    ///     map.insert(word, i);
+    /// }
+    /// map.insert("apple", 10);
+    /// map.remove(&"pear");
+    /// map.insert("pear", 20);
+    ///
+    /// assert_eq!(
+    ///     map.iter().collect::<Vec<_>>(),
+    ///     [(&"apple", &10), (&"fig", &2), (&"kiwi", &3), (&"pear", &20)]
+    /// );
+    /// ```
+    pub fn ordered() -> Self {
+        Self {
+            order: Some(InsertionOrder::default()),
+            ..Self::new()
+        }
+    }
+
+    /// 是否为有序模式
+    pub fn is_ordered(&self) -> bool {
+        self.order.is_some()
+    }
+
+    /// 键值对数量
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// 判断是否为空
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// 桶的数量
+    pub fn buckets(&self) -> usize {
+        self.buckets.len()
+    }
+
+    /// 负载因子
+    pub fn load_factor(&self) -> f64 {
+        self.len as f64 / self.buckets.len() as f64
+    }
+
+    /// 遍历所有键值对，有序模式下按插入顺序，否则顺序由哈希值决定
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> {
+        let mut unordered = self.buckets.iter().flatten();
+        let mut current = self.order.as_ref().and_then(|order| order.head);
+
+        std::iter::from_fn(move || {
+            let slot = match &self.order {
+                Some(order) => {
+                    let link = &order.links[current?];
+                    current = link.next;
+                    &self.buckets[link.bucket][link.pos]
+                }
+                None => unordered.next()?,
+            };
+
+            Some((&slot.key, &slot.value))
+        })
+    }
+}
+
+impl<K: Hash + Eq, V> ChainedHashMap<K, V> {
+    /// 哈希值对应的桶下标
+    fn bucket_of(&self, hash: u64) -> usize {
+        (hash % self.buckets.len() as u64) as usize
+    }
+
+    /// 查找键所在的桶和在桶中的位置
+    ///
+    /// 与标准库一样允许使用键的借用形式查找，例如用 &str 查找 String 类型的键，
+    /// Borrow 保证二者的哈希值和相等关系一致
+    fn find<Q>(&self, key: &Q) -> Option<(usize, usize)>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let bucket = self.bucket_of(hash_of(key));
+
+        self.buckets[bucket]
+            .iter()
+            .position(|slot| slot.key.borrow() == key)
+            .map(|pos| (bucket, pos))
+    }
+
+    /// 扩容：桶的数量乘以 EXTEND_RATIO，所有键值对重新分配到新的桶中
+    fn extend(&mut self) {
+        let buckets = (0..self.buckets.len() * EXTEND_RATIO)
+            .map(|_| Vec::new())
+            .collect();
+        let old = mem::replace(&mut self.buckets, buckets);
+
+        for slot in old.into_iter().flatten() {
+            let bucket = self.bucket_of(hash_of(&slot.key));
+            self.buckets[bucket].push(slot);
+        }
+
+        // 所有键值对都换了位置，更新插入顺序链表中记录的位置
+        if let Some(order) = &mut self.order {
+            for (bucket, slots) in self.buckets.iter().enumerate() {
+                for (pos, slot) in slots.iter().enumerate() {
+                    let link = &mut order.links[slot.link];
+                    (link.bucket, link.pos) = (bucket, pos);
+                }
+            }
+        }
+    }
+
+    /// 获取键对应的值
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let (bucket, pos) = self.find(key)?;
+
+        Some(&self.buckets[bucket][pos].value)
+    }
+
+    /// 获取键对应值的可变借用
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let (bucket, pos) = self.find(key)?;
+
+        Some(&mut self.buckets[bucket][pos].value)
+    }
+
+    /// 判断是否包含指定的键
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.find(key).is_some()
+    }
+
+    /// 插入键值对，键已存在时替换其值并返回旧值
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        match self.entry(key) {
+            Entry::Occupied(mut entry) => Some(entry.insert(value)),
+            Entry::Vacant(entry) => {
+                entry.insert(value);
+                None
+            }
+        }
+    }
+
+    /// 删除键对应的键值对，返回被删除的值
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let (bucket, pos) = self.find(key)?;
+        self.len -= 1;
+
+        // 桶内的顺序无关紧要，用最后一个元素填补空位
+        let slot = self.buckets[bucket].swap_remove(pos);
+        if let Some(order) = &mut self.order {
+            order.remove(slot.link);
+            if let Some(moved) = self.buckets[bucket].get(pos) {
+                order.links[moved.link].pos = pos;
+            }
+        }
+
+        Some(slot.value)
+    }
+
+    /// 获取键对应的条目，用于原地修改或按需插入，只需计算一次哈希值
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use base::ChainedHashMap;
+    ///
+    /// let mut counts = ChainedHashMap::new();
+    /// for c in "hello".chars() {
+    ///     counts.entry(c).and_modify(|n| *n += 1).or_insert(1);
+    /// }
+    ///
+    /// assert_eq!(counts.get(&'l'), Some(&2));
+    /// assert_eq!(counts.len(), 4);
+    /// ```
+    pub fn entry(&mut self, key: K) -> Entry<'_, K, V> {
+        let hash = hash_of(&key);
+        let bucket = self.bucket_of(hash);
+
+        match self.buckets[bucket].iter().position(|slot| slot.key == key) {
+            Some(pos) => Entry::Occupied(OccupiedEntry {
+                slot: &mut self.buckets[bucket][pos],
+            }),
+            None => Entry::Vacant(VacantEntry {
+                map: self,
+                hash,
+                key,
+            }),
+        }
+    }
+}
+
+impl<K, V> Default for ChainedHashMap<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Hash + Eq, V> FromIterator<(K, V)> for ChainedHashMap<K, V> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut map = ChainedHashMap::new();
+
+        iter.into_iter().for_each(|(key, value)| {
+            map.insert(key, value);
+        });
+
+        map
+    }
+}
+
+/// 哈希表中的一个条目，由 [`ChainedHashMap::entry`] 返回
+pub enum Entry<'a, K, V> {
+    /// 键已存在
+    Occupied(OccupiedEntry<'a, K, V>),
+    /// 键不存在
+    Vacant(VacantEntry<'a, K, V>),
+}
+
+/// 已存在的条目
+pub struct OccupiedEntry<'a, K, V> {
+    slot: &'a mut Slot<K, V>,
+}
+
+/// 不存在的条目，保存了键的哈希值，插入时不必重新计算
+pub struct VacantEntry<'a, K, V> {
+    map: &'a mut ChainedHashMap<K, V>,
+    hash: u64,
+    key: K,
+}
+
+impl<'a, K: Hash + Eq, V> Entry<'a, K, V> {
+    /// 条目的键
+    pub fn key(&self) -> &K {
+        match self {
+            Entry::Occupied(entry) => entry.key(),
+            Entry::Vacant(entry) => entry.key(),
+        }
+    }
+
+    /// 键不存在时插入 default，返回值的可变借用
+    pub fn or_insert(self, default: V) -> &'a mut V {
+        self.or_insert_with(|| default)
+    }
+
+    /// 键不存在时插入 f 的返回值，返回值的可变借用
+    pub fn or_insert_with<F: FnOnce() -> V>(self, f: F) -> &'a mut V {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(f()),
+        }
+    }
+
+    /// 键存在时用 f 修改其值
+    pub fn and_modify<F: FnOnce(&mut V)>(mut self, f: F) -> Self {
+        if let Entry::Occupied(entry) = &mut self {
+            f(entry.get_mut());
+        }
+
+        self
+    }
+}
+
+impl<'a, K: Hash + Eq, V: Default> Entry<'a, K, V> {
+    /// 键不存在时插入默认值，返回值的可变借用
+    pub fn or_default(self) -> &'a mut V {
+        self.or_insert_with(V::default)
+    }
+}
+
+impl<'a, K, V> OccupiedEntry<'a, K, V> {
+    /// 条目的键
+    pub fn key(&self) -> &K {
+        &self.slot.key
+    }
+
+    /// 条目的值
+    pub fn get(&self) -> &V {
+        &self.slot.value
+    }
+
+    /// 条目的值的可变借用
+    pub fn get_mut(&mut self) -> &mut V {
+        &mut self.slot.value
+    }
+
+    /// 转换为值的可变借用，其生命周期与哈希表的借用相同
+    pub fn into_mut(self) -> &'a mut V {
+        &mut self.slot.value
+    }
+
+    /// 替换条目的值，返回旧值
+    pub fn insert(&mut self, value: V) -> V {
+        mem::replace(&mut self.slot.value, value)
+    }
+}
+
+impl<'a, K: Hash + Eq, V> VacantEntry<'a, K, V> {
+    /// 条目的键
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+
+    /// 取回条目的键
+    pub fn into_key(self) -> K {
+        self.key
+    }
+
+    /// 插入值，返回值的可变借用
+    pub fn insert(self, value: V) -> &'a mut V {
+        let map = self.map;
+        map.len += 1;
+        if map.load_factor() > LOAD_FACTOR_THRESHOLD {
+            map.extend();
+        }
+
+        let bucket = map.bucket_of(self.hash);
+        let pos = map.buckets[bucket].len();
+        let link = match &mut map.order {
+            Some(order) => order.push_back(bucket, pos),
+            None => 0,
+        };
+        map.buckets[bucket].push(Slot {
+            key: self.key,
+            value,
+            link,
+        });
+
+        &mut map.buckets[bucket][pos].value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::generate::{self, Operation};
+
+    #[test]
+    fn chained_hash_map_should_match_std() {
+        let mut map = ChainedHashMap::new();
+        let mut expected = HashMap::new();
+
+        for (i, op) in generate::operations(5000, 300, 3).into_iter().enumerate() {
+            match op {
+                Operation::Insert(key) => assert_eq!(map.insert(key, i), expected.insert(key, i)),
+                Operation::Remove(key) => assert_eq!(map.remove(&key), expected.remove(&key)),
+                Operation::Get(key) => assert_eq!(map.get(&key), expected.get(&key)),
+            }
+        }
+
+        assert_eq!(map.len(), expected.len());
+        assert_eq!(map.iter().count(), expected.len());
+        assert!(map.iter().all(|(k, v)| expected.get(k) == Some(v)));
+        assert!(map.load_factor() <= LOAD_FACTOR_THRESHOLD);
+    }
+
+    #[test]
+    fn ordered_hash_map_should_keep_insertion_order() {
+        let mut map = ChainedHashMap::ordered();
+        // 按插入顺序排列的键值对
+        let mut expected: Vec<(u64, usize)> = Vec::new();
+
+        for (i, op) in generate::operations(5000, 300, 5).into_iter().enumerate() {
+            let pos = |key| expected.iter().position(|&(k, _)| k == key);
+            match op {
+                Operation::Insert(key) => match pos(key) {
+                    Some(pos) => {
+                        assert_eq!(map.insert(key, i), Some(expected[pos].1));
+                        expected[pos].1 = i;
+                    }
+                    None => {
+                        assert_eq!(map.insert(key, i), None);
+                        expected.push((key, i));
+                    }
+                },
+                Operation::Remove(key) => {
+                    let removed = pos(key).map(|pos| expected.remove(pos).1);
+                    assert_eq!(map.remove(&key), removed);
+                }
+                Operation::Get(key) => {
+                    let value = pos(key).map(|pos| &expected[pos].1);
+                    assert_eq!(map.get(&key), value);
+                }
+            }
+        }
+
+        assert!(map.is_ordered());
+        assert_eq!(map.len(), expected.len());
+        assert!(
+            map.iter()
+                .map(|(&k, &v)| (k, v))
+                .eq(expected.iter().copied())
+        );
+
+        // entry 插入的键同样排在最后
+        *map.entry(u64::MAX).or_default() += 1;
+        assert_eq!(map.iter().last(), Some((&u64::MAX, &1)));
+    }
+
+    #[test]
+    fn chained_hash_map_iteration_should_be_deterministic() {
+        let build = |map: &mut ChainedHashMap<String, usize>| {
+            for (i, key) in generate::uniform(500, 9).into_iter().enumerate() {
+                map.insert(format!("key{}", key % 200), i);
+            }
+        };
+
+        let (mut a, mut b) = (ChainedHashMap::new(), ChainedHashMap::new());
+        build(&mut a);
+        build(&mut b);
+        assert!(!a.is_ordered());
+        assert!(a.iter().eq(b.iter()));
+    }
+
+    #[test]
+    fn chained_hash_map_should_extend() {
+        let mut map = ChainedHashMap::new();
+        assert_eq!(map.buckets(), INITIAL_BUCKETS);
+
+        for i in 0..100 {
+            map.insert(i, i * i);
+        }
+        assert!(map.buckets() >= 128);
+        assert!((0..100).all(|i| map.get(&i) == Some(&(i * i))));
+
+        *map.get_mut(&7).unwrap() = 0;
+        assert_eq!(map.remove(&7), Some(0));
+        assert!(!map.contains_key(&7));
+    }
+
+    #[test]
+    fn chained_hash_map_entry_should_work() {
+        let mut map = ChainedHashMap::new();
+
+        *map.entry("x").or_default() += 1;
+        *map.entry("x").or_default() += 1;
+        map.entry("y").and_modify(|v| *v = 100).or_insert_with(|| 7);
+        assert_eq!(map.get(&"x"), Some(&2));
+        assert_eq!(map.get(&"y"), Some(&7));
+
+        match map.entry("x") {
+            Entry::Occupied(mut entry) => {
+                assert_eq!(entry.key(), &"x");
+                assert_eq!(entry.insert(5), 2);
+            }
+            Entry::Vacant(_) => unreachable!(),
+        }
+        match map.entry("z") {
+            Entry::Occupied(_) => unreachable!(),
+            Entry::Vacant(entry) => assert_eq!(entry.key(), &"z"),
+        }
+        assert_eq!(map.len(), 2);
+    }
+}
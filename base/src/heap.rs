@@ -15,7 +15,14 @@
 
 #![allow(dead_code)]
 
-trait Heap<T> {
+use std::{
+    cmp::{Ordering, Reverse},
+    collections::BinaryHeap,
+    fmt::{self, Display},
+};
+
+/// 堆的公共接口
+pub trait Heap<T> {
     /// 关联大顶堆或小顶堆
     type HeapTp;
 
@@ -49,6 +56,16 @@ impl<T: PartialOrd> MaxHeap<T> {
     pub fn new() -> Self {
         Self(Vec::new())
     }
+
+    /// 堆的数组表示，即完全二叉树的层序遍历
+    pub fn as_slice(&self) -> &[T] {
+        &self.0
+    }
+
+    /// 转换为堆的数组表示
+    pub fn into_vec(self) -> Vec<T> {
+        self.0
+    }
 }
 
 impl<T: PartialOrd> Heap<T> for MaxHeap<T> {
@@ -61,10 +78,7 @@ impl<T: PartialOrd> Heap<T> for MaxHeap<T> {
     {
         // 将列表元素直接放进堆中
         let mut heap = MaxHeap(v.into());
-        // 堆化除叶节点外的其它节点
-        for i in (0..=parent(heap.len() - 1)).rev() {
-            sift_down_max(&mut heap.0, i);
-        }
+        heapify(&mut heap.0, sift_down_max);
 
         heap
     }
@@ -117,6 +131,56 @@ impl<T: PartialOrd, const N: usize> From<[T; N]> for MaxHeap<T> {
     }
 }
 
+/// 按层输出堆，见 [`fmt_levels`]
+///
+/// # Examples
+///
+/// ```rust
+/// use base::MaxHeap;
+///
+/// let heap = MaxHeap::from([1, 2, 3, 4, 5, 6, 7, 8]);
+///
+/// assert_eq!(heap.to_string(), "8\n5 7\n4 1 6 3\n2\n");
+/// assert_eq!(format!("{heap:.2}"), "8\n5 7\n...（还有 5 个元素）\n");
+/// ```
+impl<T: Display> Display for MaxHeap<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_levels(&self.0, f)
+    }
+}
+
+/// 用字面量构建 [`MaxHeap`]，时间复杂度：O(n)
+///
+/// # Examples
+///
+/// ```rust
+/// use base::{Heap, max_heap};
+///
+/// let mut heap = max_heap![3, 1, 4, 1, 5];
+///
+/// assert_eq!(heap.pop(), Some(5));
+/// assert_eq!(heap.peek(), Some(&4));
+/// ```
+#[macro_export]
+macro_rules! max_heap {
+    ($($x:expr),* $(,)?) => {
+        $crate::MaxHeap::from([$($x),*])
+    };
+}
+
+impl<T: Ord> From<BinaryHeap<T>> for MaxHeap<T> {
+    fn from(heap: BinaryHeap<T>) -> Self {
+        // into_vec 不保证元素的顺序，需要重新建堆
+        MaxHeap::from_vec(heap.into_vec())
+    }
+}
+
+impl<T: Ord> From<MaxHeap<T>> for BinaryHeap<T> {
+    fn from(heap: MaxHeap<T>) -> Self {
+        BinaryHeap::from(heap.0)
+    }
+}
+
 /// 大顶堆的从底至顶堆化
 fn sift_up_max<T: PartialOrd>(v: &mut [T], i: usize) {
     sift_up(v, i, |a, b| a <= b);
@@ -138,6 +202,16 @@ impl<T: PartialOrd> MinHeap<T> {
     pub fn new() -> Self {
         Self(Vec::new())
     }
+
+    /// 堆的数组表示，即完全二叉树的层序遍历
+    pub fn as_slice(&self) -> &[T] {
+        &self.0
+    }
+
+    /// 转换为堆的数组表示
+    pub fn into_vec(self) -> Vec<T> {
+        self.0
+    }
 }
 
 impl<T: PartialOrd> Heap<T> for MinHeap<T> {
@@ -148,10 +222,7 @@ impl<T: PartialOrd> Heap<T> for MinHeap<T> {
         I: Into<Vec<T>>,
     {
         let mut heap = MinHeap(v.into());
-
-        for i in (0..=heap.len()).rev() {
-            sift_down_min(&mut heap.0, i);
-        }
+        heapify(&mut heap.0, sift_down_min);
 
         heap
     }
@@ -202,6 +273,51 @@ impl<T: PartialOrd, const N: usize> From<[T; N]> for MinHeap<T> {
     }
 }
 
+/// 按层输出堆，见 [`fmt_levels`]
+impl<T: PartialOrd + Display> Display for MinHeap<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_levels(&self.0, f)
+    }
+}
+
+/// 用字面量构建 [`MinHeap`]，时间复杂度：O(n)
+///
+/// # Examples
+///
+/// ```rust
+/// use base::{Heap, min_heap};
+///
+/// let mut heap = min_heap![3, 1, 4, 1, 5];
+///
+/// assert_eq!(heap.pop(), Some(1));
+/// assert_eq!(heap.pop(), Some(1));
+/// assert_eq!(heap.peek(), Some(&3));
+/// ```
+#[macro_export]
+macro_rules! min_heap {
+    ($($x:expr),* $(,)?) => {
+        $crate::MinHeap::from([$($x),*])
+    };
+}
+
+impl<T: Ord> From<BinaryHeap<Reverse<T>>> for MinHeap<T> {
+    fn from(heap: BinaryHeap<Reverse<T>>) -> Self {
+        let v = heap
+            .into_vec()
+            .into_iter()
+            .map(|Reverse(val)| val)
+            .collect::<Vec<_>>();
+
+        MinHeap::from_vec(v)
+    }
+}
+
+impl<T: Ord> From<MinHeap<T>> for BinaryHeap<Reverse<T>> {
+    fn from(heap: MinHeap<T>) -> Self {
+        heap.0.into_iter().map(Reverse).collect()
+    }
+}
+
 /// 小顶堆的从底至顶堆化
 fn sift_up_min<T: PartialOrd>(v: &mut [T], i: usize) {
     sift_up(v, i, |a, b| a >= b);
@@ -212,19 +328,148 @@ fn sift_down_min<T: PartialOrd>(v: &mut [T], i: usize) {
     sift_down(v, i, |a, b| a < b);
 }
 
+/// 带插入序号的元素，优先级相同时序号小（先插入）的更大
+#[derive(Debug)]
+struct Sequenced<T> {
+    value: T,
+    seq: u64,
+}
+
+impl<T: PartialEq> PartialEq for Sequenced<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value && self.seq == other.seq
+    }
+}
+
+impl<T: PartialOrd> PartialOrd for Sequenced<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        match self.value.partial_cmp(&other.value)? {
+            Ordering::Equal => Some(other.seq.cmp(&self.seq)),
+            ordering => Some(ordering),
+        }
+    }
+}
+
+/// 稳定的大顶堆：优先级相同的元素按插入顺序出堆（先进先出）
+///
+/// 堆排序不稳定，[`MaxHeap`] 中优先级相同的元素的出堆顺序取决于堆的形状。
+/// StableHeap 为每个元素附加递增的插入序号，比较时优先级相同再比较序号，
+/// 调度等需要“同优先级先来先服务”的场景可以直接使用。需要小顶堆时可以用 [`Reverse`] 包装元素。
+///
+/// # Examples
+///
+/// ```rust
+/// use std::cmp::Ordering;
+///
+/// use base::{Heap, StableHeap};
+///
+/// /// 只按优先级比较的任务，相等与大小关系都只看优先级，两者保持一致
+/// #[derive(Debug)]
+/// struct Task(u32, &'static str);
+///
+/// impl PartialEq for Task {
+///     fn eq(&self, other: &Self) -> bool {
+///         self.0 == other.0
+///     }
+/// }
+///
+/// impl PartialOrd for Task {
+///     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+///         self.0.partial_cmp(&other.0)
+///     }
+/// }
+///
+/// let mut heap = StableHeap::new();
+/// heap.push(Task(1, "a"));
+/// heap.push(Task(2, "b"));
+/// heap.push(Task(1, "c"));
+/// heap.push(Task(2, "d"));
+///
+/// let order = std::iter::from_fn(|| heap.pop()).map(|task| task.1);
+/// assert_eq!(order.collect::<Vec<_>>(), ["b", "d", "a", "c"]);
+/// ```
+#[derive(Debug)]
+pub struct StableHeap<T> {
+    heap: MaxHeap<Sequenced<T>>,
+    /// 下一个插入的元素的序号
+    next_seq: u64,
+}
+
+impl<T: PartialOrd> StableHeap<T> {
+    /// 创建一个空的 StableHeap
+    pub fn new() -> Self {
+        Self {
+            heap: MaxHeap::new(),
+            next_seq: 0,
+        }
+    }
+}
+
+impl<T: PartialOrd> Heap<T> for StableHeap<T> {
+    type HeapTp = StableHeap<T>;
+
+    /// 列表中靠前的元素视为先插入，时间复杂度：O(n)
+    fn from_vec<I>(v: I) -> Self::HeapTp
+    where
+        I: Into<Vec<T>>,
+    {
+        let v = v
+            .into()
+            .into_iter()
+            .zip(0..)
+            .map(|(value, seq)| Sequenced { value, seq })
+            .collect::<Vec<_>>();
+
+        Self {
+            next_seq: v.len() as u64,
+            heap: MaxHeap::from_vec(v),
+        }
+    }
+
+    fn peek(&self) -> Option<&T> {
+        self.heap.peek().map(|top| &top.value)
+    }
+
+    fn push(&mut self, val: T) {
+        let seq = self.next_seq;
+
+        self.next_seq += 1;
+        self.heap.push(Sequenced { value: val, seq });
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        self.heap.pop().map(|top| top.value)
+    }
+
+    fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+}
+
+impl<T: PartialOrd> Default for StableHeap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: PartialOrd> From<Vec<T>> for StableHeap<T> {
+    fn from(v: Vec<T>) -> Self {
+        StableHeap::from_vec(v)
+    }
+}
+
 /// 从节点 i 开始，从底至顶堆化
 fn sift_up<T, F>(v: &mut [T], mut i: usize, cmp: F)
 where
     T: PartialOrd,
     F: Fn(&T, &T) -> bool,
 {
-    loop {
-        if i == 0 {
-            // 节点 i 已经是堆顶节点了，结束堆化
-            break;
-        }
-        // 获取节点 i 的父节点索引 p
-        let p = parent(i);
+    // 获取节点 i 的父节点索引 p，节点 i 已经是堆顶节点时结束堆化
+    while let Some(p) = parent(i) {
         if cmp(&v[i], &v[p]) {
             // 该节点满足要求，结束堆化
             break;
@@ -262,20 +507,58 @@ where
     }
 }
 
-/// 获取左子节点的索引
+/// 建堆：从最后一个非叶节点开始，依次从顶至底堆化，空堆和只有一个元素的堆无需处理
+fn heapify<T>(v: &mut [T], sift_down: fn(&mut [T], usize)) {
+    // 下标不小于 len / 2 的节点都是叶节点
+    for i in (0..v.len() / 2).rev() {
+        sift_down(v, i);
+    }
+}
+
+/// 按层输出堆的数组表示，每层一行，层内元素以空格分隔
+///
+/// 精度表示最多输出的层数，其余层折叠为一行，例如 `format!("{heap:.3}")` 只输出前 3 层。
+fn fmt_levels<T: Display>(v: &[T], f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let max_levels = f.precision().unwrap_or(usize::MAX);
+    let (mut start, mut width) = (0, 1);
+
+    for _ in 0..max_levels {
+        if start >= v.len() {
+            return Ok(());
+        }
+
+        let end = v.len().min(start + width);
+        for (i, val) in v[start..end].iter().enumerate() {
+            if i > 0 {
+                f.write_str(" ")?;
+            }
+            write!(f, "{val}")?;
+        }
+        writeln!(f)?;
+        (start, width) = (end, width * 2);
+    }
+
+    if start < v.len() {
+        writeln!(f, "...（还有 {} 个元素）", v.len() - start)?;
+    }
+
+    Ok(())
+}
+
+/// 获取左子节点的索引，溢出时饱和为 usize::MAX，必然越界
 fn left(i: usize) -> usize {
-    2 * i + 1
+    i.saturating_mul(2).saturating_add(1)
 }
 
-/// 获取右子节点的索引
+/// 获取右子节点的索引，溢出时饱和为 usize::MAX，必然越界
 fn right(i: usize) -> usize {
-    2 * i + 2
+    i.saturating_mul(2).saturating_add(2)
 }
 
-// 获取父节点的索引
-fn parent(i: usize) -> usize {
+/// 获取父节点的索引，堆顶节点没有父节点
+fn parent(i: usize) -> Option<usize> {
     // 向下整除
-    (i - 1) / 2
+    i.checked_sub(1).map(|i| i / 2)
 }
 
 #[cfg(test)]
@@ -284,10 +567,154 @@ mod tests {
 
     #[test]
     fn parent_index_should_work() {
-        assert_eq!(parent(3), 1);
-        assert_eq!(parent(4), 1);
-        assert_eq!(parent(5), 2);
-        assert_eq!(parent(6), 2);
+        assert_eq!(parent(0), None);
+        assert_eq!(parent(1), Some(0));
+        assert_eq!(parent(3), Some(1));
+        assert_eq!(parent(4), Some(1));
+        assert_eq!(parent(5), Some(2));
+        assert_eq!(parent(6), Some(2));
+        assert_eq!(left(usize::MAX / 2), usize::MAX);
+        assert_eq!(right(usize::MAX / 2), usize::MAX);
+    }
+
+    #[test]
+    fn heap_constructors_should_handle_empty_and_single() {
+        let empty_max = [
+            MaxHeap::<i32>::new(),
+            MaxHeap::from_vec(Vec::new()),
+            MaxHeap::from(Vec::new()),
+            MaxHeap::from([]),
+            MaxHeap::from(BinaryHeap::new()),
+            max_heap![],
+        ];
+        for mut heap in empty_max {
+            assert!(heap.is_empty());
+            assert_eq!(heap.peek(), None);
+            assert_eq!(heap.pop(), None);
+        }
+
+        let empty_min = [
+            MinHeap::<i32>::new(),
+            MinHeap::from_vec(Vec::new()),
+            MinHeap::from(Vec::new()),
+            MinHeap::from([]),
+            MinHeap::from(BinaryHeap::new()),
+            min_heap![],
+        ];
+        for mut heap in empty_min {
+            assert!(heap.is_empty());
+            assert_eq!(heap.peek(), None);
+            assert_eq!(heap.pop(), None);
+        }
+
+        let mut heap = MaxHeap::from([7]);
+        assert_eq!((heap.peek(), heap.len()), (Some(&7), 1));
+        assert_eq!((heap.pop(), heap.pop()), (Some(7), None));
+        let mut heap = MinHeap::from(vec![7]);
+        assert_eq!((heap.peek(), heap.len()), (Some(&7), 1));
+        assert_eq!((heap.pop(), heap.pop()), (Some(7), None));
+    }
+
+    #[test]
+    fn heap_std_conversions_should_work() {
+        let mut heap = MaxHeap::from(BinaryHeap::from([3, 1, 4, 1, 5]));
+        assert_eq!(heap.pop(), Some(5));
+        let mut std_heap = BinaryHeap::from(heap);
+        assert_eq!(std_heap.pop(), Some(4));
+        assert_eq!(std_heap.into_sorted_vec(), [1, 1, 3]);
+
+        let mut heap = MinHeap::from(BinaryHeap::from([Reverse(2), Reverse(0), Reverse(7)]));
+        assert_eq!(heap.pop(), Some(0));
+        let mut std_heap = BinaryHeap::from(heap);
+        assert_eq!(std_heap.pop(), Some(Reverse(2)));
+
+        assert!(MaxHeap::from(BinaryHeap::<i32>::new()).is_empty());
+        assert!(MinHeap::from(BinaryHeap::<Reverse<i32>>::new()).is_empty());
+    }
+
+    #[test]
+    fn heap_macros_should_match_from() {
+        let mut heap = max_heap![2, 7, 1, 8, 2, 8];
+        let mut expected = MaxHeap::from([2, 7, 1, 8, 2, 8]);
+        assert_eq!(heap.as_slice(), expected.as_slice());
+        while let Some(top) = heap.pop() {
+            assert_eq!(Some(top), expected.pop());
+        }
+
+        let heap = min_heap![2, 7, 1, 8,];
+        assert_eq!(heap.as_slice(), MinHeap::from([2, 7, 1, 8]).as_slice());
+        assert_eq!(heap.peek(), Some(&1));
+
+        let empty: MaxHeap<i32> = max_heap![];
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn heap_display_should_print_levels() {
+        let heap = MinHeap::from([5, 4, 3, 2, 1]);
+        assert_eq!(heap.to_string(), "1\n2 3\n5 4\n");
+        assert_eq!(format!("{heap:.1}"), "1\n...（还有 4 个元素）\n");
+        assert_eq!(format!("{heap:.3}"), heap.to_string());
+        assert_eq!(MaxHeap::<i32>::new().to_string(), "");
+
+        // 只输出前两层，不会格式化其余的元素
+        let heap = MaxHeap::from((0..1_000_000).rev().collect::<Vec<_>>());
+        assert_eq!(
+            format!("{heap:.2}"),
+            "999999\n999998 999997\n...（还有 999997 个元素）\n"
+        );
+    }
+
+    /// 只按优先级比较的元素，id 记录插入顺序
+    #[derive(Debug)]
+    struct Job {
+        priority: u64,
+        id: usize,
+    }
+
+    impl PartialEq for Job {
+        fn eq(&self, other: &Self) -> bool {
+            self.priority == other.priority
+        }
+    }
+
+    impl PartialOrd for Job {
+        fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+            self.priority.partial_cmp(&other.priority)
+        }
+    }
+
+    #[test]
+    fn stable_heap_should_match_stable_sort() {
+        let priorities = crate::generate::few_distinct(500, 5, 3);
+        // 稳定排序：优先级降序，相同时按插入顺序
+        let mut expected = priorities.iter().copied().enumerate().collect::<Vec<_>>();
+        expected.sort_by_key(|&(_, priority)| Reverse(priority));
+
+        let jobs = || {
+            priorities
+                .iter()
+                .enumerate()
+                .map(|(id, &priority)| Job { priority, id })
+        };
+        let mut pushed = StableHeap::new();
+        jobs().for_each(|job| pushed.push(job));
+        let mut built = StableHeap::from(jobs().collect::<Vec<_>>());
+
+        // Job 的相等只看优先级，取出字段比较才能检查插入顺序
+        let fields = |job: &Job| (job.id, job.priority);
+        for &expected in &expected {
+            assert_eq!(pushed.peek().map(fields), Some(expected));
+            assert_eq!(pushed.pop().as_ref().map(fields), Some(expected));
+            assert_eq!(built.pop().as_ref().map(fields), Some(expected));
+        }
+        assert!(pushed.is_empty() && built.is_empty());
+
+        // 出堆后继续插入，序号仍然递增
+        built.push(Job { priority: 1, id: 0 });
+        built.push(Job { priority: 1, id: 1 });
+        assert_eq!(built.pop().map(|job| job.id), Some(0));
+        assert_eq!(built.len(), 1);
     }
 
     #[test]
@@ -0,0 +1,346 @@
+//! 树链剖分（重链剖分，heavy-light decomposition）
+//!
+//! 对每个节点，将其子树最大的子节点称为“重子节点”，连向重子节点的边称为重边，
+//! 由重边连成的链称为重链。DFS 时优先访问重子节点，则每条重链在 DFS 序中是连续的。
+//! 任意节点到根的路径最多经过 O(logn) 条重链，因此树上任意一条路径都可以拆成
+//! O(logn) 段 DFS 序上的连续区间，再交给线段树处理。
+//!
+//! 路径查询、路径修改的时间复杂度均为 O(log²n)。
+
+use std::ops::Range;
+
+use crate::LazySegmentTree;
+
+/// 路径上的聚合值
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PathAggregate {
+    pub sum: i64,
+    pub min: i64,
+    pub max: i64,
+}
+
+impl PathAggregate {
+    const IDENTITY: Self = Self {
+        sum: 0,
+        min: i64::MAX,
+        max: i64::MIN,
+    };
+
+    fn merge(&self, other: &Self) -> Self {
+        Self {
+            sum: self.sum + other.sum,
+            min: self.min.min(other.min),
+            max: self.max.max(other.max),
+        }
+    }
+
+    /// count 个元素的聚合值在每个元素加上 delta 之后的结果
+    fn add(&self, delta: i64, count: usize) -> Self {
+        Self {
+            sum: self.sum + delta * count as i64,
+            min: self.min + delta,
+            max: self.max + delta,
+        }
+    }
+}
+
+/// 基于重链剖分的路径查询结构，节点编号为 0..n，节点值为 i64
+#[derive(Debug, Clone)]
+pub struct HldIndex {
+    parent: Vec<Option<usize>>,
+    depth: Vec<usize>,
+    /// 节点所在重链的链顶
+    head: Vec<usize>,
+    /// 节点在 DFS 序中的位置
+    pos: Vec<usize>,
+    /// 按 DFS 序排列的节点值，懒标记为区间增量
+    tree: LazySegmentTree<PathAggregate, i64>,
+}
+
+impl HldIndex {
+    /// 从以 root 为根的多叉树构建，children[v] 为节点 v 的所有子节点，values[v] 为节点 v 的值
+    ///
+    /// children 为空时返回空的索引，此时忽略 root。
+    ///
+    /// # Panics
+    ///
+    /// Panics if `values.len() != children.len()`, or some nodes are unreachable from `root`.
+    pub fn from_children(root: usize, children: &[Vec<usize>], values: &[i64]) -> Self {
+        let n = children.len();
+        assert_eq!(values.len(), n, "Each node should have exactly one value");
+        if n == 0 {
+            return Self {
+                parent: Vec::new(),
+                depth: Vec::new(),
+                head: Vec::new(),
+                pos: Vec::new(),
+                tree: Self::new_tree(Vec::new()),
+            };
+        }
+
+        let mut parent = vec![None; n];
+        let mut depth = vec![0; n];
+
+        // 1. 先序遍历得到一个父节点先于子节点的顺序，并记录父节点和深度
+        let mut order = Vec::with_capacity(n);
+        let mut stack = vec![root];
+        while let Some(v) = stack.pop() {
+            order.push(v);
+            for &c in &children[v] {
+                parent[c] = Some(v);
+                depth[c] = depth[v] + 1;
+                stack.push(c);
+            }
+        }
+        assert_eq!(order.len(), n, "All nodes should be reachable from root");
+
+        // 2. 逆序累加子树大小，并找出每个节点的重子节点
+        let mut size = vec![1; n];
+        let mut heavy = vec![None; n];
+        for &v in order.iter().rev() {
+            if let Some(p) = parent[v] {
+                size[p] += size[v];
+            }
+            heavy[v] = children[v].iter().copied().max_by_key(|&c| size[c]);
+        }
+
+        // 3. 优先沿重子节点 DFS，为每个节点分配链顶和 DFS 序位置
+        let mut head = vec![0; n];
+        let mut pos = vec![0; n];
+        let mut next_pos = 0;
+        let mut stack = vec![root];
+        while let Some(h) = stack.pop() {
+            // h 是一条重链的链顶，沿重边走完整条链
+            let mut current = Some(h);
+            while let Some(v) = current {
+                head[v] = h;
+                pos[v] = next_pos;
+                next_pos += 1;
+                // 轻子节点各自作为新重链的链顶
+                for &c in &children[v] {
+                    if Some(c) != heavy[v] {
+                        stack.push(c);
+                    }
+                }
+                current = heavy[v];
+            }
+        }
+
+        let mut flattened = vec![PathAggregate::IDENTITY; n];
+        for v in 0..n {
+            flattened[pos[v]] = PathAggregate {
+                sum: values[v],
+                min: values[v],
+                max: values[v],
+            };
+        }
+
+        Self {
+            parent,
+            depth,
+            head,
+            pos,
+            tree: Self::new_tree(flattened),
+        }
+    }
+
+    /// 从父节点数组构建，parents[v] 为节点 v 的父节点，根节点为 None
+    ///
+    /// parents 为空时返回空的索引。
+    ///
+    /// # Panics
+    ///
+    /// Panics if `parents` is not empty and there is not exactly one root.
+    pub fn from_parents(parents: &[Option<usize>], values: &[i64]) -> Self {
+        let mut children = vec![Vec::new(); parents.len()];
+        let mut roots = Vec::new();
+
+        for (v, parent) in parents.iter().enumerate() {
+            match *parent {
+                Some(p) => children[p].push(v),
+                None => roots.push(v),
+            }
+        }
+        if parents.is_empty() {
+            return Self::from_children(0, &children, values);
+        }
+        assert_eq!(roots.len(), 1, "Tree should have exactly one root");
+
+        Self::from_children(roots[0], &children, values)
+    }
+
+    fn new_tree(values: Vec<PathAggregate>) -> LazySegmentTree<PathAggregate, i64> {
+        LazySegmentTree::new(
+            values,
+            PathAggregate::IDENTITY,
+            PathAggregate::merge,
+            0,
+            |&delta, agg, count| agg.add(delta, count),
+            |a, b| a + b,
+        )
+    }
+
+    /// 节点数量
+    pub fn len(&self) -> usize {
+        self.pos.len()
+    }
+
+    /// 判断是否为空
+    pub fn is_empty(&self) -> bool {
+        self.pos.is_empty()
+    }
+
+    /// 节点 u 和 v 的最近公共祖先，时间复杂度：O(logn)
+    pub fn lca(&self, u: usize, v: usize) -> usize {
+        let (mut u, mut v) = (u, v);
+
+        while self.head[u] != self.head[v] {
+            // 链顶较深的节点跳到链顶的父节点
+            if self.depth[self.head[u]] < self.depth[self.head[v]] {
+                std::mem::swap(&mut u, &mut v);
+            }
+            // Safety: 两节点不在同一条链上时，链顶较深的那条链一定不是根所在的链
+            u = self.parent[self.head[u]].unwrap();
+        }
+
+        if self.depth[u] < self.depth[v] { u } else { v }
+    }
+
+    /// 将 u 到 v 的路径拆分为 DFS 序上的若干连续区间
+    pub fn path_ranges(&self, u: usize, v: usize) -> Vec<Range<usize>> {
+        let (mut u, mut v) = (u, v);
+        let mut ranges = Vec::new();
+
+        while self.head[u] != self.head[v] {
+            if self.depth[self.head[u]] < self.depth[self.head[v]] {
+                std::mem::swap(&mut u, &mut v);
+            }
+            let h = self.head[u];
+            ranges.push(self.pos[h]..self.pos[u] + 1);
+            // Safety: 同 lca
+            u = self.parent[h].unwrap();
+        }
+
+        let (lo, hi) = if self.pos[u] < self.pos[v] {
+            (self.pos[u], self.pos[v])
+        } else {
+            (self.pos[v], self.pos[u])
+        };
+        ranges.push(lo..hi + 1);
+
+        ranges
+    }
+
+    /// 查询 u 到 v 路径上（包含两端）所有节点值的聚合
+    pub fn query_path(&self, u: usize, v: usize) -> PathAggregate {
+        self.path_ranges(u, v)
+            .into_iter()
+            .map(|range| self.tree.query(range))
+            .reduce(|a, b| a.merge(&b))
+            // Safety: 路径至少包含一个节点，ranges 一定不为空
+            .unwrap()
+    }
+
+    /// u 到 v 路径上所有节点值之和
+    pub fn path_sum(&self, u: usize, v: usize) -> i64 {
+        self.query_path(u, v).sum
+    }
+
+    /// u 到 v 路径上节点值的最小值
+    pub fn path_min(&self, u: usize, v: usize) -> i64 {
+        self.query_path(u, v).min
+    }
+
+    /// u 到 v 路径上节点值的最大值
+    pub fn path_max(&self, u: usize, v: usize) -> i64 {
+        self.query_path(u, v).max
+    }
+
+    /// 将 u 到 v 路径上（包含两端）所有节点的值加上 delta
+    pub fn path_add(&mut self, u: usize, v: usize, delta: i64) {
+        for range in self.path_ranges(u, v) {
+            self.tree.apply(range, delta);
+        }
+    }
+
+    /// 获取节点 v 的值
+    pub fn get(&self, v: usize) -> i64 {
+        let p = self.pos[v];
+        self.tree.query(p..p + 1).sum
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    //           0(1)
+    //         /     \
+    //      1(2)     2(3)
+    //     /   \        \
+    //   3(4)  4(5)     5(6)
+    //         /
+    //       6(7)
+    fn new_index() -> HldIndex {
+        HldIndex::from_parents(
+            &[None, Some(0), Some(0), Some(1), Some(1), Some(2), Some(4)],
+            &[1, 2, 3, 4, 5, 6, 7],
+        )
+    }
+
+    #[test]
+    fn hld_lca_should_work() {
+        let index = new_index();
+
+        assert_eq!(index.lca(3, 6), 1);
+        assert_eq!(index.lca(6, 5), 0);
+        assert_eq!(index.lca(4, 6), 4);
+        assert_eq!(index.lca(5, 5), 5);
+    }
+
+    #[test]
+    fn hld_path_query_should_work() {
+        let index = new_index();
+
+        // 6 -> 4 -> 1 -> 0 -> 2 -> 5
+        assert_eq!(
+            index.query_path(6, 5),
+            PathAggregate {
+                sum: 24,
+                min: 1,
+                max: 7
+            }
+        );
+        // 3 -> 1 -> 4
+        assert_eq!(index.path_sum(3, 4), 11);
+        assert_eq!(index.path_min(3, 4), 2);
+        assert_eq!(index.path_max(3, 4), 5);
+        assert_eq!(index.path_sum(2, 2), 3);
+    }
+
+    #[test]
+    fn hld_path_add_should_work() {
+        let mut index = new_index();
+
+        // 3 -> 1 -> 4 -> 6 全部加 10
+        index.path_add(3, 6, 10);
+        assert_eq!(index.get(3), 14);
+        assert_eq!(index.get(0), 1);
+        assert_eq!(index.path_sum(3, 6), 4 + 2 + 5 + 7 + 40);
+        assert_eq!(index.path_min(6, 5), 1);
+        assert_eq!(index.path_max(6, 5), 17);
+
+        index.path_add(0, 5, -1);
+        assert_eq!(index.path_sum(0, 5), 1 + 3 + 6 - 3);
+        assert_eq!(index.path_min(6, 5), 0);
+    }
+
+    #[test]
+    fn hld_empty_tree_should_work() {
+        let index = HldIndex::from_children(0, &[], &[]);
+        assert!(index.is_empty());
+        assert_eq!(index.len(), 0);
+
+        assert!(HldIndex::from_parents(&[], &[]).is_empty());
+    }
+}
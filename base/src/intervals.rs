@@ -0,0 +1,512 @@
+//! 区间问题
+//! 本模块中的区间均为闭区间 `(start, end)`，即 start <= x <= end，
+//! 两个区间只要有公共点（包括端点相接）即视为重叠。
+//!
+//! * merge：合并所有重叠区间
+//! * insert_and_merge：向有序且互不重叠的区间列表中插入新区间
+//! * intersect：求两个有序且互不重叠的区间列表的交集
+//! * DisjointIntervalSet：动态维护一组互不重叠的区间
+//! * SweepLine：扫描线，按起点顺序处理区间并维护覆盖当前位置的区间
+//! * max_overlap：同一点最多被多少个区间覆盖
+//! * merge_overlapping_stream：惰性合并按起点有序的区间流
+
+use crate::{AvlMap, Heap, MinHeap};
+
+/// 合并所有重叠的区间，返回按起点升序排列且互不重叠的区间列表
+///
+/// 时间复杂度：O(nlogn)，主要开销在排序
+///
+/// # Examples
+///
+/// ```rust
+/// use base::intervals;
+///
+/// let merged = intervals::merge([(1, 3), (8, 10), (2, 6), (15, 18)]);
+///
+/// assert_eq!(merged, vec![(1, 6), (8, 10), (15, 18)]);
+/// ```
+pub fn merge<T, I>(intervals: I) -> Vec<(T, T)>
+where
+    T: Ord + Copy,
+    I: IntoIterator<Item = (T, T)>,
+{
+    let mut intervals = intervals.into_iter().collect::<Vec<_>>();
+    // 按区间起点排序，这样可能重叠的区间一定相邻
+    intervals.sort_unstable();
+
+    let mut merged: Vec<(T, T)> = Vec::with_capacity(intervals.len());
+    for (start, end) in intervals {
+        match merged.last_mut() {
+            // 与上一个区间重叠，延伸上一个区间的终点
+            Some(last) if start <= last.1 => last.1 = last.1.max(end),
+            _ => merged.push((start, end)),
+        }
+    }
+
+    merged
+}
+
+/// 向按起点升序排列且互不重叠的区间列表中插入一个新区间，并合并重叠部分
+///
+/// 时间复杂度：O(n)
+pub fn insert_and_merge<T: Ord + Copy>(intervals: &[(T, T)], new: (T, T)) -> Vec<(T, T)> {
+    let (mut start, mut end) = new;
+    let mut result = Vec::with_capacity(intervals.len() + 1);
+    let mut i = 0;
+
+    // 1. 完全位于新区间左侧的区间原样保留
+    while i < intervals.len() && intervals[i].1 < start {
+        result.push(intervals[i]);
+        i += 1;
+    }
+
+    // 2. 与新区间重叠的区间全部并入新区间
+    while i < intervals.len() && intervals[i].0 <= end {
+        start = start.min(intervals[i].0);
+        end = end.max(intervals[i].1);
+        i += 1;
+    }
+    result.push((start, end));
+
+    // 3. 完全位于新区间右侧的区间原样保留
+    result.extend_from_slice(&intervals[i..]);
+
+    result
+}
+
+/// 求两个按起点升序排列且互不重叠的区间列表的交集
+///
+/// 使用双指针，时间复杂度：O(m + n)
+pub fn intersect<T: Ord + Copy>(a: &[(T, T)], b: &[(T, T)]) -> Vec<(T, T)> {
+    let (mut i, mut j) = (0, 0);
+    let mut result = Vec::new();
+
+    while i < a.len() && j < b.len() {
+        // 交集的起点取两者起点的较大值，终点取两者终点的较小值
+        let start = a[i].0.max(b[j].0);
+        let end = a[i].1.min(b[j].1);
+        if start <= end {
+            result.push((start, end));
+        }
+
+        // 终点较小的区间不可能再与后续区间相交，将其指针后移
+        if a[i].1 < b[j].1 {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+
+    result
+}
+
+/// 互不重叠的区间集合，插入时自动合并重叠的区间
+///
+/// 使用 [`AvlMap`] 以起点为键、终点为值保存区间，插入和查询的时间复杂度均为 O(logn)
+/// （插入时被合并掉的区间的删除开销均摊到它们各自的插入操作上）
+#[derive(Debug, Clone)]
+pub struct DisjointIntervalSet<T> {
+    map: AvlMap<T, T>,
+}
+
+impl<T> DisjointIntervalSet<T> {
+    /// 创建空的区间集合
+    pub fn new() -> Self {
+        Self { map: AvlMap::new() }
+    }
+
+    /// 集合中互不重叠的区间数量
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// 判断集合是否为空
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// 按起点升序遍历集合中的区间
+    pub fn iter(&self) -> impl Iterator<Item = (&T, &T)> {
+        self.map.iter()
+    }
+}
+
+impl<T: Ord + Copy> DisjointIntervalSet<T> {
+    /// 插入闭区间 [start, end]，与之重叠的区间会被合并
+    ///
+    /// # Panics
+    ///
+    /// Panics if `start > end`.
+    pub fn insert(&mut self, start: T, end: T) {
+        assert!(start <= end, "Invalid interval: start > end");
+
+        let (mut start, mut end) = (start, end);
+
+        // 起点不大于 start 的最后一个区间，若其与新区间重叠则并入
+        if let Some((&s, &e)) = self.map.floor(&start)
+            && e >= start
+        {
+            start = s;
+            end = end.max(e);
+        }
+
+        // 起点落在 [start, end] 内的区间全部与新区间重叠
+        while let Some((&s, &e)) = self.map.ceiling(&start).filter(|&(&s, _)| s <= end) {
+            end = end.max(e);
+            self.map.remove(&s);
+        }
+
+        self.map.insert(start, end);
+    }
+
+    /// 返回包含点 x 的区间，不存在则返回 None
+    pub fn find(&self, x: T) -> Option<(T, T)> {
+        self.map
+            .floor(&x)
+            .filter(|&(_, &e)| e >= x)
+            .map(|(&s, &e)| (s, e))
+    }
+
+    /// 判断点 x 是否被集合中的某个区间覆盖
+    pub fn contains(&self, x: T) -> bool {
+        self.find(x).is_some()
+    }
+
+    /// 判断闭区间 [start, end] 是否与集合中的某个区间重叠
+    pub fn overlaps(&self, start: T, end: T) -> bool {
+        self.contains(start) || self.map.ceiling(&start).is_some_and(|(&s, _)| s <= end)
+    }
+
+    /// 按起点升序转换为区间列表
+    pub fn to_vec(&self) -> Vec<(T, T)> {
+        self.map.iter().map(|(&s, &e)| (s, e)).collect()
+    }
+}
+
+impl<T> Default for DisjointIntervalSet<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Ord + Copy> FromIterator<(T, T)> for DisjointIntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = (T, T)>>(iter: I) -> Self {
+        let mut set = DisjointIntervalSet::new();
+
+        iter.into_iter().for_each(|(start, end)| {
+            set.insert(start, end);
+        });
+
+        set
+    }
+}
+
+/// 扫描线：从左到右依次扫过每个区间的起点，同时维护覆盖扫描位置的活跃区间
+///
+/// 尚未扫到的区间保存在按起点排列的小顶堆中（事件堆），活跃区间的终点保存在另一个小顶堆中，
+/// 扫到新的起点时先弹出终点在它之前的区间，每一步的时间复杂度为 O(logn)。
+///
+/// # Examples
+///
+/// ```rust
+/// use base::intervals::SweepLine;
+///
+/// let mut sweep = SweepLine::from_iter([(1, 4), (2, 3), (5, 6)]);
+///
+/// assert_eq!(sweep.step(), Some((1, 1)));
+/// assert_eq!(sweep.step(), Some((2, 2)));
+/// // [1, 4] 仍覆盖 5 之前的位置，但扫到 5 时两个区间都已结束
+/// assert_eq!(sweep.step(), Some((5, 1)));
+/// assert_eq!(sweep.step(), None);
+/// ```
+#[derive(Debug)]
+pub struct SweepLine<T: PartialOrd> {
+    /// 尚未扫到的区间，按起点排列
+    pending: MinHeap<(T, T)>,
+    /// 活跃区间的终点
+    active: MinHeap<T>,
+    /// 上一次扫到的位置
+    position: Option<T>,
+}
+
+impl<T: Ord + Copy> SweepLine<T> {
+    /// 创建空的扫描线
+    pub fn new() -> Self {
+        Self {
+            pending: MinHeap::new(),
+            active: MinHeap::new(),
+            position: None,
+        }
+    }
+
+    /// 加入闭区间 [start, end]，起点不能位于已经扫过的位置之前
+    ///
+    /// # Panics
+    ///
+    /// Panics if `start > end` or `start` is before the current sweep position.
+    pub fn push(&mut self, start: T, end: T) {
+        assert!(start <= end, "Invalid interval: start > end");
+        assert!(
+            self.position.is_none_or(|p| p <= start),
+            "Cannot push an interval behind the sweep line"
+        );
+
+        self.pending.push((start, end));
+    }
+
+    /// 扫到下一个区间的起点，返回起点和此时覆盖它的区间数量（包括这个区间），没有区间时返回 None
+    pub fn step(&mut self) -> Option<(T, usize)> {
+        let (start, end) = self.pending.pop()?;
+
+        // 闭区间在端点相接时也算重叠，只弹出终点严格小于起点的区间
+        while self.active.peek().is_some_and(|&e| e < start) {
+            self.active.pop();
+        }
+        self.active.push(end);
+        self.position = Some(start);
+
+        Some((start, self.active.len()))
+    }
+
+    /// 当前活跃区间的数量
+    pub fn active_len(&self) -> usize {
+        self.active.len()
+    }
+
+    /// 尚未扫到的区间数量
+    pub fn pending_len(&self) -> usize {
+        self.pending.len()
+    }
+}
+
+impl<T: Ord + Copy> Default for SweepLine<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Ord + Copy> FromIterator<(T, T)> for SweepLine<T> {
+    fn from_iter<I: IntoIterator<Item = (T, T)>>(iter: I) -> Self {
+        let mut sweep = SweepLine::new();
+
+        iter.into_iter().for_each(|(start, end)| {
+            sweep.push(start, end);
+        });
+
+        sweep
+    }
+}
+
+/// 同一点最多被多少个区间覆盖，即需要的最少会议室数量
+///
+/// 覆盖数只会在区间的起点处增加，因此只需在每个起点处统计活跃区间的数量。
+/// 时间复杂度：O(nlogn)
+///
+/// # Panics
+///
+/// Panics if an interval has `start > end`.
+///
+/// # Examples
+///
+/// ```rust
+/// use base::intervals;
+///
+/// assert_eq!(intervals::max_overlap([(0, 30), (5, 10), (15, 20)]), 2);
+/// // 端点相接也算重叠
+/// assert_eq!(intervals::max_overlap([(1, 2), (2, 3), (3, 4)]), 2);
+/// ```
+pub fn max_overlap<T, I>(intervals: I) -> usize
+where
+    T: Ord + Copy,
+    I: IntoIterator<Item = (T, T)>,
+{
+    let mut sweep = SweepLine::from_iter(intervals);
+
+    std::iter::from_fn(|| sweep.step())
+        .map(|(_, count)| count)
+        .max()
+        .unwrap_or(0)
+}
+
+/// 惰性合并按起点升序到达的区间流，一个合并后的区间结束时（下一个区间的起点超过它的终点）立即产出
+///
+/// 只保存正在合并的一个区间，适合处理无法全部放入内存的有序日志等数据。
+///
+/// # Panics
+///
+/// Panics if the intervals are not sorted by start or an interval has `start > end`.
+///
+/// # Examples
+///
+/// ```rust
+/// use base::intervals;
+///
+/// let stream = [(1, 3), (2, 6), (8, 10), (10, 12), (15, 18)].into_iter();
+/// let mut merged = intervals::merge_overlapping_stream(stream);
+///
+/// assert_eq!(merged.next(), Some((1, 6)));
+/// assert_eq!(merged.collect::<Vec<_>>(), [(8, 12), (15, 18)]);
+/// ```
+pub fn merge_overlapping_stream<T, I>(intervals: I) -> impl Iterator<Item = (T, T)>
+where
+    T: Ord + Copy,
+    I: IntoIterator<Item = (T, T)>,
+{
+    let mut intervals = intervals.into_iter();
+    let mut current: Option<(T, T)> = None;
+
+    std::iter::from_fn(move || {
+        for (start, end) in intervals.by_ref() {
+            assert!(start <= end, "Invalid interval: start > end");
+
+            match &mut current {
+                None => current = Some((start, end)),
+                Some((s, e)) => {
+                    assert!(start >= *s, "Intervals must be sorted by start");
+                    if start <= *e {
+                        *e = (*e).max(end);
+                    } else {
+                        return current.replace((start, end));
+                    }
+                }
+            }
+        }
+
+        current.take()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generate::Rng;
+
+    /// 随机区间，端点在 [0, range) 内
+    fn random_intervals(len: usize, range: u64, seed: u64) -> Vec<(u64, u64)> {
+        let mut rng = Rng::new(seed);
+        (0..len)
+            .map(|_| {
+                let (a, b) = (rng.below(range), rng.below(range));
+                (a.min(b), a.max(b))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn merge_should_work() {
+        assert_eq!(merge([(1, 4), (4, 5)]), vec![(1, 5)]);
+        assert_eq!(merge([(5, 6), (1, 10), (2, 3)]), vec![(1, 10)]);
+        assert_eq!(merge(Vec::<(i32, i32)>::new()), vec![]);
+    }
+
+    #[test]
+    fn insert_and_merge_should_work() {
+        let intervals = [(1, 2), (3, 5), (6, 7), (8, 10), (12, 16)];
+
+        assert_eq!(
+            insert_and_merge(&intervals, (4, 8)),
+            vec![(1, 2), (3, 10), (12, 16)]
+        );
+        assert_eq!(insert_and_merge(&[(1, 5)], (6, 8)), vec![(1, 5), (6, 8)]);
+        assert_eq!(insert_and_merge(&[], (1, 2)), vec![(1, 2)]);
+    }
+
+    #[test]
+    fn intersect_should_work() {
+        let a = [(0, 2), (5, 10), (13, 23), (24, 25)];
+        let b = [(1, 5), (8, 12), (15, 24), (25, 26)];
+
+        assert_eq!(
+            intersect(&a, &b),
+            vec![(1, 2), (5, 5), (8, 10), (15, 23), (24, 24), (25, 25)]
+        );
+        assert_eq!(intersect(&a, &[]), vec![]);
+    }
+
+    #[test]
+    fn disjoint_interval_set_should_work() {
+        let mut set = DisjointIntervalSet::new();
+
+        set.insert(1, 3);
+        set.insert(7, 9);
+        set.insert(12, 15);
+        assert_eq!(set.len(), 3);
+
+        // 同时与 [1, 3] 和 [7, 9] 重叠
+        set.insert(2, 8);
+        assert_eq!(set.to_vec(), vec![(1, 9), (12, 15)]);
+
+        assert!(set.contains(5));
+        assert!(set.contains(15));
+        assert!(!set.contains(10));
+        assert_eq!(set.find(13), Some((12, 15)));
+
+        assert!(set.overlaps(10, 12));
+        assert!(!set.overlaps(10, 11));
+
+        let set = DisjointIntervalSet::from_iter([(5, 6), (1, 2), (2, 4)]);
+        assert_eq!(set.to_vec(), vec![(1, 4), (5, 6)]);
+    }
+
+    #[test]
+    fn disjoint_interval_set_should_match_merge() {
+        for seed in 0..5 {
+            let intervals = random_intervals(80, 1000, seed);
+            let mut set = DisjointIntervalSet::new();
+
+            for (i, &(start, end)) in intervals.iter().enumerate() {
+                set.insert(start, end);
+                assert_eq!(set.to_vec(), merge(intervals[..=i].iter().copied()));
+            }
+            for x in 0..1000 {
+                let expected = intervals.iter().any(|&(s, e)| s <= x && x <= e);
+                assert_eq!(set.contains(x), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn max_overlap_should_match_brute_force() {
+        for seed in 0..5 {
+            let intervals = random_intervals(60, 100, seed);
+            let expected = (0..100)
+                .map(|x| intervals.iter().filter(|&&(s, e)| s <= x && x <= e).count())
+                .max()
+                .unwrap();
+
+            assert_eq!(max_overlap(intervals), expected);
+        }
+        assert_eq!(max_overlap(Vec::<(i32, i32)>::new()), 0);
+    }
+
+    #[test]
+    fn sweep_line_should_track_active_intervals() {
+        let mut sweep = SweepLine::new();
+        sweep.push(10, 20);
+        sweep.push(0, 5);
+        assert_eq!(sweep.step(), Some((0, 1)));
+
+        // 扫描过程中继续加入区间
+        sweep.push(3, 12);
+        assert_eq!(sweep.pending_len(), 2);
+        assert_eq!(sweep.step(), Some((3, 2)));
+        assert_eq!(sweep.step(), Some((10, 2)));
+        assert_eq!(sweep.active_len(), 2);
+        assert_eq!(sweep.step(), None);
+    }
+
+    #[test]
+    fn merge_overlapping_stream_should_match_merge() {
+        for seed in 0..5 {
+            let mut intervals = random_intervals(80, 1000, seed);
+            intervals.sort_unstable();
+
+            let merged = merge_overlapping_stream(intervals.iter().copied()).collect::<Vec<_>>();
+            assert_eq!(merged, merge(intervals));
+        }
+        assert_eq!(
+            merge_overlapping_stream(Vec::<(i32, i32)>::new()).next(),
+            None
+        );
+    }
+}
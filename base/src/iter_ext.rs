@@ -0,0 +1,301 @@
+//! 迭代器适配器：把 crate 中的算法接入迭代器链
+//!
+//! [`IterExt`] 为所有迭代器提供以下方法，除 `k_smallest` 外都是惰性的，不会先收集到 Vec 中：
+//! * `merge_sorted_with` / `merge_sorted`：归并两个有序迭代器，即归并排序中的合并步骤
+//! * `dedup_consecutive`：去掉相邻的重复元素，与 `Vec::dedup` 相同
+//! * `chunk_by`：把相邻且满足条件的元素分为一组，与 `slice::chunk_by` 相同
+//! * `k_smallest`：用大小为 k 的大顶堆选出最小的 k 个元素，与 TOP-K 问题相同
+
+use std::{cmp::Ordering, iter::Peekable};
+
+use crate::{Heap, MaxHeap};
+
+/// 迭代器的扩展方法
+///
+/// # Examples
+///
+/// ```rust
+/// use base::IterExt;
+///
+/// let merged = [1, 3, 3, 5].into_iter().merge_sorted([2, 3, 6]);
+/// let distinct = merged.dedup_consecutive().collect::<Vec<_>>();
+///
+/// assert_eq!(distinct, [1, 2, 3, 5, 6]);
+/// ```
+pub trait IterExt: Iterator + Sized {
+    /// 按 cmp 归并两个有序的迭代器，相等的元素中 self 的元素在前（稳定）
+    fn merge_sorted_with<J, F>(self, other: J, cmp: F) -> MergeSortedWith<Self, J::IntoIter, F>
+    where
+        J: IntoIterator<Item = Self::Item>,
+        F: FnMut(&Self::Item, &Self::Item) -> Ordering,
+    {
+        MergeSortedWith {
+            left: self.peekable(),
+            right: other.into_iter().peekable(),
+            cmp,
+        }
+    }
+
+    /// 归并两个升序的迭代器
+    #[allow(clippy::type_complexity)]
+    fn merge_sorted<J>(
+        self,
+        other: J,
+    ) -> MergeSortedWith<Self, J::IntoIter, fn(&Self::Item, &Self::Item) -> Ordering>
+    where
+        J: IntoIterator<Item = Self::Item>,
+        Self::Item: Ord,
+    {
+        self.merge_sorted_with(other, Ord::cmp)
+    }
+
+    /// 去掉相邻的重复元素，每组相等的元素只保留第一个
+    fn dedup_consecutive(self) -> DedupConsecutive<Self>
+    where
+        Self::Item: PartialEq,
+    {
+        DedupConsecutive {
+            iter: self,
+            pending: None,
+        }
+    }
+
+    /// 把相邻的元素分组，same_group(a, b) 对组内每一对相邻元素都返回 true
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use base::IterExt;
+    ///
+    /// let runs = [1, 2, 3, 2, 5, 6].into_iter().chunk_by(|a, b| a < b);
+    ///
+    /// assert!(runs.eq([vec![1, 2, 3], vec![2, 5, 6]]));
+    /// ```
+    fn chunk_by<F>(self, same_group: F) -> ChunkBy<Self, F>
+    where
+        F: FnMut(&Self::Item, &Self::Item) -> bool,
+    {
+        ChunkBy {
+            iter: self,
+            pending: None,
+            same_group,
+        }
+    }
+
+    /// 最小的 k 个元素，按升序返回
+    ///
+    /// 大顶堆中保存当前最小的 k 个元素，堆顶是其中最大的，新元素比堆顶小时替换堆顶。
+    /// 时间复杂度：O(nlogk)，空间复杂度：O(k)
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use base::IterExt;
+    ///
+    /// let words = ["pear", "fig", "banana", "kiwi", "apple"];
+    ///
+    /// assert_eq!(words.iter().map(|w| w.len()).k_smallest(3), [3, 4, 4]);
+    /// ```
+    fn k_smallest(self, k: usize) -> Vec<Self::Item>
+    where
+        Self::Item: Ord,
+    {
+        if k == 0 {
+            return Vec::new();
+        }
+
+        let mut heap = MaxHeap::new();
+        for item in self {
+            if heap.len() < k {
+                heap.push(item);
+            } else if heap.peek().is_some_and(|top| item < *top) {
+                heap.pop();
+                heap.push(item);
+            }
+        }
+
+        // 依次弹出的是降序
+        let mut smallest = Vec::with_capacity(heap.len());
+        while let Some(item) = heap.pop() {
+            smallest.push(item);
+        }
+        smallest.reverse();
+
+        smallest
+    }
+}
+
+impl<I: Iterator> IterExt for I {}
+
+/// [`IterExt::merge_sorted_with`] 返回的迭代器
+pub struct MergeSortedWith<I: Iterator, J: Iterator, F> {
+    left: Peekable<I>,
+    right: Peekable<J>,
+    cmp: F,
+}
+
+impl<I, J, F> Iterator for MergeSortedWith<I, J, F>
+where
+    I: Iterator,
+    J: Iterator<Item = I::Item>,
+    F: FnMut(&I::Item, &I::Item) -> Ordering,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match (self.left.peek(), self.right.peek()) {
+            (Some(l), Some(r)) if (self.cmp)(l, r) == Ordering::Greater => self.right.next(),
+            (Some(_), _) => self.left.next(),
+            (None, _) => self.right.next(),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (left_low, left_high) = self.left.size_hint();
+        let (right_low, right_high) = self.right.size_hint();
+        let high = left_high
+            .zip(right_high)
+            .and_then(|(l, r)| l.checked_add(r));
+
+        (left_low.saturating_add(right_low), high)
+    }
+}
+
+/// [`IterExt::dedup_consecutive`] 返回的迭代器
+#[derive(Debug, Clone)]
+pub struct DedupConsecutive<I: Iterator> {
+    iter: I,
+    /// 已经从 iter 中取出、与上一个返回的元素不相等的元素
+    pending: Option<I::Item>,
+}
+
+impl<I> Iterator for DedupConsecutive<I>
+where
+    I: Iterator,
+    I::Item: PartialEq,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let current = self.pending.take().or_else(|| self.iter.next())?;
+
+        // 跳过与 current 相等的元素，第一个不相等的元素留到下一次返回
+        self.pending = self.iter.by_ref().find(|item| *item != current);
+
+        Some(current)
+    }
+}
+
+/// [`IterExt::chunk_by`] 返回的迭代器
+#[derive(Debug, Clone)]
+pub struct ChunkBy<I: Iterator, F> {
+    iter: I,
+    /// 下一组的第一个元素
+    pending: Option<I::Item>,
+    same_group: F,
+}
+
+impl<I, F> Iterator for ChunkBy<I, F>
+where
+    I: Iterator,
+    F: FnMut(&I::Item, &I::Item) -> bool,
+{
+    type Item = Vec<I::Item>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let first = self.pending.take().or_else(|| self.iter.next())?;
+        let mut chunk = vec![first];
+
+        for item in self.iter.by_ref() {
+            // Safety: chunk 中至少有一个元素
+            if (self.same_group)(chunk.last().unwrap(), &item) {
+                chunk.push(item);
+            } else {
+                self.pending = Some(item);
+                break;
+            }
+        }
+
+        Some(chunk)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generate;
+
+    #[test]
+    fn merge_sorted_should_match_sort() {
+        for (n, m, seed) in [(0, 0, 1), (0, 5, 2), (100, 37, 3), (500, 500, 4)] {
+            let mut a = generate::few_distinct(n, 20, seed);
+            let mut b = generate::few_distinct(m, 20, seed + 10);
+            a.sort();
+            b.sort();
+
+            let merged = a.iter().merge_sorted(&b);
+            assert_eq!(merged.size_hint(), (n + m, Some(n + m)));
+
+            let mut expected = [a.clone(), b.clone()].concat();
+            expected.sort();
+            assert!(merged.eq(expected.iter()));
+        }
+    }
+
+    #[test]
+    fn merge_sorted_with_should_be_stable() {
+        let left = [(1, 'a'), (2, 'a'), (2, 'b'), (4, 'a')];
+        let right = [(2, 'c'), (3, 'c'), (4, 'c')];
+
+        let merged = left
+            .into_iter()
+            .merge_sorted_with(right, |x, y| x.0.cmp(&y.0))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            merged,
+            [
+                (1, 'a'),
+                (2, 'a'),
+                (2, 'b'),
+                (2, 'c'),
+                (3, 'c'),
+                (4, 'a'),
+                (4, 'c')
+            ]
+        );
+    }
+
+    #[test]
+    fn dedup_consecutive_should_match_vec_dedup() {
+        for seed in 0..5 {
+            let v = generate::few_distinct(300, 3, seed);
+            let mut expected = v.clone();
+            expected.dedup();
+
+            assert!(v.iter().dedup_consecutive().eq(expected.iter()));
+        }
+        assert_eq!(std::iter::empty::<u8>().dedup_consecutive().next(), None);
+    }
+
+    #[test]
+    fn chunk_by_should_match_slice_chunk_by() {
+        for seed in 0..5 {
+            let v = generate::few_distinct(300, 4, seed);
+
+            let chunks = v.iter().copied().chunk_by(|a, b| a <= b);
+            assert!(chunks.eq(v.chunk_by(|a, b| a <= b).map(<[u64]>::to_vec)));
+        }
+    }
+
+    #[test]
+    fn k_smallest_should_match_sort() {
+        let v = generate::few_distinct(1000, 100, 6);
+        let mut sorted = v.clone();
+        sorted.sort();
+
+        for k in [0, 1, 10, 999, 1000, 2000] {
+            let expected = &sorted[..k.min(sorted.len())];
+            assert_eq!(v.iter().copied().k_smallest(k), expected);
+        }
+    }
+}
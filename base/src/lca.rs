@@ -0,0 +1,252 @@
+//! 最近公共祖先（LCA）- 倍增法
+//!
+//! 对于静态树，预处理出每个节点向上跳 2^j 步到达的祖先 up[j][v]，
+//! 其中 up[j][v] = up[j-1][up[j-1][v]]。查询时：
+//! 1. 先将较深的节点向上跳到与另一节点相同的深度
+//! 2. 再从大到小尝试跳 2^j 步，只要两节点跳完后不相同就跳
+//! 3. 最终两节点的父节点即为最近公共祖先
+//!
+//! 预处理时间复杂度：O(nlogn)，单次查询时间复杂度：O(logn)
+
+use std::collections::VecDeque;
+
+/// 基于倍增法的最近公共祖先索引，节点编号为 0..n
+#[derive(Debug, Clone)]
+pub struct LcaIndex {
+    /// up[j][v] 表示节点 v 向上跳 2^j 步到达的祖先，越过根节点时为 None
+    up: Vec<Vec<Option<usize>>>,
+    depth: Vec<usize>,
+    /// 每个节点所在树的根节点，用于判断两节点是否位于同一棵树中
+    root: Vec<usize>,
+}
+
+impl LcaIndex {
+    /// 从父节点数组构建索引，parents[v] 为节点 v 的父节点，根节点为 None
+    ///
+    /// 允许存在多个根节点（森林）
+    ///
+    /// # Panics
+    ///
+    /// Panics if any parent index is out of range or the parents contain a cycle.
+    pub fn from_parents(parents: &[Option<usize>]) -> Self {
+        let n = parents.len();
+        let mut children = vec![Vec::new(); n];
+        let mut roots = Vec::new();
+
+        for (v, parent) in parents.iter().enumerate() {
+            match *parent {
+                Some(p) => {
+                    assert!(p < n, "Parent index out of range: {p}, len: {n}");
+                    children[p].push(v);
+                }
+                None => roots.push(v),
+            }
+        }
+
+        Self::build(parents, &children, &roots)
+    }
+
+    /// 从以 root 为根的多叉树构建索引，children[v] 为节点 v 的所有子节点
+    ///
+    /// # Panics
+    ///
+    /// Panics if any node index is out of range or a node has more than one parent.
+    pub fn from_children(root: usize, children: &[Vec<usize>]) -> Self {
+        let n = children.len();
+        assert!(root < n, "Root index out of range: {root}, len: {n}");
+
+        let mut parents = vec![None; n];
+        for (p, nodes) in children.iter().enumerate() {
+            for &v in nodes {
+                assert!(v < n, "Child index out of range: {v}, len: {n}");
+                assert!(parents[v].is_none(), "Node {v} has more than one parent");
+                parents[v] = Some(p);
+            }
+        }
+
+        Self::build(&parents, children, &[root])
+    }
+
+    fn build(parents: &[Option<usize>], children: &[Vec<usize>], roots: &[usize]) -> Self {
+        let n = parents.len();
+        let mut depth = vec![0; n];
+        let mut root = vec![usize::MAX; n];
+
+        // 从根节点开始层序遍历，计算每个节点的深度
+        let mut queue = VecDeque::new();
+        for &r in roots {
+            root[r] = r;
+            queue.push_back(r);
+        }
+        let mut visited = 0;
+        while let Some(v) = queue.pop_front() {
+            visited += 1;
+            for &c in &children[v] {
+                depth[c] = depth[v] + 1;
+                root[c] = root[v];
+                queue.push_back(c);
+            }
+        }
+        assert_eq!(visited, n, "Parents contain a cycle");
+
+        // 最大深度不超过 n-1，因此跳跃层数取 log2(n) + 1 即可
+        let levels = (usize::BITS - n.leading_zeros()).max(1) as usize;
+        let mut up = vec![parents.to_vec()];
+        for j in 1..levels {
+            let level = (0..n)
+                .map(|v| up[j - 1][v].and_then(|mid| up[j - 1][mid]))
+                .collect();
+            up.push(level);
+        }
+
+        Self { up, depth, root }
+    }
+
+    /// 节点数量
+    pub fn len(&self) -> usize {
+        self.depth.len()
+    }
+
+    /// 判断索引是否为空
+    pub fn is_empty(&self) -> bool {
+        self.depth.is_empty()
+    }
+
+    /// 节点 v 的深度，根节点深度为 0
+    pub fn depth(&self, v: usize) -> usize {
+        self.depth[v]
+    }
+
+    /// 返回节点 v 的第 k 个祖先，k = 0 时返回 v 本身，不存在则返回 None
+    pub fn kth_ancestor(&self, v: usize, k: usize) -> Option<usize> {
+        if k > self.depth[v] {
+            return None;
+        }
+
+        let mut v = v;
+        // 将 k 按二进制拆分，逐位向上跳
+        for (j, level) in self.up.iter().enumerate() {
+            if k >> j & 1 == 1 {
+                v = level[v]?;
+            }
+        }
+
+        Some(v)
+    }
+
+    /// 返回节点 u 和 v 的最近公共祖先，两节点不在同一棵树中时返回 None
+    pub fn lca(&self, u: usize, v: usize) -> Option<usize> {
+        if self.root[u] != self.root[v] {
+            return None;
+        }
+
+        // 保证 u 是较深的节点，并将其跳到与 v 相同的深度
+        let (u, v) = if self.depth[u] >= self.depth[v] {
+            (u, v)
+        } else {
+            (v, u)
+        };
+        let mut u = self.kth_ancestor(u, self.depth[u] - self.depth[v])?;
+        let mut v = v;
+        if u == v {
+            return Some(u);
+        }
+
+        // 从大到小尝试，跳完后不相同才跳，最终 u 和 v 停在 LCA 的下一层
+        for level in self.up.iter().rev() {
+            if level[u] != level[v] {
+                u = level[u]?;
+                v = level[v]?;
+            }
+        }
+
+        self.up[0][u]
+    }
+
+    /// 节点 u 和 v 之间路径上的边数，两节点不在同一棵树中时返回 None
+    pub fn distance(&self, u: usize, v: usize) -> Option<usize> {
+        self.lca(u, v)
+            .map(|a| self.depth[u] + self.depth[v] - 2 * self.depth[a])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    //         0
+    //       / | \
+    //      1  2  3
+    //     / \     \
+    //    4   5     6
+    //   /
+    //  7
+    fn new_index() -> LcaIndex {
+        LcaIndex::from_parents(&[
+            None,
+            Some(0),
+            Some(0),
+            Some(0),
+            Some(1),
+            Some(1),
+            Some(3),
+            Some(4),
+        ])
+    }
+
+    #[test]
+    fn lca_should_work() {
+        let index = new_index();
+
+        assert_eq!(index.lca(7, 5), Some(1));
+        assert_eq!(index.lca(7, 6), Some(0));
+        assert_eq!(index.lca(4, 7), Some(4));
+        assert_eq!(index.lca(2, 2), Some(2));
+        assert_eq!(index.distance(7, 6), Some(5));
+    }
+
+    #[test]
+    fn kth_ancestor_should_work() {
+        let index = new_index();
+
+        assert_eq!(index.depth(7), 3);
+        assert_eq!(index.kth_ancestor(7, 0), Some(7));
+        assert_eq!(index.kth_ancestor(7, 1), Some(4));
+        assert_eq!(index.kth_ancestor(7, 3), Some(0));
+        assert_eq!(index.kth_ancestor(7, 4), None);
+    }
+
+    #[test]
+    fn from_children_should_match_from_parents() {
+        let index = LcaIndex::from_children(
+            0,
+            &[
+                vec![1, 2, 3],
+                vec![4, 5],
+                vec![],
+                vec![6],
+                vec![7],
+                vec![],
+                vec![],
+                vec![],
+            ],
+        );
+        let expected = new_index();
+
+        for u in 0..8 {
+            for v in 0..8 {
+                assert_eq!(index.lca(u, v), expected.lca(u, v));
+            }
+        }
+    }
+
+    #[test]
+    fn lca_in_forest_should_work() {
+        // 0 - 1 和 2 - 3 两棵树
+        let index = LcaIndex::from_parents(&[None, Some(0), None, Some(2)]);
+
+        assert_eq!(index.lca(1, 0), Some(0));
+        assert_eq!(index.lca(1, 3), None);
+        assert_eq!(index.distance(1, 3), None);
+    }
+}
@@ -1,28 +1,185 @@
+mod aho_corasick;
+pub use aho_corasick::{AhoCorasick, Match, StreamFilter};
+
+#[cfg(feature = "alloc-counter")]
+pub mod alloc_counter;
+
+pub mod analysis;
+
+mod autocomplete;
+pub use autocomplete::Autocomplete;
+
+pub mod avl_map;
+pub use avl_map::AvlMap;
+
 mod avl_tree;
 pub use avl_tree::AvlTree;
 
+mod b_heap;
+pub use b_heap::BHeap;
+
+pub mod bits;
+
 mod binary_tree;
-pub use binary_tree::{BinarySearchTree, BinaryTree};
+pub use binary_tree::{BinarySearchTree, BinaryTree, DedupStats, ParseTreeError, TreeBuilder};
+
+mod binary_trie;
+pub use binary_trie::{BinaryTrie, BitKey};
+
+mod cartesian_tree;
+pub use cartesian_tree::CartesianTree;
+
+pub mod compress;
+
+mod counter;
+pub use counter::Counter;
+
+pub mod discretize;
+
+pub mod encoding;
+
+pub mod exact_cover;
+pub use exact_cover::ExactCover;
+
+pub mod generate;
+
+pub mod geometry;
+
+pub mod graph;
+
+pub mod hash_map;
+pub use hash_map::ChainedHashMap;
 
 mod heap;
-pub use heap::{MaxHeap, MinHeap};
+pub use heap::{Heap, MaxHeap, MinHeap, StableHeap};
+
+mod hld;
+pub use hld::{HldIndex, PathAggregate};
+
+pub mod intervals;
+
+mod iter_ext;
+pub use iter_ext::{ChunkBy, DedupConsecutive, IterExt, MergeSortedWith};
+
+mod lca;
+pub use lca::LcaIndex;
+
+mod linked_hash_map;
+pub use linked_hash_map::{LinkedHashMap, LinkedHashSet};
 
 mod linked_list;
-pub use linked_list::LinkedList;
+pub use linked_list::{LinkedList, ListSnapshot, NodeHandle};
+
+mod llrb_tree;
+pub use llrb_tree::LlrbTree;
+
+mod lru_cache;
+pub use lru_cache::LruCache;
+
+pub mod math;
+
+pub mod memo;
+
+pub mod mermaid;
+
+pub mod mo;
+
+mod ordered_handle_list;
+pub use ordered_handle_list::{OrderHandle, OrderedHandleList};
+
+mod ordered_set;
+pub use ordered_set::OrderedSet;
+
+#[cfg(feature = "rayon")]
+pub mod par;
+
+mod persistent_bst;
+pub use persistent_bst::{BstVersions, PersistentBst};
+
+mod pool;
+pub use pool::{Pool, PoolGuard};
+
+mod prefix_sum;
+pub use prefix_sum::{DiffArray, PrefixSums, PrefixSums2D};
+
+mod priority_deque;
+pub use priority_deque::PriorityDeque;
 
 mod queue;
 pub use queue::{QueueWithArray, QueueWithList};
 
+pub mod random;
+
+mod scapegoat_tree;
+pub use scapegoat_tree::ScapegoatTree;
+
+mod segment_tree;
+pub use segment_tree::{LazySegmentTree, SegmentTree};
+
+pub mod search;
+
+pub mod select;
+
+mod singly_linked_list;
+pub use singly_linked_list::SinglyLinkedList;
+
+mod skip_list;
+pub use skip_list::SkipListMap;
+
+pub mod sort;
+
+pub mod sparse;
+pub use sparse::SparseMatrix;
+
 mod stack;
 pub use stack::{StackWithList, StackWithVec};
 
+mod stats;
+pub use stats::{MovingAverage, MovingStd, Quantile};
+
+pub mod strings;
+
+mod subtree_query;
+pub use subtree_query::SubtreeQuery;
+
+mod succinct;
+pub use succinct::{RankSelectBitVec, WaveletTree};
+
+mod suffix_array;
+pub use suffix_array::{SuffixArray, SuffixSort};
+
+mod suffix_automaton;
+pub use suffix_automaton::SuffixAutomaton;
+
+mod timer_wheel;
+pub use timer_wheel::{TimerHandle, TimerWheel};
+
 mod top_k;
 pub use top_k::top_k_heap;
 
+#[cfg(feature = "serde")]
+pub mod tree_serde;
+
+pub mod trampoline;
+
+mod trie;
+pub use trie::Trie;
+
+pub mod two_pointers;
+
+mod veb;
+pub use veb::VebSet;
+
+mod weight_balanced_tree;
+pub use weight_balanced_tree::WeightBalancedTree;
+
 pub mod bt {
-    use std::collections::VecDeque;
+    use std::{collections::VecDeque, fmt::Display};
 
-    use crate::binary_tree::OptionNodeRc;
+    use crate::{
+        binary_tree::{NodeRc, OptionNodeRc, TreeNode},
+        trampoline::Bounce,
+    };
 
     /// 广度优先搜索（BFS），其思想与队列一致
     pub fn contains_bfs<T: PartialEq>(root: &OptionNodeRc<T>, val: &T) -> bool {
@@ -87,57 +244,1074 @@ pub mod bt {
         }
     }
 
-    /// 前序遍历
+    /// 前序遍历，基于 [`PreOrderIter`] 的显式栈，树很深时也不会栈溢出
     pub fn pre_order<T: Clone>(root: &OptionNodeRc<T>) -> Vec<T> {
-        let mut ordered = Vec::new();
+        values(PreOrderIter::new(root))
+    }
 
-        pre_order_recursive(root, &mut ordered);
+    /// 中序遍历，基于 [`InOrderIter`] 的显式栈
+    pub fn in_order<T: Clone>(root: &OptionNodeRc<T>) -> Vec<T> {
+        values(InOrderIter::new(root))
+    }
 
-        ordered
+    /// 后序遍历，基于 [`PostOrderIter`] 的显式栈
+    pub fn post_order<T: Clone>(root: &OptionNodeRc<T>) -> Vec<T> {
+        values(PostOrderIter::new(root))
     }
 
-    /// 中序遍历
-    pub fn in_order<T: Clone>(root: &OptionNodeRc<T>) -> Vec<T> {
-        let mut ordered = Vec::new();
+    /// 按遍历顺序复制结点的值
+    fn values<T: Clone>(nodes: impl Iterator<Item = NodeRc<T>>) -> Vec<T> {
+        nodes.map(|node| node.borrow().value.clone()).collect()
+    }
 
-        in_order_recursive(root, &mut ordered);
+    /// 由前序遍历和中序遍历序列重建二叉树，结点的值必须互不相同
+    ///
+    /// 前序序列中每个值要么是栈顶结点的左子结点，要么是某个已完成左子树的结点的右子结点：
+    /// 栈顶的值还不是中序序列的下一个值时，说明栈顶的左子树尚未结束，新值作为它的左子结点；
+    /// 否则依次弹出与中序序列相同的结点，新值作为最后弹出的结点的右子结点。
+    /// 用显式的栈代替递归，树很深时也不会栈溢出，时间复杂度：O(n)
+    ///
+    /// 两个序列不是来自同一棵树时，结果没有意义。
+    ///
+    /// # Panics
+    ///
+    /// Panics if the two traversals have different lengths.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use base::bt;
+    ///
+    /// let root = bt::build_from_pre_in(&[3, 9, 20, 15, 7], &[9, 3, 15, 20, 7]);
+    ///
+    /// assert_eq!(bt::level_order(&root), [3, 9, 20, 15, 7]);
+    /// assert_eq!(bt::post_order(&root), [9, 15, 7, 20, 3]);
+    /// ```
+    pub fn build_from_pre_in<T: PartialEq + Clone>(pre: &[T], inorder: &[T]) -> OptionNodeRc<T> {
+        assert_eq!(
+            pre.len(),
+            inorder.len(),
+            "Traversals must have the same length"
+        );
 
-        ordered
+        build_from_traversal(pre.iter(), inorder.iter(), false)
     }
 
-    /// 后序遍历
-    pub fn post_order<T: Clone>(root: &OptionNodeRc<T>) -> Vec<T> {
+    /// 由中序遍历和后序遍历序列重建二叉树，结点的值必须互不相同
+    ///
+    /// 逆序的后序序列是“根、右、左”的顺序，与逆序的中序序列一起，
+    /// 按 [`build_from_pre_in`] 的方法交换左右子结点重建即可，时间复杂度：O(n)
+    ///
+    /// # Panics
+    ///
+    /// Panics if the two traversals have different lengths.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use base::bt;
+    ///
+    /// let root = bt::build_from_in_post(&[9, 3, 15, 20, 7], &[9, 15, 7, 20, 3]);
+    ///
+    /// assert_eq!(bt::pre_order(&root), [3, 9, 20, 15, 7]);
+    /// ```
+    pub fn build_from_in_post<T: PartialEq + Clone>(inorder: &[T], post: &[T]) -> OptionNodeRc<T> {
+        assert_eq!(
+            post.len(),
+            inorder.len(),
+            "Traversals must have the same length"
+        );
+
+        build_from_traversal(post.iter().rev(), inorder.iter().rev(), true)
+    }
+
+    /// 由“根、左、右”顺序的序列和中序序列重建二叉树，mirrored 为 true 时交换左右
+    fn build_from_traversal<'a, T: PartialEq + Clone + 'a>(
+        mut order: impl Iterator<Item = &'a T>,
+        mut inorder: impl Iterator<Item = &'a T>,
+        mirrored: bool,
+    ) -> OptionNodeRc<T> {
+        let root = TreeNode::new_node_rc(order.next()?.clone());
+        let mut stack = vec![root.clone()];
+        let mut next_in = inorder.next();
+        let child = |node: &NodeRc<T>, value: &T, right: bool| {
+            let new_node = TreeNode::new_node_rc(value.clone());
+            let mut node = node.borrow_mut();
+            if right != mirrored {
+                node.right = Some(new_node.clone());
+            } else {
+                node.left = Some(new_node.clone());
+            }
+            new_node
+        };
+
+        for value in order {
+            // Safety: 栈在每次迭代结束时都非空
+            let top = stack.last().unwrap().clone();
+            if next_in != Some(&top.borrow().value) {
+                stack.push(child(&top, value, false));
+                continue;
+            }
+
+            let mut parent = top;
+            while let Some(node) = stack.last() {
+                if next_in != Some(&node.borrow().value) {
+                    break;
+                }
+                // Safety: 上面已经确认栈非空
+                parent = stack.pop().unwrap();
+                next_in = inorder.next();
+            }
+            stack.push(child(&parent, value, true));
+        }
+
+        Some(root)
+    }
+
+    /// 惰性的前序遍历迭代器，依次产出结点，用显式的栈代替递归
+    ///
+    /// 产出的是结点的引用计数指针，只读访问时不需要 `T: Clone`，也可以随时停止遍历。
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use base::{BinaryTree, bt};
+    ///
+    /// let tree = BinaryTree::from([1, 2, 3, 4, 5]);
+    ///
+    /// let values = bt::PreOrderIter::new(&tree.root).map(|node| node.borrow().value);
+    /// assert_eq!(values.collect::<Vec<_>>(), [1, 2, 4, 5, 3]);
+    ///
+    /// // 找到第一个偶数后立即停止
+    /// let first_even = bt::PreOrderIter::new(&tree.root).find(|node| node.borrow().value % 2 == 0);
+    /// assert_eq!(first_even.map(|node| node.borrow().value), Some(2));
+    /// ```
+    pub struct PreOrderIter<T> {
+        stack: Vec<NodeRc<T>>,
+    }
+
+    impl<T> PreOrderIter<T> {
+        /// 从根结点开始遍历
+        pub fn new(root: &OptionNodeRc<T>) -> Self {
+            Self {
+                stack: Vec::from_iter(root.clone()),
+            }
+        }
+    }
+
+    impl<T> Iterator for PreOrderIter<T> {
+        type Item = NodeRc<T>;
+
+        fn next(&mut self) -> Option<Self::Item> {
+            let node = self.stack.pop()?;
+            {
+                // 先压右子结点，使左子树先被访问
+                let node = node.borrow();
+                self.stack.extend(node.right.clone());
+                self.stack.extend(node.left.clone());
+            }
+
+            Some(node)
+        }
+    }
+
+    /// 惰性的中序遍历迭代器，见 [`PreOrderIter`]
+    ///
+    /// 栈中保存从当前结点到根结点的路径上、左子树尚未访问完的结点，额外空间为 O(树高)。
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use base::{BinarySearchTree, BinaryTree, bt};
+    ///
+    /// let tree = BinaryTree::from([1, 2, 3, 4, 5]);
+    /// let values = bt::InOrderIter::new(&tree.root).map(|node| node.borrow().value);
+    /// assert_eq!(values.collect::<Vec<_>>(), [4, 2, 5, 1, 3]);
+    ///
+    /// // 二叉搜索树中最小的两个元素，只访问左侧的路径
+    /// let bst = BinarySearchTree::from([5, 3, 8, 1, 4, 9]).to_tree();
+    /// let smallest = bt::InOrderIter::new(&bst.root).take(2).map(|node| node.borrow().value);
+    /// assert_eq!(smallest.collect::<Vec<_>>(), [1, 3]);
+    /// ```
+    pub struct InOrderIter<T> {
+        stack: Vec<NodeRc<T>>,
+    }
+
+    impl<T> InOrderIter<T> {
+        /// 从根结点开始遍历
+        pub fn new(root: &OptionNodeRc<T>) -> Self {
+            let mut iter = Self { stack: Vec::new() };
+
+            iter.push_left_path(root.clone());
+            iter
+        }
+
+        /// 沿左子结点一路向下压栈
+        fn push_left_path(&mut self, mut current: OptionNodeRc<T>) {
+            while let Some(node) = current {
+                current = node.borrow().left.clone();
+                self.stack.push(node);
+            }
+        }
+    }
+
+    impl<T> Iterator for InOrderIter<T> {
+        type Item = NodeRc<T>;
+
+        fn next(&mut self) -> Option<Self::Item> {
+            let node = self.stack.pop()?;
+            let right = node.borrow().right.clone();
+            self.push_left_path(right);
+
+            Some(node)
+        }
+    }
+
+    /// 惰性的后序遍历迭代器，见 [`PreOrderIter`]
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use base::{BinaryTree, bt};
+    ///
+    /// let tree = BinaryTree::from([1, 2, 3, 4, 5]);
+    /// let values = bt::PostOrderIter::new(&tree.root).map(|node| node.borrow().value);
+    ///
+    /// assert_eq!(values.collect::<Vec<_>>(), [4, 5, 2, 3, 1]);
+    /// ```
+    pub struct PostOrderIter<T> {
+        /// (结点, 子结点是否已经压栈)
+        stack: Vec<(NodeRc<T>, bool)>,
+    }
+
+    impl<T> PostOrderIter<T> {
+        /// 从根结点开始遍历
+        pub fn new(root: &OptionNodeRc<T>) -> Self {
+            Self {
+                stack: root.iter().map(|node| (node.clone(), false)).collect(),
+            }
+        }
+    }
+
+    impl<T> Iterator for PostOrderIter<T> {
+        type Item = NodeRc<T>;
+
+        fn next(&mut self) -> Option<Self::Item> {
+            loop {
+                let (node, expanded) = self.stack.pop()?;
+                if expanded {
+                    return Some(node);
+                }
+
+                // 结点在其子结点之后出栈：依次压入结点、右子结点、左子结点
+                let (left, right) = {
+                    let node = node.borrow();
+                    (node.left.clone(), node.right.clone())
+                };
+                self.stack.push((node, true));
+                self.stack.extend(right.map(|right| (right, false)));
+                self.stack.extend(left.map(|left| (left, false)));
+            }
+        }
+    }
+
+    /// 层序遍历（广度优先），从上到下、每层从左到右
+    pub fn level_order<T: Clone>(root: &OptionNodeRc<T>) -> Vec<T> {
         let mut ordered = Vec::new();
+        let mut queue = VecDeque::from_iter(root.clone());
 
-        post_order_recursive(root, &mut ordered);
+        while let Some(node) = queue.pop_front() {
+            let node = node.borrow();
+            ordered.push(node.value.clone());
+            queue.extend(node.left.clone());
+            queue.extend(node.right.clone());
+        }
 
         ordered
     }
 
-    fn pre_order_recursive<T: Clone>(root: &OptionNodeRc<T>, ordered: &mut Vec<T>) {
-        if let Some(node) = root {
-            ordered.push(node.borrow().value.clone());
-            pre_order_recursive(&node.borrow().left, ordered);
-            pre_order_recursive(&node.borrow().right, ordered);
+    /// 层序遍历，按深度分组，第 i 组是深度为 i 的所有结点的值
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use base::{bt, tree};
+    ///
+    /// let tree = tree! {1, left: tree! {2, right: 4}, right: 3};
+    ///
+    /// assert_eq!(bt::level_order(&tree.root), [1, 2, 3, 4]);
+    /// assert_eq!(bt::level_order_groups(&tree.root), [vec![1], vec![2, 3], vec![4]]);
+    /// ```
+    pub fn level_order_groups<T: Clone>(root: &OptionNodeRc<T>) -> Vec<Vec<T>> {
+        let mut groups = Vec::new();
+        let mut level = Vec::from_iter(root.clone());
+
+        while !level.is_empty() {
+            let mut next = Vec::new();
+            groups.push(
+                level
+                    .iter()
+                    .map(|node| {
+                        let node = node.borrow();
+                        next.extend(node.left.clone());
+                        next.extend(node.right.clone());
+                        node.value.clone()
+                    })
+                    .collect(),
+            );
+            level = next;
         }
+
+        groups
     }
 
-    fn in_order_recursive<T: Clone>(root: &OptionNodeRc<T>, ordered: &mut Vec<T>) {
-        if let Some(node) = root {
-            in_order_recursive(&node.borrow().left, ordered);
-            ordered.push(node.borrow().value.clone());
-            in_order_recursive(&node.borrow().right, ordered);
+    /// 树高，即从根结点到最远叶结点的边数：叶结点高度为 0，空树高度为 -1，与 AVL 树的约定相同
+    ///
+    /// 按层遍历计数，树退化成链时也不会栈溢出，时间复杂度：O(n)
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use base::{BinaryTree, bt, tree};
+    ///
+    /// let tree = tree! {1, left: tree! {2, right: 4}, right: 3};
+    ///
+    /// assert_eq!(bt::height(&tree.root), 2);
+    /// assert_eq!(bt::height(&tree! {1}.root), 0);
+    /// assert_eq!(bt::height(&BinaryTree::<i32>::new().root), -1);
+    /// ```
+    pub fn height<T>(root: &OptionNodeRc<T>) -> i32 {
+        let mut level = Vec::from_iter(root.clone());
+        let mut height = -1;
+
+        while !level.is_empty() {
+            height += 1;
+            level = level
+                .iter()
+                .flat_map(|node| {
+                    let node = node.borrow();
+                    [node.left.clone(), node.right.clone()]
+                })
+                .flatten()
+                .collect();
         }
+
+        height
     }
 
-    fn post_order_recursive<T: Clone>(root: &OptionNodeRc<T>, ordered: &mut Vec<T>) {
-        if let Some(node) = root {
-            post_order_recursive(&node.borrow().left, ordered);
-            post_order_recursive(&node.borrow().right, ordered);
-            ordered.push(node.borrow().value.clone());
+    /// 值为 val 的结点的深度，即从根结点到它的边数，根结点深度为 0；
+    /// 有多个这样的结点时返回最浅的，不存在时返回 None
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use base::{bt, tree};
+    ///
+    /// let tree = tree! {1, left: tree! {2, right: 4}, right: 4};
+    ///
+    /// assert_eq!(bt::depth_of(&tree.root, &1), Some(0));
+    /// assert_eq!(bt::depth_of(&tree.root, &4), Some(1));
+    /// assert_eq!(bt::depth_of(&tree.root, &5), None);
+    /// ```
+    pub fn depth_of<T: PartialEq>(root: &OptionNodeRc<T>, val: &T) -> Option<usize> {
+        let mut level = Vec::from_iter(root.clone());
+        let mut depth = 0;
+
+        while !level.is_empty() {
+            let mut next = Vec::new();
+            for node in &level {
+                let node = node.borrow();
+                if &node.value == val {
+                    return Some(depth);
+                }
+                next.extend(node.left.clone());
+                next.extend(node.right.clone());
+            }
+            level = next;
+            depth += 1;
+        }
+
+        None
+    }
+
+    /// 结点总数，时间复杂度：O(n)
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use base::{bt, tree};
+    ///
+    /// let tree = tree! {1, left: tree! {2, left: 4, right: 5}, right: 3};
+    ///
+    /// assert_eq!(bt::count_nodes(&tree.root), 5);
+    /// assert_eq!(bt::count_leaves(&tree.root), 3);
+    /// assert_eq!(bt::count_at_level(&tree.root, 1), 2);
+    /// assert_eq!(bt::count_at_level(&tree.root, 3), 0);
+    /// ```
+    pub fn count_nodes<T>(root: &OptionNodeRc<T>) -> usize {
+        count_matching(root, |_| true)
+    }
+
+    /// 叶结点（没有子结点的结点）的数量，时间复杂度：O(n)
+    pub fn count_leaves<T>(root: &OptionNodeRc<T>) -> usize {
+        count_matching(root, |node| node.left.is_none() && node.right.is_none())
+    }
+
+    /// 深度为 k 的结点数量，根结点深度为 0，时间复杂度：O(n)
+    pub fn count_at_level<T>(root: &OptionNodeRc<T>, k: usize) -> usize {
+        let mut level = Vec::from_iter(root.clone());
+
+        for _ in 0..k {
+            if level.is_empty() {
+                break;
+            }
+            level = level
+                .iter()
+                .flat_map(|node| {
+                    let node = node.borrow();
+                    [node.left.clone(), node.right.clone()]
+                })
+                .flatten()
+                .collect();
+        }
+
+        level.len()
+    }
+
+    /// 判断是否平衡：每个结点左右子树的高度差不超过 1，空树是平衡的
+    ///
+    /// 一次后序遍历自底向上求出子树高度，发现不平衡的结点时立即返回，时间复杂度：O(n)
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use base::{bt, tree};
+    ///
+    /// let balanced = tree! {1, left: tree! {2, left: 4}, right: 3};
+    /// let unbalanced = tree! {1, left: tree! {2, left: 4}};
+    ///
+    /// assert!(bt::is_balanced(&balanced.root));
+    /// assert!(!bt::is_balanced(&unbalanced.root));
+    /// ```
+    pub fn is_balanced<T>(root: &OptionNodeRc<T>) -> bool {
+        enum Step<T> {
+            Visit(OptionNodeRc<T>),
+            /// 左右子树都已处理完毕，它们的高度位于 heights 的栈顶
+            Combine,
+        }
+
+        let mut steps = vec![Step::Visit(root.clone())];
+        let mut heights = Vec::new();
+
+        while let Some(step) = steps.pop() {
+            match step {
+                Step::Visit(None) => heights.push(-1),
+                Step::Visit(Some(node)) => {
+                    let node = node.borrow();
+                    steps.push(Step::Combine);
+                    steps.push(Step::Visit(node.right.clone()));
+                    steps.push(Step::Visit(node.left.clone()));
+                }
+                Step::Combine => {
+                    // Safety: 每个 Combine 之前都恰好压入了左右子树的高度
+                    let right: i32 = heights.pop().unwrap();
+                    let left = heights.pop().unwrap();
+                    if left.abs_diff(right) > 1 {
+                        return false;
+                    }
+                    heights.push(left.max(right) + 1);
+                }
+            }
+        }
+
+        true
+    }
+
+    /// 判断是否为完全二叉树：除最后一层外都是满的，最后一层的结点都靠左，空树是完全二叉树
+    ///
+    /// 层序遍历时遇到空位之后不能再出现结点，时间复杂度：O(n)
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use base::{BinaryTree, bt, tree};
+    ///
+    /// assert!(bt::is_complete(&BinaryTree::from([1, 2, 3, 4]).root));
+    /// assert!(!bt::is_complete(&tree! {1, left: 2, right: tree! {3, left: 4}}.root));
+    /// ```
+    pub fn is_complete<T>(root: &OptionNodeRc<T>) -> bool {
+        let mut queue = VecDeque::from([root.clone()]);
+        let mut seen_gap = false;
+
+        while let Some(node) = queue.pop_front() {
+            match node {
+                None => seen_gap = true,
+                Some(_) if seen_gap => return false,
+                Some(node) => {
+                    let node = node.borrow();
+                    queue.push_back(node.left.clone());
+                    queue.push_back(node.right.clone());
+                }
+            }
+        }
+
+        true
+    }
+
+    /// 判断是否为满二叉树（full）：每个结点都有 0 个或 2 个子结点，空树是满二叉树
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use base::{bt, tree};
+    ///
+    /// assert!(bt::is_full(&tree! {1, left: 2, right: tree! {3, left: 4, right: 5}}.root));
+    /// assert!(!bt::is_full(&tree! {1, left: 2}.root));
+    /// ```
+    pub fn is_full<T>(root: &OptionNodeRc<T>) -> bool {
+        count_matching(root, |node| node.left.is_some() != node.right.is_some()) == 0
+    }
+
+    /// 判断是否为完美二叉树（perfect）：所有叶结点深度相同且其余结点都有 2 个子结点，
+    /// 即第 k 层恰好有 2^k 个结点，空树是完美二叉树
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use base::{BinaryTree, bt};
+    ///
+    /// assert!(bt::is_perfect(&BinaryTree::from([1, 2, 3]).root));
+    /// assert!(!bt::is_perfect(&BinaryTree::from([1, 2, 3, 4]).root));
+    /// ```
+    pub fn is_perfect<T>(root: &OptionNodeRc<T>) -> bool {
+        let mut level = Vec::from_iter(root.clone());
+        let mut expected = 1;
+
+        while !level.is_empty() {
+            if level.len() != expected {
+                return false;
+            }
+            level = level
+                .iter()
+                .flat_map(|node| {
+                    let node = node.borrow();
+                    [node.left.clone(), node.right.clone()]
+                })
+                .flatten()
+                .collect();
+            expected *= 2;
+        }
+
+        true
+    }
+
+    /// 满足条件的结点数量，用栈代替递归
+    fn count_matching<T>(root: &OptionNodeRc<T>, matches: impl Fn(&TreeNode<T>) -> bool) -> usize {
+        let mut stack = Vec::from_iter(root.clone());
+        let mut count = 0;
+
+        while let Some(node) = stack.pop() {
+            let node = node.borrow();
+            count += matches(&node) as usize;
+            stack.extend(node.left.clone());
+            stack.extend(node.right.clone());
+        }
+
+        count
+    }
+
+    /// 前序遍历，通过蹦床执行，树很深（如一百万个结点的链）时也不会栈溢出
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use base::{BinaryTree, bt};
+    ///
+    /// let tree = BinaryTree::from([1, 2, 3, 4]);
+    ///
+    /// assert_eq!(bt::pre_order_trampolined(&tree.root), bt::pre_order(&tree.root));
+    /// ```
+    pub fn pre_order_trampolined<T: Clone + 'static>(root: &OptionNodeRc<T>) -> Vec<T> {
+        traverse_trampolined(root.clone(), Order::Pre, Vec::new(), Box::new(Bounce::Done)).run()
+    }
+
+    /// 中序遍历，通过蹦床执行，见 [`pre_order_trampolined`]
+    pub fn in_order_trampolined<T: Clone + 'static>(root: &OptionNodeRc<T>) -> Vec<T> {
+        traverse_trampolined(root.clone(), Order::In, Vec::new(), Box::new(Bounce::Done)).run()
+    }
+
+    /// 后序遍历，通过蹦床执行，见 [`pre_order_trampolined`]
+    pub fn post_order_trampolined<T: Clone + 'static>(root: &OptionNodeRc<T>) -> Vec<T> {
+        traverse_trampolined(
+            root.clone(),
+            Order::Post,
+            Vec::new(),
+            Box::new(Bounce::Done),
+        )
+        .run()
+    }
+
+    /// 以目录树的形式渲染二叉树，每个结点先列出左子结点，再列出右子结点，缺失的子结点显示为 ∅
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use base::{BinaryTree, bt};
+    ///
+    /// let tree = BinaryTree::from([1, 2, 3, 4]);
+    ///
+    /// assert_eq!(
+    ///     bt::render(&tree.root),
+    ///     "1\n├── 2\n│   ├── 4\n│   └── ∅\n└── 3\n"
+    /// );
+    /// ```
+    pub fn render<T: Display>(root: &OptionNodeRc<T>) -> String {
+        render_depth(root, usize::MAX)
+    }
+
+    /// 与 [`render`] 相同，但只渲染深度不超过 max_depth 的结点（根结点深度为 0），
+    /// 更深的子树折叠为一行 …，适合查看很大或很深的树
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use base::{BinaryTree, bt};
+    ///
+    /// let tree = BinaryTree::from([1, 2, 3, 4]);
+    ///
+    /// assert_eq!(bt::render_depth(&tree.root, 1), "1\n├── 2\n│   └── …\n└── 3\n");
+    /// assert_eq!(bt::render_depth(&tree.root, 0), "1\n└── …\n");
+    /// ```
+    pub fn render_depth<T: Display>(root: &OptionNodeRc<T>, max_depth: usize) -> String {
+        let mut out = String::new();
+        let Some(root) = root else {
+            return out;
+        };
+
+        out.push_str(&format!("{}\n", root.borrow().value));
+        // 用栈代替递归，很深的树也不会栈溢出，元素为 (子结点, 前缀, 是否为右子结点, 子结点的深度)
+        let mut stack = Vec::new();
+        expand_children(root, "", 0, max_depth, &mut stack, &mut out);
+
+        while let Some((child, prefix, last, depth)) = stack.pop() {
+            let (branch, indent) = if last {
+                ("└── ", "    ")
+            } else {
+                ("├── ", "│   ")
+            };
+            match child {
+                Some(child) => {
+                    out.push_str(&format!("{prefix}{branch}{}\n", child.borrow().value));
+                    let prefix = format!("{prefix}{indent}");
+                    expand_children(&child, &prefix, depth, max_depth, &mut stack, &mut out);
+                }
+                None => out.push_str(&format!("{prefix}{branch}∅\n")),
+            }
+        }
+
+        out
+    }
+
+    /// 待渲染的子结点：(子结点, 前缀, 是否为右子结点, 子结点的深度)
+    type PendingChild<T> = (OptionNodeRc<T>, String, bool, usize);
+
+    /// 把结点的子结点压栈，先压右子结点，使左子结点先输出；结点已达到最大深度时输出折叠标记
+    fn expand_children<T>(
+        node: &NodeRc<T>,
+        prefix: &str,
+        depth: usize,
+        max_depth: usize,
+        stack: &mut Vec<PendingChild<T>>,
+        out: &mut String,
+    ) {
+        let node = node.borrow();
+        if node.left.is_none() && node.right.is_none() {
+            return;
+        }
+        if depth >= max_depth {
+            out.push_str(&format!("{prefix}└── …\n"));
+            return;
+        }
+
+        stack.push((node.right.clone(), prefix.to_string(), true, depth + 1));
+        stack.push((node.left.clone(), prefix.to_string(), false, depth + 1));
+    }
+
+    #[derive(Clone, Copy)]
+    enum Order {
+        Pre,
+        In,
+        Post,
+    }
+
+    /// 遍历完一棵子树后要做的事，参数为目前的遍历结果
+    type Continuation<T> = Box<dyn FnOnce(Vec<T>) -> Bounce<Vec<T>>>;
+
+    /// 续延传递风格的遍历：遍历以 node 为根的子树，把结果追加到 ordered 后交给 k
+    ///
+    /// 每次调用都立即返回，调用 k 时也包装为 [`Bounce::Call`]，因此调用栈不会随树高增长
+    fn traverse_trampolined<T: Clone + 'static>(
+        node: OptionNodeRc<T>,
+        order: Order,
+        mut ordered: Vec<T>,
+        k: Continuation<T>,
+    ) -> Bounce<Vec<T>> {
+        let Some(node) = node else {
+            return Bounce::call(move || k(ordered));
+        };
+        let (value, left, right) = {
+            let node = node.borrow();
+            (node.value.clone(), node.left.clone(), node.right.clone())
+        };
+
+        match order {
+            Order::Pre => {
+                ordered.push(value);
+                Bounce::call(move || {
+                    traverse_trampolined(
+                        left,
+                        order,
+                        ordered,
+                        Box::new(move |ordered| traverse_trampolined(right, order, ordered, k)),
+                    )
+                })
+            }
+            Order::In => Bounce::call(move || {
+                traverse_trampolined(
+                    left,
+                    order,
+                    ordered,
+                    Box::new(move |mut ordered| {
+                        ordered.push(value);
+                        traverse_trampolined(right, order, ordered, k)
+                    }),
+                )
+            }),
+            Order::Post => Bounce::call(move || {
+                traverse_trampolined(
+                    left,
+                    order,
+                    ordered,
+                    Box::new(move |ordered| {
+                        traverse_trampolined(
+                            right,
+                            order,
+                            ordered,
+                            Box::new(move |mut ordered| {
+                                ordered.push(value);
+                                Bounce::call(move || k(ordered))
+                            }),
+                        )
+                    }),
+                )
+            }),
         }
     }
 }
 
 #[cfg(test)]
-mod tests {}
+mod tests {
+    use crate::{
+        BinarySearchTree, BinaryTree,
+        binary_tree::{NodeRc, OptionNodeRc, TreeNode},
+        bt, generate, tree,
+    };
+
+    /// 深度为 n 的链，结点交替地作为左子结点和右子结点
+    fn zigzag(n: u64) -> BinaryTree<u64> {
+        let mut root: OptionNodeRc<u64> = None;
+        for val in (0..n).rev() {
+            let node = TreeNode::new_node_rc(val);
+            if val % 2 == 0 {
+                node.borrow_mut().left = root.take();
+            } else {
+                node.borrow_mut().right = root.take();
+            }
+            root = Some(node);
+        }
+
+        BinaryTree { root }
+    }
+
+    #[test]
+    fn trampolined_traversals_should_match_recursive() {
+        let mut tree = BinaryTree::new();
+        for val in generate::uniform(200, 9) {
+            tree.insert(val);
+        }
+        let sparse = zigzag(100);
+
+        for tree in [&tree, &sparse, &BinaryTree::new()] {
+            assert_eq!(
+                bt::pre_order_trampolined(&tree.root),
+                bt::pre_order(&tree.root)
+            );
+            assert_eq!(
+                bt::in_order_trampolined(&tree.root),
+                bt::in_order(&tree.root)
+            );
+            assert_eq!(
+                bt::post_order_trampolined(&tree.root),
+                bt::post_order(&tree.root)
+            );
+        }
+    }
+
+    /// 用迭代器遍历并取出值
+    fn values<I: Iterator<Item = NodeRc<u64>>>(iter: I) -> Vec<u64> {
+        iter.map(|node| node.borrow().value).collect()
+    }
+
+    #[test]
+    fn traversal_iters_should_match_recursive() {
+        for len in [0, 1, 2, 7, 100] {
+            let mut tree = BinaryTree::new();
+            for val in generate::uniform(len, len as u64) {
+                tree.insert(val);
+            }
+
+            let pre = values(bt::PreOrderIter::new(&tree.root));
+            assert_eq!(pre, bt::pre_order(&tree.root));
+            let in_ = values(bt::InOrderIter::new(&tree.root));
+            assert_eq!(in_, bt::in_order(&tree.root));
+            let post = values(bt::PostOrderIter::new(&tree.root));
+            assert_eq!(post, bt::post_order(&tree.root));
+        }
+
+        // 不要求 T: Clone
+        struct NoClone(u8);
+        let tree = tree! {NoClone(1), left: NoClone(2), right: NoClone(3)};
+        let sum = bt::PostOrderIter::new(&tree.root).map(|node| node.borrow().value.0);
+        assert_eq!(sum.sum::<u8>(), 6);
+    }
+
+    #[test]
+    fn traversal_iters_should_follow_shape() {
+        //       1
+        //      / \
+        //     2   3
+        //      \   \
+        //       4   5
+        //      /
+        //     6
+        let tree =
+            tree! {1, left: tree! {2, right: tree! {4, left: 6}}, right: tree! {3, right: 5}};
+
+        assert_eq!(
+            values(bt::PreOrderIter::new(&tree.root)),
+            [1, 2, 4, 6, 3, 5]
+        );
+        assert_eq!(values(bt::InOrderIter::new(&tree.root)), [2, 6, 4, 1, 3, 5]);
+        assert_eq!(
+            values(bt::PostOrderIter::new(&tree.root)),
+            [6, 4, 2, 5, 3, 1]
+        );
+
+        // 惰性遍历，可以提前停止
+        let mut iter = bt::InOrderIter::new(&tree.root);
+        assert_eq!(iter.nth(2).map(|node| node.borrow().value), Some(4));
+
+        // 只有单侧子结点的链
+        let tree = zigzag(5);
+        assert_eq!(values(bt::PreOrderIter::new(&tree.root)), [0, 1, 2, 3, 4]);
+        assert_eq!(values(bt::InOrderIter::new(&tree.root)), [1, 3, 4, 2, 0]);
+        assert_eq!(values(bt::PostOrderIter::new(&tree.root)), [4, 3, 2, 1, 0]);
+    }
+
+    #[test]
+    fn level_order_should_group_by_depth() {
+        let tree = BinaryTree::from(std::array::from_fn::<_, 10, _>(|i| i));
+        assert!(bt::level_order(&tree.root).into_iter().eq(0..10));
+        assert_eq!(
+            bt::level_order_groups(&tree.root),
+            [vec![0], vec![1, 2], vec![3, 4, 5, 6], vec![7, 8, 9]]
+        );
+
+        let tree = zigzag(4);
+        assert_eq!(bt::level_order(&tree.root), [0, 1, 2, 3]);
+        assert_eq!(bt::level_order_groups(&tree.root).len(), 4);
+
+        let empty = BinaryTree::<i32>::new();
+        assert!(bt::level_order(&empty.root).is_empty());
+        assert!(bt::level_order_groups(&empty.root).is_empty());
+    }
+
+    #[test]
+    fn height_and_depth_should_match_levels() {
+        let tree = BinaryTree::from(std::array::from_fn::<_, 10, _>(|i| i));
+        assert_eq!(tree.height(), 3);
+        for (depth, group) in bt::level_order_groups(&tree.root).iter().enumerate() {
+            for val in group {
+                assert_eq!(bt::depth_of(&tree.root, val), Some(depth));
+            }
+        }
+        assert_eq!(bt::depth_of(&tree.root, &10), None);
+
+        // 退化成链的树
+        let tree = zigzag(5);
+        assert_eq!(tree.height(), 4);
+        assert_eq!(bt::depth_of(&tree.root, &4), Some(4));
+
+        // 左右子树高度不同，重复的值取最浅的
+        let tree = tree! {1, left: tree! {2, right: tree! {4, left: 5}}, right: 5};
+        assert_eq!(tree.height(), 3);
+        assert_eq!(bt::height(&tree.root.as_ref().unwrap().borrow().right), 0);
+        assert_eq!(bt::depth_of(&tree.root, &4), Some(2));
+        assert_eq!(bt::depth_of(&tree.root, &5), Some(1));
+
+        assert_eq!(BinaryTree::<i32>::new().height(), -1);
+        assert_eq!(bt::depth_of(&None, &0), None);
+    }
+
+    #[test]
+    fn insert_should_keep_complete_shape() {
+        let mut tree = BinaryTree::new();
+        for n in 0..64 {
+            let root = &tree.root;
+            assert!(bt::is_complete(root) && bt::is_balanced(root));
+            // 完全二叉树中，结点数为奇数时每个内部结点都有两个子结点
+            assert_eq!(bt::is_full(root), n % 2 == 1 || n == 0);
+            assert_eq!(bt::is_perfect(root), (n + 1u32).is_power_of_two());
+            tree.insert(n);
+        }
+    }
+
+    #[test]
+    fn shape_predicates_should_reject_irregular_trees() {
+        // 两个结点都只有左子结点：平衡但不完全
+        let tree = tree! {1, left: tree! {2, left: 4}, right: tree! {3, left: 5}};
+        assert!(bt::is_balanced(&tree.root) && !bt::is_full(&tree.root));
+        assert!(!bt::is_complete(&tree.root) && !bt::is_perfect(&tree.root));
+
+        // 满但不平衡
+        let tree =
+            tree! {1, left: tree! {2, left: tree! {4, left: 6, right: 7}, right: 5}, right: 3};
+        assert!(bt::is_full(&tree.root) && !bt::is_balanced(&tree.root));
+        assert!(!bt::is_complete(&tree.root) && !bt::is_perfect(&tree.root));
+
+        // 退化成链
+        let tree = zigzag(3);
+        assert!(!bt::is_balanced(&tree.root) && !bt::is_complete(&tree.root));
+        assert!(!bt::is_full(&tree.root) && !bt::is_perfect(&tree.root));
+
+        // 完全但不满：最后一个内部结点只有左子结点
+        let tree = tree! {1, left: tree! {2, left: 4}, right: 3};
+        assert!(bt::is_complete(&tree.root) && bt::is_balanced(&tree.root));
+        assert!(!bt::is_full(&tree.root) && !bt::is_perfect(&tree.root));
+
+        // 最后一层不靠左
+        let tree = tree! {1, left: tree! {2, right: 5}, right: tree! {3, left: 6, right: 7}};
+        assert!(!bt::is_complete(&tree.root) && bt::is_balanced(&tree.root));
+    }
+
+    #[test]
+    fn build_from_traversals_should_round_trip() {
+        for len in [0, 1, 2, 10, 200] {
+            let mut bst = BinarySearchTree::new();
+            for val in generate::insertion_order(len, len as u64) {
+                bst.insert(val);
+            }
+            let bst = bst.to_tree();
+            let (pre, inorder) = (bt::pre_order(&bst.root), bt::in_order(&bst.root));
+            let post = bt::post_order(&bst.root);
+
+            let root = bt::build_from_pre_in(&pre, &inorder);
+            assert_eq!(bt::level_order(&root), bt::level_order(&bst.root));
+            let root = bt::build_from_in_post(&inorder, &post);
+            assert_eq!(bt::level_order(&root), bt::level_order(&bst.root));
+        }
+
+        // 只有单侧子结点的链，以及左右交错的树
+        let shapes = [
+            zigzag(6),
+            tree! {1, left: tree! {2, right: tree! {4, left: 6}}, right: tree! {3, right: 5}},
+        ];
+        for tree in shapes {
+            let (pre, inorder) = (bt::pre_order(&tree.root), bt::in_order(&tree.root));
+            let post = bt::post_order(&tree.root);
+
+            for root in [
+                bt::build_from_pre_in(&pre, &inorder),
+                bt::build_from_in_post(&inorder, &post),
+            ] {
+                assert_eq!(bt::render(&root), bt::render(&tree.root));
+            }
+        }
+    }
+
+    #[test]
+    fn counts_should_match_traversals() {
+        let tree = BinaryTree::from(std::array::from_fn::<_, 10, _>(|i| i));
+        assert_eq!(tree.count_nodes(), 10);
+        // 完全二叉树中，下标 n / 2 及之后的结点都是叶结点
+        assert_eq!(tree.count_leaves(), 5);
+        let groups = bt::level_order_groups(&tree.root);
+        for (k, group) in groups.iter().enumerate() {
+            assert_eq!(tree.count_at_level(k), group.len());
+        }
+        assert_eq!(tree.count_at_level(groups.len()), 0);
+
+        let tree = zigzag(5);
+        assert_eq!(tree.count_nodes(), 5);
+        assert_eq!(tree.count_leaves(), 1);
+        assert_eq!(tree.count_at_level(4), 1);
+        assert_eq!(tree.count_at_level(5), 0);
+
+        // 叶结点位于不同的层
+        let tree = tree! {1, left: tree! {2, left: 4, right: tree! {5, right: 7}}, right: 3};
+        assert_eq!(tree.count_nodes(), 6);
+        assert_eq!(tree.count_leaves(), 3);
+        assert_eq!(tree.count_at_level(2), 2);
+        assert_eq!(tree.count_at_level(3), 1);
+
+        let empty = BinaryTree::<i32>::new();
+        assert_eq!(empty.count_nodes(), 0);
+        assert_eq!(empty.count_leaves(), 0);
+        assert_eq!(empty.count_at_level(0), 0);
+    }
+
+    #[test]
+    fn render_depth_should_fold_deep_subtrees() {
+        let tree = BinaryTree::from([1, 2, 3, 4, 5, 6, 7]);
+        assert_eq!(bt::render_depth(&tree.root, 2), bt::render(&tree.root));
+        assert_eq!(bt::render_depth(&BinaryTree::<i32>::new().root, 3), "");
+
+        // 只渲染前几层，更深的链折叠为 …
+        let tree = zigzag(10);
+        assert_eq!(
+            bt::render_depth(&tree.root, 2),
+            "0\n├── 1\n│   ├── ∅\n│   └── 2\n│       └── …\n└── ∅\n"
+        );
+    }
+
+    #[test]
+    fn deep_trees_should_be_stack_safe() {
+        // 一百万个结点的链：遍历、复制和释放都不能递归
+        let n = 1_000_000;
+        let tree = zigzag(n);
+
+        assert!(bt::pre_order_trampolined(&tree.root).into_iter().eq(0..n));
+        assert_eq!(
+            bt::in_order_trampolined(&tree.root),
+            bt::in_order(&tree.root)
+        );
+        assert!(
+            bt::post_order_trampolined(&tree.root)
+                .into_iter()
+                .eq((0..n).rev())
+        );
+        assert!(bt::pre_order(&tree.root).into_iter().eq(0..n));
+        assert!(bt::post_order(&tree.root).into_iter().eq((0..n).rev()));
+
+        // 与 tree 共享结点的树释放时只拆除自己独占的结点
+        let search_tree = BinarySearchTree {
+            root: tree.root.clone(),
+        };
+        let copy = search_tree.to_tree();
+        drop(search_tree);
+        assert_eq!(tree.count_nodes(), n as usize);
+
+        drop(tree);
+        assert_eq!(copy.height(), n as i32 - 1);
+    }
+}
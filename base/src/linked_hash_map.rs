@@ -0,0 +1,392 @@
+//! 链式哈希表（linked hash map）：按插入顺序遍历的哈希表
+//!
+//! 键值对保存在双向链表 [`LinkedList`] 中，哈希表把键映射到链表结点的句柄 [`NodeHandle`]，
+//! 查找、插入、删除都是 O(1)，遍历时沿链表按插入顺序输出。
+//!
+//! 借助结点句柄可以在 O(1) 时间内把任意键值对移到链表尾部（[`LinkedHashMap::move_to_back`]），
+//! 再从头部淘汰最久未访问的键值对（[`LinkedHashMap::pop_front`]），这正是 LRU 缓存的做法。
+//! [`LinkedHashSet`] 只保存键，可以在保持首次出现顺序的同时去重。
+//!
+//! 键同时保存在哈希表和链表中，因此要求 `K: Clone`。
+
+use std::{borrow::Borrow, hash::Hash};
+
+use crate::{ChainedHashMap, LinkedList, NodeHandle};
+
+/// 按插入顺序遍历的哈希表
+///
+/// # Examples
+///
+/// ```rust
+/// use base::LinkedHashMap;
+///
+/// let mut map = LinkedHashMap::new();
+/// map.insert("a", 1);
+/// map.insert("b", 2);
+/// map.insert("c", 3);
+///
+/// // 替换值不改变位置，move_to_back 把键值对移到最后
+/// map.insert("a", 10);
+/// map.move_to_back(&"b");
+/// assert_eq!(map.iter().collect::<Vec<_>>(), [(&"a", &10), (&"c", &3), (&"b", &2)]);
+///
+/// assert_eq!(map.pop_front(), Some(("a", 10)));
+/// assert_eq!(map.remove(&"c"), Some(3));
+/// assert_eq!(map.len(), 1);
+/// ```
+#[derive(Debug)]
+pub struct LinkedHashMap<K, V> {
+    index: ChainedHashMap<K, NodeHandle<(K, V)>>,
+    entries: LinkedList<(K, V)>,
+}
+
+impl<K, V> LinkedHashMap<K, V> {
+    /// 创建空的哈希表
+    pub fn new() -> Self {
+        Self {
+            index: ChainedHashMap::new(),
+            entries: LinkedList::new(),
+        }
+    }
+
+    /// 键值对数量
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// 判断是否为空
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// 按插入顺序遍历所有键值对
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> {
+        self.entries.iter().map(|(key, value)| (key, value))
+    }
+
+    /// 最早插入的键值对
+    pub fn front(&self) -> Option<(&K, &V)> {
+        self.entries.front().map(|(key, value)| (key, value))
+    }
+
+    /// 最后插入的键值对
+    pub fn back(&self) -> Option<(&K, &V)> {
+        self.entries.back().map(|(key, value)| (key, value))
+    }
+}
+
+impl<K: Hash + Eq + Clone, V> LinkedHashMap<K, V> {
+    /// 获取键对应的值
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let &handle = self.index.get(key)?;
+
+        self.entries.value(handle).map(|(_, value)| value)
+    }
+
+    /// 获取键对应值的可变借用
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let &handle = self.index.get(key)?;
+
+        self.entries.value_mut(handle).map(|(_, value)| value)
+    }
+
+    /// 判断是否包含指定的键
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.index.contains_key(key)
+    }
+
+    /// 插入键值对，键已存在时替换其值并返回旧值，位置不变；否则放到最后
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        if let Some(old) = self.get_mut(&key) {
+            return Some(std::mem::replace(old, value));
+        }
+
+        let handle = self.entries.push_back_node((key.clone(), value));
+        self.index.insert(key, handle);
+
+        None
+    }
+
+    /// 删除键对应的键值对，返回被删除的值
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let handle = self.index.remove(key)?;
+
+        self.entries.remove_node(handle).map(|(_, value)| value)
+    }
+
+    /// 把键值对移到最后，键不存在时返回 false，时间复杂度：O(1)
+    pub fn move_to_back<Q>(&mut self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let Some(handle) = self.index.get_mut(key) else {
+            return false;
+        };
+
+        // Safety: 哈希表中的句柄一定有效
+        let entry = self.entries.remove_node(*handle).unwrap();
+        *handle = self.entries.push_back_node(entry);
+
+        true
+    }
+
+    /// 删除并返回最早插入的键值对
+    pub fn pop_front(&mut self) -> Option<(K, V)> {
+        let (key, value) = self.entries.pop_front()?;
+        self.index.remove(&key);
+
+        Some((key, value))
+    }
+
+    /// 删除并返回最后插入的键值对
+    pub fn pop_back(&mut self) -> Option<(K, V)> {
+        let (key, value) = self.entries.pop_back()?;
+        self.index.remove(&key);
+
+        Some((key, value))
+    }
+}
+
+impl<K, V> Default for LinkedHashMap<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Hash + Eq + Clone, V: Clone> Clone for LinkedHashMap<K, V> {
+    fn clone(&self) -> Self {
+        // 句柄只属于原链表，需要重新建立索引
+        self.iter()
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect()
+    }
+}
+
+impl<K: Hash + Eq + Clone, V> FromIterator<(K, V)> for LinkedHashMap<K, V> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut map = LinkedHashMap::new();
+
+        iter.into_iter().for_each(|(key, value)| {
+            map.insert(key, value);
+        });
+
+        map
+    }
+}
+
+/// 按插入顺序遍历的哈希集合
+///
+/// # Examples
+///
+/// 去重并保持首次出现的顺序：
+///
+/// ```rust
+/// use base::LinkedHashSet;
+///
+/// let words = "the quick fox and the lazy dog and the cat".split(' ');
+/// let set = words.collect::<LinkedHashSet<_>>();
+///
+/// assert_eq!(
+///     set.iter().collect::<Vec<_>>(),
+///     [&"the", &"quick", &"fox", &"and", &"lazy", &"dog", &"cat"]
+/// );
+/// ```
+#[derive(Debug)]
+pub struct LinkedHashSet<T> {
+    map: LinkedHashMap<T, ()>,
+}
+
+impl<T> LinkedHashSet<T> {
+    /// 创建空的集合
+    pub fn new() -> Self {
+        Self {
+            map: LinkedHashMap::new(),
+        }
+    }
+
+    /// 元素数量
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// 判断是否为空
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// 按插入顺序遍历所有元素
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.map.iter().map(|(value, _)| value)
+    }
+
+    /// 最早插入的元素
+    pub fn front(&self) -> Option<&T> {
+        self.map.front().map(|(value, _)| value)
+    }
+
+    /// 最后插入的元素
+    pub fn back(&self) -> Option<&T> {
+        self.map.back().map(|(value, _)| value)
+    }
+}
+
+impl<T: Hash + Eq + Clone> LinkedHashSet<T> {
+    /// 判断是否包含指定元素
+    pub fn contains<Q>(&self, value: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.map.contains_key(value)
+    }
+
+    /// 插入元素，元素已存在时返回 false，位置不变
+    pub fn insert(&mut self, value: T) -> bool {
+        self.map.insert(value, ()).is_none()
+    }
+
+    /// 删除元素，元素不存在时返回 false
+    pub fn remove<Q>(&mut self, value: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.map.remove(value).is_some()
+    }
+
+    /// 把元素移到最后，元素不存在时返回 false
+    pub fn move_to_back<Q>(&mut self, value: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.map.move_to_back(value)
+    }
+
+    /// 删除并返回最早插入的元素
+    pub fn pop_front(&mut self) -> Option<T> {
+        self.map.pop_front().map(|(value, _)| value)
+    }
+
+    /// 删除并返回最后插入的元素
+    pub fn pop_back(&mut self) -> Option<T> {
+        self.map.pop_back().map(|(value, _)| value)
+    }
+}
+
+impl<T: Hash + Eq + Clone> Clone for LinkedHashSet<T> {
+    fn clone(&self) -> Self {
+        Self {
+            map: self.map.clone(),
+        }
+    }
+}
+
+impl<T> Default for LinkedHashSet<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Hash + Eq + Clone> FromIterator<T> for LinkedHashSet<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut set = LinkedHashSet::new();
+
+        iter.into_iter().for_each(|value| {
+            set.insert(value);
+        });
+
+        set
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generate::{self, Operation};
+
+    #[test]
+    fn linked_hash_map_should_match_brute_force() {
+        let mut map = LinkedHashMap::new();
+        // 按链表顺序排列的键值对
+        let mut expected: Vec<(u64, usize)> = Vec::new();
+
+        for (i, op) in generate::operations(5000, 50, 6).into_iter().enumerate() {
+            let (Operation::Insert(key) | Operation::Remove(key) | Operation::Get(key)) = op;
+            let pos = expected.iter().position(|&(k, _)| k == key);
+            match op {
+                Operation::Insert(key) => match pos {
+                    Some(pos) => {
+                        assert_eq!(map.insert(key, i), Some(expected[pos].1));
+                        expected[pos].1 = i;
+                    }
+                    None => {
+                        assert_eq!(map.insert(key, i), None);
+                        expected.push((key, i));
+                    }
+                },
+                Operation::Remove(key) => {
+                    let removed = pos.map(|pos| expected.remove(pos).1);
+                    assert_eq!(map.remove(&key), removed);
+                }
+                // 读取时顺便移到最后，模拟 LRU 的访问
+                Operation::Get(key) => {
+                    assert_eq!(map.get(&key), pos.map(|pos| &expected[pos].1));
+                    assert_eq!(map.move_to_back(&key), pos.is_some());
+                    if let Some(pos) = pos {
+                        let entry = expected.remove(pos);
+                        expected.push(entry);
+                    }
+                }
+            }
+
+            assert_eq!(map.len(), expected.len());
+            assert!(
+                map.iter()
+                    .map(|(&k, &v)| (k, v))
+                    .eq(expected.iter().copied())
+            );
+        }
+
+        let cloned = map.clone();
+        while let Some(entry) = map.pop_front() {
+            assert_eq!(entry, expected.remove(0));
+        }
+        assert!(map.is_empty());
+        assert_eq!(cloned.len(), cloned.iter().count());
+        assert!(cloned.iter().all(|(k, v)| cloned.get(k) == Some(v)));
+    }
+
+    #[test]
+    fn linked_hash_set_should_work() {
+        let mut set = LinkedHashSet::from_iter([3, 1, 4, 1, 5, 9, 2, 6, 5, 3]);
+        assert!(set.iter().eq(&[3, 1, 4, 5, 9, 2, 6]));
+
+        assert!(!set.insert(4));
+        assert!(set.move_to_back(&3));
+        assert!(set.remove(&9));
+        assert!(!set.remove(&9));
+        assert_eq!((set.front(), set.back()), (Some(&1), Some(&3)));
+        assert_eq!(set.pop_front(), Some(1));
+        assert_eq!(set.pop_back(), Some(3));
+        assert!(set.iter().eq(&[4, 5, 2, 6]));
+        assert!(set.contains(&2) && !set.contains(&3));
+    }
+}
@@ -1,15 +1,31 @@
+mod snapshot;
+
+pub use snapshot::ListSnapshot;
+
 use std::{
+    collections::{self, VecDeque},
     fmt::{self, Display},
     hash::{Hash, Hasher},
     marker::PhantomData,
-    mem,
+    mem::{self, MaybeUninit},
+    ops::{Index, IndexMut},
     ptr::NonNull,
+    sync::atomic::{AtomicU64, Ordering},
 };
 
+/// 结点代数和链表编号共用的全局计数器，保证它们互不相同
+static NEXT_GENERATION: AtomicU64 = AtomicU64::new(1);
+
+/// 已删除结点的代数，不会与任何句柄的代数相同
+const RETIRED: u64 = u64::MAX;
+
 #[derive(Debug)]
 struct Node<T> {
     prev: Option<NonNull<Node<T>>>,
     next: Option<NonNull<Node<T>>>,
+    /// 第一次为结点创建句柄或复用 retired 中的内存时分配，0 表示没有句柄可能指向这块内存，
+    /// 结点被删除后为 RETIRED
+    generation: u64,
     elem: T,
 }
 
@@ -18,25 +34,25 @@ impl<T: Default> Default for Node<T> {
         Self {
             prev: None,
             next: None,
+            generation: 0,
             elem: T::default(),
         }
     }
 }
 
-impl<T: Display> Display for Node<T> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self.next {
-            Some(node) => write!(f, "{}, {}", self.elem, unsafe { node.as_ref() }),
-            None => write!(f, "{}", self.elem),
-        }
-    }
-}
-
 /// 简易链表实现
 pub struct LinkedList<T> {
     head: Option<NonNull<Node<T>>>,
     tail: Option<NonNull<Node<T>>>,
     len: usize,
+    /// 句柄所属的链表编号，0 表示当前没有有效的句柄
+    id: u64,
+    /// 创建过句柄、已被删除的结点，保留内存使句柄检查代数时不会访问已释放的内存
+    retired: Vec<NonNull<Node<T>>>,
+    /// 空闲结点池，弹出的结点放回这里供之后插入时复用
+    pool: Vec<Box<MaybeUninit<Node<T>>>>,
+    /// 结点池最多保留的空闲结点数量，0 表示不使用结点池
+    pool_capacity: usize,
 }
 
 impl<T> LinkedList<T> {
@@ -46,9 +62,54 @@ impl<T> LinkedList<T> {
             head: None,
             tail: None,
             len: 0,
+            id: 0,
+            retired: Vec::new(),
+            pool: Vec::new(),
+            pool_capacity: 0,
         }
     }
 
+    /// 创建空链表，并预先分配 capacity 个空闲结点放入结点池
+    ///
+    /// 删除元素时结点的内存不立即释放，而是放回结点池（最多保留 capacity 个），之后插入元素时优先复用，
+    /// 频繁插入、删除时可以省去大部分内存分配。[`LinkedList::new`] 创建的链表不使用结点池。
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use base::LinkedList;
+    ///
+    /// let mut list = LinkedList::with_pool_capacity(2);
+    /// assert_eq!(list.pool_len(), 2);
+    ///
+    /// list.extend([1, 2, 3]);
+    /// assert_eq!(list.pool_len(), 0);
+    ///
+    /// // 结点池已满时多出的结点直接释放
+    /// list.clear();
+    /// assert_eq!(list.pool_len(), 2);
+    ///
+    /// list.shrink_pool();
+    /// assert_eq!(list.pool_len(), 0);
+    /// ```
+    pub fn with_pool_capacity(capacity: usize) -> Self {
+        let mut list = Self::new();
+
+        list.pool = (0..capacity).map(|_| Box::new_uninit()).collect();
+        list.pool_capacity = capacity;
+        list
+    }
+
+    /// 结点池中空闲结点的数量
+    pub fn pool_len(&self) -> usize {
+        self.pool.len()
+    }
+
+    /// 释放结点池中的全部空闲结点，之后删除的结点仍会放回结点池
+    pub fn shrink_pool(&mut self) {
+        self.pool = Vec::new();
+    }
+
     /// 向链表头部插入一个元素
     pub fn push_front(&mut self, elt: T) {
         self.push(elt, true);
@@ -93,11 +154,22 @@ impl<T> LinkedList<T> {
             .map(|node_ptr| unsafe { &mut (*node_ptr.as_ptr()).elem })
     }
 
-    /// 在指定位置插入一个元素
+    /// 在指定位置插入一个元素，从离 at 较近的一端开始查找，时间复杂度：O(min(at, len - at))
     ///
     /// # Panics
     ///
     /// Panics if `at > len`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use base::LinkedList;
+    ///
+    /// let mut list = LinkedList::from([1, 2, 4]);
+    /// list.insert(2, 3);
+    ///
+    /// assert_eq!(list, LinkedList::from([1, 2, 3, 4]));
+    /// ```
     pub fn insert(&mut self, at: usize, elt: T) {
         let len = self.len();
 
@@ -110,20 +182,39 @@ impl<T> LinkedList<T> {
             return;
         }
 
-        // 从 at 位置将链表分开
-        let mut rhs = self.split_off(at);
-
-        // 在分割后的新链表头部插入指定元素
-        rhs.push_front(elt);
-        // 两个链表重新合并
-        self.append(&mut rhs);
+        // Safety: 0 < at < len，at 处的结点及其前驱一定存在
+        let next_ptr = self.get_node(at).unwrap();
+        unsafe {
+            let prev_ptr = (*next_ptr.as_ptr()).prev.unwrap();
+            let node_ptr = self.alloc_node(Node {
+                prev: Some(prev_ptr),
+                next: Some(next_ptr),
+                generation: 0,
+                elem: elt,
+            });
+
+            (*prev_ptr.as_ptr()).next = Some(node_ptr);
+            (*next_ptr.as_ptr()).prev = Some(node_ptr);
+        }
+        self.len += 1;
     }
 
-    /// 删除指定位置的元素，并将其返回
+    /// 删除指定位置的元素，并将其返回，从离 at 较近的一端开始查找，时间复杂度：O(min(at, len - at))
     ///
     /// # Panics
     ///
     /// Panics if `at >= len`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use base::LinkedList;
+    ///
+    /// let mut list = LinkedList::from([1, 2, 3, 4]);
+    ///
+    /// assert_eq!(list.remove(2), Some(3));
+    /// assert_eq!(list, LinkedList::from([1, 2, 4]));
+    /// ```
     pub fn remove(&mut self, at: usize) -> Option<T> {
         let len = self.len();
 
@@ -134,17 +225,132 @@ impl<T> LinkedList<T> {
             return self.pop_back();
         }
 
-        let mut rhs = self.split_off(at);
-        let elem = rhs.pop_front();
+        // Safety: 0 < at < len - 1，at 处的结点一定存在
+        let node_ptr = self.get_node(at).unwrap();
+
+        Some(unsafe { self.unlink(node_ptr) }.elem)
+    }
+
+    /// 删除第一个等于 value 的元素并将其返回，不存在时返回 None
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use base::LinkedList;
+    ///
+    /// let mut list = LinkedList::from([1, 2, 3, 2]);
+    ///
+    /// assert_eq!(list.remove_item(&2), Some(2));
+    /// assert_eq!(list.remove_item(&5), None);
+    /// assert_eq!(list, LinkedList::from([1, 3, 2]));
+    /// ```
+    pub fn remove_item(&mut self, value: &T) -> Option<T>
+    where
+        T: PartialEq,
+    {
+        self.extract_if(|elem| elem == value).next()
+    }
+
+    /// 删除所有等于 value 的元素，返回删除的数量
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use base::LinkedList;
+    ///
+    /// let mut list = LinkedList::from([2, 1, 2, 3, 2]);
+    ///
+    /// assert_eq!(list.remove_all(&2), 3);
+    /// assert_eq!(list, LinkedList::from([1, 3]));
+    /// ```
+    pub fn remove_all(&mut self, value: &T) -> usize
+    where
+        T: PartialEq,
+    {
+        let len = self.len;
+        self.retain(|elem| elem != value);
+
+        len - self.len
+    }
+
+    /// 只保留满足条件的元素，一次遍历完成，不满足条件的结点直接摘下并销毁
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use base::LinkedList;
+    ///
+    /// let mut list = LinkedList::from([1, 2, 3, 4, 5, 6]);
+    /// list.retain(|&x| x % 2 == 0);
+    ///
+    /// assert_eq!(list, LinkedList::from([2, 4, 6]));
+    /// ```
+    pub fn retain<F: FnMut(&T) -> bool>(&mut self, mut f: F) {
+        self.retain_mut(|elem| f(elem));
+    }
+
+    /// 只保留满足条件的元素，判断时可以修改元素
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use base::LinkedList;
+    ///
+    /// let mut list = LinkedList::from([1, 2, 3, 4]);
+    /// list.retain_mut(|x| {
+    ///     *x *= 10;
+    ///     *x > 20
+    /// });
+    ///
+    /// assert_eq!(list, LinkedList::from([30, 40]));
+    /// ```
+    pub fn retain_mut<F: FnMut(&mut T) -> bool>(&mut self, mut f: F) {
+        let mut current = self.head;
+
+        while let Some(node_ptr) = current {
+            // Safety: node_ptr 是链表中的有效结点，先保存后继再决定是否摘下
+            unsafe {
+                current = (*node_ptr.as_ptr()).next;
+                if !f(&mut (*node_ptr.as_ptr()).elem) {
+                    drop(self.unlink(node_ptr).elem);
+                }
+            }
+        }
+    }
 
-        self.append(&mut rhs);
-        elem
+    /// 返回一个迭代器，惰性地摘下并产出满足条件的元素，其余元素按原顺序留在链表中
+    ///
+    /// 迭代器被提前丢弃时，尚未访问的元素都保留在链表中。
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use base::LinkedList;
+    ///
+    /// let mut numbers = LinkedList::from([1, 2, 3, 4, 5, 6, 7, 8]);
+    /// let evens = numbers.extract_if(|x| *x % 2 == 0).collect::<LinkedList<_>>();
+    ///
+    /// assert_eq!(evens, LinkedList::from([2, 4, 6, 8]));
+    /// assert_eq!(numbers, LinkedList::from([1, 3, 5, 7]));
+    ///
+    /// // 只取出第一个大于 3 的元素
+    /// assert_eq!(numbers.extract_if(|x| *x > 3).next(), Some(5));
+    /// assert_eq!(numbers, LinkedList::from([1, 3, 7]));
+    /// ```
+    pub fn extract_if<F: FnMut(&mut T) -> bool>(&mut self, pred: F) -> ExtractIf<'_, T, F> {
+        ExtractIf {
+            current: self.head,
+            list: self,
+            pred,
+        }
     }
 
     /// 返回不可变迭代器
     pub fn iter(&self) -> Iter<'_, T> {
         Iter {
-            current: self.head,
+            head: self.head,
+            tail: self.tail,
+            len: self.len,
             _marker: PhantomData,
         }
     }
@@ -152,7 +358,9 @@ impl<T> LinkedList<T> {
     /// 返回可变的代器
     pub fn iter_mut(&mut self) -> IterMut<'_, T> {
         IterMut {
-            current: self.head,
+            head: self.head,
+            tail: self.tail,
+            len: self.len,
             _marker: PhantomData,
         }
     }
@@ -175,6 +383,76 @@ impl<T> LinkedList<T> {
         false
     }
 
+    /// 判断链表是否以 prefix 开头，从两个链表的头部向后比较，时间复杂度：O(prefix.len())
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use base::LinkedList;
+    ///
+    /// let list = LinkedList::from([1, 2, 3, 4]);
+    ///
+    /// assert!(list.starts_with(&LinkedList::from([1, 2])));
+    /// assert!(!list.starts_with(&LinkedList::from([2, 3])));
+    /// assert!(list.starts_with(&LinkedList::new()));
+    /// ```
+    pub fn starts_with(&self, prefix: &Self) -> bool
+    where
+        T: PartialEq,
+    {
+        prefix.len <= self.len && self.iter().zip(prefix).all(|(a, b)| a == b)
+    }
+
+    /// 判断链表是否以 suffix 结尾，从两个链表的尾部向前比较，时间复杂度：O(suffix.len())
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use base::LinkedList;
+    ///
+    /// let list = LinkedList::from([1, 2, 3, 4]);
+    ///
+    /// assert!(list.ends_with(&LinkedList::from([3, 4])));
+    /// assert!(!list.ends_with(&LinkedList::from([2, 3])));
+    /// assert!(list.ends_with(&list.clone()));
+    /// ```
+    pub fn ends_with(&self, suffix: &Self) -> bool
+    where
+        T: PartialEq,
+    {
+        suffix.len <= self.len
+            && self
+                .iter()
+                .rev()
+                .zip(suffix.iter().rev())
+                .all(|(a, b)| a == b)
+    }
+
+    /// 判断链表是否为回文，即正序和逆序读到的元素相同
+    ///
+    /// 利用双向链表，同时从头部向后、从尾部向前比较，相遇时停止，
+    /// 不需要复制到 Vec 或反转后半部分。时间复杂度：O(n)，空间复杂度：O(1)
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use base::LinkedList;
+    ///
+    /// assert!(LinkedList::from([1, 2, 3, 2, 1]).is_palindrome());
+    /// assert!(LinkedList::from(['a', 'b', 'b', 'a']).is_palindrome());
+    /// assert!(!LinkedList::from([1, 2, 3]).is_palindrome());
+    /// assert!(LinkedList::<i32>::new().is_palindrome());
+    /// ```
+    pub fn is_palindrome(&self) -> bool
+    where
+        T: PartialEq,
+    {
+        self.iter()
+            .zip(self.iter().rev())
+            .take(self.len / 2)
+            .all(|(front, back)| front == back)
+    }
+
     /// 将链表反转
     ///
     /// # Examples
@@ -211,10 +489,71 @@ impl<T> LinkedList<T> {
         mem::swap(&mut self.head, &mut self.tail);
     }
 
+    /// 将链表向左旋转 k 个位置：头部的 k 个结点移到尾部，第 k 个元素成为新的头部
+    ///
+    /// 只修改首尾相接处的指针，不移动元素，时间复杂度：O(min(k, len - k))
+    ///
+    /// # Panics
+    ///
+    /// Panics if `k > len`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use base::LinkedList;
+    ///
+    /// let mut list = LinkedList::from([1, 2, 3, 4, 5]);
+    ///
+    /// list.rotate_left(2);
+    /// assert_eq!(list, LinkedList::from([3, 4, 5, 1, 2]));
+    ///
+    /// list.rotate_right(3);
+    /// assert_eq!(list, LinkedList::from([5, 1, 2, 3, 4]));
+    /// ```
+    pub fn rotate_left(&mut self, k: usize) {
+        let len = self.len();
+
+        assert!(k <= len, "Cannot rotate by {k}, len: {len}");
+        if k == 0 || k == len {
+            return;
+        }
+
+        // Safety: 0 < k < len，新的头结点及其前驱一定存在，链表非空时 head 和 tail 也一定存在
+        let new_head = self.get_node(k).unwrap();
+        unsafe {
+            let new_tail = (*new_head.as_ptr()).prev.unwrap();
+            let (head, tail) = (self.head.unwrap(), self.tail.unwrap());
+
+            // 先首尾相接成环，再从新的头结点处断开
+            (*tail.as_ptr()).next = Some(head);
+            (*head.as_ptr()).prev = Some(tail);
+            (*new_tail.as_ptr()).next = None;
+            (*new_head.as_ptr()).prev = None;
+
+            self.head = Some(new_head);
+            self.tail = Some(new_tail);
+        }
+    }
+
+    /// 将链表向右旋转 k 个位置：尾部的 k 个结点移到头部，见 [`LinkedList::rotate_left`]
+    ///
+    /// # Panics
+    ///
+    /// Panics if `k > len`.
+    pub fn rotate_right(&mut self, k: usize) {
+        let len = self.len();
+
+        assert!(k <= len, "Cannot rotate by {k}, len: {len}");
+        self.rotate_left(len - k);
+    }
+
     /// 将 other 中的全部元素移动到链表尾部，完成后 other 为空
     pub fn append(&mut self, other: &mut Self) {
         match self.tail {
-            None => mem::swap(self, other),
+            None => {
+                self.swap_nodes(other);
+                other.invalidate_handles();
+            }
             Some(mut tail_ptr) => {
                 if let Some(mut head_other) = other.head.take() {
                     // 这里使用 `as_mut` 是可行的，因为我们拥有
@@ -226,11 +565,79 @@ impl<T> LinkedList<T> {
 
                     self.tail = other.tail.take();
                     self.len += mem::replace(&mut other.len, 0);
+                    other.invalidate_handles();
                 }
             }
         }
     }
 
+    /// 将 other 中的全部元素移动到链表头部，完成后 other 为空，时间复杂度：O(1)
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use base::LinkedList;
+    ///
+    /// let mut list = LinkedList::from([3, 4]);
+    /// let mut other = LinkedList::from([1, 2]);
+    ///
+    /// list.prepend(&mut other);
+    /// assert_eq!(list, LinkedList::from([1, 2, 3, 4]));
+    /// assert!(other.is_empty());
+    /// ```
+    pub fn prepend(&mut self, other: &mut Self) {
+        self.splice_at(0, other);
+    }
+
+    /// 将 other 中的全部元素插入到链表的 at 处，完成后 other 为空
+    ///
+    /// 从离 at 较近的一端找到插入位置后，只需修改两处连接，
+    /// 时间复杂度：O(min(at, len - at))，与 other 的长度无关
+    ///
+    /// # Panics
+    ///
+    /// Panics if `at > len`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use base::LinkedList;
+    ///
+    /// let mut list = LinkedList::from([1, 2, 5, 6]);
+    /// let mut other = LinkedList::from([3, 4]);
+    ///
+    /// list.splice_at(2, &mut other);
+    /// assert_eq!(list, LinkedList::from([1, 2, 3, 4, 5, 6]));
+    /// assert!(other.is_empty());
+    /// ```
+    pub fn splice_at(&mut self, at: usize, other: &mut Self) {
+        let len = self.len();
+
+        assert!(at <= len, "Cannot splice at index: {at}, len: {len}");
+        if at == len {
+            self.append(other);
+            return;
+        }
+        let (Some(other_head), Some(other_tail)) = (other.head.take(), other.tail.take()) else {
+            return;
+        };
+
+        // Safety: at < len，at 处的结点一定存在
+        let next_ptr = self.get_node(at).unwrap();
+        unsafe {
+            match (*next_ptr.as_ptr()).prev {
+                Some(prev_ptr) => (*prev_ptr.as_ptr()).next = Some(other_head),
+                None => self.head = Some(other_head),
+            }
+            (*other_head.as_ptr()).prev = (*next_ptr.as_ptr()).prev;
+            (*other_tail.as_ptr()).next = Some(next_ptr);
+            (*next_ptr.as_ptr()).prev = Some(other_tail);
+        }
+
+        self.len += mem::replace(&mut other.len, 0);
+        other.invalidate_handles();
+    }
+
     /// 将链表从指定位置一分为二，返回 at 及 at 之后所有元素组成的新链表。
     ///
     /// # Panics
@@ -253,62 +660,94 @@ impl<T> LinkedList<T> {
 
         assert!(at <= len, "Cannot split off at a nonexistent index");
         if at == 0 {
-            return mem::take(self);
+            return self.take_nodes();
         } else if at == len {
             return Self::new();
         }
 
-        let split_node = {
-            let mut iter = self.iter_mut();
-            // 手动跳过前 at-1 个结点，不要使用 `.skip()`，避免依赖 Skip
-            for _ in 0..at - 1 {
-                iter.next();
-            }
-            iter.current
-        };
-
-        // split_node 是第一部分的新 tail 结点，它也包含第二部分的 head 结点。
-        if let Some(mut split_node) = split_node {
-            let second_part_head;
-            let second_part_tail;
-
-            unsafe {
-                second_part_head = split_node.as_mut().next.take();
-            }
-            if let Some(mut head) = second_part_head {
-                unsafe {
-                    head.as_mut().prev = None;
-                }
-                second_part_tail = self.tail;
-            } else {
-                second_part_tail = None;
-            }
-
-            let second_part = LinkedList {
-                head: second_part_head,
-                tail: second_part_tail,
-                len: self.len - at,
-            };
-
-            // 更新第一部分的 tail 指针
-            self.tail = Some(split_node);
-            self.len = at;
+        // Safety: 0 < at < len，at - 1 一定是有效位置
+        let split_node = self.get_node(at - 1).unwrap();
 
-            second_part
-        } else {
-            std::mem::take(self)
-        }
+        self.split_after(split_node, at)
     }
 
-    /// 交换两个结点的值
+    /// 链表中间的元素，即下标为 len / 2 的元素，长度为偶数时是后半部分的第一个元素
     ///
-    /// # Panics
+    /// 使用快慢指针：快指针每次走两步，慢指针每次走一步，快指针到达尾部时慢指针恰好在中间，
+    /// 只需遍历一次，不依赖链表长度。
     ///
-    /// Panics if `i >= len` or `j` >= len.
-    pub fn swap(&mut self, i: usize, j: usize) {
-        let len = self.len();
+    /// # Examples
+    ///
+    /// ```rust
+    /// use base::LinkedList;
+    ///
+    /// assert_eq!(LinkedList::from([1, 2, 3, 4, 5]).middle(), Some(&3));
+    /// assert_eq!(LinkedList::from([1, 2, 3, 4]).middle(), Some(&3));
+    /// assert_eq!(LinkedList::<i32>::new().middle(), None);
+    /// ```
+    pub fn middle(&self) -> Option<&T> {
+        self.middle_node()
+            .map(|node_ptr| unsafe { &(*node_ptr.as_ptr()).elem })
+    }
 
-        assert!(i < len, "Cannot swap at index: {i}, len: {len}");
+    /// 从中间一分为二，返回从 [`middle`](Self::middle) 开始的后半部分，与 `split_off(len / 2)` 相同
+    ///
+    /// 前半部分有 len / 2 个元素，后半部分有 len - len / 2 个元素，
+    /// 因此只有一个元素时前半部分为空，归并排序等递归算法应在长度小于 2 时停止拆分。
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use base::LinkedList;
+    ///
+    /// let mut list = LinkedList::from([1, 2, 3, 4, 5]);
+    /// let back = list.split_in_half();
+    ///
+    /// assert_eq!(list, LinkedList::from([1, 2]));
+    /// assert_eq!(back, LinkedList::from([3, 4, 5]));
+    /// ```
+    pub fn split_in_half(&mut self) -> LinkedList<T> {
+        let at = self.len / 2;
+        if at == 0 {
+            return self.take_nodes();
+        }
+
+        // Safety: 链表至少有两个元素，中间结点不是头结点，一定有前驱结点
+        let split_node = self
+            .middle_node()
+            .and_then(|node_ptr| unsafe { node_ptr.as_ref().prev })
+            .unwrap();
+
+        self.split_after(split_node, at)
+    }
+
+    /// 交换两个位置的元素，与 `slice::swap` 一致，时间复杂度：O(max(i, j))
+    ///
+    /// 只交换结点中的值，不修改链接，因此结点句柄仍指向原来的结点，但读到的是交换后的值。
+    ///
+    /// # Panics
+    ///
+    /// Panics if `i >= len` or `j >= len`.
+    ///
+    /// # Examples
+    ///
+    /// 基于链表的选择排序：
+    ///
+    /// ```rust
+    /// use base::LinkedList;
+    ///
+    /// let mut list = LinkedList::from([3, 1, 4, 1, 5, 9, 2, 6]);
+    /// for i in 0..list.len() {
+    ///     let min = (i..list.len()).min_by_key(|&j| list[j]).unwrap();
+    ///     list.swap(i, min);
+    /// }
+    ///
+    /// assert_eq!(list, LinkedList::from([1, 1, 2, 3, 4, 5, 6, 9]));
+    /// ```
+    pub fn swap(&mut self, i: usize, j: usize) {
+        let len = self.len();
+
+        assert!(i < len, "Cannot swap at index: {i}, len: {len}");
         assert!(j < len, "Cannot swap at index: {j}, len: {len}");
 
         if i == j {
@@ -355,35 +794,58 @@ impl<T> LinkedList<T> {
         let mut current = self.head.take();
         // 遍历所有结点
         while let Some(node_ptr) = current {
-            let node_ptr_raw = node_ptr.as_ptr();
             // Safety:
-            // 1. 我们正在 drop 链表，保证了对该内存的所有权是唯一的；
-            // 2. Box::from_raw(node_ptr_raw) 恢复了 Rust 对该堆内存的所有权。
-            let mut node = unsafe { Box::from_raw(node_ptr_raw) };
+            // 1. 我们正在清空链表，保证了对该内存的所有权是唯一的；
+            // 2. recycle 读出结点后，结点的内存放回结点池或被释放。
+            let node = unsafe { self.recycle(node_ptr) };
 
             // 移动到下一个结点
-            // 必须使用 take() 将其从当前结点中移出，这样 current 才能指向下一个结点。
-            current = node.next.take();
+            current = node.next;
 
-            // 当 node（Box） 离开作用域时会被 Rust 自动 drop
+            // 当 node 离开作用域时元素会被 Rust 自动 drop
         }
 
         self.tail.take();
         self.len = 0;
+        self.invalidate_handles();
     }
 
-    /// 从前往后获取指定位置元素的不可变借用，如果 index 无效返回 None
+    /// 获取指定位置元素的不可变借用，如果 index 无效返回 None
     pub fn get(&self, at: usize) -> Option<&T> {
         self.get_node(at)
             .map(|node_ptr| unsafe { &(*node_ptr.as_ptr()).elem })
     }
 
-    /// 从前往后获取指定位置元素的可变借用，如果 index 无效返回 None
+    /// 获取指定位置元素的可变借用，如果 index 无效返回 None
     pub fn get_mut(&mut self, at: usize) -> Option<&mut T> {
         self.get_node(at)
             .map(|node_ptr| unsafe { &mut (*node_ptr.as_ptr()).elem })
     }
 
+    /// 获取倒数第 index 个元素（从 0 开始）的不可变借用，如果 index 无效返回 None
+    ///
+    /// 与 `get(len - 1 - index)` 相同，靠近尾部的元素从尾部开始查找
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use base::LinkedList;
+    ///
+    /// let list = LinkedList::from([1, 2, 3, 4]);
+    ///
+    /// assert_eq!(list.get_from_back(0), Some(&4));
+    /// assert_eq!(list.get_from_back(3), Some(&1));
+    /// assert_eq!(list.get_from_back(4), None);
+    /// ```
+    pub fn get_from_back(&self, index: usize) -> Option<&T> {
+        self.get(self.len.checked_sub(index)?.checked_sub(1)?)
+    }
+
+    /// 获取倒数第 index 个元素（从 0 开始）的可变借用，如果 index 无效返回 None
+    pub fn get_from_back_mut(&mut self, index: usize) -> Option<&mut T> {
+        self.get_mut(self.len.checked_sub(index)?.checked_sub(1)?)
+    }
+
     /// 返回链表中元素数量
     pub fn len(&self) -> usize {
         self.len
@@ -395,16 +857,253 @@ impl<T> LinkedList<T> {
     }
 }
 
+impl<T> LinkedList<T> {
+    /// 从头到尾返回所有结点的句柄
+    ///
+    /// 句柄只在创建它的链表中有效，不受其他插入、删除操作的影响，
+    /// 可以保存起来以 O(1) 的时间访问、删除结点或在其后插入。
+    /// 结点被删除，或者链表的结点被成批移走（[`clear`](Self::clear)、[`split_off`](Self::split_off)、
+    /// [`split_in_half`](Self::split_in_half)，或作为 other 传给 [`append`](Self::append)、
+    /// [`prepend`](Self::prepend)、[`splice_at`](Self::splice_at)、[`merge`](Self::merge)）之后，
+    /// 这个链表之前创建的句柄失效，需要重新获取。
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use base::LinkedList;
+    ///
+    /// let mut list = LinkedList::from([1, 2, 3]);
+    /// let handles = list.iter_nodes().collect::<Vec<_>>();
+    ///
+    /// list.insert_after(handles[0], 10);
+    /// assert_eq!(list.remove_node(handles[1]), Some(2));
+    /// assert_eq!(list, LinkedList::from([1, 10, 3]));
+    ///
+    /// // 已删除结点的句柄失效
+    /// assert_eq!(list.value(handles[1]), None);
+    /// assert_eq!(list.value(handles[2]), Some(&3));
+    /// ```
+    pub fn iter_nodes(&mut self) -> impl Iterator<Item = NodeHandle<T>> + '_ {
+        let mut current = self.head;
+
+        std::iter::from_fn(move || {
+            let node_ptr = current?;
+            // Safety: node_ptr 是链表中的有效结点
+            current = unsafe { (*node_ptr.as_ptr()).next };
+            Some(self.handle(node_ptr))
+        })
+    }
+
+    /// 向链表头部插入一个元素，返回新结点的句柄
+    pub fn push_front_node(&mut self, elt: T) -> NodeHandle<T> {
+        self.push_front(elt);
+        // Safety: 刚刚插入了结点，head 一定存在
+        self.handle(self.head.unwrap())
+    }
+
+    /// 向链表尾部插入一个元素，返回新结点的句柄
+    pub fn push_back_node(&mut self, elt: T) -> NodeHandle<T> {
+        self.push_back(elt);
+        // Safety: 刚刚插入了结点，tail 一定存在
+        self.handle(self.tail.unwrap())
+    }
+
+    /// 返回句柄指向的元素，句柄已失效时返回 None
+    pub fn value(&self, handle: NodeHandle<T>) -> Option<&T> {
+        self.resolve(handle)
+            .map(|node_ptr| unsafe { &(*node_ptr.as_ptr()).elem })
+    }
+
+    /// 返回句柄指向的元素的可变借用，句柄已失效时返回 None
+    pub fn value_mut(&mut self, handle: NodeHandle<T>) -> Option<&mut T> {
+        self.resolve(handle)
+            .map(|node_ptr| unsafe { &mut (*node_ptr.as_ptr()).elem })
+    }
+
+    /// 在句柄指向的结点之后插入一个元素，返回新结点的句柄，时间复杂度：O(1)
+    ///
+    /// # Panics
+    ///
+    /// Panics if `handle` is invalid.
+    pub fn insert_after(&mut self, handle: NodeHandle<T>, elt: T) -> NodeHandle<T> {
+        let prev_ptr = self.resolve(handle).expect("Invalid node handle");
+
+        // Safety: prev_ptr 是链表中的有效结点
+        let node_ptr = unsafe {
+            let next = (*prev_ptr.as_ptr()).next;
+            let node_ptr = self.alloc_node(Node {
+                prev: Some(prev_ptr),
+                next,
+                generation: 0,
+                elem: elt,
+            });
+
+            (*prev_ptr.as_ptr()).next = Some(node_ptr);
+            match next {
+                Some(next_ptr) => (*next_ptr.as_ptr()).prev = Some(node_ptr),
+                None => self.tail = Some(node_ptr),
+            }
+            node_ptr
+        };
+        self.len += 1;
+
+        self.handle(node_ptr)
+    }
+
+    /// 删除句柄指向的结点并返回它的元素，句柄已失效时返回 None，时间复杂度：O(1)
+    pub fn remove_node(&mut self, handle: NodeHandle<T>) -> Option<T> {
+        let node_ptr = self.resolve(handle)?;
+
+        // Safety: 句柄有效，node_ptr 是链表中的有效结点
+        Some(unsafe { self.unlink(node_ptr) }.elem)
+    }
+}
+
+impl<T: Ord> LinkedList<T> {
+    /// 将有序链表 other 合并到有序链表中，完成后 other 为空
+    ///
+    /// 两个链表都应按升序排列，合并是稳定的：相等的元素中，原链表的元素排在 other 的元素之前。
+    /// 只重新连接结点的指针，不分配新结点，时间复杂度：O(n + m)
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use base::LinkedList;
+    ///
+    /// let mut list = LinkedList::from([1, 3, 5, 7]);
+    /// let mut other = LinkedList::from([2, 3, 4, 8, 9]);
+    ///
+    /// list.merge(&mut other);
+    /// assert_eq!(list, LinkedList::from([1, 2, 3, 3, 4, 5, 7, 8, 9]));
+    /// assert!(other.is_empty());
+    /// ```
+    pub fn merge(&mut self, other: &mut Self) {
+        if other.is_empty() {
+            return;
+        }
+
+        let (mut left, mut right) = (self.head.take(), other.head.take());
+        let mut tail: Option<NonNull<Node<T>>> = None;
+        self.len += mem::replace(&mut other.len, 0);
+        other.invalidate_handles();
+        let other_tail = other.tail.take();
+
+        // 每次取两个链表头部较小的结点接到结果尾部，相等时先取原链表的结点
+        unsafe {
+            while let (Some(l), Some(r)) = (left, right) {
+                let node_ptr = if (*r.as_ptr()).elem < (*l.as_ptr()).elem {
+                    right = (*r.as_ptr()).next;
+                    r
+                } else {
+                    left = (*l.as_ptr()).next;
+                    l
+                };
+
+                (*node_ptr.as_ptr()).prev = tail;
+                match tail {
+                    Some(tail_ptr) => (*tail_ptr.as_ptr()).next = Some(node_ptr),
+                    None => self.head = Some(node_ptr),
+                }
+                tail = Some(node_ptr);
+            }
+
+            // 剩余的结点已经有序，整段接到结果尾部
+            let (rest, rest_tail) = match left {
+                Some(_) => (left, self.tail),
+                None => (right, other_tail),
+            };
+            if let Some(rest_ptr) = rest {
+                (*rest_ptr.as_ptr()).prev = tail;
+                match tail {
+                    Some(tail_ptr) => (*tail_ptr.as_ptr()).next = Some(rest_ptr),
+                    None => self.head = Some(rest_ptr),
+                }
+            }
+            self.tail = rest_tail.or(tail);
+        }
+    }
+}
+
 impl<T> LinkedList<T> {
     #[inline]
-    fn new_node(&self, elt: T, is_front: bool) -> Option<NonNull<Node<T>>> {
-        let node = Box::new(Node {
+    fn new_node(&mut self, elt: T, is_front: bool) -> Option<NonNull<Node<T>>> {
+        let node = Node {
             prev: if is_front { None } else { self.tail },
             next: if is_front { self.head } else { None },
+            generation: 0,
             elem: elt,
-        });
+        };
 
-        Some(unsafe { NonNull::new_unchecked(Box::into_raw(node)) })
+        Some(self.alloc_node(node))
+    }
+
+    /// 把结点放到堆上，结点池中有空闲结点时复用它的内存
+    #[inline]
+    fn alloc_node(&mut self, node: Node<T>) -> NonNull<Node<T>> {
+        if let Some(node_ptr) = self.retired.pop() {
+            // 旧句柄可能仍指向这块内存，新结点直接分配一个新的代数：
+            // 指向旧结点的句柄会被拒绝，新结点删除时也会再次放回 retired 而不是被释放
+            let node = Node {
+                generation: NEXT_GENERATION.fetch_add(1, Ordering::Relaxed),
+                ..node
+            };
+            // Safety: 已删除结点的元素已被取走，内存仍然有效，直接写入新结点
+            unsafe { node_ptr.as_ptr().write(node) };
+            return node_ptr;
+        }
+        let node_ptr = match self.pool.pop() {
+            Some(slot) => Box::into_raw(Box::write(slot, node)),
+            None => Box::into_raw(Box::new(node)),
+        };
+
+        // Safety: Box 的指针不为空
+        unsafe { NonNull::new_unchecked(node_ptr) }
+    }
+
+    /// 取出结点，结点的内存放回结点池，结点池已满时释放；
+    /// 创建过句柄的结点的内存保留到句柄整体失效时再回收
+    ///
+    /// # Safety
+    ///
+    /// node_ptr 必须是由 alloc_node 分配、不再被链表引用的结点，之后不能再通过它访问结点
+    #[inline]
+    unsafe fn recycle(&mut self, node_ptr: NonNull<Node<T>>) -> Node<T> {
+        // Safety: 读出结点后，这块内存只作为未初始化的内存使用，不会再次 drop 结点
+        let node = unsafe { node_ptr.as_ptr().read() };
+        if node.generation != 0 {
+            // Safety: 只改写代数，内存中其余的字段不再被读取
+            unsafe { (*node_ptr.as_ptr()).generation = RETIRED };
+            self.retired.push(node_ptr);
+        } else {
+            // Safety: 没有句柄指向这个结点，内存可以回收
+            unsafe { self.release_slot(node_ptr) };
+        }
+
+        node
+    }
+
+    /// 结点的内存放回结点池，结点池已满时释放
+    ///
+    /// # Safety
+    ///
+    /// node_ptr 必须是由 alloc_node 分配、元素已被取走的结点，之后不能再通过它访问结点
+    #[inline]
+    unsafe fn release_slot(&mut self, node_ptr: NonNull<Node<T>>) {
+        let slot = unsafe { Box::from_raw(node_ptr.as_ptr().cast::<MaybeUninit<Node<T>>>()) };
+        if self.pool.len() < self.pool_capacity {
+            self.pool.push(slot);
+        }
+    }
+
+    /// 使这个链表之前创建的所有句柄失效，并回收已删除结点保留的内存，时间复杂度：O(已删除结点数)
+    ///
+    /// 链表的结点被成批移走时调用，此后旧句柄的链表编号不再匹配，不会再读取结点中的代数。
+    fn invalidate_handles(&mut self) {
+        self.id = 0;
+        for node_ptr in mem::take(&mut self.retired) {
+            // Safety: retired 中的结点不在链表中，元素已被取走
+            unsafe { self.release_slot(node_ptr) };
+        }
     }
 
     #[inline]
@@ -431,10 +1130,9 @@ impl<T> LinkedList<T> {
     fn pop(&mut self, is_front: bool) -> Option<T> {
         if is_front {
             self.head.map(|head_ptr| {
-                let node_ptr_raw = head_ptr.as_ptr();
                 // Safety:
-                // 使用 Box 接管这个结点的内存，取走值后销毁这个结点
-                let node = unsafe { Box::from_raw(node_ptr_raw) };
+                // 取出结点后它的内存放回结点池或被释放，之后不再访问 head_ptr
+                let node = unsafe { self.recycle(head_ptr) };
 
                 match node.next {
                     Some(next_ptr) => {
@@ -453,10 +1151,9 @@ impl<T> LinkedList<T> {
             })
         } else {
             self.tail.map(|tail_ptr| {
-                let node_ptr_raw = tail_ptr.as_ptr();
                 // Safety:
-                // 使用 Box 接管这个结点的内存，取走值后销毁这个结点
-                let node = unsafe { Box::from_raw(node_ptr_raw) };
+                // 取出结点后它的内存放回结点池或被释放，之后不再访问 tail_ptr
+                let node = unsafe { self.recycle(tail_ptr) };
 
                 match node.prev {
                     Some(prev_ptr) => {
@@ -476,6 +1173,131 @@ impl<T> LinkedList<T> {
         }
     }
 
+    /// 把结点从链表中摘下并取出，结点的内存放回结点池或被释放
+    ///
+    /// # Safety
+    ///
+    /// node_ptr 必须是这个链表中的有效结点
+    #[inline]
+    unsafe fn unlink(&mut self, node_ptr: NonNull<Node<T>>) -> Node<T> {
+        let node = unsafe { self.recycle(node_ptr) };
+
+        match node.prev {
+            Some(prev_ptr) => unsafe { (*prev_ptr.as_ptr()).next = node.next },
+            None => self.head = node.next,
+        }
+        match node.next {
+            Some(next_ptr) => unsafe { (*next_ptr.as_ptr()).prev = node.prev },
+            None => self.tail = node.prev,
+        }
+        self.len -= 1;
+
+        node
+    }
+
+    /// 为结点创建句柄
+    fn handle(&mut self, node_ptr: NonNull<Node<T>>) -> NodeHandle<T> {
+        // Safety: node_ptr 是链表中的有效结点
+        let node = unsafe { &mut *node_ptr.as_ptr() };
+        if node.generation == 0 {
+            node.generation = NEXT_GENERATION.fetch_add(1, Ordering::Relaxed);
+        }
+        if self.id == 0 {
+            self.id = NEXT_GENERATION.fetch_add(1, Ordering::Relaxed);
+        }
+
+        NodeHandle {
+            node: node_ptr,
+            generation: node.generation,
+            list: self.id,
+        }
+    }
+
+    /// 句柄有效时返回它指向的结点，时间复杂度：O(1)
+    ///
+    /// 编号匹配说明句柄由这个链表创建，且此后链表的结点没有被成批移走：
+    /// 句柄指向的结点要么仍在链表中，要么已被删除而内存保留在 retired 中，
+    /// 因此可以安全地读取结点中的代数，代数相同说明结点没有被删除。
+    #[inline]
+    fn resolve(&self, handle: NodeHandle<T>) -> Option<NonNull<Node<T>>> {
+        if handle.list == 0 || handle.list != self.id {
+            return None;
+        }
+
+        // Safety: 见上，结点的内存仍然有效
+        let generation = unsafe { (*handle.node.as_ptr()).generation };
+        (generation == handle.generation).then_some(handle.node)
+    }
+
+    /// 交换两个链表的结点，各自的结点池和句柄状态保持不变，调用方负责使句柄失效
+    fn swap_nodes(&mut self, other: &mut Self) {
+        mem::swap(&mut self.head, &mut other.head);
+        mem::swap(&mut self.tail, &mut other.tail);
+        mem::swap(&mut self.len, &mut other.len);
+    }
+
+    /// 取走全部结点组成新链表，结点池留在原链表中
+    fn take_nodes(&mut self) -> Self {
+        let mut list = Self::new();
+
+        self.swap_nodes(&mut list);
+        self.invalidate_handles();
+        list
+    }
+
+    /// 从 split_node 之后断开，split_node 是下标为 at - 1 的结点，返回后半部分
+    fn split_after(&mut self, mut split_node: NonNull<Node<T>>, at: usize) -> LinkedList<T> {
+        // split_node 是第一部分的新 tail 结点，它也包含第二部分的 head 结点。
+        let second_part_head = unsafe { split_node.as_mut().next.take() };
+        let second_part_tail = match second_part_head {
+            Some(mut head) => {
+                unsafe {
+                    head.as_mut().prev = None;
+                }
+                self.tail
+            }
+            None => None,
+        };
+
+        let second_part = LinkedList {
+            head: second_part_head,
+            tail: second_part_tail,
+            len: self.len - at,
+            id: 0,
+            retired: Vec::new(),
+            pool: Vec::new(),
+            pool_capacity: 0,
+        };
+        // 不逐个检查第二部分的结点，直接使原链表的所有句柄失效
+        self.invalidate_handles();
+
+        // 更新第一部分的 tail 指针
+        self.tail = Some(split_node);
+        self.len = at;
+
+        second_part
+    }
+
+    /// 快慢指针查找下标为 len / 2 的结点
+    fn middle_node(&self) -> Option<NonNull<Node<T>>> {
+        let mut slow = self.head?;
+        let mut fast = slow;
+
+        // Safety: 快慢指针都只沿 next 指向链表中的结点
+        unsafe {
+            while let Some(next) = fast.as_ref().next {
+                slow = slow.as_ref().next?;
+                match next.as_ref().next {
+                    Some(next) => fast = next,
+                    None => break,
+                }
+            }
+        }
+
+        Some(slow)
+    }
+
+    /// 查找指定位置的结点，链表是双向的，从离 at 较近的一端开始遍历
     #[inline]
     fn get_node(&self, at: usize) -> Option<NonNull<Node<T>>> {
         if at >= self.len {
@@ -483,11 +1305,21 @@ impl<T> LinkedList<T> {
         }
 
         let mut current = self.head;
-        for _ in 0..at {
-            // Safety: 这里的 index 一定在有效范围内
-            let node_ptr = current.unwrap();
+        if at <= self.len / 2 {
+            for _ in 0..at {
+                // Safety: 这里的 index 一定在有效范围内
+                let node_ptr = current.unwrap();
 
-            current = unsafe { (*node_ptr.as_ptr()).next }
+                current = unsafe { (*node_ptr.as_ptr()).next }
+            }
+        } else {
+            current = self.tail;
+            for _ in at + 1..self.len {
+                // Safety: 同上
+                let node_ptr = current.unwrap();
+
+                current = unsafe { (*node_ptr.as_ptr()).prev }
+            }
         }
 
         current
@@ -506,11 +1338,34 @@ impl<T> Default for LinkedList<T> {
     }
 }
 
+/// 元素之间以 ", " 分隔，逐个结点输出，很长的链表也不会栈溢出
+///
+/// 精度表示最多输出的元素数量，其余元素显示为 "..."。
+///
+/// # Examples
+///
+/// ```rust
+/// use base::LinkedList;
+///
+/// let list = (1..=100).collect::<LinkedList<_>>();
+///
+/// assert_eq!(format!("{list:.3}"), "1, 2, 3, ...");
+/// assert_eq!(format!("{:.3}", LinkedList::from([1, 2])), "1, 2");
+/// ```
 impl<T: Display> Display for LinkedList<T> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self.head {
-            Some(node) => write!(f, "{}", unsafe { node.as_ref() }),
-            None => Ok(()),
+        let limit = f.precision().unwrap_or(usize::MAX);
+
+        for (i, elem) in self.iter().take(limit).enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "{elem}")?;
+        }
+        match limit {
+            _ if self.len <= limit => Ok(()),
+            0 => f.write_str("..."),
+            _ => f.write_str(", ..."),
         }
     }
 }
@@ -519,6 +1374,20 @@ impl<T: Clone> Clone for LinkedList<T> {
     fn clone(&self) -> Self {
         Self::from_iter(self.iter().cloned())
     }
+
+    /// 复用已有的结点：逐个覆盖元素，多出的结点从尾部删除，不足时在尾部补齐，
+    /// 长度不变时不分配内存。保留下来的结点的句柄仍然有效，指向覆盖后的元素
+    fn clone_from(&mut self, source: &Self) {
+        while self.len > source.len {
+            drop(self.pop_back());
+        }
+
+        let mut source_iter = source.iter();
+        for (elem, source_elem) in self.iter_mut().zip(&mut source_iter) {
+            elem.clone_from(source_elem);
+        }
+        self.extend(source_iter.cloned());
+    }
 }
 
 impl<T> Drop for LinkedList<T> {
@@ -561,6 +1430,43 @@ impl<T: Hash> Hash for LinkedList<T> {
     }
 }
 
+/// 按下标访问元素，从离下标较近的一端开始遍历，时间复杂度：O(min(at, len - at))
+///
+/// # Panics
+///
+/// Panics if `at >= len`.
+///
+/// # Examples
+///
+/// ```rust
+/// use base::LinkedList;
+///
+/// let mut list = LinkedList::from([1, 2, 3]);
+/// list[1] = 20;
+///
+/// assert_eq!(list[1], 20);
+/// assert_eq!(list, LinkedList::from([1, 20, 3]));
+/// ```
+impl<T> Index<usize> for LinkedList<T> {
+    type Output = T;
+
+    fn index(&self, at: usize) -> &T {
+        let len = self.len;
+
+        self.get(at)
+            .unwrap_or_else(|| panic!("Index out of bounds: {at}, len: {len}"))
+    }
+}
+
+impl<T> IndexMut<usize> for LinkedList<T> {
+    fn index_mut(&mut self, at: usize) -> &mut T {
+        let len = self.len;
+
+        self.get_mut(at)
+            .unwrap_or_else(|| panic!("Index out of bounds: {at}, len: {len}"))
+    }
+}
+
 impl<T, const N: usize> From<[T; N]> for LinkedList<T> {
     fn from(v: [T; N]) -> Self {
         Self::from_iter(v)
@@ -573,6 +1479,53 @@ impl<T> From<Vec<T>> for LinkedList<T> {
     }
 }
 
+/// 用字面量构建 [`LinkedList`]，与 [`vec!`] 类似
+///
+/// # Examples
+///
+/// ```rust
+/// use base::{LinkedList, linked_list};
+///
+/// let list = linked_list![1, 2, 3];
+///
+/// assert_eq!(list, LinkedList::from([1, 2, 3]));
+/// assert!(linked_list![0; 0].is_empty());
+/// assert!(linked_list!['a'; 3].iter().eq(&['a', 'a', 'a']));
+/// ```
+#[macro_export]
+macro_rules! linked_list {
+    ($elem:expr; $n:expr) => {
+        $crate::LinkedList::from_iter(::std::iter::repeat_n($elem, $n))
+    };
+    ($($x:expr),* $(,)?) => {
+        $crate::LinkedList::from([$($x),*])
+    };
+}
+
+impl<T> From<VecDeque<T>> for LinkedList<T> {
+    fn from(v: VecDeque<T>) -> Self {
+        Self::from_iter(v)
+    }
+}
+
+impl<T> From<LinkedList<T>> for VecDeque<T> {
+    fn from(list: LinkedList<T>) -> Self {
+        list.into_iter().collect()
+    }
+}
+
+impl<T> From<collections::LinkedList<T>> for LinkedList<T> {
+    fn from(list: collections::LinkedList<T>) -> Self {
+        Self::from_iter(list)
+    }
+}
+
+impl<T> From<LinkedList<T>> for collections::LinkedList<T> {
+    fn from(list: LinkedList<T>) -> Self {
+        list.into_iter().collect()
+    }
+}
+
 impl<T> FromIterator<T> for LinkedList<T> {
     fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
         let mut list = LinkedList::new();
@@ -596,6 +1549,45 @@ impl<'a, T: 'a + Copy> Extend<&'a T> for LinkedList<T> {
     }
 }
 
+/// 链表结点的句柄，由 [`LinkedList::iter_nodes`] 等方法创建
+///
+/// 句柄记录结点的地址、代数和所属链表的编号，链表先比较编号，再比较结点中的代数，
+/// 以 O(1) 的时间判断句柄是否仍然有效。
+/// 句柄指向结点而不是位置，[`LinkedList::swap`] 交换的是结点中的元素。
+pub struct NodeHandle<T> {
+    node: NonNull<Node<T>>,
+    generation: u64,
+    list: u64,
+}
+
+impl<T> Clone for NodeHandle<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for NodeHandle<T> {}
+
+impl<T> PartialEq for NodeHandle<T> {
+    fn eq(&self, other: &Self) -> bool {
+        (self.generation, self.list) == (other.generation, other.list)
+    }
+}
+
+impl<T> Eq for NodeHandle<T> {}
+
+impl<T> Hash for NodeHandle<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        (self.generation, self.list).hash(state);
+    }
+}
+
+impl<T> fmt::Debug for NodeHandle<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("NodeHandle").field(&self.generation).finish()
+    }
+}
+
 /// 消耗所有权的迭代器
 pub struct IntoIter<T> {
     list: LinkedList<T>,
@@ -642,13 +1634,19 @@ impl<T> IntoIterator for LinkedList<T> {
     }
 }
 
+/// 不可变迭代器，同时记录两端尚未访问的结点和剩余数量，可以从两端迭代
 pub struct Iter<'a, T: 'a> {
-    current: Option<NonNull<Node<T>>>,
+    head: Option<NonNull<Node<T>>>,
+    tail: Option<NonNull<Node<T>>>,
+    len: usize,
     _marker: PhantomData<&'a Node<T>>,
 }
 
+/// 可变迭代器，两端相遇时依靠剩余数量停止，同一个结点不会被借出两次
 pub struct IterMut<'a, T: 'a> {
-    current: Option<NonNull<Node<T>>>,
+    head: Option<NonNull<Node<T>>>,
+    tail: Option<NonNull<Node<T>>>,
+    len: usize,
     _marker: PhantomData<&'a mut Node<T>>,
 }
 
@@ -656,31 +1654,124 @@ impl<'a, T> Iterator for Iter<'a, T> {
     type Item = &'a T;
 
     fn next(&mut self) -> Option<Self::Item> {
-        self.current.map(|node_ptr| {
+        if self.len == 0 {
+            return None;
+        }
+
+        self.head.map(|node_ptr| {
+            let node = unsafe { &(*node_ptr.as_ptr()) };
+
+            self.len -= 1;
+            self.head = node.next;
+            &node.elem
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<T> DoubleEndedIterator for Iter<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+
+        self.tail.map(|node_ptr| {
             let node = unsafe { &(*node_ptr.as_ptr()) };
 
-            self.current = node.next;
+            self.len -= 1;
+            self.tail = node.prev;
             &node.elem
         })
     }
 }
 
+impl<T> ExactSizeIterator for Iter<'_, T> {}
+
+impl<T> Clone for Iter<'_, T> {
+    fn clone(&self) -> Self {
+        Self { ..*self }
+    }
+}
+
 impl<'a, T> Iterator for IterMut<'a, T> {
     type Item = &'a mut T;
 
     fn next(&mut self) -> Option<Self::Item> {
-        self.current.map(|node_ptr| {
+        if self.len == 0 {
+            return None;
+        }
+
+        self.head.map(|node_ptr| {
+            let node = unsafe { &mut (*node_ptr.as_ptr()) };
+
+            self.len -= 1;
+            self.head = node.next;
+            &mut node.elem
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<T> DoubleEndedIterator for IterMut<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+
+        self.tail.map(|node_ptr| {
             let node = unsafe { &mut (*node_ptr.as_ptr()) };
 
-            self.current = node.next;
+            self.len -= 1;
+            self.tail = node.prev;
             &mut node.elem
         })
     }
 }
 
+impl<T> ExactSizeIterator for IterMut<'_, T> {}
+
+/// 摘下并产出满足条件的元素的迭代器，由 [`LinkedList::extract_if`] 创建
+pub struct ExtractIf<'a, T, F> {
+    list: &'a mut LinkedList<T>,
+    /// 下一个要判断的结点
+    current: Option<NonNull<Node<T>>>,
+    pred: F,
+}
+
+impl<T, F: FnMut(&mut T) -> bool> Iterator for ExtractIf<'_, T, F> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(node_ptr) = self.current {
+            // Safety: node_ptr 是链表中的有效结点，先保存后继再决定是否摘下
+            unsafe {
+                self.current = (*node_ptr.as_ptr()).next;
+                if (self.pred)(&mut (*node_ptr.as_ptr()).elem) {
+                    return Some(self.list.unlink(node_ptr).elem);
+                }
+            }
+        }
+
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.list.len))
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use std::rc::Rc;
+
     use super::*;
+    use crate::generate::{self, Rng};
 
     #[test]
     fn list_basics_should_work() {
@@ -767,6 +1858,50 @@ mod tests {
         assert_eq!(iter.next(), Some(5));
     }
 
+    #[test]
+    fn list_std_conversions_should_work() {
+        let list = LinkedList::from(VecDeque::from([1, 2, 3]));
+        assert_eq!(list, LinkedList::from([1, 2, 3]));
+        assert_eq!(VecDeque::from(list), [1, 2, 3]);
+
+        let std_list = collections::LinkedList::from([4, 5]);
+        let list = LinkedList::from(std_list.clone());
+        assert_eq!(list.iter().collect::<Vec<_>>(), [&4, &5]);
+        assert_eq!(collections::LinkedList::from(list), std_list);
+    }
+
+    #[test]
+    fn list_is_palindrome_should_match_vec() {
+        let mut rng = Rng::new(8);
+
+        for len in 0..40 {
+            // 一半的用例由随机的前半部分镜像得到
+            let mut v = (0..len).map(|_| rng.below(2)).collect::<Vec<_>>();
+            if len % 4 < 2 {
+                for i in 0..len / 2 {
+                    v[len - 1 - i] = v[i];
+                }
+            }
+
+            let expected = v.iter().eq(v.iter().rev());
+            assert_eq!(LinkedList::from(v).is_palindrome(), expected);
+        }
+    }
+
+    #[test]
+    fn list_starts_with_ends_with_should_match_slice() {
+        let mut rng = Rng::new(13);
+
+        for _ in 0..200 {
+            let v = (0..rng.below(6)).map(|_| rng.below(2)).collect::<Vec<_>>();
+            let w = (0..rng.below(4)).map(|_| rng.below(2)).collect::<Vec<_>>();
+            let (list, other) = (LinkedList::from(v.clone()), LinkedList::from(w.clone()));
+
+            assert_eq!(list.starts_with(&other), v.starts_with(&w));
+            assert_eq!(list.ends_with(&other), v.ends_with(&w));
+        }
+    }
+
     #[test]
     fn list_reverse_should_work() {
         let mut list = LinkedList::from([2, 4, 6, 8, 0]);
@@ -776,6 +1911,79 @@ mod tests {
         assert_eq!(list, LinkedList::from([0, 8, 6, 4, 2]));
     }
 
+    #[cfg(feature = "alloc-counter")]
+    #[test]
+    fn list_reverse_should_not_allocate() {
+        use crate::alloc_counter::measure_allocations;
+
+        let mut list = (0..100).collect::<LinkedList<_>>();
+        let ((), stats) = measure_allocations(|| list.reverse());
+
+        assert_eq!(stats, Default::default());
+        assert_eq!(list.into_vec(), (0..100).rev().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn list_pool_should_recycle_nodes() {
+        let mut list = LinkedList::with_pool_capacity(4);
+        let mut expected = VecDeque::new();
+        let mut rng = Rng::new(17);
+
+        for round in 0..500 {
+            match rng.below(4) {
+                0 => {
+                    list.push_front(round);
+                    expected.push_front(round);
+                }
+                1 => {
+                    list.push_back(round);
+                    expected.push_back(round);
+                }
+                2 => assert_eq!(list.pop_front(), expected.pop_front()),
+                _ => assert_eq!(list.pop_back(), expected.pop_back()),
+            }
+
+            assert!(list.pool_len() <= 4);
+            assert!(list.iter().eq(expected.iter()));
+            assert!(list.iter().rev().eq(expected.iter().rev()));
+        }
+
+        // 复用的结点不能让旧句柄重新生效
+        let handle = list.push_back_node(-1);
+        assert_eq!(list.remove_node(handle), Some(-1));
+        let reused = list.push_back_node(-2);
+        assert_eq!(list.value(handle), None);
+        assert_eq!(list.value(reused), Some(&-2));
+
+        // 拆分出的链表不带走结点池
+        list.clear();
+        assert_eq!(list.pool_len(), 4);
+        list.extend([1, 2, 3]);
+        let front = list.split_off(0);
+        assert_eq!((front.len(), front.pool_len()), (3, 0));
+        assert_eq!(list.pool_len(), 1);
+    }
+
+    #[cfg(feature = "alloc-counter")]
+    #[test]
+    fn list_pool_should_not_allocate_when_warm() {
+        use crate::alloc_counter::measure_allocations;
+
+        let mut list = LinkedList::with_pool_capacity(16);
+        let ((), stats) = measure_allocations(|| {
+            for round in 0..100 {
+                list.extend(0..15);
+                list.insert(8, round);
+                list.retain(|&x| x % 2 == 0);
+                list.clear();
+            }
+        });
+
+        assert_eq!(stats.allocations, 0);
+        list.shrink_pool();
+        assert_eq!(list.pool_len(), 0);
+    }
+
     #[test]
     fn list_append_should_work() {
         let mut list = LinkedList::from([1, 2, 3]);
@@ -787,6 +1995,136 @@ mod tests {
         assert!(list_src.is_empty());
     }
 
+    #[test]
+    fn list_macro_should_work() {
+        assert_eq!(linked_list![1, 2, 3,], LinkedList::from([1, 2, 3]));
+        assert_eq!(
+            linked_list![vec![0]; 2],
+            LinkedList::from([vec![0], vec![0]])
+        );
+
+        let empty: LinkedList<i32> = linked_list![];
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn list_display_should_be_bounded() {
+        assert_eq!(LinkedList::<i32>::new().to_string(), "");
+        assert_eq!(LinkedList::from([1, 2, 3]).to_string(), "1, 2, 3");
+        assert_eq!(format!("{:.0}", LinkedList::from([1])), "...");
+
+        // 递归输出会在这里栈溢出
+        let list = (0..1_000_000).collect::<LinkedList<_>>();
+        assert_eq!(list.to_string().len(), 5_888_890 + 2 * 999_999);
+        assert_eq!(format!("{list:.2}"), "0, 1, ...");
+    }
+
+    #[test]
+    fn list_clone_from_should_match_clone() {
+        for (len, source_len) in [(0, 0), (0, 3), (3, 0), (2, 5), (5, 2), (4, 4)] {
+            let source = (0..source_len)
+                .map(|i| vec![i; i])
+                .collect::<LinkedList<_>>();
+            let mut list = (0..len).map(|i| vec![100 + i]).collect::<LinkedList<_>>();
+
+            list.clone_from(&source);
+            assert_eq!(list, source);
+            assert!(list.iter().rev().eq(source.iter().rev()));
+        }
+
+        // 保留下来的结点的句柄仍然有效
+        let mut list = LinkedList::from([1, 2, 3]);
+        let handle = list.iter_nodes().nth(1).unwrap();
+        list.clone_from(&LinkedList::from([4, 5]));
+        assert_eq!(list.value(handle), Some(&5));
+    }
+
+    #[cfg(feature = "alloc-counter")]
+    #[test]
+    fn list_clone_from_should_reuse_nodes() {
+        use crate::alloc_counter::measure_allocations;
+
+        let source = (0..100).collect::<LinkedList<_>>();
+        let mut list = (100..200).collect::<LinkedList<_>>();
+        let ((), stats) = measure_allocations(|| list.clone_from(&source));
+
+        assert_eq!(stats.allocations, 0);
+        assert_eq!(list, source);
+    }
+
+    #[test]
+    fn list_prepend_should_work() {
+        let mut list = LinkedList::from([4, 5]);
+        let mut empty = LinkedList::new();
+
+        list.prepend(&mut empty);
+        assert_eq!(list, LinkedList::from([4, 5]));
+
+        // 从后往前构建，接收结点的链表保留自己的句柄
+        let mut front = LinkedList::new();
+        let moved = front.push_back_node(3);
+        let kept = list.push_back_node(6);
+        list.prepend(&mut front);
+        list.prepend(&mut LinkedList::from([1, 2]));
+        assert_eq!(list.value(moved), None);
+        assert_eq!(list.remove_node(kept), Some(6));
+        empty.prepend(&mut list);
+
+        assert!(list.is_empty());
+        assert_eq!(empty.len(), 5);
+        assert!(empty.iter().eq(&[1, 2, 3, 4, 5]));
+        assert!(empty.iter().rev().eq(&[5, 4, 3, 2, 1]));
+        assert_eq!(empty.value(kept), None);
+    }
+
+    #[test]
+    fn list_splice_at_should_match_vec() {
+        let mut list = LinkedList::new();
+        let mut expected = Vec::new();
+        let mut rng = Rng::new(21);
+
+        for round in 0..50 {
+            let at = rng.below(list.len() as u64 + 1) as usize;
+            let len = rng.below(4) as usize;
+            let mut other = (0..len).map(|i| round * 10 + i).collect::<LinkedList<_>>();
+            let handle = other.iter_nodes().last();
+
+            list.splice_at(at, &mut other);
+            expected.splice(at..at, (0..len).map(|i| round * 10 + i));
+
+            assert!(other.is_empty());
+            assert_eq!(list.len(), expected.len());
+            assert!(list.iter().eq(expected.iter()));
+            assert!(list.iter().rev().eq(expected.iter().rev()));
+            // other 的句柄不能在 list 中使用
+            if let Some(handle) = handle {
+                assert_eq!(list.value(handle), None);
+            }
+        }
+    }
+
+    #[test]
+    fn list_split_in_half_should_match_split_off() {
+        for len in 0..10 {
+            let mut list = (0..len).collect::<LinkedList<_>>();
+            let handles = list.iter_nodes().collect::<Vec<_>>();
+            assert_eq!(list.middle(), (len > 0).then_some(&(len / 2)));
+
+            let back = list.split_in_half();
+            assert!(list.iter().copied().eq(0..len / 2));
+            assert!(back.iter().copied().eq(len / 2..len));
+            assert!(list.iter().rev().copied().eq((0..len / 2).rev()));
+            assert!(back.iter().rev().copied().eq((len / 2..len).rev()));
+            assert_eq!((list.len(), back.len()), (len / 2, len - len / 2));
+
+            // 拆分后旧句柄在两部分中都失效
+            for handle in handles {
+                assert_eq!(list.value(handle), None);
+                assert_eq!(back.value(handle), None);
+            }
+        }
+    }
+
     #[test]
     fn list_split_should_work() {
         let mut list = LinkedList::from([1, 3, 5, 7, 9]);
@@ -823,6 +2161,393 @@ mod tests {
         assert_eq!(list.len(), 2);
     }
 
+    #[test]
+    fn list_positional_ops_should_match_vec_deque() {
+        let mut list = LinkedList::new();
+        let mut expected = VecDeque::new();
+        let mut rng = Rng::new(5);
+
+        for i in 0..3000 {
+            let len = expected.len() as u64;
+            if rng.below(3) > 0 || len == 0 {
+                let at = rng.below(len + 1) as usize;
+                list.insert(at, i);
+                expected.insert(at, i);
+            } else {
+                let at = rng.below(len) as usize;
+                assert_eq!(list.remove(at), expected.remove(at));
+            }
+
+            let at = rng.below(len + 1) as usize;
+            assert_eq!(list.get(at), expected.get(at));
+        }
+
+        assert_eq!(list.len(), expected.len());
+        assert!(list.iter().eq(expected.iter()));
+        // 反向链接同样正确
+        assert!(list.into_iter().rev().eq(expected.into_iter().rev()));
+    }
+
+    #[test]
+    fn list_index_should_match_vec() {
+        let mut list = (0..9).collect::<LinkedList<_>>();
+        let expected = (0..9).map(|x| x * 10).collect::<Vec<_>>();
+
+        // 前后两半分别从头、尾开始查找
+        for at in 0..9 {
+            list[at] *= 10;
+        }
+        for at in 0..9 {
+            assert_eq!(list[at], expected[at]);
+        }
+    }
+
+    #[test]
+    fn list_get_from_back_should_match_vec() {
+        let mut list = (0..9).collect::<LinkedList<_>>();
+        let mut expected = (0..9).collect::<Vec<_>>();
+
+        for index in 0..9 {
+            *list.get_from_back_mut(index).unwrap() += 100;
+            expected[8 - index] += 100;
+        }
+        for index in 0..9 {
+            assert_eq!(list.get_from_back(index), expected.iter().nth_back(index));
+        }
+        assert_eq!(list.get_from_back(9), None);
+        assert_eq!(list.get_from_back_mut(usize::MAX), None);
+        assert_eq!(LinkedList::<i32>::new().get_from_back(0), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "Index out of bounds: 3, len: 3")]
+    fn list_index_should_panic_out_of_bounds() {
+        let list = LinkedList::from([1, 2, 3]);
+        let _ = list[3];
+    }
+
+    #[test]
+    fn list_retain_should_work() {
+        for keep in 0..4 {
+            let v = generate::few_distinct(200, 4, keep);
+            let mut list = LinkedList::from(v.clone());
+
+            list.retain(|&x| x != keep);
+            let expected = v.into_iter().filter(|&x| x != keep).collect::<Vec<_>>();
+            assert_eq!(list.len(), expected.len());
+            assert!(list.iter().eq(expected.iter()));
+            assert_eq!(list.back(), expected.last());
+        }
+
+        let mut list = LinkedList::from([1, 2, 3]);
+        list.retain(|_| false);
+        assert!(list.is_empty());
+        assert_eq!((list.front(), list.back()), (None, None));
+        list.push_back(4);
+        assert_eq!(list, LinkedList::from([4]));
+
+        // 被删除的元素都被销毁
+        let counter = Rc::new(());
+        let mut list = (0..10)
+            .map(|i| (i, counter.clone()))
+            .collect::<LinkedList<_>>();
+        list.retain_mut(|(i, _)| {
+            *i += 1;
+            *i % 3 == 0
+        });
+        assert_eq!(Rc::strong_count(&counter), 4);
+        assert!(list.iter().map(|(i, _)| *i).eq([3, 6, 9]));
+    }
+
+    #[test]
+    fn list_extract_if_should_work() {
+        for seed in 0..4 {
+            let v = generate::few_distinct(200, 5, seed);
+            let mut list = LinkedList::from(v.clone());
+
+            let extracted = list.extract_if(|x| *x % 2 == 0).collect::<Vec<_>>();
+            let (expected_out, expected_rest): (Vec<_>, Vec<_>) =
+                v.into_iter().partition(|x| *x % 2 == 0);
+            assert_eq!(extracted, expected_out);
+            assert_eq!(list.len(), expected_rest.len());
+            assert!(list.iter().eq(expected_rest.iter()));
+            assert_eq!(list.back(), expected_rest.last());
+        }
+
+        // 提前丢弃迭代器，未访问的元素保留在链表中
+        let mut list = LinkedList::from([1, 2, 3, 4, 5, 6]);
+        assert_eq!(list.extract_if(|x| *x >= 3).next(), Some(3));
+        assert_eq!(list, LinkedList::from([1, 2, 4, 5, 6]));
+
+        // 取出所有元素后链表为空，仍可正常使用
+        let mut list = LinkedList::from([1, 2, 3]);
+        assert_eq!(list.extract_if(|_| true).count(), 3);
+        assert!(list.is_empty());
+        assert_eq!((list.front(), list.back()), (None, None));
+        list.push_front(1);
+        assert_eq!(list, LinkedList::from([1]));
+    }
+
+    #[test]
+    fn list_node_handles_should_match_vec() {
+        let mut list = LinkedList::new();
+        let mut handles = vec![list.push_back_node(0u64)];
+        // 与链表顺序相同的 (句柄, 值)
+        let mut expected = vec![(handles[0], 0)];
+        let mut rng = Rng::new(7);
+
+        for i in 1..2000 {
+            let handle = handles[rng.below(handles.len() as u64) as usize];
+            let pos = expected.iter().position(|&(h, _)| h == handle);
+
+            match rng.below(3) {
+                0 | 1 if pos.is_some() => {
+                    let new_handle = list.insert_after(handle, i);
+                    expected.insert(pos.unwrap() + 1, (new_handle, i));
+                    handles.push(new_handle);
+                }
+                0 | 1 => {
+                    let new_handle = list.push_front_node(i);
+                    expected.insert(0, (new_handle, i));
+                    handles.push(new_handle);
+                }
+                _ => {
+                    let removed = pos.map(|pos| expected.remove(pos).1);
+                    assert_eq!(list.remove_node(handle), removed);
+                }
+            }
+
+            let value = expected.iter().find(|(h, _)| *h == handle);
+            assert_eq!(list.value(handle), value.map(|(_, v)| v));
+            assert_eq!(list.len(), expected.len());
+            assert!(list.iter().eq(expected.iter().map(|(_, v)| v)));
+            assert_eq!(list.back(), expected.last().map(|(_, v)| v));
+        }
+
+        // 迭代得到的句柄与插入时返回的句柄相同
+        let from_iter = list.iter_nodes().collect::<Vec<_>>();
+        assert!(from_iter.iter().eq(expected.iter().map(|(h, _)| h)));
+    }
+
+    #[test]
+    fn list_node_handles_should_be_invalidated_by_transfers() {
+        let mut list = LinkedList::from([1, 2, 3, 4]);
+        let handles = list.iter_nodes().collect::<Vec<_>>();
+
+        // 拆分后原链表的句柄全部失效，句柄也不能在新链表中使用
+        let mut second = list.split_off(2);
+        assert!(handles.iter().all(|&h| list.value(h).is_none()));
+        assert!(handles.iter().all(|&h| second.value(h).is_none()));
+
+        // 重新获取的句柄指向同一个结点，但与旧句柄不同
+        let renewed = second.iter_nodes().collect::<Vec<_>>();
+        assert_ne!(renewed[0], handles[2]);
+        *second.value_mut(renewed[1]).unwrap() = 40;
+
+        // 接收结点的链表保留自己的句柄，other 的句柄失效
+        let front = list.iter_nodes().collect::<Vec<_>>();
+        list.append(&mut second);
+        assert_eq!(list.value(renewed[1]), None);
+        assert_eq!(list.remove_node(front[1]), Some(2));
+        assert_eq!(list.remove_node(front[1]), None);
+
+        let mut other = LinkedList::from([0]);
+        list.prepend(&mut other);
+        assert_eq!(list.value(front[0]), Some(&1));
+        assert_eq!(list, LinkedList::from([0, 1, 3, 40]));
+
+        // 句柄失效后不会因地址复用而误判
+        assert_eq!(list.remove_node(front[0]), Some(1));
+        list.push_front(5);
+        assert_eq!(list.value(front[0]), None);
+        assert_eq!(list, LinkedList::from([5, 0, 3, 40]));
+
+        let tail = list.push_back_node(6);
+        assert_eq!(list.value(tail), Some(&6));
+        list.clear();
+        assert_eq!(list.value(tail), None);
+        let reused = list.push_back_node(7);
+        assert_eq!(list.value(tail), None);
+        assert_eq!(list.value(reused), Some(&7));
+    }
+
+    #[test]
+    fn list_node_handles_should_survive_reused_slots() {
+        // 复用已删除结点内存的新结点再次被删除时，内存仍要保留给旧句柄检查
+        let mut list = LinkedList::new();
+        let handle = list.push_back_node(String::from("s"));
+        assert_eq!(list.remove_node(handle), Some(String::from("s")));
+
+        list.push_back(String::from("t"));
+        assert_eq!(list.pop_back(), Some(String::from("t")));
+        assert_eq!(list.value(handle), None);
+        assert_eq!(list.remove_node(handle), None);
+
+        let reused = list.push_front_node(String::from("u"));
+        assert_eq!(list.value(handle), None);
+        assert_eq!(list.value(reused).map(String::as_str), Some("u"));
+    }
+
+    #[test]
+    fn list_remove_by_value_should_work() {
+        let v = generate::few_distinct(300, 6, 11);
+        let mut list = LinkedList::from(v.clone());
+        let mut expected = v.clone();
+
+        for value in &v[..20] {
+            let removed = expected
+                .iter()
+                .position(|x| x == value)
+                .map(|i| expected.remove(i));
+            assert_eq!(list.remove_item(value), removed);
+        }
+        assert!(list.iter().eq(expected.iter()));
+
+        let target = v[0];
+        let count = expected.iter().filter(|&&x| x == target).count();
+        expected.retain(|&x| x != target);
+        assert_eq!(list.remove_all(&target), count);
+        assert_eq!(list.remove_all(&target), 0);
+        assert_eq!(list.remove_item(&target), None);
+        assert_eq!(list.len(), expected.len());
+        assert!(list.iter().eq(expected.iter()));
+        assert_eq!(list.back(), expected.last());
+    }
+
+    #[test]
+    fn list_iter_should_be_double_ended() {
+        let v = generate::uniform(101, 12);
+        let mut list = LinkedList::from(v.clone());
+
+        assert!(list.iter().rev().eq(v.iter().rev()));
+        assert!((&list).into_iter().rev().eq(v.iter().rev()));
+        assert_eq!(list.iter().len(), v.len());
+
+        // 从两端交替迭代，在中间相遇后停止
+        let mut iter = list.iter();
+        let mut expected = v.iter();
+        for i in 0..120 {
+            if i % 3 == 0 {
+                assert_eq!(iter.next_back(), expected.next_back());
+            } else {
+                assert_eq!(iter.next(), expected.next());
+            }
+            assert_eq!(iter.len(), expected.len());
+        }
+
+        for (i, elem) in list.iter_mut().rev().enumerate() {
+            *elem = i as u64;
+        }
+        assert!(list.iter().copied().eq((0..101).rev()));
+
+        let mut iter = list.iter_mut();
+        while let (Some(front), Some(back)) = (iter.next(), iter.next_back()) {
+            mem::swap(front, back);
+        }
+        assert!(list.iter().copied().eq(0..101));
+        assert_eq!(LinkedList::<i32>::new().iter().next_back(), None);
+    }
+
+    #[test]
+    fn list_rotate_should_match_vec_deque() {
+        let mut list = (0..17).collect::<LinkedList<_>>();
+        let mut expected = (0..17).collect::<VecDeque<_>>();
+        let handle = list.iter_nodes().nth(5).unwrap();
+        let mut rng = Rng::new(13);
+
+        for _ in 0..100 {
+            let k = rng.below(18) as usize;
+            if rng.below(2) == 0 {
+                list.rotate_left(k);
+                expected.rotate_left(k);
+            } else {
+                list.rotate_right(k);
+                expected.rotate_right(k);
+            }
+
+            assert!(list.iter().eq(expected.iter()));
+            assert!(list.iter().rev().eq(expected.iter().rev()));
+        }
+
+        // 结点没有被替换，句柄仍然有效
+        assert_eq!(list.value(handle), Some(&5));
+        let mut empty = LinkedList::<i32>::new();
+        empty.rotate_left(0);
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn list_merge_should_be_stable() {
+        /// 只按 key 比较，origin 记录来自哪个链表的第几个元素
+        #[derive(Debug, Clone, Copy)]
+        struct Tagged {
+            key: u64,
+            origin: (u8, usize),
+        }
+
+        impl PartialEq for Tagged {
+            fn eq(&self, other: &Self) -> bool {
+                self.key == other.key
+            }
+        }
+
+        impl Eq for Tagged {}
+
+        impl PartialOrd for Tagged {
+            fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+
+        impl Ord for Tagged {
+            fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+                self.key.cmp(&other.key)
+            }
+        }
+
+        let fields = |t: &Tagged| (t.key, t.origin);
+        for (n, m) in [(0, 0), (0, 5), (5, 0), (40, 25), (1, 60)] {
+            let mut a = generate::few_distinct(n, 10, n as u64);
+            let mut b = generate::few_distinct(m, 10, m as u64 + 1);
+            a.sort();
+            b.sort();
+            let tag = |list: u8, keys: &[u64]| {
+                keys.iter()
+                    .enumerate()
+                    .map(|(i, &key)| Tagged {
+                        key,
+                        origin: (list, i),
+                    })
+                    .collect::<Vec<_>>()
+            };
+            let (a, b) = (tag(0, &a), tag(1, &b));
+
+            let mut list = a.iter().copied().collect::<LinkedList<_>>();
+            let mut other = b.iter().copied().collect::<LinkedList<_>>();
+            let handle = other.iter_nodes().next();
+            list.merge(&mut other);
+
+            // 拼接两个输入后稳定排序：相等元素中原链表的在前，各自保持原来的顺序
+            let mut expected = a.iter().chain(&b).map(fields).collect::<Vec<_>>();
+            expected.sort_by_key(|&(key, _)| key);
+            assert!(list.iter().map(fields).eq(expected.iter().copied()));
+            assert!(
+                list.iter()
+                    .rev()
+                    .map(fields)
+                    .eq(expected.iter().rev().copied())
+            );
+            assert_eq!(list.len(), n + m);
+            assert!(other.is_empty() && other.iter().next().is_none());
+
+            // other 的结点被移走，它的句柄在两个链表中都失效
+            if let Some(handle) = handle {
+                assert_eq!(list.value(handle), None);
+                assert_eq!(other.value(handle), None);
+            }
+        }
+    }
+
     #[test]
     fn list_swap_should_work() {
         let mut list = LinkedList::from([1, 2, 3, 4, 5]);
@@ -830,4 +2555,45 @@ mod tests {
         list.swap(2, 4);
         assert_eq!(list, LinkedList::from([1, 2, 5, 4, 3]));
     }
+
+    #[test]
+    fn list_swap_should_match_slice() {
+        let mut list = (0..20).collect::<LinkedList<_>>();
+        let mut expected = (0..20).collect::<Vec<_>>();
+        let mut rng = Rng::new(9);
+
+        for _ in 0..200 {
+            let (i, j) = (rng.below(20) as usize, rng.below(20) as usize);
+            list.swap(i, j);
+            expected.swap(i, j);
+        }
+
+        assert!(list.iter().eq(expected.iter()));
+        assert!(list.iter().rev().eq(expected.iter().rev()));
+    }
+
+    #[test]
+    #[should_panic(expected = "Cannot swap at index: 3, len: 3")]
+    fn list_swap_should_panic_out_of_bounds() {
+        LinkedList::from([1, 2, 3]).swap(0, 3);
+    }
+
+    #[test]
+    fn list_snapshot_should_work() {
+        let mut list = LinkedList::from([1, 2, 3]);
+        let snapshot = list.snapshot();
+        let shared = snapshot.clone();
+
+        list.push_front(0);
+        *list.back_mut().unwrap() = 9;
+        assert_eq!(list, LinkedList::from([0, 1, 2, 9]));
+
+        assert!(snapshot.ptr_eq(&shared));
+        assert!(!snapshot.ptr_eq(&list.snapshot()));
+        assert_eq!(snapshot.len(), 3);
+        assert_eq!(snapshot.to_list(), LinkedList::from([1, 2, 3]));
+
+        list.restore(&snapshot);
+        assert_eq!(list, LinkedList::from([1, 2, 3]));
+    }
 }
@@ -0,0 +1,256 @@
+//! 链表的快照：不可变的持久化单链表
+//!
+//! 快照不与 [`LinkedList`] 共享结点：创建快照时从后往前复制一遍元素，时间复杂度 O(n)，
+//! 之后链表的修改不会影响快照，链表本身也不需要为快照做任何额外的记录，拆分、拼接依然是 O(1)。
+//!
+//! 快照的结点通过 `Rc` 共享且创建后不再修改，因此复制快照只增加引用计数，时间复杂度 O(1)；
+//! 在头部添加或去掉元素得到的新快照与原快照共享其余的结点，同样是 O(1)。
+
+use std::{fmt, rc::Rc};
+
+use super::LinkedList;
+
+struct SnapshotNode<T> {
+    elem: T,
+    next: Option<Rc<SnapshotNode<T>>>,
+}
+
+/// 链表的只读快照，复制快照的时间复杂度为 O(1)
+///
+/// # Examples
+///
+/// ```rust
+/// use base::ListSnapshot;
+///
+/// let base = ListSnapshot::new().push_front(3).push_front(2);
+/// let left = base.push_front(1);
+/// let right = base.push_front(0);
+///
+/// // left 和 right 共享 base 的结点
+/// assert_eq!(left.to_vec(), [1, 2, 3]);
+/// assert_eq!(right.to_vec(), [0, 2, 3]);
+/// assert!(left.tail().ptr_eq(&right.tail()));
+/// ```
+pub struct ListSnapshot<T> {
+    head: Option<Rc<SnapshotNode<T>>>,
+    len: usize,
+}
+
+impl<T> ListSnapshot<T> {
+    /// 创建空快照
+    pub fn new() -> Self {
+        Self { head: None, len: 0 }
+    }
+
+    /// 元素数量
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// 判断快照是否为空
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// 第一个元素
+    pub fn front(&self) -> Option<&T> {
+        self.head.as_ref().map(|node| &node.elem)
+    }
+
+    /// 在头部添加一个元素得到的新快照，与原快照共享其余的结点，时间复杂度：O(1)
+    pub fn push_front(&self, elem: T) -> Self {
+        Self {
+            head: Some(Rc::new(SnapshotNode {
+                elem,
+                next: self.head.clone(),
+            })),
+            len: self.len + 1,
+        }
+    }
+
+    /// 去掉第一个元素得到的新快照，空快照返回空快照，时间复杂度：O(1)
+    pub fn tail(&self) -> Self {
+        match &self.head {
+            Some(node) => Self {
+                head: node.next.clone(),
+                len: self.len - 1,
+            },
+            None => Self::new(),
+        }
+    }
+
+    /// 判断两个快照是否共享同一组结点
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        match (&self.head, &other.head) {
+            (Some(a), Some(b)) => Rc::ptr_eq(a, b),
+            (a, b) => a.is_none() && b.is_none(),
+        }
+    }
+
+    /// 按顺序返回不可变迭代器
+    pub fn iter(&self) -> SnapshotIter<'_, T> {
+        SnapshotIter {
+            next: self.head.as_deref(),
+            len: self.len,
+        }
+    }
+}
+
+impl<T: Clone> ListSnapshot<T> {
+    /// 按顺序复制快照中的元素
+    pub fn to_vec(&self) -> Vec<T> {
+        self.iter().cloned().collect()
+    }
+
+    /// 从快照重新构建一个链表
+    pub fn to_list(&self) -> LinkedList<T> {
+        self.iter().cloned().collect()
+    }
+}
+
+impl<T> Clone for ListSnapshot<T> {
+    fn clone(&self) -> Self {
+        Self {
+            head: self.head.clone(),
+            len: self.len,
+        }
+    }
+}
+
+impl<T> Default for ListSnapshot<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Drop for ListSnapshot<T> {
+    fn drop(&mut self) {
+        // 逐个释放只被这个快照引用的结点，避免长链表递归 drop 导致栈溢出；
+        // 遇到仍被其他快照共享的结点即可停止
+        let mut next = self.head.take();
+        while let Some(node) = next {
+            next = match Rc::try_unwrap(node) {
+                Ok(mut node) => node.next.take(),
+                Err(_) => None,
+            };
+        }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for ListSnapshot<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: PartialEq> PartialEq for ListSnapshot<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && self.iter().eq(other.iter())
+    }
+}
+
+impl<T: Eq> Eq for ListSnapshot<T> {}
+
+impl<'a, T> IntoIterator for &'a ListSnapshot<T> {
+    type Item = &'a T;
+    type IntoIter = SnapshotIter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// 快照的不可变迭代器
+pub struct SnapshotIter<'a, T> {
+    next: Option<&'a SnapshotNode<T>>,
+    len: usize,
+}
+
+impl<'a, T> Iterator for SnapshotIter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.next?;
+        self.next = node.next.as_deref();
+        self.len -= 1;
+
+        Some(&node.elem)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<T> ExactSizeIterator for SnapshotIter<'_, T> {}
+
+impl<T: Clone> LinkedList<T> {
+    /// 生成链表当前内容的只读快照，时间复杂度：O(n)
+    ///
+    /// 快照是不可变的持久化单链表，与链表互不影响，之后复制快照的时间复杂度为 O(1)，
+    /// 适合在试探性修改链表之前保存检查点，失败时通过 restore 回滚。
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use base::LinkedList;
+    ///
+    /// let mut list = LinkedList::from([1, 2, 3]);
+    /// let snapshot = list.snapshot();
+    ///
+    /// list.push_back(4);
+    /// list.reverse();
+    /// assert_eq!(snapshot.to_vec(), [1, 2, 3]);
+    ///
+    /// list.restore(&snapshot);
+    /// assert_eq!(list, LinkedList::from([1, 2, 3]));
+    /// ```
+    pub fn snapshot(&self) -> ListSnapshot<T> {
+        self.iter()
+            .rev()
+            .fold(ListSnapshot::new(), |snapshot, elem| {
+                snapshot.push_front(elem.clone())
+            })
+    }
+
+    /// 将链表内容恢复为快照中的内容，之前的句柄全部失效
+    pub fn restore(&mut self, snapshot: &ListSnapshot<T>) {
+        self.clear();
+        self.extend(snapshot.iter().cloned());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn list_snapshot_should_share_nodes() {
+        let list = LinkedList::from([2, 3]);
+        let base = list.snapshot();
+        let longer = base.push_front(1);
+
+        assert_eq!(longer.to_vec(), [1, 2, 3]);
+        assert_eq!(longer.front(), Some(&1));
+        assert!(longer.tail().ptr_eq(&base));
+        assert_eq!(longer.tail(), base);
+        assert_eq!(base.tail().tail().tail(), ListSnapshot::new());
+        assert!(ListSnapshot::<i32>::new().ptr_eq(&base.tail().tail()));
+
+        // 释放共享结点的快照不影响其他快照
+        drop(base);
+        assert_eq!(format!("{longer:?}"), "[1, 2, 3]");
+        assert_eq!(longer.iter().len(), 3);
+    }
+
+    #[test]
+    fn list_snapshot_should_drop_long_chains() {
+        let list = (0..100_000).collect::<LinkedList<_>>();
+        let snapshot = list.snapshot();
+        let shared = snapshot.tail();
+
+        drop(snapshot);
+        assert_eq!(shared.len(), 99_999);
+        assert_eq!(shared.front(), Some(&1));
+    }
+}
@@ -0,0 +1,397 @@
+//! 左偏红黑树（left-leaning red-black tree，LLRB）
+//!
+//! 红黑树与 2-3 树一一对应：把红色链接连接的两个结点看作 2-3 树中的一个 3-结点，
+//! 所有黑色链接构成的就是一棵完美平衡的 2-3 树。左偏红黑树额外要求红色链接只能是左链接，
+//! 使每棵 2-3 树只对应唯一的红黑树，插入和删除只需处理很少的情况：
+//! * 右链接为红色而左链接为黑色：左旋
+//! * 左链接和左子结点的左链接都为红色：右旋
+//! * 左右链接都为红色（4-结点）：颜色翻转，把中间的结点“上移”到父结点
+//!
+//! 插入时先按普通二叉搜索树插入一个红色结点，再沿路径自底向上执行以上三步。
+//! 删除时自顶向下保证当前结点不是 2-结点（借用兄弟结点或与父结点合并），删除后再自底向上修复。
+//! 树高不超过 2log(n + 1)，各操作的时间复杂度均为 O(logn)。
+
+use std::cmp::Ordering;
+
+use crate::OrderedSet;
+
+type Link<T> = Option<Box<LlrbNode<T>>>;
+
+#[derive(Debug, Clone)]
+struct LlrbNode<T> {
+    value: T,
+    /// 指向该结点的链接是否为红色
+    red: bool,
+    left: Link<T>,
+    right: Link<T>,
+}
+
+/// 左偏红黑树
+///
+/// # Examples
+///
+/// ```rust
+/// use base::LlrbTree;
+///
+/// let mut tree = LlrbTree::from_iter(["S", "E", "A", "R", "C", "H"]);
+///
+/// assert!(!tree.insert("A"));
+/// assert!(tree.remove(&"R"));
+/// assert!(tree.contains(&"H"));
+/// assert!(tree.iter().eq(&["A", "C", "E", "H", "S"]));
+/// ```
+#[derive(Debug, Clone)]
+pub struct LlrbTree<T> {
+    root: Link<T>,
+    len: usize,
+}
+
+impl<T> LlrbTree<T> {
+    /// 创建空树
+    pub fn new() -> Self {
+        Self { root: None, len: 0 }
+    }
+
+    /// 元素数量
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// 判断树是否为空
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// 按升序遍历
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        let mut stack = Vec::new();
+        let mut current = self.root.as_deref();
+
+        std::iter::from_fn(move || {
+            while let Some(node) = current {
+                stack.push(node);
+                current = node.left.as_deref();
+            }
+
+            let node = stack.pop()?;
+            current = node.right.as_deref();
+
+            Some(&node.value)
+        })
+    }
+}
+
+impl<T: Ord> LlrbTree<T> {
+    /// 判断树中是否包含元素
+    pub fn contains(&self, value: &T) -> bool {
+        let mut current = &self.root;
+
+        while let Some(node) = current {
+            match value.cmp(&node.value) {
+                Ordering::Less => current = &node.left,
+                Ordering::Equal => return true,
+                Ordering::Greater => current = &node.right,
+            }
+        }
+
+        false
+    }
+
+    /// 插入元素，树中已存在相同的元素时不插入并返回 false，时间复杂度：O(logn)
+    pub fn insert(&mut self, value: T) -> bool {
+        let mut inserted = false;
+        let mut root = insert(self.root.take(), value, &mut inserted);
+
+        // 根结点总是黑色
+        root.red = false;
+        self.root = Some(root);
+        if inserted {
+            self.len += 1;
+        }
+
+        inserted
+    }
+
+    /// 删除元素，元素不存在时返回 false，时间复杂度：O(logn)
+    pub fn remove(&mut self, value: &T) -> bool {
+        // 自顶向下的变换假定元素存在
+        if !self.contains(value) {
+            return false;
+        }
+
+        // Safety: 树中包含 value，根结点一定存在
+        let mut root = self.root.take().unwrap();
+        // 根结点的两个子结点都是黑色时，把根结点暂时看作 3-结点的一部分
+        if !is_red(&root.left) && !is_red(&root.right) {
+            root.red = true;
+        }
+        self.root = remove(root, value);
+        if let Some(root) = self.root.as_mut() {
+            root.red = false;
+        }
+        self.len -= 1;
+
+        true
+    }
+}
+
+impl<T> Default for LlrbTree<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Ord> Extend<T> for LlrbTree<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for value in iter {
+            self.insert(value);
+        }
+    }
+}
+
+impl<T: Ord> FromIterator<T> for LlrbTree<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut tree = Self::new();
+
+        tree.extend(iter);
+        tree
+    }
+}
+
+impl<T: Ord> OrderedSet<T> for LlrbTree<T> {
+    fn insert(&mut self, value: T) -> bool {
+        LlrbTree::insert(self, value)
+    }
+
+    fn remove(&mut self, value: &T) -> bool {
+        LlrbTree::remove(self, value)
+    }
+
+    fn contains(&self, value: &T) -> bool {
+        LlrbTree::contains(self, value)
+    }
+}
+
+fn is_red<T>(link: &Link<T>) -> bool {
+    link.as_ref().is_some_and(|node| node.red)
+}
+
+/// 左子结点的左链接是否为红色
+fn is_left_left_red<T>(node: &LlrbNode<T>) -> bool {
+    node.left.as_ref().is_some_and(|left| is_red(&left.left))
+}
+
+/// 右子结点的左链接是否为红色
+fn is_right_left_red<T>(node: &LlrbNode<T>) -> bool {
+    node.right.as_ref().is_some_and(|right| is_red(&right.left))
+}
+
+// 左旋操作：把右侧的红色链接转到左侧
+fn rotate_left<T>(mut node: Box<LlrbNode<T>>) -> Box<LlrbNode<T>> {
+    // Safety: 只在右链接为红色时左旋，右子结点一定存在
+    let mut child = node.right.take().unwrap();
+
+    node.right = child.left.take();
+    child.red = node.red;
+    node.red = true;
+    child.left = Some(node);
+
+    child
+}
+
+// 右旋操作：把左侧的红色链接转到右侧
+fn rotate_right<T>(mut node: Box<LlrbNode<T>>) -> Box<LlrbNode<T>> {
+    // Safety: 只在左链接为红色时右旋，左子结点一定存在
+    let mut child = node.left.take().unwrap();
+
+    node.left = child.right.take();
+    child.red = node.red;
+    node.red = true;
+    child.right = Some(node);
+
+    child
+}
+
+/// 翻转结点和两个子结点的颜色
+///
+/// 插入时把 4-结点拆开、中间结点上移；删除时反过来，与兄弟结点合并为 4-结点
+fn flip_colors<T>(node: &mut LlrbNode<T>) {
+    node.red = !node.red;
+    for child in [&mut node.left, &mut node.right].into_iter().flatten() {
+        child.red = !child.red;
+    }
+}
+
+/// 自底向上恢复左偏红黑树的性质
+fn fix_up<T>(mut node: Box<LlrbNode<T>>) -> Box<LlrbNode<T>> {
+    if is_red(&node.right) && !is_red(&node.left) {
+        node = rotate_left(node);
+    }
+    if is_red(&node.left) && is_left_left_red(&node) {
+        node = rotate_right(node);
+    }
+    if is_red(&node.left) && is_red(&node.right) {
+        flip_colors(&mut node);
+    }
+
+    node
+}
+
+fn insert<T: Ord>(link: Link<T>, value: T, inserted: &mut bool) -> Box<LlrbNode<T>> {
+    let Some(mut node) = link else {
+        *inserted = true;
+        return Box::new(LlrbNode {
+            value,
+            red: true,
+            left: None,
+            right: None,
+        });
+    };
+
+    match value.cmp(&node.value) {
+        Ordering::Less => node.left = Some(insert(node.left.take(), value, inserted)),
+        Ordering::Equal => {}
+        Ordering::Greater => node.right = Some(insert(node.right.take(), value, inserted)),
+    }
+
+    fix_up(node)
+}
+
+/// 保证左子结点不是 2-结点：从右侧的兄弟结点借一个结点，或者合并为 4-结点
+fn move_red_left<T>(mut node: Box<LlrbNode<T>>) -> Box<LlrbNode<T>> {
+    flip_colors(&mut node);
+    if is_right_left_red(&node) {
+        node.right = node.right.take().map(rotate_right);
+        node = rotate_left(node);
+        flip_colors(&mut node);
+    }
+
+    node
+}
+
+/// 保证右子结点不是 2-结点
+fn move_red_right<T>(mut node: Box<LlrbNode<T>>) -> Box<LlrbNode<T>> {
+    flip_colors(&mut node);
+    if is_left_left_red(&node) {
+        node = rotate_right(node);
+        flip_colors(&mut node);
+    }
+
+    node
+}
+
+/// 删除子树中的最小结点，返回 (剩余的子树, 最小值)
+fn remove_min<T>(mut node: Box<LlrbNode<T>>) -> (Link<T>, T) {
+    // 左偏红黑树中没有左子结点的结点也没有右子结点
+    let Some(left) = node.left.as_ref() else {
+        return (None, node.value);
+    };
+
+    if !left.red && !is_red(&left.left) {
+        node = move_red_left(node);
+    }
+    // Safety: move_red_left 不会移除左子结点
+    let (left, min) = remove_min(node.left.take().unwrap());
+    node.left = left;
+
+    (Some(fix_up(node)), min)
+}
+
+/// 删除子树中的 value，调用者保证 value 存在
+fn remove<T: Ord>(mut node: Box<LlrbNode<T>>, value: &T) -> Link<T> {
+    if *value < node.value {
+        if !is_red(&node.left) && !is_left_left_red(&node) {
+            node = move_red_left(node);
+        }
+        // Safety: value 在左子树中，左子结点一定存在
+        node.left = remove(node.left.take().unwrap(), value);
+    } else {
+        if is_red(&node.left) {
+            node = rotate_right(node);
+        }
+        if *value == node.value && node.right.is_none() {
+            return None;
+        }
+        if !is_red(&node.right) && !is_right_left_red(&node) {
+            node = move_red_right(node);
+        }
+
+        // Safety: value 等于当前结点时右子结点存在，大于当前结点时 value 在右子树中
+        let right = node.right.take().unwrap();
+        if *value == node.value {
+            // 用右子树的最小值替换当前结点的值
+            let (right, min) = remove_min(right);
+            node.value = min;
+            node.right = right;
+        } else {
+            node.right = remove(right, value);
+        }
+    }
+
+    Some(fix_up(node))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use super::*;
+    use crate::generate::{self, Operation};
+
+    /// 检查左偏红黑树的性质，返回黑高
+    fn check<T: Ord>(link: &Link<T>, parent_red: bool) -> usize {
+        let Some(node) = link else {
+            return 0;
+        };
+
+        assert!(!is_red(&node.right), "right-leaning red link");
+        assert!(!(parent_red && node.red), "two red links in a row");
+        assert!(node.left.as_ref().is_none_or(|l| l.value < node.value));
+        assert!(node.right.as_ref().is_none_or(|r| r.value > node.value));
+
+        let left = check(&node.left, node.red);
+        let right = check(&node.right, node.red);
+        assert_eq!(left, right, "unbalanced black height");
+
+        left + usize::from(!node.red)
+    }
+
+    #[test]
+    fn llrb_tree_should_match_btree_set() {
+        let mut tree = LlrbTree::new();
+        let mut expected = BTreeSet::new();
+
+        for op in generate::operations(5000, 500, 2) {
+            match op {
+                Operation::Insert(key) => assert_eq!(tree.insert(key), expected.insert(key)),
+                Operation::Remove(key) => assert_eq!(tree.remove(&key), expected.remove(&key)),
+                Operation::Get(key) => assert_eq!(tree.contains(&key), expected.contains(&key)),
+            }
+
+            assert_eq!(tree.len(), expected.len());
+            assert!(!is_red(&tree.root));
+            check(&tree.root, false);
+        }
+
+        assert!(tree.iter().eq(expected.iter()));
+    }
+
+    #[test]
+    fn llrb_tree_should_stay_balanced_on_sorted_input() {
+        let mut tree = (0..4096).collect::<LlrbTree<_>>();
+        // 4096 个结点的 2-3 树高度不超过 12
+        assert!(check(&tree.root, false) <= 12);
+
+        for key in 0..4000 {
+            assert!(tree.remove(&key));
+            check(&tree.root, false);
+        }
+        assert!(tree.iter().copied().eq(4000..4096));
+
+        for key in 4000..4096 {
+            assert!(tree.remove(&key));
+        }
+        assert!(tree.is_empty());
+        assert!(tree.root.is_none());
+    }
+}
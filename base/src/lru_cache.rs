@@ -0,0 +1,294 @@
+//! LRU（least recently used）缓存
+//!
+//! 容量有限的键值缓存，已满时淘汰最久未被访问的键值对。
+//!
+//! 哈希表把键映射到结点下标，结点组成按访问时间排序的双向链表（表头最新、表尾最旧）。
+//! 访问一个键时把它的结点移到表头，插入时若已满则删除表尾结点，各操作的时间复杂度均为 O(1)。
+//! 结点保存在数组中、通过下标互相连接，删除的结点下标放入空闲列表以便复用。
+
+use std::hash::Hash;
+
+use crate::ChainedHashMap;
+
+#[derive(Debug, Clone)]
+struct LruNode<K, V> {
+    key: K,
+    value: V,
+    prev: Option<usize>,
+    next: Option<usize>,
+}
+
+/// LRU 缓存
+///
+/// # Examples
+///
+/// ```rust
+/// use base::LruCache;
+///
+/// let mut cache = LruCache::new(2);
+/// cache.put("a", 1);
+/// cache.put("b", 2);
+///
+/// // 访问 a 之后，b 成为最久未访问的键
+/// assert_eq!(cache.get(&"a"), Some(&1));
+/// assert_eq!(cache.put("c", 3), Some(("b", 2)));
+///
+/// assert!(!cache.contains(&"b"));
+/// assert_eq!(cache.iter().collect::<Vec<_>>(), [(&"c", &3), (&"a", &1)]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct LruCache<K, V> {
+    index: ChainedHashMap<K, usize>,
+    nodes: Vec<Option<LruNode<K, V>>>,
+    /// 已删除、可复用的结点下标
+    free: Vec<usize>,
+    /// 最近访问的结点
+    head: Option<usize>,
+    /// 最久未访问的结点
+    tail: Option<usize>,
+    capacity: usize,
+}
+
+impl<K, V> LruCache<K, V> {
+    /// 创建容量为 capacity 的空缓存
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity == 0`.
+    pub fn new(capacity: usize) -> Self {
+        assert!(capacity > 0, "Capacity must be positive");
+
+        Self {
+            index: ChainedHashMap::new(),
+            nodes: Vec::with_capacity(capacity),
+            free: Vec::new(),
+            head: None,
+            tail: None,
+            capacity,
+        }
+    }
+
+    /// 容量
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// 键值对数量
+    pub fn len(&self) -> usize {
+        self.index.len()
+    }
+
+    /// 判断缓存是否为空
+    pub fn is_empty(&self) -> bool {
+        self.index.is_empty()
+    }
+
+    /// 从最近访问到最久未访问遍历所有键值对
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> {
+        let mut current = self.head;
+
+        std::iter::from_fn(move || {
+            let node = self.node(current?);
+            current = node.next;
+            Some((&node.key, &node.value))
+        })
+    }
+
+    fn node(&self, index: usize) -> &LruNode<K, V> {
+        // Safety: 链表和哈希表中的下标一定指向有效的结点
+        self.nodes[index].as_ref().unwrap()
+    }
+
+    fn node_mut(&mut self, index: usize) -> &mut LruNode<K, V> {
+        // Safety: 同上
+        self.nodes[index].as_mut().unwrap()
+    }
+
+    /// 把结点从链表中摘下
+    fn unlink(&mut self, index: usize) {
+        let (prev, next) = {
+            let node = self.node(index);
+            (node.prev, node.next)
+        };
+
+        match prev {
+            Some(prev) => self.node_mut(prev).next = next,
+            None => self.head = next,
+        }
+        match next {
+            Some(next) => self.node_mut(next).prev = prev,
+            None => self.tail = prev,
+        }
+    }
+
+    /// 把结点插入到表头
+    fn push_front(&mut self, index: usize) {
+        let head = self.head;
+        {
+            let node = self.node_mut(index);
+            node.prev = None;
+            node.next = head;
+        }
+
+        match head {
+            Some(head) => self.node_mut(head).prev = Some(index),
+            None => self.tail = Some(index),
+        }
+        self.head = Some(index);
+    }
+
+    /// 把结点移到表头，标记为最近访问
+    fn touch(&mut self, index: usize) {
+        if self.head != Some(index) {
+            self.unlink(index);
+            self.push_front(index);
+        }
+    }
+}
+
+impl<K: Hash + Eq + Clone, V> LruCache<K, V> {
+    /// 获取键对应的值，并将其标记为最近访问
+    pub fn get(&mut self, key: &K) -> Option<&V> {
+        let index = *self.index.get(key)?;
+        self.touch(index);
+
+        Some(&self.node(index).value)
+    }
+
+    /// 获取键对应的值的可变借用，并将其标记为最近访问
+    pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        let index = *self.index.get(key)?;
+        self.touch(index);
+
+        Some(&mut self.node_mut(index).value)
+    }
+
+    /// 获取键对应的值，不改变访问顺序
+    pub fn peek(&self, key: &K) -> Option<&V> {
+        self.index.get(key).map(|&index| &self.node(index).value)
+    }
+
+    /// 判断是否包含键，不改变访问顺序
+    pub fn contains(&self, key: &K) -> bool {
+        self.index.contains_key(key)
+    }
+
+    /// 插入键值对并将其标记为最近访问，返回被淘汰的键值对
+    ///
+    /// 键已存在时替换它的值，返回旧的键值对；否则缓存已满时淘汰最久未访问的键值对。
+    pub fn put(&mut self, key: K, value: V) -> Option<(K, V)> {
+        if let Some(&index) = self.index.get(&key) {
+            self.touch(index);
+            let node = self.node_mut(index);
+            let old_value = std::mem::replace(&mut node.value, value);
+            return Some((key, old_value));
+        }
+
+        let evicted = if self.len() == self.capacity {
+            // Safety: 缓存已满且容量大于 0，表尾一定存在
+            let tail = self.tail.unwrap();
+            let key = self.node(tail).key.clone();
+            self.remove(&key).map(|value| (key, value))
+        } else {
+            None
+        };
+
+        let node = LruNode {
+            key: key.clone(),
+            value,
+            prev: None,
+            next: None,
+        };
+        let index = match self.free.pop() {
+            Some(index) => {
+                self.nodes[index] = Some(node);
+                index
+            }
+            None => {
+                self.nodes.push(Some(node));
+                self.nodes.len() - 1
+            }
+        };
+        self.push_front(index);
+        self.index.insert(key, index);
+
+        evicted
+    }
+
+    /// 删除键值对并返回它的值
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        let index = self.index.remove(key)?;
+        self.unlink(index);
+        self.free.push(index);
+
+        self.nodes[index].take().map(|node| node.value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generate::{self, Operation};
+
+    #[test]
+    fn lru_cache_should_match_brute_force() {
+        let mut cache = LruCache::new(8);
+        // 按访问时间从新到旧排列的键值对
+        let mut expected: Vec<(u64, usize)> = Vec::new();
+
+        for (i, op) in generate::operations(5000, 20, 4).into_iter().enumerate() {
+            match op {
+                Operation::Insert(key) => {
+                    let evicted = cache.put(key, i);
+                    match expected.iter().position(|&(k, _)| k == key) {
+                        Some(pos) => {
+                            let (k, v) = expected.remove(pos);
+                            assert_eq!(evicted, Some((k, v)));
+                        }
+                        None if expected.len() == 8 => assert_eq!(evicted, expected.pop()),
+                        None => assert_eq!(evicted, None),
+                    }
+                    expected.insert(0, (key, i));
+                }
+                Operation::Remove(key) => {
+                    let pos = expected.iter().position(|&(k, _)| k == key);
+                    let removed = pos.map(|pos| expected.remove(pos).1);
+                    assert_eq!(cache.remove(&key), removed);
+                }
+                Operation::Get(key) => {
+                    let pos = expected.iter().position(|&(k, _)| k == key);
+                    let value = pos.map(|pos| expected.remove(pos).1);
+                    assert_eq!(cache.get(&key), value.as_ref());
+                    if let Some(value) = value {
+                        expected.insert(0, (key, value));
+                    }
+                }
+            }
+
+            assert_eq!(cache.len(), expected.len());
+            assert!(
+                cache
+                    .iter()
+                    .map(|(&k, &v)| (k, v))
+                    .eq(expected.iter().copied())
+            );
+        }
+
+        // 结点下标被复用，数组不会超过容量
+        assert!(cache.nodes.len() <= cache.capacity());
+    }
+
+    #[test]
+    fn lru_cache_peek_should_not_touch() {
+        let mut cache = LruCache::new(2);
+        cache.put(1, "one");
+        cache.put(2, "two");
+
+        assert_eq!(cache.peek(&1), Some(&"one"));
+        assert_eq!(cache.put(3, "three"), Some((1, "one")));
+
+        *cache.get_mut(&2).unwrap() = "deux";
+        assert_eq!(cache.put(4, "four"), Some((3, "three")));
+        assert_eq!(cache.peek(&2), Some(&"deux"));
+    }
+}
@@ -0,0 +1,266 @@
+//! 数论与组合数学
+//!
+//! * 快速幂：把指数写成二进制，a^e = ∏ a^(2^i)（e 的第 i 位为 1），只需 O(loge) 次乘法
+//! * 组合数取模：预处理 [0, n] 的阶乘及其逆元，C(n, k) = n! / (k!(n-k)!) 只需两次乘法。
+//!   模数 p 为素数时，由费马小定理 a^(p-2) ≡ a^(-1) (mod p)，用快速幂求出 n! 的逆元，
+//!   再由 1/(i-1)! = i / i! 依次递推出其余的逆元
+//! * 卡特兰数：C_n = C(2n, n) / (n + 1) = C(2n, n) - C(2n, n + 1)，
+//!   n 个结点的二叉树形态数、n 对括号的合法序列数、凸 n + 2 边形的三角剖分数都是 C_n
+//!
+//! 精确计算的 [`binomial`] 和 [`catalan`] 在结果超出 u64 时返回 None，适合在测试中验证计数；
+//! 更大的规模使用 [`Factorials`] 取模计算。
+//!
+//! 多项式乘法（快速数论变换）见 [`fft`]。
+
+pub mod fft;
+
+/// 快速幂取模：base^exp mod modulus，时间复杂度：O(logexp)
+///
+/// # Panics
+///
+/// Panics if `modulus == 0`.
+///
+/// # Examples
+///
+/// ```rust
+/// use base::math;
+///
+/// assert_eq!(math::pow_mod(2, 10, 1000), 24);
+/// assert_eq!(math::pow_mod(3, 0, 7), 1);
+/// // 费马小定理：a^(p-1) ≡ 1 (mod p)
+/// assert_eq!(math::pow_mod(12345, 1_000_000_006, 1_000_000_007), 1);
+/// ```
+pub fn pow_mod(base: u64, mut exp: u64, modulus: u64) -> u64 {
+    assert!(modulus > 0, "Modulus must be positive");
+
+    let mul = |a: u64, b: u64| (a as u128 * b as u128 % modulus as u128) as u64;
+    let mut base = base % modulus;
+    let mut result = 1 % modulus;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = mul(result, base);
+        }
+        base = mul(base, base);
+        exp >>= 1;
+    }
+
+    result
+}
+
+/// 组合数 C(n, k) 的精确值，k > n 时为 0，超出 u64 时返回 None
+///
+/// 依次计算 C(n-k+1, 1), C(n-k+2, 2), ..., C(n, k)，每一步的乘积都能被 i 整除，时间复杂度：O(k)
+///
+/// # Examples
+///
+/// ```rust
+/// use base::math;
+///
+/// assert_eq!(math::binomial(5, 2), Some(10));
+/// assert_eq!(math::binomial(3, 5), Some(0));
+/// assert_eq!(math::binomial(67, 33), Some(14_226_520_737_620_288_370));
+/// assert_eq!(math::binomial(68, 34), None);
+/// ```
+pub fn binomial(n: u64, k: u64) -> Option<u64> {
+    if k > n {
+        return Some(0);
+    }
+
+    let k = k.min(n - k);
+    let mut result = 1u64;
+    for i in 1..=k {
+        // C(n-k+i, i) = C(n-k+i-1, i-1) * (n-k+i) / i，结果递增，中间结果超出 u64 时最终结果也超出
+        let next = result as u128 * (n - k + i) as u128 / i as u128;
+        result = u64::try_from(next).ok()?;
+    }
+
+    Some(result)
+}
+
+/// 第 n 个卡特兰数的精确值，超出 u64（n > 36）时返回 None
+///
+/// 递推 C_(i+1) = C_i * 2(2i + 1) / (i + 2)，时间复杂度：O(n)
+///
+/// # Examples
+///
+/// ```rust
+/// use base::math;
+///
+/// let first = (0..8).map(|n| math::catalan(n).unwrap()).collect::<Vec<_>>();
+///
+/// assert_eq!(first, [1, 1, 2, 5, 14, 42, 132, 429]);
+/// assert!(math::catalan(36).is_some());
+/// assert!(math::catalan(37).is_none());
+/// ```
+pub fn catalan(n: u64) -> Option<u64> {
+    let mut result = 1u64;
+    for i in 0..n {
+        let next = result as u128 * (4 * i + 2) as u128 / (i + 2) as u128;
+        result = u64::try_from(next).ok()?;
+    }
+
+    Some(result)
+}
+
+/// 预处理的阶乘及其逆元，O(1) 计算模素数 p 的组合数
+///
+/// # Examples
+///
+/// ```rust
+/// use base::math::Factorials;
+///
+/// let f = Factorials::new(200_000, 1_000_000_007);
+///
+/// assert_eq!(f.binomial(5, 2), 10);
+/// assert_eq!(f.binomial(100_000, 50_000), 149_033_233);
+/// assert_eq!(f.catalan(4), 14);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Factorials {
+    modulus: u64,
+    /// fact[i] = i! mod p
+    fact: Vec<u64>,
+    /// inv_fact[i] = (i!)^(-1) mod p
+    inv_fact: Vec<u64>,
+}
+
+impl Factorials {
+    /// 预处理 [0, max_n] 的阶乘，modulus 必须是大于 max_n 的素数，时间复杂度：O(max_n + logp)
+    ///
+    /// # Panics
+    ///
+    /// Panics if `modulus < 2` or `modulus <= max_n`.
+    pub fn new(max_n: usize, modulus: u64) -> Self {
+        assert!(modulus >= 2, "Modulus {modulus} must be a prime");
+        assert!(
+            modulus > max_n as u64,
+            "Modulus {modulus} must be a prime greater than {max_n}"
+        );
+
+        let mul = |a: u64, b: u64| (a as u128 * b as u128 % modulus as u128) as u64;
+        let mut fact = vec![1 % modulus; max_n + 1];
+        for i in 1..=max_n {
+            fact[i] = mul(fact[i - 1], i as u64);
+        }
+        let mut inv_fact = vec![0; max_n + 1];
+        inv_fact[max_n] = pow_mod(fact[max_n], modulus - 2, modulus);
+        for i in (1..=max_n).rev() {
+            inv_fact[i - 1] = mul(inv_fact[i], i as u64);
+        }
+
+        Self {
+            modulus,
+            fact,
+            inv_fact,
+        }
+    }
+
+    /// 模数
+    pub fn modulus(&self) -> u64 {
+        self.modulus
+    }
+
+    /// n! mod p
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n > max_n`.
+    pub fn factorial(&self, n: usize) -> u64 {
+        self.fact[n]
+    }
+
+    /// C(n, k) mod p，k > n 时为 0
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n > max_n`.
+    pub fn binomial(&self, n: usize, k: usize) -> u64 {
+        if k > n {
+            return 0;
+        }
+
+        let result = self.mul(self.fact[n], self.inv_fact[k]);
+        self.mul(result, self.inv_fact[n - k])
+    }
+
+    /// 第 n 个卡特兰数 mod p
+    ///
+    /// # Panics
+    ///
+    /// Panics if `2n > max_n`.
+    pub fn catalan(&self, n: usize) -> u64 {
+        let p = self.modulus;
+
+        (self.binomial(2 * n, n) + p - self.binomial(2 * n, n + 1)) % p
+    }
+
+    fn mul(&self, a: u64, b: u64) -> u64 {
+        (a as u128 * b as u128 % self.modulus as u128) as u64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generate::Rng;
+
+    const P: u64 = 1_000_000_007;
+
+    #[test]
+    fn pow_mod_should_match_repeated_multiplication() {
+        let mut rng = Rng::new(17);
+        for _ in 0..100 {
+            let (base, exp, modulus) = (rng.next_u64(), rng.below(200), rng.below(1 << 40) + 1);
+            let expected = (0..exp).fold(1 % modulus, |acc, _| {
+                (acc as u128 * base as u128 % modulus as u128) as u64
+            });
+            assert_eq!(pow_mod(base, exp, modulus), expected);
+        }
+
+        assert_eq!(pow_mod(5, 3, 1), 0);
+        assert_eq!(pow_mod(u64::MAX, u64::MAX, u64::MAX - 1), 1);
+    }
+
+    #[test]
+    fn binomial_should_match_pascal_triangle() {
+        let f = Factorials::new(1000, P);
+        let mut row = vec![1u64];
+        for n in 0..=67 {
+            for (k, &c) in row.iter().enumerate() {
+                assert_eq!(binomial(n, k as u64), Some(c));
+                assert_eq!(f.binomial(n as usize, k), c % P);
+            }
+            assert_eq!(binomial(n, n + 1), Some(0));
+            assert_eq!(f.binomial(n as usize, n as usize + 1), 0);
+
+            // Pascal 恒等式 C(n+1, k) = C(n, k-1) + C(n, k)，第 67 行中间的数会超出 u64
+            row = (0..=row.len())
+                .map(|k| {
+                    let left = k.checked_sub(1).map_or(0, |k| row[k]);
+                    left.saturating_add(row.get(k).copied().unwrap_or(0))
+                })
+                .collect();
+        }
+    }
+
+    #[test]
+    fn catalan_should_match_recurrence() {
+        // C_(n+1) = Σ C_i * C_(n-i)，即左子树有 i 个结点的二叉树形态数之和
+        let f = Factorials::new(100, P);
+        let mut exact = vec![1u128];
+        for n in 0..50 {
+            let expected = exact[n as usize];
+            assert_eq!(catalan(n), u64::try_from(expected).ok());
+            assert_eq!(f.catalan(n as usize) as u128, expected % P as u128);
+
+            let next = (0..exact.len()).map(|i| exact[i] * exact[exact.len() - 1 - i]);
+            exact.push(next.sum());
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Modulus 1 must be a prime")]
+    fn factorials_should_reject_modulus_below_two() {
+        Factorials::new(0, 1);
+    }
+}
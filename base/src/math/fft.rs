@@ -0,0 +1,179 @@
+//! 快速数论变换（number-theoretic transform，NTT）与多项式乘法
+//!
+//! 两个多项式的乘积的系数是它们系数的卷积，直接计算需要 O(nm)。
+//! 先求出两个多项式在 n 个单位根处的值（离散傅里叶变换），逐点相乘，再插值回系数（逆变换），
+//! 用分治的蝴蝶运算完成变换只需 O(nlogn)。
+//!
+//! 复数 FFT 有浮点误差，系数较大时结果不精确；NTT 在模素数 p = 998244353 = 119·2^23 + 1 的域中进行，
+//! 用原根 3 的幂代替复数单位根，结果是精确的模 p 余数，长度最多为 2^23。
+//! 真实的系数都小于 p 时，模 p 的结果就是真实结果，例如大整数乘法中每一位的乘积之和。
+//!
+//! 变换采用迭代实现：先按下标的二进制逆序重排，再自底向上合并长度为 2, 4, 8, ... 的区间。
+
+use super::pow_mod;
+
+/// NTT 的模数
+pub const MODULUS: u64 = 998_244_353;
+/// 模数的原根
+const ROOT: u64 = 3;
+/// 变换的最大长度
+pub const MAX_LEN: usize = 1 << 23;
+/// 较短的多项式不超过该长度时直接按定义计算
+const NAIVE_LEN: usize = 32;
+
+/// 原地进行数论变换，invert 为 true 时进行逆变换，时间复杂度：O(nlogn)
+///
+/// # Panics
+///
+/// Panics if `a.len()` is not a power of two or is greater than [`MAX_LEN`].
+///
+/// # Examples
+///
+/// ```rust
+/// use base::math::fft;
+///
+/// let mut a = vec![1, 2, 3, 4];
+/// fft::ntt(&mut a, false);
+/// // 第 0 项是各系数之和，即多项式在 x = 1 处的值
+/// assert_eq!(a[0], 10);
+///
+/// fft::ntt(&mut a, true);
+/// assert_eq!(a, [1, 2, 3, 4]);
+/// ```
+pub fn ntt(a: &mut [u64], invert: bool) {
+    let n = a.len();
+    assert!(
+        n.is_power_of_two() && n <= MAX_LEN,
+        "Length must be a power of two not greater than {MAX_LEN}: {n}"
+    );
+
+    // 按下标的二进制逆序重排
+    let mut j = 0;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j |= bit;
+        if i < j {
+            a.swap(i, j);
+        }
+    }
+
+    let mut len = 2;
+    while len <= n {
+        // len 次单位根，逆变换使用它的逆元
+        let mut w = pow_mod(ROOT, (MODULUS - 1) / len as u64, MODULUS);
+        if invert {
+            w = pow_mod(w, MODULUS - 2, MODULUS);
+        }
+        for chunk in a.chunks_exact_mut(len) {
+            let (lo, hi) = chunk.split_at_mut(len / 2);
+            let mut wk = 1;
+            for (u, v) in lo.iter_mut().zip(hi) {
+                let t = *v * wk % MODULUS;
+                *v = (*u + MODULUS - t) % MODULUS;
+                *u = (*u + t) % MODULUS;
+                wk = wk * w % MODULUS;
+            }
+        }
+        len <<= 1;
+    }
+
+    if invert {
+        let n_inv = pow_mod(n as u64, MODULUS - 2, MODULUS);
+        for x in a {
+            *x = *x * n_inv % MODULUS;
+        }
+    }
+}
+
+/// 多项式乘法，系数从低次到高次排列，结果的系数对 [`MODULUS`] 取模，长度为 a.len() + b.len() - 1
+///
+/// 较短的多项式很短时直接计算 O(nm)，否则用 NTT 计算 O((n + m)log(n + m))
+///
+/// # Panics
+///
+/// Panics if both polynomials are longer than 32 and the product is longer than [`MAX_LEN`].
+///
+/// # Examples
+///
+/// ```rust
+/// use base::math::fft;
+///
+/// // (1 + 2x)(3 + 4x + 5x²) = 3 + 10x + 13x² + 10x³
+/// assert_eq!(fft::multiply_polynomials(&[1, 2], &[3, 4, 5]), [3, 10, 13, 10]);
+/// assert!(fft::multiply_polynomials(&[], &[1, 2]).is_empty());
+/// ```
+pub fn multiply_polynomials(a: &[u64], b: &[u64]) -> Vec<u64> {
+    if a.is_empty() || b.is_empty() {
+        return Vec::new();
+    }
+    let len = a.len() + b.len() - 1;
+
+    if a.len().min(b.len()) <= NAIVE_LEN {
+        let mut product = vec![0; len];
+        for (i, &x) in a.iter().enumerate() {
+            for (j, &y) in b.iter().enumerate() {
+                product[i + j] = (product[i + j] + x % MODULUS * (y % MODULUS)) % MODULUS;
+            }
+        }
+        return product;
+    }
+
+    let n = len.next_power_of_two();
+    let transform = |p: &[u64]| {
+        let mut v = p.iter().map(|x| x % MODULUS).collect::<Vec<_>>();
+        v.resize(n, 0);
+        ntt(&mut v, false);
+        v
+    };
+    let mut fa = transform(a);
+    let fb = transform(b);
+    for (x, y) in fa.iter_mut().zip(fb) {
+        *x = *x * y % MODULUS;
+    }
+    ntt(&mut fa, true);
+    fa.truncate(len);
+
+    fa
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generate::Rng;
+
+    #[test]
+    fn multiply_polynomials_should_match_naive() {
+        let mut rng = Rng::new(19);
+        for (n, m) in [(1, 1), (33, 33), (100, 40), (257, 300), (1000, 1)] {
+            let a = (0..n).map(|_| rng.below(MODULUS * 3)).collect::<Vec<_>>();
+            let b = (0..m).map(|_| rng.below(MODULUS)).collect::<Vec<_>>();
+
+            let mut expected = vec![0u128; n + m - 1];
+            for (i, &x) in a.iter().enumerate() {
+                for (j, &y) in b.iter().enumerate() {
+                    expected[i + j] += x as u128 * y as u128;
+                }
+            }
+            let expected = expected.iter().map(|&x| (x % MODULUS as u128) as u64);
+
+            assert!(multiply_polynomials(&a, &b).into_iter().eq(expected));
+            assert_eq!(multiply_polynomials(&a, &b), multiply_polynomials(&b, &a));
+        }
+    }
+
+    #[test]
+    fn ntt_should_invert() {
+        let mut rng = Rng::new(23);
+        for n in [1, 2, 8, 1024] {
+            let original = (0..n).map(|_| rng.below(MODULUS)).collect::<Vec<_>>();
+            let mut a = original.clone();
+            ntt(&mut a, false);
+            ntt(&mut a, true);
+            assert_eq!(a, original);
+        }
+    }
+}
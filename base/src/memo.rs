@@ -0,0 +1,208 @@
+//! 记忆化（memoization）：自顶向下的动态规划
+//!
+//! 递归地描述子问题之间的关系，再把每个子问题的结果缓存起来，相同的子问题只计算一次。
+//! 与自底向上填表相比，只会计算真正用到的子问题，也不必事先确定计算顺序。
+//!
+//! [`memoize`] 接收的函数以“递归调用自身的函数”为第一个参数，记忆化后的递归调用都经过缓存：
+//! * [`memoize`]：用哈希表缓存所有结果
+//! * [`memoize_lru`]：用 [`LruCache`] 只缓存最近用到的结果，内存有上限，
+//!   适合子问题很多但只有局部会被反复用到的情形
+//!
+//! 递归深度与直接递归相同，子问题依赖链很长时仍可能栈溢出。
+
+use std::{cell::RefCell, collections::HashMap, hash::Hash};
+
+use crate::LruCache;
+
+/// 记忆化使用的缓存
+trait Cache<A, R> {
+    fn lookup(&mut self, arg: &A) -> Option<R>;
+    fn store(&mut self, arg: A, result: R);
+}
+
+impl<A: Hash + Eq, R: Clone> Cache<A, R> for HashMap<A, R> {
+    fn lookup(&mut self, arg: &A) -> Option<R> {
+        self.get(arg).cloned()
+    }
+
+    fn store(&mut self, arg: A, result: R) {
+        self.insert(arg, result);
+    }
+}
+
+impl<A: Hash + Eq + Clone, R: Clone> Cache<A, R> for LruCache<A, R> {
+    fn lookup(&mut self, arg: &A) -> Option<R> {
+        self.get(arg).cloned()
+    }
+
+    fn store(&mut self, arg: A, result: R) {
+        self.put(arg, result);
+    }
+}
+
+struct Memo<F, C> {
+    f: F,
+    cache: RefCell<C>,
+}
+
+impl<F, C> Memo<F, C> {
+    fn call<A, R>(&self, arg: A) -> R
+    where
+        A: Clone,
+        R: Clone,
+        F: Fn(&dyn Fn(A) -> R, A) -> R,
+        C: Cache<A, R>,
+    {
+        // 计算期间不能持有缓存的借用，递归调用还要访问缓存
+        if let Some(result) = self.cache.borrow_mut().lookup(&arg) {
+            return result;
+        }
+
+        let result = (self.f)(&|arg| self.call(arg), arg.clone());
+        self.cache.borrow_mut().store(arg, result.clone());
+
+        result
+    }
+}
+
+/// 记忆化函数 f，f 的第一个参数用于递归调用，返回的闭包与 f 的计算结果相同
+///
+/// # Examples
+///
+/// ```rust
+/// use base::memo;
+///
+/// // 斐波那契数列：直接递归为指数时间，记忆化后为线性时间
+/// let fib = memo::memoize(|fib: &dyn Fn(u64) -> u64, n: u64| {
+///     if n < 2 { n } else { fib(n - 1) + fib(n - 2) }
+/// });
+/// assert_eq!(fib(90), 2_880_067_194_370_816_120);
+///
+/// // 编辑距离：子问题为两个后缀之间的编辑距离
+/// let (a, b) = (b"kitten", b"sitting");
+/// let distance = memo::memoize(|d: &dyn Fn((usize, usize)) -> usize, (i, j): (usize, usize)| {
+///     if i == a.len() || j == b.len() {
+///         return (a.len() - i) + (b.len() - j);
+///     }
+///     if a[i] == b[j] {
+///         return d((i + 1, j + 1));
+///     }
+///     1 + d((i + 1, j)).min(d((i, j + 1))).min(d((i + 1, j + 1)))
+/// });
+/// assert_eq!(distance((0, 0)), 3);
+/// ```
+pub fn memoize<A, R, F>(f: F) -> impl Fn(A) -> R
+where
+    A: Hash + Eq + Clone,
+    R: Clone,
+    F: Fn(&dyn Fn(A) -> R, A) -> R,
+{
+    let memo = Memo {
+        f,
+        cache: RefCell::new(HashMap::new()),
+    };
+
+    move |arg| memo.call(arg)
+}
+
+/// 记忆化函数 f，最多缓存最近用到的 capacity 个结果，见 [`memoize`]
+///
+/// 被淘汰的结果再次用到时需要重新计算，容量小于同时“活跃”的子问题数量时可能退化为指数时间。
+///
+/// # Panics
+///
+/// Panics if `capacity == 0`.
+///
+/// # Examples
+///
+/// 每次走 1、2 或 3 级台阶，走完 n 级台阶的方法数：每个子问题只依赖后面三个子问题，
+/// 深度优先地计算时只需缓存最近的几个结果。
+///
+/// ```rust
+/// use base::memo;
+///
+/// let ways = memo::memoize_lru(4, |ways: &dyn Fn(u64) -> u64, n: u64| match n {
+///     0 | 1 => 1,
+///     2 => 2,
+///     _ => ways(n - 1) + ways(n - 2) + ways(n - 3),
+/// });
+///
+/// assert_eq!(ways(4), 7);
+/// assert_eq!(ways(60), 4_680_045_560_037_375);
+/// ```
+pub fn memoize_lru<A, R, F>(capacity: usize, f: F) -> impl Fn(A) -> R
+where
+    A: Hash + Eq + Clone,
+    R: Clone,
+    F: Fn(&dyn Fn(A) -> R, A) -> R,
+{
+    let memo = Memo {
+        f,
+        cache: RefCell::new(LruCache::new(capacity)),
+    };
+
+    move |arg| memo.call(arg)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use super::*;
+
+    /// 最长公共子序列的长度，直接递归
+    fn lcs_naive(a: &[u8], b: &[u8], calls: &Cell<usize>) -> usize {
+        calls.set(calls.get() + 1);
+        match (a.split_first(), b.split_first()) {
+            (Some((x, ra)), Some((y, rb))) if x == y => 1 + lcs_naive(ra, rb, calls),
+            (Some((_, ra)), Some((_, rb))) => lcs_naive(ra, b, calls).max(lcs_naive(a, rb, calls)),
+            _ => 0,
+        }
+    }
+
+    #[test]
+    fn memoize_should_compute_each_subproblem_once() {
+        let (a, b) = (b"ACCGGTCGAG", b"GTCGTTCGGA");
+        let calls = Cell::new(0);
+        let lcs = memoize(
+            |lcs: &dyn Fn((usize, usize)) -> usize, (i, j): (usize, usize)| {
+                calls.set(calls.get() + 1);
+                if i == a.len() || j == b.len() {
+                    0
+                } else if a[i] == b[j] {
+                    1 + lcs((i + 1, j + 1))
+                } else {
+                    lcs((i + 1, j)).max(lcs((i, j + 1)))
+                }
+            },
+        );
+
+        let naive_calls = Cell::new(0);
+        let expected = lcs_naive(a, b, &naive_calls);
+        assert_eq!(lcs((0, 0)), expected);
+        assert!(calls.get() <= (a.len() + 1) * (b.len() + 1));
+        assert!(naive_calls.get() > 10 * calls.get());
+
+        // 再次调用直接命中缓存
+        let before = calls.get();
+        assert_eq!(lcs((0, 0)), expected);
+        assert_eq!(calls.get(), before);
+    }
+
+    #[test]
+    fn memoize_lru_should_bound_cache() {
+        let calls = Cell::new(0);
+        let fib = memoize_lru(3, |fib: &dyn Fn(u64) -> u64, n: u64| {
+            calls.set(calls.get() + 1);
+            if n < 2 { n } else { fib(n - 1) + fib(n - 2) }
+        });
+
+        // 算完 fib(n - 1) 时 fib(n - 2) 还在缓存中，容量为 3 也只需线性次调用
+        assert_eq!(fib(80), 23_416_728_348_467_685);
+        assert_eq!(calls.get(), 81);
+
+        // 早先的结果已被淘汰，需要重新计算
+        assert_eq!(fib(5), 5);
+        assert_eq!(calls.get(), 81 + 6);
+    }
+}
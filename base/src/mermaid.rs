@@ -0,0 +1,296 @@
+//! 渲染为 Mermaid 流程图
+//!
+//! 文档站点和 GitHub 等代码托管平台都能直接渲染 Mermaid，把输出放进 ```` ```mermaid ```` 代码块即可。
+//! 可以高亮一组结点（如搜索过程中访问过的结点），或者高亮一条路径上的结点和边。
+//!
+//! * 图：邻接表表示，结点编号即标签。无向图的每条边在邻接表中出现两次，只输出一次
+//! * 二叉树：自上而下排列，Mermaid 不区分子结点的左右，因此在边上标注 L 或 R
+
+use std::fmt::Display;
+
+use crate::{
+    BinarySearchTree, BinaryTree,
+    binary_tree::{NodeRc, OptionNodeRc},
+};
+
+/// 需要高亮的部分
+#[derive(Debug)]
+pub enum Highlight<'a, T> {
+    /// 不高亮
+    None,
+    /// 高亮这些结点
+    Nodes(&'a [T]),
+    /// 高亮路径上的结点，以及路径上相邻两个结点之间的边
+    Path(&'a [T]),
+}
+
+impl<T: PartialEq> Highlight<'_, T> {
+    fn contains_node(&self, node: &T) -> bool {
+        match self {
+            Highlight::None => false,
+            Highlight::Nodes(nodes) | Highlight::Path(nodes) => nodes.contains(node),
+        }
+    }
+
+    /// 有向边只匹配路径上的同向边
+    fn contains_edge(&self, from: &T, to: &T, directed: bool) -> bool {
+        let Highlight::Path(path) = self else {
+            return false;
+        };
+
+        path.windows(2)
+            .any(|w| (w[0] == *from && w[1] == *to) || (!directed && w[0] == *to && w[1] == *from))
+    }
+}
+
+/// 流程图的中间表示，结点编号为 0..labels.len()
+struct Flowchart {
+    labels: Vec<String>,
+    /// (起点, 终点, 边上的文字)
+    edges: Vec<(usize, usize, Option<&'static str>)>,
+    arrow: &'static str,
+    highlighted_nodes: Vec<usize>,
+    highlighted_edges: Vec<usize>,
+}
+
+impl Flowchart {
+    fn render(&self, direction: &str) -> String {
+        let mut out = format!("flowchart {direction}\n");
+
+        for (id, label) in self.labels.iter().enumerate() {
+            // 双引号会提前结束标签，使用 Mermaid 的实体编码
+            out.push_str(&format!(
+                "    n{id}[\"{}\"]\n",
+                label.replace('"', "#quot;")
+            ));
+        }
+        for &(from, to, text) in &self.edges {
+            let arrow = match text {
+                Some(text) => format!("{}|{text}|", self.arrow),
+                None => self.arrow.to_string(),
+            };
+            out.push_str(&format!("    n{from} {arrow} n{to}\n"));
+        }
+
+        if !self.highlighted_nodes.is_empty() {
+            let ids = self.highlighted_nodes.iter().map(|id| format!("n{id}"));
+            out.push_str("    classDef highlight fill:#fde68a,stroke:#d97706,stroke-width:2px\n");
+            out.push_str(&format!(
+                "    class {} highlight\n",
+                ids.collect::<Vec<_>>().join(",")
+            ));
+        }
+        if !self.highlighted_edges.is_empty() {
+            let ids = self.highlighted_edges.iter().map(usize::to_string);
+            out.push_str(&format!(
+                "    linkStyle {} stroke:#d97706,stroke-width:3px\n",
+                ids.collect::<Vec<_>>().join(",")
+            ));
+        }
+
+        out
+    }
+}
+
+/// 把邻接表表示的图渲染为从左到右排列的流程图
+///
+/// # Panics
+///
+/// Panics if an edge points to a node out of bounds.
+///
+/// # Examples
+///
+/// ```rust
+/// use base::mermaid::{self, Highlight};
+///
+/// let adj = vec![vec![1, 2], vec![2], vec![]];
+/// let chart = mermaid::graph(&adj, true, Highlight::Path(&[0, 1, 2]));
+///
+/// assert_eq!(
+///     chart,
+///     "flowchart LR\n    n0[\"0\"]\n    n1[\"1\"]\n    n2[\"2\"]\n    n0 --> n1\n    n0 --> n2\n    n1 --> n2\n    \
+///      classDef highlight fill:#fde68a,stroke:#d97706,stroke-width:2px\n    class n0,n1,n2 highlight\n    \
+///      linkStyle 0,2 stroke:#d97706,stroke-width:3px\n"
+/// );
+/// ```
+pub fn graph(adj: &[Vec<usize>], directed: bool, highlight: Highlight<'_, usize>) -> String {
+    let n = adj.len();
+    let mut chart = Flowchart {
+        labels: (0..n).map(|u| u.to_string()).collect(),
+        edges: Vec::new(),
+        arrow: if directed { "-->" } else { "---" },
+        highlighted_nodes: (0..n).filter(|u| highlight.contains_node(u)).collect(),
+        highlighted_edges: Vec::new(),
+    };
+
+    for (u, neighbors) in adj.iter().enumerate() {
+        for &v in neighbors {
+            assert!(v < n, "Edge {u} -> {v} out of bounds, len: {n}");
+            // 无向图的边只在 u <= v 时输出
+            if !directed && v < u {
+                continue;
+            }
+            if highlight.contains_edge(&u, &v, directed) {
+                chart.highlighted_edges.push(chart.edges.len());
+            }
+            chart.edges.push((u, v, None));
+        }
+    }
+
+    chart.render("LR")
+}
+
+/// 把二叉树渲染为自上而下排列的流程图，按结点的值匹配需要高亮的部分
+///
+/// 路径可以经过父结点向上走，因此树边不区分方向。
+///
+/// # Examples
+///
+/// ```rust
+/// use base::{
+///     BinaryTree,
+///     mermaid::{self, Highlight},
+/// };
+///
+/// let tree = BinaryTree::from([1, 2, 3]);
+///
+/// assert_eq!(
+///     mermaid::tree(&tree.root, Highlight::Nodes(&[3])),
+///     "flowchart TD\n    n0[\"1\"]\n    n1[\"2\"]\n    n2[\"3\"]\n    n0 -->|L| n1\n    n0 -->|R| n2\n    \
+///      classDef highlight fill:#fde68a,stroke:#d97706,stroke-width:2px\n    class n2 highlight\n"
+/// );
+/// ```
+pub fn tree<T: Display + PartialEq>(root: &OptionNodeRc<T>, highlight: Highlight<'_, T>) -> String {
+    let mut chart = Flowchart {
+        labels: Vec::new(),
+        edges: Vec::new(),
+        arrow: "-->",
+        highlighted_nodes: Vec::new(),
+        highlighted_edges: Vec::new(),
+    };
+
+    if let Some(root) = root {
+        add_subtree(root, &highlight, &mut chart);
+    }
+
+    chart.render("TD")
+}
+
+/// 按前序遍历为子树的结点编号，返回子树根结点的编号
+fn add_subtree<T: Display + PartialEq>(
+    node: &NodeRc<T>,
+    highlight: &Highlight<'_, T>,
+    chart: &mut Flowchart,
+) -> usize {
+    let node = node.borrow();
+    let id = chart.labels.len();
+    chart.labels.push(node.value.to_string());
+    if highlight.contains_node(&node.value) {
+        chart.highlighted_nodes.push(id);
+    }
+
+    for (child, text) in [(&node.left, "L"), (&node.right, "R")] {
+        if let Some(child) = child {
+            if highlight.contains_edge(&node.value, &child.borrow().value, false) {
+                chart.highlighted_edges.push(chart.edges.len());
+            }
+            // 先占住边的位置，保证边的编号与输出顺序一致
+            let edge = chart.edges.len();
+            chart.edges.push((id, id, Some(text)));
+            chart.edges[edge].1 = add_subtree(child, highlight, chart);
+        }
+    }
+
+    id
+}
+
+impl<T: Display + PartialEq> BinaryTree<T> {
+    /// 渲染为 Mermaid 流程图，见 [`tree`]
+    pub fn to_mermaid(&self, highlight: Highlight<'_, T>) -> String {
+        tree(&self.root, highlight)
+    }
+}
+
+impl<T: Display + PartialEq> BinarySearchTree<T> {
+    /// 渲染为 Mermaid 流程图，见 [`tree`]
+    pub fn to_mermaid(&self, highlight: Highlight<'_, T>) -> String {
+        tree(&self.root, highlight)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(chart: &str) -> Vec<&str> {
+        chart.lines().map(str::trim).collect()
+    }
+
+    #[test]
+    fn graph_should_render_edges_once() {
+        // 无向图：0 - 1 - 2，0 - 2
+        let adj = vec![vec![1, 2], vec![0, 2], vec![1, 0]];
+        let chart = graph(&adj, false, Highlight::Path(&[2, 1]));
+
+        assert_eq!(
+            lines(&chart),
+            [
+                "flowchart LR",
+                "n0[\"0\"]",
+                "n1[\"1\"]",
+                "n2[\"2\"]",
+                "n0 --- n1",
+                "n0 --- n2",
+                "n1 --- n2",
+                "classDef highlight fill:#fde68a,stroke:#d97706,stroke-width:2px",
+                "class n1,n2 highlight",
+                "linkStyle 2 stroke:#d97706,stroke-width:3px",
+            ]
+        );
+
+        // 有向图只高亮同向的边
+        let chart = graph(&[vec![1], vec![]], true, Highlight::Path(&[1, 0]));
+        assert!(!chart.contains("linkStyle"));
+        assert_eq!(graph(&[], true, Highlight::None), "flowchart LR\n");
+    }
+
+    #[test]
+    fn tree_should_keep_edge_order() {
+        let mut tree = BinarySearchTree::new();
+        for val in [5, 3, 8, 4, 9] {
+            tree.insert(val);
+        }
+
+        // 从 4 经过 3、5 走到 8
+        let chart = tree.to_mermaid(Highlight::Path(&[4, 3, 5, 8]));
+        assert_eq!(
+            lines(&chart),
+            [
+                "flowchart TD",
+                "n0[\"5\"]",
+                "n1[\"3\"]",
+                "n2[\"4\"]",
+                "n3[\"8\"]",
+                "n4[\"9\"]",
+                "n0 -->|L| n1",
+                "n1 -->|R| n2",
+                "n0 -->|R| n3",
+                "n3 -->|R| n4",
+                "classDef highlight fill:#fde68a,stroke:#d97706,stroke-width:2px",
+                "class n0,n1,n2,n3 highlight",
+                "linkStyle 0,1,2 stroke:#d97706,stroke-width:3px",
+            ]
+        );
+
+        let quoted = BinaryTree::from(["say \"hi\""]);
+        assert!(
+            quoted
+                .to_mermaid(Highlight::None)
+                .contains("n0[\"say #quot;hi#quot;\"]")
+        );
+        assert_eq!(
+            BinaryTree::<i32>::new().to_mermaid(Highlight::None),
+            "flowchart TD\n"
+        );
+    }
+}
@@ -0,0 +1,222 @@
+//! 莫队算法（Mo's algorithm）：离线处理区间查询
+//!
+//! 有些区间查询难以用线段树合并（如区间内不同值的数量），但维护一个区间时，
+//! 在两端加入或删除一个元素的代价很小。莫队算法读入所有查询后重新排序，
+//! 让当前区间的左右端点在相邻两次查询之间移动的总距离尽量短：
+//! * 把下标分成大小为 B 的块，按左端点所在的块排序，同一块内按右端点排序
+//! * 同一块内左端点每次最多移动 B，右端点单调移动，总移动距离为 O(qB + n²/B)
+//! * 取 B = n / √q 时总移动距离为 O(n√q)
+//! * 奇数块内右端点降序排列，右端点在相邻两块之间不必回到开头，常数约减少一半
+//!
+//! 移动端点时调用 add / remove 修改状态，到达查询区间后调用 answer 读取答案。
+//! 三个回调都需要访问同一份状态，因此状态作为参数传入，而不是被闭包捕获。
+
+use std::ops::Range;
+
+use crate::discretize;
+
+/// 按莫队算法的顺序处理区间查询，按原顺序返回每个查询的答案
+///
+/// 当前区间初始为空，add(state, i) 把下标 i 加入区间，remove(state, i) 把下标 i 移出区间，
+/// answer(state) 在当前区间恰好为某个查询区间时被调用。
+/// 时间复杂度：O(n√q) 次 add / remove，n 为最大的右端点，q 为查询数量
+///
+/// # Panics
+///
+/// Panics if a query has `start > end`.
+///
+/// # Examples
+///
+/// 区间和：
+///
+/// ```rust
+/// use base::mo;
+///
+/// let v = [3, 1, 4, 1, 5, 9, 2, 6];
+/// let sums = mo::process(
+///     &[0..3, 2..8, 5..5],
+///     &mut 0,
+///     |sum, i| *sum += v[i],
+///     |sum, i| *sum -= v[i],
+///     |sum| *sum,
+/// );
+///
+/// assert_eq!(sums, [8, 27, 0]);
+/// ```
+pub fn process<S, R, A, D, F>(
+    queries: &[Range<usize>],
+    state: &mut S,
+    mut add: A,
+    mut remove: D,
+    mut answer: F,
+) -> Vec<R>
+where
+    A: FnMut(&mut S, usize),
+    D: FnMut(&mut S, usize),
+    F: FnMut(&S) -> R,
+{
+    for query in queries {
+        assert!(
+            query.start <= query.end,
+            "Invalid query range: {}..{}",
+            query.start,
+            query.end
+        );
+    }
+
+    let n = queries.iter().map(|query| query.end).max().unwrap_or(0);
+    let block = block_size(n, queries.len());
+    let mut order = (0..queries.len()).collect::<Vec<_>>();
+    order.sort_unstable_by_key(|&i| {
+        let Range { start, end } = queries[i];
+        let b = start / block;
+        // 奇数块内右端点降序
+        (
+            b,
+            if b.is_multiple_of(2) {
+                end
+            } else {
+                usize::MAX - end
+            },
+        )
+    });
+
+    let mut answers = Vec::with_capacity(queries.len());
+    let (mut l, mut r) = (0, 0);
+    for i in order {
+        let Range { start, end } = queries[i];
+        // 先扩大再缩小，当前区间始终合法
+        while r < end {
+            add(state, r);
+            r += 1;
+        }
+        while l > start {
+            l -= 1;
+            add(state, l);
+        }
+        while r > end {
+            r -= 1;
+            remove(state, r);
+        }
+        while l < start {
+            remove(state, l);
+            l += 1;
+        }
+
+        answers.push((i, answer(state)));
+    }
+
+    // 恢复原来的查询顺序
+    answers.sort_unstable_by_key(|&(i, _)| i);
+    answers.into_iter().map(|(_, answer)| answer).collect()
+}
+
+/// 块大小 n / √q，至少为 1
+fn block_size(n: usize, q: usize) -> usize {
+    let q = (q as f64).sqrt().max(1.0);
+
+    ((n as f64 / q) as usize).max(1)
+}
+
+/// 每个查询区间内不同值的数量
+///
+/// 先离散化，再用数组记录当前区间内每个值出现的次数，次数在 0 和 1 之间变化时更新答案。
+///
+/// # Panics
+///
+/// Panics if a query is out of bounds or has `start > end`.
+///
+/// # Examples
+///
+/// ```rust
+/// use base::mo;
+///
+/// let v = ["a", "b", "a", "c", "b", "a"];
+///
+/// assert_eq!(mo::distinct_count(&v, &[0..3, 1..5, 2..3, 4..4]), [2, 3, 1, 0]);
+/// ```
+pub fn distinct_count<T: Ord + Clone>(v: &[T], queries: &[Range<usize>]) -> Vec<usize> {
+    if let Some(query) = queries.iter().find(|query| query.end > v.len()) {
+        panic!(
+            "Query out of bounds: {}..{}, len: {}",
+            query.start,
+            query.end,
+            v.len()
+        );
+    }
+
+    let (ranks, values) = discretize::compress_coordinates(v);
+    // (每个值的出现次数, 不同值的数量)
+    let mut state = (vec![0_usize; values.len()], 0);
+
+    process(
+        queries,
+        &mut state,
+        |(counts, distinct), i| {
+            counts[ranks[i]] += 1;
+            if counts[ranks[i]] == 1 {
+                *distinct += 1;
+            }
+        },
+        |(counts, distinct), i| {
+            counts[ranks[i]] -= 1;
+            if counts[ranks[i]] == 0 {
+                *distinct -= 1;
+            }
+        },
+        |&(_, distinct)| distinct,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{BTreeSet, HashSet};
+
+    use super::*;
+    use crate::generate::{self, Rng};
+
+    fn random_queries(n: usize, q: usize, seed: u64) -> Vec<Range<usize>> {
+        let mut rng = Rng::new(seed);
+        (0..q)
+            .map(|_| {
+                let a = rng.below(n as u64 + 1) as usize;
+                let b = rng.below(n as u64 + 1) as usize;
+                a.min(b)..a.max(b)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn distinct_count_should_match_brute_force() {
+        for (n, distinct, q, seed) in [(1, 1, 5, 1), (300, 20, 200, 2), (1000, 400, 50, 3)] {
+            let v = generate::few_distinct(n, distinct, seed);
+            let queries = random_queries(n, q, seed);
+
+            let expected = queries
+                .iter()
+                .map(|query| v[query.clone()].iter().collect::<HashSet<_>>().len())
+                .collect::<Vec<_>>();
+            assert_eq!(distinct_count(&v, &queries), expected);
+        }
+
+        assert!(distinct_count::<u64>(&[], &[]).is_empty());
+    }
+
+    #[test]
+    fn process_should_visit_each_range_exactly() {
+        let queries = random_queries(500, 300, 4);
+
+        // 状态记录当前区间内的所有下标，不能重复加入或删除不存在的下标
+        let ranges = process(
+            &queries,
+            &mut BTreeSet::new(),
+            |set, i| assert!(set.insert(i)),
+            |set, i| assert!(set.remove(&i)),
+            |set| set.iter().copied().collect::<Vec<_>>(),
+        );
+
+        for (range, query) in ranges.into_iter().zip(queries) {
+            assert!(range.into_iter().eq(query));
+        }
+    }
+}
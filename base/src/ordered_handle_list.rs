@@ -0,0 +1,556 @@
+//! 顺序维护链表（order-maintenance list）
+//!
+//! 采用两级标签：链表中连续的结点划分为若干组，每组最多 64 个结点，
+//! 结点的顺序由（所在组的标签，组内标签）决定。判断两个结点的先后只需比较这两个整数对，时间复杂度 O(1)。
+//!
+//! * 组内：在结点 a 之后插入新结点时，若 a 与其组内后继之间的标签还有空隙，直接取中点即可；
+//!   否则将整组均匀重新编号，代价为 O(64)。重新编号后相邻标签的间距至少为 2^58，
+//!   之后至少还要在组内插入 57 次才会再次耗尽空隙。组满时将其一分为二并分别重新编号。
+//! * 组间：新组的标签同样取相邻组标签的中点。空隙耗尽时从该组所在的对齐标签区间
+//!   [base, base + 2^i) 开始逐步扩大 i，直到区间内的组足够稀疏（组数 c 满足 c² <= 2^i），
+//!   再将区间内的组均匀重新编号，每次新建组的均摊代价为 O(logn)。
+//!
+//! 分裂后的两组各有 32 个结点，每次分裂之前该组至少插入了 32 个结点；而标签为 64 位整数，
+//! logn <= 64，因此组间重新编号的开销均摊到每次插入上为 O(1)，插入操作的均摊时间复杂度为 O(1)。
+
+use std::cmp::Ordering;
+
+/// 链表结点的句柄，结点被删除后句柄失效
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct OrderHandle {
+    index: usize,
+    generation: u32,
+}
+
+#[derive(Debug)]
+struct Slot<T> {
+    /// 组内标签
+    tag: u64,
+    group: usize,
+    prev: Option<usize>,
+    next: Option<usize>,
+    generation: u32,
+    value: Option<T>,
+}
+
+/// 一组在链表中连续的结点
+#[derive(Debug)]
+struct Group {
+    tag: u64,
+    prev: Option<usize>,
+    next: Option<usize>,
+    /// 组内的第一个结点
+    first: usize,
+    len: usize,
+}
+
+/// 支持 O(1) 判断结点先后顺序的链表
+#[derive(Debug)]
+pub struct OrderedHandleList<T> {
+    /// 下标 0 为哨兵结点，始终是第 0 组的第一个结点，不保存元素
+    slots: Vec<Slot<T>>,
+    /// 已删除、可复用的结点下标
+    free: Vec<usize>,
+    /// 下标 0 为哨兵结点所在的组，始终是第一个组
+    groups: Vec<Group>,
+    /// 已删除、可复用的组下标
+    free_groups: Vec<usize>,
+    tail: usize,
+    len: usize,
+}
+
+/// 标签空间的上界 2^64
+const TAG_LIMIT: u128 = 1 << 64;
+
+/// 每组最多的结点数，不小于标签的位数
+const GROUP_CAPACITY: usize = 64;
+
+impl<T> OrderedHandleList<T> {
+    /// 创建空链表
+    pub fn new() -> Self {
+        Self {
+            slots: vec![Slot {
+                tag: 0,
+                group: 0,
+                prev: None,
+                next: None,
+                generation: 0,
+                value: None,
+            }],
+            free: Vec::new(),
+            groups: vec![Group {
+                tag: 0,
+                prev: None,
+                next: None,
+                first: 0,
+                len: 1,
+            }],
+            free_groups: Vec::new(),
+            tail: 0,
+            len: 0,
+        }
+    }
+
+    /// 元素数量
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// 判断链表是否为空
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// 向链表头部插入一个元素，返回其句柄
+    pub fn push_front(&mut self, val: T) -> OrderHandle {
+        self.insert_after_index(0, val)
+    }
+
+    /// 向链表尾部插入一个元素，返回其句柄
+    pub fn push_back(&mut self, val: T) -> OrderHandle {
+        self.insert_after_index(self.tail, val)
+    }
+
+    /// 在句柄 at 对应的结点之后插入一个元素，返回其句柄
+    ///
+    /// # Panics
+    ///
+    /// Panics if `at` is no longer valid.
+    pub fn insert_after(&mut self, at: OrderHandle, val: T) -> OrderHandle {
+        let index = self.checked_index(at);
+        self.insert_after_index(index, val)
+    }
+
+    /// 在句柄 at 对应的结点之前插入一个元素，返回其句柄
+    ///
+    /// # Panics
+    ///
+    /// Panics if `at` is no longer valid.
+    pub fn insert_before(&mut self, at: OrderHandle, val: T) -> OrderHandle {
+        let index = self.checked_index(at);
+        // Safety: 有效结点的前驱至少是哨兵结点
+        let prev = self.slots[index].prev.unwrap();
+        self.insert_after_index(prev, val)
+    }
+
+    /// 删除句柄对应的结点并返回其元素，句柄无效时返回 None
+    pub fn remove(&mut self, handle: OrderHandle) -> Option<T> {
+        if !self.is_valid(handle) {
+            return None;
+        }
+
+        let index = handle.index;
+        let (prev, next) = (self.slots[index].prev, self.slots[index].next);
+        let group = self.slots[index].group;
+        self.groups[group].len -= 1;
+        if self.groups[group].len == 0 {
+            self.remove_group(group);
+        } else if self.groups[group].first == index {
+            // Safety: 组内还有其他结点，它们都位于第一个结点之后
+            self.groups[group].first = next.unwrap();
+        }
+        // Safety: 有效结点的前驱至少是哨兵结点
+        self.slots[prev.unwrap()].next = next;
+        match next {
+            Some(next) => self.slots[next].prev = prev,
+            None => self.tail = prev.unwrap(),
+        }
+
+        let slot = &mut self.slots[index];
+        slot.prev = None;
+        slot.next = None;
+        // 增加代数，使指向该结点的旧句柄全部失效
+        slot.generation = slot.generation.wrapping_add(1);
+        self.free.push(index);
+        self.len -= 1;
+
+        slot.value.take()
+    }
+
+    /// 判断句柄是否仍然有效
+    pub fn is_valid(&self, handle: OrderHandle) -> bool {
+        handle.index != 0
+            && self
+                .slots
+                .get(handle.index)
+                .is_some_and(|slot| slot.generation == handle.generation && slot.value.is_some())
+    }
+
+    /// 获取句柄对应元素的不可变借用
+    pub fn get(&self, handle: OrderHandle) -> Option<&T> {
+        if !self.is_valid(handle) {
+            return None;
+        }
+
+        self.slots[handle.index].value.as_ref()
+    }
+
+    /// 获取句柄对应元素的可变借用
+    pub fn get_mut(&mut self, handle: OrderHandle) -> Option<&mut T> {
+        if !self.is_valid(handle) {
+            return None;
+        }
+
+        self.slots[handle.index].value.as_mut()
+    }
+
+    /// 判断结点 a 是否位于结点 b 之前，时间复杂度：O(1)
+    ///
+    /// # Panics
+    ///
+    /// Panics if `a` or `b` is no longer valid.
+    pub fn is_before(&self, a: OrderHandle, b: OrderHandle) -> bool {
+        self.compare(a, b) == Ordering::Less
+    }
+
+    /// 比较结点 a 和结点 b 在链表中的先后顺序，时间复杂度：O(1)
+    ///
+    /// # Panics
+    ///
+    /// Panics if `a` or `b` is no longer valid.
+    pub fn compare(&self, a: OrderHandle, b: OrderHandle) -> Ordering {
+        let (a, b) = (self.checked_index(a), self.checked_index(b));
+
+        self.label(a).cmp(&self.label(b))
+    }
+
+    /// 按链表顺序返回不可变迭代器
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        let mut current = self.slots[0].next;
+
+        std::iter::from_fn(move || {
+            let slot = &self.slots[current?];
+            current = slot.next;
+            slot.value.as_ref()
+        })
+    }
+
+    fn checked_index(&self, handle: OrderHandle) -> usize {
+        assert!(self.is_valid(handle), "Invalid handle: {handle:?}");
+        handle.index
+    }
+
+    /// 结点的两级标签：（所在组的标签，组内标签）
+    fn label(&self, index: usize) -> (u64, u64) {
+        let slot = &self.slots[index];
+
+        (self.groups[slot.group].tag, slot.tag)
+    }
+
+    /// 结点与其组内后继之间的标签区间，没有组内后继时上界为标签空间上界
+    fn local_gap(&self, index: usize) -> (u128, u128) {
+        let slot = &self.slots[index];
+        let hi = slot
+            .next
+            .filter(|&next| self.slots[next].group == slot.group)
+            .map_or(TAG_LIMIT, |next| self.slots[next].tag as u128);
+
+        (slot.tag as u128, hi)
+    }
+
+    fn insert_after_index(&mut self, index: usize, val: T) -> OrderHandle {
+        if self.groups[self.slots[index].group].len >= GROUP_CAPACITY {
+            self.split_group(self.slots[index].group);
+        }
+
+        let group = self.slots[index].group;
+        let (lo, hi) = self.local_gap(index);
+        let tag = if hi - lo >= 2 {
+            lo + (hi - lo) / 2
+        } else {
+            self.relabel_group(group);
+            let (lo, hi) = self.local_gap(index);
+            lo + (hi - lo) / 2
+        };
+
+        let next = self.slots[index].next;
+        let slot = Slot {
+            tag: tag as u64,
+            group,
+            prev: Some(index),
+            next,
+            generation: 0,
+            value: Some(val),
+        };
+        let new_index = match self.free.pop() {
+            Some(free) => {
+                let generation = self.slots[free].generation;
+                self.slots[free] = Slot { generation, ..slot };
+                free
+            }
+            None => {
+                self.slots.push(slot);
+                self.slots.len() - 1
+            }
+        };
+
+        self.slots[index].next = Some(new_index);
+        match next {
+            Some(next) => self.slots[next].prev = Some(new_index),
+            None => self.tail = new_index,
+        }
+        self.groups[group].len += 1;
+        self.len += 1;
+
+        OrderHandle {
+            index: new_index,
+            generation: self.slots[new_index].generation,
+        }
+    }
+
+    /// 将组内结点均匀重新编号，时间复杂度：O(GROUP_CAPACITY)
+    fn relabel_group(&mut self, group: usize) {
+        let Group { first, len, .. } = self.groups[group];
+        let gap = TAG_LIMIT / len as u128;
+
+        let mut current = first;
+        for i in 0..len {
+            self.slots[current].tag = (i as u128 * gap) as u64;
+            if i + 1 < len {
+                // Safety: 组内的结点在链表中连续
+                current = self.slots[current].next.unwrap();
+            }
+        }
+    }
+
+    /// 将组的后一半结点移入紧随其后的新组，并分别重新编号
+    fn split_group(&mut self, group: usize) {
+        let Group { first, len, .. } = self.groups[group];
+        let keep = len / 2;
+
+        let mut moved = first;
+        for _ in 0..keep {
+            // Safety: 组内的结点在链表中连续
+            moved = self.slots[moved].next.unwrap();
+        }
+        let new_group = self.insert_group_after(group, moved, len - keep);
+        let mut current = moved;
+        for i in keep..len {
+            self.slots[current].group = new_group;
+            if i + 1 < len {
+                // Safety: 同上
+                current = self.slots[current].next.unwrap();
+            }
+        }
+        self.groups[group].len = keep;
+
+        self.relabel_group(group);
+        self.relabel_group(new_group);
+    }
+
+    /// 在组 index 之后插入一个新组，返回其下标
+    fn insert_group_after(&mut self, index: usize, first: usize, len: usize) -> usize {
+        let (lo, hi) = (self.groups[index].tag as u128, self.next_group_tag(index));
+        let tag = if hi - lo >= 2 {
+            lo + (hi - lo) / 2
+        } else {
+            self.relabel(index);
+            let (lo, hi) = (self.groups[index].tag as u128, self.next_group_tag(index));
+            lo + (hi - lo) / 2
+        };
+
+        let next = self.groups[index].next;
+        let group = Group {
+            tag: tag as u64,
+            prev: Some(index),
+            next,
+            first,
+            len,
+        };
+        let new_index = match self.free_groups.pop() {
+            Some(free) => {
+                self.groups[free] = group;
+                free
+            }
+            None => {
+                self.groups.push(group);
+                self.groups.len() - 1
+            }
+        };
+
+        self.groups[index].next = Some(new_index);
+        if let Some(next) = next {
+            self.groups[next].prev = Some(new_index);
+        }
+
+        new_index
+    }
+
+    /// 摘除已经没有结点的组
+    fn remove_group(&mut self, index: usize) {
+        let (prev, next) = (self.groups[index].prev, self.groups[index].next);
+        // Safety: 第 0 组始终包含哨兵结点，不会被摘除，其他组的前驱至少是第 0 组
+        self.groups[prev.unwrap()].next = next;
+        if let Some(next) = next {
+            self.groups[next].prev = prev;
+        }
+
+        self.free_groups.push(index);
+    }
+
+    /// 组的后继标签，没有后继时为标签空间上界
+    fn next_group_tag(&self, index: usize) -> u128 {
+        self.groups[index]
+            .next
+            .map_or(TAG_LIMIT, |next| self.groups[next].tag as u128)
+    }
+
+    /// 为在组 index 之后插入新组腾出标签空隙
+    fn relabel(&mut self, index: usize) {
+        let tag = self.groups[index].tag as u128;
+
+        for i in 1..=64 {
+            let range = 1u128 << i;
+            let base = tag & !(range - 1);
+
+            // 找出标签落在 [base, base + range) 内的连续组
+            let mut count = 1;
+            let mut first = index;
+            while let Some(prev) = self.groups[first].prev
+                && self.groups[prev].tag as u128 >= base
+            {
+                first = prev;
+                count += 1;
+            }
+            let mut last = index;
+            while let Some(next) = self.groups[last].next
+                && (self.groups[next].tag as u128) < base + range
+            {
+                last = next;
+                count += 1;
+            }
+
+            // 加上待插入的新组，区间足够稀疏时均匀重新编号
+            let c = count as u128 + 1;
+            if c * c <= range {
+                let gap = range / c;
+                let mut current = Some(first);
+                let mut tag = base;
+                while let Some(group) = current {
+                    self.groups[group].tag = tag as u64;
+                    tag += gap;
+                    if group == last {
+                        break;
+                    }
+                    current = self.groups[group].next;
+                }
+                return;
+            }
+        }
+
+        panic!("Tag space exhausted, len: {}", self.len);
+    }
+}
+
+impl<T> Default for OrderedHandleList<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generate::Rng;
+
+    #[test]
+    fn ordered_handle_list_basics_should_work() {
+        let mut list = OrderedHandleList::new();
+
+        let b = list.push_back('b');
+        let d = list.push_back('d');
+        let a = list.push_front('a');
+        let c = list.insert_after(b, 'c');
+        let e = list.insert_before(d, 'x');
+
+        assert_eq!(list.len(), 5);
+        assert_eq!(list.iter().collect::<String>(), "abcxd");
+        assert!(list.is_before(a, b));
+        assert!(list.is_before(c, e));
+        assert!(!list.is_before(d, e));
+        assert_eq!(list.compare(c, c), Ordering::Equal);
+
+        *list.get_mut(e).unwrap() = 'y';
+        assert_eq!(list.remove(c), Some('c'));
+        assert_eq!(list.remove(c), None);
+        assert!(list.get(c).is_none());
+        assert_eq!(list.iter().collect::<String>(), "abyd");
+
+        // 复用已删除的结点，旧句柄依然无效
+        let f = list.push_back('f');
+        assert!(!list.is_valid(c));
+        assert!(list.is_before(d, f));
+    }
+
+    #[test]
+    fn ordered_handle_list_relabel_should_work() {
+        let mut list = OrderedHandleList::new();
+
+        // 反复在同一位置之后插入，迫使标签空间耗尽并重新编号
+        let first = list.push_back(0);
+        let mut handles = vec![first];
+        for i in 1..2000 {
+            handles.push(list.insert_after(first, i));
+        }
+        // 链表顺序为 0, 1999, 1998, ..., 1
+        for w in handles[1..].windows(2) {
+            assert!(list.is_before(w[1], w[0]));
+        }
+        assert!(list.is_before(first, handles[1999]));
+
+        // 反复在同一位置之前插入
+        let last = list.push_back(-1);
+        let handles = (0..2000)
+            .map(|i| list.insert_before(last, -i - 2))
+            .collect::<Vec<_>>();
+        for w in handles.windows(2) {
+            assert!(list.is_before(w[0], w[1]));
+        }
+        assert!(list.is_before(handles[1999], last));
+        assert_eq!(list.len(), 4001);
+    }
+
+    #[test]
+    fn ordered_handle_list_should_match_vec() {
+        let mut rng = Rng::new(23);
+        let mut list = OrderedHandleList::new();
+        // 按链表顺序保存的句柄和元素
+        let mut expected: Vec<(OrderHandle, u64)> = Vec::new();
+
+        for val in 0..5000 {
+            let i = rng.below(expected.len() as u64 + 1) as usize;
+            match rng.below(6) {
+                0 => expected.insert(0, (list.push_front(val), val)),
+                1 => expected.push((list.push_back(val), val)),
+                // 集中在少数位置插入，迫使组内空隙耗尽、组分裂
+                2 | 3 if !expected.is_empty() => {
+                    let i = i.min(expected.len() - 1).min(3);
+                    expected.insert(i + 1, (list.insert_after(expected[i].0, val), val));
+                }
+                4 if !expected.is_empty() => {
+                    let i = i.min(expected.len() - 1);
+                    expected.insert(i, (list.insert_before(expected[i].0, val), val));
+                }
+                _ if i < expected.len() && val % 2 == 0 => {
+                    let (handle, val) = expected.remove(i);
+                    assert_eq!(list.remove(handle), Some(val));
+                }
+                _ => {}
+            }
+
+            if !expected.is_empty() {
+                let (a, b) = (
+                    rng.below(expected.len() as u64) as usize,
+                    rng.below(expected.len() as u64) as usize,
+                );
+                assert_eq!(list.compare(expected[a].0, expected[b].0), a.cmp(&b));
+            }
+        }
+
+        assert_eq!(list.len(), expected.len());
+        assert!(list.iter().eq(expected.iter().map(|(_, val)| val)));
+        for w in expected.windows(2) {
+            assert!(list.is_before(w[0].0, w[1].0));
+        }
+    }
+}
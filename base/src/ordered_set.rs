@@ -0,0 +1,69 @@
+//! 有序集合的公共接口
+//!
+//! crate 中有多种平衡二叉搜索树，它们的插入、删除和查找接口略有不同（如 [`AvlTree::insert`] 不返回是否插入成功）。
+//! [`OrderedSet`] 统一了这三种操作，基准测试和对拍测试可以写成泛型函数，新的实现只需实现该 trait 即可加入比较。
+
+use std::collections::BTreeSet;
+
+use crate::{AvlTree, SkipListMap};
+
+/// 不含重复元素的有序集合
+pub trait OrderedSet<T> {
+    /// 插入元素，集合中已存在相同的元素时不插入并返回 false
+    fn insert(&mut self, value: T) -> bool;
+
+    /// 删除元素，元素不存在时返回 false
+    fn remove(&mut self, value: &T) -> bool;
+
+    /// 判断集合中是否包含元素
+    fn contains(&self, value: &T) -> bool;
+}
+
+impl<T: Ord> OrderedSet<T> for BTreeSet<T> {
+    fn insert(&mut self, value: T) -> bool {
+        BTreeSet::insert(self, value)
+    }
+
+    fn remove(&mut self, value: &T) -> bool {
+        BTreeSet::remove(self, value)
+    }
+
+    fn contains(&self, value: &T) -> bool {
+        BTreeSet::contains(self, value)
+    }
+}
+
+impl<T: Ord + Clone> OrderedSet<T> for AvlTree<T> {
+    fn insert(&mut self, value: T) -> bool {
+        self.try_insert(value)
+    }
+
+    fn remove(&mut self, value: &T) -> bool {
+        // AvlTree::remove 不返回是否删除成功，先查找一次
+        let found = self.search(value).is_some();
+        if found {
+            AvlTree::remove(self, value);
+        }
+
+        found
+    }
+
+    fn contains(&self, value: &T) -> bool {
+        self.search(value).is_some()
+    }
+}
+
+/// 值为 () 的跳表即有序集合
+impl<T: Ord + Clone> OrderedSet<T> for SkipListMap<T, ()> {
+    fn insert(&mut self, value: T) -> bool {
+        SkipListMap::insert(self, value, ()).is_none()
+    }
+
+    fn remove(&mut self, value: &T) -> bool {
+        SkipListMap::remove(self, value).is_some()
+    }
+
+    fn contains(&self, value: &T) -> bool {
+        self.contains_key(value)
+    }
+}
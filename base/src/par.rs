@@ -0,0 +1,142 @@
+//! rayon 并行迭代器，需要启用 `rayon` 特性
+//!
+//! 为链表、树和堆实现 [`IntoParallelIterator`]，从而可以使用 `par_iter()`、`into_par_iter()`
+//! 把逐元素的批量计算分摊到所有 CPU 核心上。
+//!
+//! 这些结构都不能直接按下标随机访问或在线程间共享，因此先顺序地把元素（或元素的引用）
+//! 收集到 Vec 中，再交给 rayon 切分。收集的开销为 O(n)，只有每个元素上的计算量较大时并行才有收益。
+//!
+//! * 链表：按从头到尾的顺序
+//! * 堆：按数组表示的顺序，即层序遍历，与 [`std::collections::BinaryHeap`] 的并行迭代器一致
+//! * 树：按中序遍历的顺序。树的结点使用 `Rc<RefCell<_>>` 连接，不能跨线程访问，
+//!   只能复制出元素的值，因此只实现了借用版本且要求 `T: Clone`
+
+use rayon::{iter::IntoParallelIterator, slice, vec};
+
+use crate::{AvlTree, BinarySearchTree, BinaryTree, LinkedList, MaxHeap, MinHeap};
+
+impl<T: Send> IntoParallelIterator for LinkedList<T> {
+    type Iter = vec::IntoIter<T>;
+    type Item = T;
+
+    fn into_par_iter(self) -> Self::Iter {
+        self.into_vec().into_par_iter()
+    }
+}
+
+impl<'a, T: Sync> IntoParallelIterator for &'a LinkedList<T> {
+    type Iter = vec::IntoIter<&'a T>;
+    type Item = &'a T;
+
+    fn into_par_iter(self) -> Self::Iter {
+        self.iter().collect::<Vec<_>>().into_par_iter()
+    }
+}
+
+impl<'a, T: Send> IntoParallelIterator for &'a mut LinkedList<T> {
+    type Iter = vec::IntoIter<&'a mut T>;
+    type Item = &'a mut T;
+
+    fn into_par_iter(self) -> Self::Iter {
+        self.iter_mut().collect::<Vec<_>>().into_par_iter()
+    }
+}
+
+/// 为堆实现并行迭代器，不提供可变借用的版本，以免破坏堆的性质
+macro_rules! impl_heap_par_iter {
+    ($($heap:ident),*) => {$(
+        impl<T: PartialOrd + Send> IntoParallelIterator for $heap<T> {
+            type Iter = vec::IntoIter<T>;
+            type Item = T;
+
+            fn into_par_iter(self) -> Self::Iter {
+                self.into_vec().into_par_iter()
+            }
+        }
+
+        impl<'a, T: PartialOrd + Sync> IntoParallelIterator for &'a $heap<T> {
+            type Iter = slice::Iter<'a, T>;
+            type Item = &'a T;
+
+            fn into_par_iter(self) -> Self::Iter {
+                self.as_slice().into_par_iter()
+            }
+        }
+    )*};
+}
+
+impl_heap_par_iter!(MaxHeap, MinHeap);
+
+impl<T: Clone + Send> IntoParallelIterator for &BinaryTree<T> {
+    type Iter = vec::IntoIter<T>;
+    type Item = T;
+
+    fn into_par_iter(self) -> Self::Iter {
+        self.to_vec().into_par_iter()
+    }
+}
+
+impl<T: Clone + Ord + Send> IntoParallelIterator for &BinarySearchTree<T> {
+    type Iter = vec::IntoIter<T>;
+    type Item = T;
+
+    fn into_par_iter(self) -> Self::Iter {
+        self.to_vec().into_par_iter()
+    }
+}
+
+impl<T: Clone + Send> IntoParallelIterator for &AvlTree<T> {
+    type Iter = vec::IntoIter<T>;
+    type Item = T;
+
+    fn into_par_iter(self) -> Self::Iter {
+        self.to_tree().to_vec().into_par_iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rayon::prelude::*;
+
+    use super::*;
+    use crate::Heap;
+
+    #[test]
+    fn list_par_iter_should_work() {
+        let mut list = (1..=1000).collect::<LinkedList<u64>>();
+
+        assert_eq!(list.par_iter().sum::<u64>(), 500500);
+        list.par_iter_mut().for_each(|x| *x *= 2);
+        assert_eq!(list.front(), Some(&2));
+        assert_eq!(list.back(), Some(&2000));
+
+        let v = list.into_par_iter().map(|x| x + 1).collect::<Vec<_>>();
+        assert_eq!(v[..3], [3, 5, 7]);
+    }
+
+    #[test]
+    fn heap_par_iter_should_work() {
+        let heap = MaxHeap::from_vec(vec![3, 1, 4, 1, 5, 9, 2, 6]);
+        assert_eq!(heap.par_iter().max(), Some(&9));
+        assert_eq!(heap.into_par_iter().sum::<i32>(), 31);
+
+        let heap = MinHeap::from_vec(vec![3, 1, 4]);
+        assert_eq!(
+            heap.par_iter().copied().collect::<Vec<_>>(),
+            heap.as_slice()
+        );
+    }
+
+    #[test]
+    fn tree_par_iter_should_work() {
+        let tree = BinaryTree::from([1, 2, 3, 4, 5]);
+        assert_eq!(tree.par_iter().sum::<i32>(), 15);
+
+        let bst = BinarySearchTree::from([5, 3, 8, 1]);
+        let squares = bst.par_iter().map(|x| x * x).collect::<Vec<_>>();
+        assert_eq!(squares, [1, 9, 25, 64]);
+
+        let avl = AvlTree::from([2, 7, 1]);
+        assert_eq!(avl.par_iter().collect::<Vec<_>>(), [1, 2, 7]);
+    }
+}
@@ -0,0 +1,317 @@
+//! 可持久化（不可变）二叉搜索树
+//!
+//! 每次插入或删除都不修改原有节点，而是复制从根节点到目标位置路径上的节点（路径复制），
+//! 未改动的子树通过 Rc 在新旧版本之间共享。因此：
+//! * 每次修改只新建 O(h) 个节点，h 为树高
+//! * 任意历史版本都保持不变，可以随时查询
+
+use std::{cmp::Ordering, rc::Rc};
+
+type Link<T> = Option<Rc<PersistentNode<T>>>;
+
+#[derive(Debug)]
+struct PersistentNode<T> {
+    value: T,
+    left: Link<T>,
+    right: Link<T>,
+}
+
+/// 不可变二叉搜索树，insert 和 remove 返回新版本，原版本保持不变
+#[derive(Debug)]
+pub struct PersistentBst<T> {
+    root: Link<T>,
+    len: usize,
+}
+
+impl<T> PersistentBst<T> {
+    /// 创建空树
+    pub fn new() -> Self {
+        Self { root: None, len: 0 }
+    }
+
+    /// 元素数量
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// 判断树是否为空
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// 判断两个版本是否共享同一个根节点
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        match (&self.root, &other.root) {
+            (Some(a), Some(b)) => Rc::ptr_eq(a, b),
+            (None, None) => true,
+            _ => false,
+        }
+    }
+}
+
+impl<T: Ord + Clone> PersistentBst<T> {
+    /// 判断树中是否包含指定值
+    pub fn contains(&self, target: &T) -> bool {
+        let mut current = &self.root;
+
+        while let Some(node) = current {
+            match target.cmp(&node.value) {
+                Ordering::Equal => return true,
+                Ordering::Less => current = &node.left,
+                Ordering::Greater => current = &node.right,
+            }
+        }
+
+        false
+    }
+
+    /// 插入节点，返回插入后的新版本，若值已存在则返回与当前版本共享根节点的副本
+    pub fn insert(&self, val: T) -> Self {
+        match insert_recursive(&self.root, val) {
+            Some(root) => Self {
+                root: Some(root),
+                len: self.len + 1,
+            },
+            None => self.clone(),
+        }
+    }
+
+    /// 删除节点，返回删除后的新版本，若值不存在则返回与当前版本共享根节点的副本
+    pub fn remove(&self, val: &T) -> Self {
+        match remove_recursive(&self.root, val) {
+            Some(root) => Self {
+                root,
+                len: self.len - 1,
+            },
+            None => self.clone(),
+        }
+    }
+
+    /// 中序遍历，转换为有序的 Vec
+    pub fn to_vec(&self) -> Vec<T> {
+        let mut ordered = Vec::with_capacity(self.len);
+        let mut stack = Vec::new();
+        let mut current = self.root.as_ref();
+
+        while current.is_some() || !stack.is_empty() {
+            while let Some(node) = current {
+                stack.push(node);
+                current = node.left.as_ref();
+            }
+            // Safety: 循环条件保证此时栈不为空
+            let node = stack.pop().unwrap();
+            ordered.push(node.value.clone());
+            current = node.right.as_ref();
+        }
+
+        ordered
+    }
+}
+
+/// 返回插入后的新子树根节点，值已存在时返回 None
+fn insert_recursive<T: Ord + Clone>(node: &Link<T>, val: T) -> Link<T> {
+    match node {
+        None => Some(Rc::new(PersistentNode {
+            value: val,
+            left: None,
+            right: None,
+        })),
+        Some(node) => {
+            // 复制路径上的节点，另一侧子树直接共享
+            let copied = match val.cmp(&node.value) {
+                Ordering::Equal => return None,
+                Ordering::Less => PersistentNode {
+                    value: node.value.clone(),
+                    left: Some(insert_recursive(&node.left, val)?),
+                    right: node.right.clone(),
+                },
+                Ordering::Greater => PersistentNode {
+                    value: node.value.clone(),
+                    left: node.left.clone(),
+                    right: Some(insert_recursive(&node.right, val)?),
+                },
+            };
+
+            Some(Rc::new(copied))
+        }
+    }
+}
+
+/// 返回删除后的新子树根节点，值不存在时返回 None
+fn remove_recursive<T: Ord + Clone>(node: &Link<T>, val: &T) -> Option<Link<T>> {
+    let node = node.as_ref()?;
+
+    let copied = match val.cmp(&node.value) {
+        Ordering::Less => PersistentNode {
+            value: node.value.clone(),
+            left: remove_recursive(&node.left, val)?,
+            right: node.right.clone(),
+        },
+        Ordering::Greater => PersistentNode {
+            value: node.value.clone(),
+            left: node.left.clone(),
+            right: remove_recursive(&node.right, val)?,
+        },
+        Ordering::Equal => match (&node.left, &node.right) {
+            // 子节点数量为0或1，直接用子节点替换当前节点
+            (None, child) | (child, None) => return Some(child.clone()),
+            // 子节点数量为2，用中序遍历的下一个节点替换当前节点
+            (Some(_), Some(right)) => {
+                let mut next = right;
+                while let Some(left) = &next.left {
+                    next = left;
+                }
+                let next_val = next.value.clone();
+
+                PersistentNode {
+                    left: node.left.clone(),
+                    // Safety: next_val 一定存在于右子树中
+                    right: remove_recursive(&node.right, &next_val).unwrap(),
+                    value: next_val,
+                }
+            }
+        },
+    };
+
+    Some(Some(Rc::new(copied)))
+}
+
+impl<T> Clone for PersistentBst<T> {
+    /// 只复制根节点的引用，时间复杂度：O(1)
+    fn clone(&self) -> Self {
+        Self {
+            root: self.root.clone(),
+            len: self.len,
+        }
+    }
+}
+
+impl<T> Default for PersistentBst<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Ord + Clone, const N: usize> From<[T; N]> for PersistentBst<T> {
+    fn from(v: [T; N]) -> Self {
+        v.into_iter()
+            .fold(PersistentBst::new(), |tree, val| tree.insert(val))
+    }
+}
+
+/// 记录 PersistentBst 所有历史版本，版本号从 0（空树）开始递增
+#[derive(Debug, Clone)]
+pub struct BstVersions<T> {
+    versions: Vec<PersistentBst<T>>,
+}
+
+impl<T> BstVersions<T> {
+    /// 创建仅包含空树（版本 0）的历史记录
+    pub fn new() -> Self {
+        Self {
+            versions: vec![PersistentBst::new()],
+        }
+    }
+
+    /// 获取最新版本
+    pub fn current(&self) -> &PersistentBst<T> {
+        // Safety: versions 至少包含版本 0
+        self.versions.last().unwrap()
+    }
+
+    /// 获取指定版本，不存在则返回 None
+    pub fn get(&self, version: usize) -> Option<&PersistentBst<T>> {
+        self.versions.get(version)
+    }
+
+    /// 最新版本的版本号
+    pub fn latest(&self) -> usize {
+        self.versions.len() - 1
+    }
+
+    /// 遍历所有版本
+    pub fn iter(&self) -> impl Iterator<Item = &PersistentBst<T>> {
+        self.versions.iter()
+    }
+}
+
+impl<T: Ord + Clone> BstVersions<T> {
+    /// 在最新版本上插入节点，生成新版本并返回其版本号
+    pub fn insert(&mut self, val: T) -> usize {
+        let next = self.current().insert(val);
+        self.versions.push(next);
+        self.latest()
+    }
+
+    /// 在最新版本上删除节点，生成新版本并返回其版本号
+    pub fn remove(&mut self, val: &T) -> usize {
+        let next = self.current().remove(val);
+        self.versions.push(next);
+        self.latest()
+    }
+}
+
+impl<T> Default for BstVersions<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn persistent_bst_basics_should_work() {
+        let v1 = PersistentBst::from([4, 2, 6, 1, 3, 5, 7]);
+        let v2 = v1.insert(8);
+        let v3 = v2.remove(&4);
+
+        assert_eq!(v1.to_vec(), vec![1, 2, 3, 4, 5, 6, 7]);
+        assert_eq!(v2.to_vec(), vec![1, 2, 3, 4, 5, 6, 7, 8]);
+        assert_eq!(v3.to_vec(), vec![1, 2, 3, 5, 6, 7, 8]);
+        assert_eq!(v3.len(), 7);
+
+        assert!(v1.contains(&4));
+        assert!(!v3.contains(&4));
+        assert!(!v1.contains(&8));
+    }
+
+    #[test]
+    fn persistent_bst_should_share_subtrees() {
+        let v1 = PersistentBst::from([4, 2, 6]);
+        let v2 = v1.insert(7);
+
+        // 插入右子树不影响左子树，左子树应被共享
+        let (left1, left2) = (v1.root.as_ref().unwrap(), v2.root.as_ref().unwrap());
+        assert!(Rc::ptr_eq(
+            left1.left.as_ref().unwrap(),
+            left2.left.as_ref().unwrap()
+        ));
+
+        // 重复插入或删除不存在的值不产生新节点
+        assert!(v2.insert(7).ptr_eq(&v2));
+        assert!(v2.remove(&9).ptr_eq(&v2));
+    }
+
+    #[test]
+    fn bst_versions_should_work() {
+        let mut versions = BstVersions::new();
+
+        versions.insert(3);
+        versions.insert(1);
+        let v = versions.insert(2);
+        versions.remove(&3);
+
+        assert_eq!(v, 3);
+        assert_eq!(versions.latest(), 4);
+        assert_eq!(versions.get(0).unwrap().to_vec(), Vec::<i32>::new());
+        assert_eq!(versions.get(v).unwrap().to_vec(), vec![1, 2, 3]);
+        assert_eq!(versions.current().to_vec(), vec![1, 2]);
+        assert!(versions.get(5).is_none());
+        assert_eq!(
+            versions.iter().map(|v| v.len()).collect::<Vec<_>>(),
+            [0, 1, 2, 3, 2]
+        );
+    }
+}
@@ -0,0 +1,211 @@
+//! 对象池：复用创建代价较高的对象（如预先分配了容量的缓冲区），避免反复分配和释放
+//!
+//! 空闲对象保存在栈 [`StackWithVec`] 中，最近归还的对象最先被取出，它的内存更可能还在缓存中。
+//! 取出的对象包装在 [`PoolGuard`] 中，离开作用域时自动归还；空闲对象已达到容量上限时直接丢弃，
+//! 使池占用的内存有界。对象池只在单线程中使用，内部用 RefCell 记录空闲对象。
+
+use std::{
+    cell::RefCell,
+    fmt,
+    ops::{Deref, DerefMut},
+};
+
+use crate::StackWithVec;
+
+/// 有界的对象池
+///
+/// # Examples
+///
+/// ```rust
+/// use base::Pool;
+///
+/// let pool = Pool::new(2, || Vec::<u8>::with_capacity(1024));
+///
+/// {
+///     let mut buf = pool.acquire();
+///     buf.extend_from_slice(b"hello");
+///     assert_eq!(pool.available(), 0);
+/// }
+///
+/// // 归还的缓冲区保留了内容和容量，使用前需要自行清空
+/// let mut buf = pool.acquire();
+/// assert_eq!(&buf[..], b"hello");
+/// buf.clear();
+/// assert!(buf.capacity() >= 1024);
+/// ```
+pub struct Pool<T, F> {
+    free: RefCell<StackWithVec<T>>,
+    capacity: usize,
+    factory: F,
+}
+
+impl<T, F: Fn() -> T> Pool<T, F> {
+    /// 创建对象池，最多保留 capacity 个空闲对象，池为空时用 factory 创建新对象
+    pub fn new(capacity: usize, factory: F) -> Self {
+        Self {
+            free: RefCell::new(StackWithVec::new()),
+            capacity,
+            factory,
+        }
+    }
+
+    /// 取出一个空闲对象，没有空闲对象时创建新对象
+    pub fn acquire(&self) -> PoolGuard<'_, T, F> {
+        let value = self
+            .free
+            .borrow_mut()
+            .pop()
+            .unwrap_or_else(|| (self.factory)());
+
+        PoolGuard {
+            pool: self,
+            value: Some(value),
+        }
+    }
+
+    /// 预先创建对象，使空闲对象达到容量上限
+    pub fn fill(&self) {
+        let mut free = self.free.borrow_mut();
+        while free.len() < self.capacity {
+            free.push((self.factory)());
+        }
+    }
+}
+
+impl<T, F> Pool<T, F> {
+    /// 最多保留的空闲对象数量
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// 当前空闲对象的数量
+    pub fn available(&self) -> usize {
+        self.free.borrow().len()
+    }
+
+    /// 丢弃全部空闲对象
+    pub fn clear(&self) {
+        self.free.borrow_mut().clear();
+    }
+
+    /// 归还对象，空闲对象已达到容量上限时丢弃
+    fn release(&self, value: T) {
+        let mut free = self.free.borrow_mut();
+        if free.len() < self.capacity {
+            free.push(value);
+        }
+    }
+}
+
+impl<T, F> fmt::Debug for Pool<T, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Pool")
+            .field("capacity", &self.capacity)
+            .field("available", &self.available())
+            .finish_non_exhaustive()
+    }
+}
+
+/// 从对象池中取出的对象，离开作用域时归还对象池
+pub struct PoolGuard<'a, T, F> {
+    pool: &'a Pool<T, F>,
+    /// 只在 drop 或 into_inner 时取走
+    value: Option<T>,
+}
+
+impl<T, F> PoolGuard<'_, T, F> {
+    /// 取走对象，不再归还对象池
+    pub fn into_inner(mut guard: Self) -> T {
+        // Safety: guard 尚未 drop，value 一定存在
+        guard.value.take().unwrap()
+    }
+}
+
+impl<T, F> Deref for PoolGuard<'_, T, F> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        // Safety: value 只在 drop 或 into_inner 时取走
+        self.value.as_ref().unwrap()
+    }
+}
+
+impl<T, F> DerefMut for PoolGuard<'_, T, F> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        // Safety: value 只在 drop 或 into_inner 时取走
+        self.value.as_mut().unwrap()
+    }
+}
+
+impl<T, F> Drop for PoolGuard<'_, T, F> {
+    fn drop(&mut self) {
+        if let Some(value) = self.value.take() {
+            self.pool.release(value);
+        }
+    }
+}
+
+impl<T: fmt::Debug, F> fmt::Debug for PoolGuard<'_, T, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("PoolGuard").field(&**self).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use super::*;
+
+    #[test]
+    fn pool_should_reuse_released_objects() {
+        let created = Cell::new(0);
+        let pool = Pool::new(2, || {
+            created.set(created.get() + 1);
+            created.get()
+        });
+
+        {
+            let (a, b, c) = (pool.acquire(), pool.acquire(), pool.acquire());
+            assert_eq!((*a, *b, *c), (1, 2, 3));
+        }
+        // 只保留 2 个空闲对象，后归还的先取出
+        assert_eq!(pool.available(), 2);
+        assert_eq!(*pool.acquire(), 2);
+        assert_eq!(*pool.acquire(), 2);
+
+        let kept = PoolGuard::into_inner(pool.acquire());
+        assert_eq!((kept, pool.available()), (2, 1));
+
+        pool.clear();
+        pool.fill();
+        assert_eq!((created.get(), pool.available()), (5, 2));
+        assert_eq!(
+            format!("{pool:?}"),
+            "Pool { capacity: 2, available: 2, .. }"
+        );
+    }
+
+    #[cfg(feature = "alloc-counter")]
+    #[test]
+    fn pool_should_not_allocate_when_warm() {
+        use crate::alloc_counter::measure_allocations;
+
+        let pool = Pool::new(4, || Vec::<u64>::with_capacity(64));
+        pool.fill();
+
+        let ((), stats) = measure_allocations(|| {
+            for round in 0..100 {
+                let mut a = pool.acquire();
+                let mut b = pool.acquire();
+                a.clear();
+                b.clear();
+                a.extend(0..round % 64);
+                b.extend(a.iter().rev());
+            }
+        });
+
+        assert_eq!(stats.allocations, 0);
+        assert_eq!(pool.available(), 4);
+    }
+}
@@ -0,0 +1,320 @@
+//! 前缀和与差分数组
+//!
+//! * 前缀和：预处理 s[i] = v[0] + v[1] + ... + v[i - 1]，区间和 v[l..r] = s[r] - s[l]，
+//!   预处理 O(n)，查询 O(1)，适用于数组不变、频繁查询区间和的场景
+//! * 二维前缀和：s[i][j] 为左上角 i 行 j 列的元素和，由容斥原理 O(1) 求出任意子矩阵的和
+//! * 差分数组：d[i] = v[i] - v[i - 1]，对区间 [l, r) 整体加 x 只需修改 d[l] += x、d[r] -= x，
+//!   区间修改 O(1)，最后对差分数组求前缀和即可还原出原数组
+
+use std::ops::{Add, Bound, Range, RangeBounds, Sub};
+
+/// 将区间转换为 [start, end)
+///
+/// # Panics
+///
+/// Panics if the range is out of bounds or decreasing.
+fn to_range(range: impl RangeBounds<usize>, len: usize) -> Range<usize> {
+    let start = match range.start_bound() {
+        Bound::Included(&start) => start,
+        Bound::Excluded(&start) => start + 1,
+        Bound::Unbounded => 0,
+    };
+    let end = match range.end_bound() {
+        Bound::Included(&end) => end + 1,
+        Bound::Excluded(&end) => end,
+        Bound::Unbounded => len,
+    };
+    assert!(
+        start <= end && end <= len,
+        "Range {start}..{end} out of bounds, len: {len}"
+    );
+
+    start..end
+}
+
+/// 一维前缀和
+#[derive(Debug, Clone)]
+pub struct PrefixSums<T> {
+    /// sums[i] 为前 i 个元素的和，长度为 n + 1
+    sums: Vec<T>,
+}
+
+impl<T> PrefixSums<T>
+where
+    T: Copy + Default + Add<Output = T> + Sub<Output = T>,
+{
+    /// 预处理前缀和，时间复杂度：O(n)
+    pub fn new(values: &[T]) -> Self {
+        values.iter().copied().collect()
+    }
+
+    /// 元素数量
+    pub fn len(&self) -> usize {
+        self.sums.len() - 1
+    }
+
+    /// 判断是否为空
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// 区间和，时间复杂度：O(1)
+    ///
+    /// # Panics
+    ///
+    /// Panics if `range` is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use base::PrefixSums;
+    ///
+    /// let sums = PrefixSums::new(&[3, 1, 4, 1, 5]);
+    ///
+    /// assert_eq!(sums.range_sum(1..4), 6);
+    /// assert_eq!(sums.range_sum(..), 14);
+    /// ```
+    pub fn range_sum(&self, range: impl RangeBounds<usize>) -> T {
+        let Range { start, end } = to_range(range, self.len());
+
+        self.sums[end] - self.sums[start]
+    }
+}
+
+impl<T> FromIterator<T> for PrefixSums<T>
+where
+    T: Copy + Default + Add<Output = T>,
+{
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut sums = vec![T::default()];
+        for value in iter {
+            // Safety: sums 至少包含一个元素
+            let last = *sums.last().unwrap();
+            sums.push(last + value);
+        }
+
+        Self { sums }
+    }
+}
+
+/// 二维前缀和
+#[derive(Debug, Clone)]
+pub struct PrefixSums2D<T> {
+    /// sums[i][j] 为前 i 行、前 j 列元素的和，大小为 (rows + 1) × (cols + 1)
+    sums: Vec<Vec<T>>,
+}
+
+impl<T> PrefixSums2D<T>
+where
+    T: Copy + Default + Add<Output = T> + Sub<Output = T>,
+{
+    /// 预处理二维前缀和，时间复杂度：O(rows × cols)
+    ///
+    /// # Panics
+    ///
+    /// Panics if the rows have different lengths.
+    pub fn new(matrix: &[Vec<T>]) -> Self {
+        let cols = matrix.first().map_or(0, Vec::len);
+        let mut sums = vec![vec![T::default(); cols + 1]; matrix.len() + 1];
+
+        for (i, row) in matrix.iter().enumerate() {
+            assert_eq!(row.len(), cols, "Row {i} has a different length");
+            for (j, &value) in row.iter().enumerate() {
+                // 容斥原理：上方 + 左方 - 左上方重复计算的部分
+                sums[i + 1][j + 1] = sums[i][j + 1] + sums[i + 1][j] - sums[i][j] + value;
+            }
+        }
+
+        Self { sums }
+    }
+
+    /// 行数
+    pub fn rows(&self) -> usize {
+        self.sums.len() - 1
+    }
+
+    /// 列数
+    pub fn cols(&self) -> usize {
+        self.sums[0].len() - 1
+    }
+
+    /// 子矩阵的元素和，时间复杂度：O(1)
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rows` or `cols` is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use base::PrefixSums2D;
+    ///
+    /// let sums = PrefixSums2D::new(&[vec![1, 2, 3], vec![4, 5, 6], vec![7, 8, 9]]);
+    ///
+    /// assert_eq!(sums.range_sum(1..3, 1..3), 28);
+    /// assert_eq!(sums.range_sum(.., ..1), 12);
+    /// ```
+    pub fn range_sum(&self, rows: impl RangeBounds<usize>, cols: impl RangeBounds<usize>) -> T {
+        let rows = to_range(rows, self.rows());
+        let cols = to_range(cols, self.cols());
+        let s = &self.sums;
+
+        s[rows.end][cols.end] - s[rows.start][cols.end] - s[rows.end][cols.start]
+            + s[rows.start][cols.start]
+    }
+}
+
+/// 差分数组，支持 O(1) 的区间加法
+#[derive(Debug, Clone)]
+pub struct DiffArray<T> {
+    /// diff[i] = v[i] - v[i - 1]，长度为 n + 1，末尾元素用于抵消对区间末尾之后的修改
+    diff: Vec<T>,
+}
+
+impl<T> DiffArray<T>
+where
+    T: Copy + Default + Add<Output = T> + Sub<Output = T>,
+{
+    /// 创建长度为 len、元素均为默认值的差分数组
+    pub fn new(len: usize) -> Self {
+        Self {
+            diff: vec![T::default(); len + 1],
+        }
+    }
+
+    /// 元素数量
+    pub fn len(&self) -> usize {
+        self.diff.len() - 1
+    }
+
+    /// 判断是否为空
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// 将区间内的所有元素加上 delta，时间复杂度：O(1)
+    ///
+    /// # Panics
+    ///
+    /// Panics if `range` is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use base::DiffArray;
+    ///
+    /// let mut diff = DiffArray::from(vec![1, 1, 1, 1, 1]);
+    /// diff.range_add(1..4, 2);
+    /// diff.range_add(3.., -1);
+    ///
+    /// assert_eq!(diff.get(3), 2);
+    /// assert_eq!(diff.to_vec(), [1, 3, 3, 2, 0]);
+    /// ```
+    pub fn range_add(&mut self, range: impl RangeBounds<usize>, delta: T) {
+        let Range { start, end } = to_range(range, self.len());
+
+        self.diff[start] = self.diff[start] + delta;
+        self.diff[end] = self.diff[end] - delta;
+    }
+
+    /// 查询单个元素，需要对差分数组求前缀和，时间复杂度：O(n)
+    ///
+    /// # Panics
+    ///
+    /// Panics if `at >= len`.
+    pub fn get(&self, at: usize) -> T {
+        assert!(at < self.len(), "Index out of bounds: {at}");
+
+        self.diff[..=at]
+            .iter()
+            .fold(T::default(), |sum, &delta| sum + delta)
+    }
+
+    /// 还原出原数组，时间复杂度：O(n)
+    pub fn to_vec(&self) -> Vec<T> {
+        self.diff[..self.len()]
+            .iter()
+            .scan(T::default(), |sum, &delta| {
+                *sum = *sum + delta;
+                Some(*sum)
+            })
+            .collect()
+    }
+}
+
+impl<T> From<Vec<T>> for DiffArray<T>
+where
+    T: Copy + Default + Add<Output = T> + Sub<Output = T>,
+{
+    fn from(values: Vec<T>) -> Self {
+        let mut diff = Vec::with_capacity(values.len() + 1);
+        let mut prev = T::default();
+        for value in values {
+            diff.push(value - prev);
+            prev = value;
+        }
+        diff.push(T::default());
+
+        Self { diff }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prefix_sums_should_work() {
+        let v = [5, -2, 7, 0, 3, -8];
+        let sums = PrefixSums::new(&v);
+
+        assert_eq!(sums.len(), 6);
+        for l in 0..=v.len() {
+            for r in l..=v.len() {
+                assert_eq!(sums.range_sum(l..r), v[l..r].iter().sum::<i32>());
+            }
+        }
+        assert_eq!(sums.range_sum(2..=3), 7);
+
+        let empty = PrefixSums::<f64>::new(&[]);
+        assert!(empty.is_empty());
+        assert_eq!(empty.range_sum(..), 0.0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn prefix_sums_out_of_bounds_should_panic() {
+        PrefixSums::new(&[1, 2, 3]).range_sum(1..4);
+    }
+
+    #[test]
+    fn prefix_sums_2d_should_work() {
+        let matrix = (0..4)
+            .map(|i| (0..5).map(|j| i * 5 + j).collect())
+            .collect::<Vec<Vec<i64>>>();
+        let sums = PrefixSums2D::new(&matrix);
+
+        assert_eq!((sums.rows(), sums.cols()), (4, 5));
+        for (rows, cols) in [(0..4, 0..5), (1..3, 2..4), (2..2, 0..5), (3..4, 4..5)] {
+            let expected = matrix[rows.clone()]
+                .iter()
+                .map(|row| row[cols.clone()].iter().sum::<i64>())
+                .sum::<i64>();
+            assert_eq!(sums.range_sum(rows, cols), expected);
+        }
+        assert_eq!(PrefixSums2D::<i64>::new(&[]).range_sum(.., ..), 0);
+    }
+
+    #[test]
+    fn diff_array_should_work() {
+        let mut diff = DiffArray::new(6);
+        let mut expected = vec![0; 6];
+        for (range, delta) in [(0..6, 1), (2..4, 5), (3..3, 100), (5..6, -2)] {
+            expected[range.clone()].iter_mut().for_each(|x| *x += delta);
+            diff.range_add(range, delta);
+        }
+
+        assert_eq!(diff.to_vec(), expected);
+        assert_eq!((0..6).map(|i| diff.get(i)).collect::<Vec<_>>(), expected);
+    }
+}
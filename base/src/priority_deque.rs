@@ -0,0 +1,348 @@
+//! 双端优先队列（double-ended priority queue），基于区间堆（interval heap）实现
+//!
+//! 区间堆是一棵完全二叉树，每个结点保存两个元素 lo <= hi，表示区间 [lo, hi]：
+//! * 所有结点的 lo 构成小顶堆，所有结点的 hi 构成大顶堆
+//! * 每个结点的区间都包含在父结点的区间之内
+//!
+//! 因此根结点的 lo 和 hi 分别是最小值和最大值。最后一个结点可能只有一个元素，
+//! 它同时充当 lo 和 hi。与堆一样使用数组存储：结点 i 的两个元素位于下标 2i 和 2i + 1，
+//! 子结点为 2i + 1 和 2i + 2。
+//!
+//! 入队、弹出最小值、弹出最大值的时间复杂度均为 O(logn)，查看两端的时间复杂度为 O(1)。
+
+use std::mem;
+
+/// 双端优先队列，可以设置容量上限，超出上限时淘汰最小的元素
+///
+/// # Examples
+///
+/// ```rust
+/// use base::PriorityDeque;
+///
+/// let mut deque = PriorityDeque::from_iter([5, 1, 9, 3]);
+///
+/// assert_eq!(deque.peek_min(), Some(&1));
+/// assert_eq!(deque.peek_max(), Some(&9));
+/// assert_eq!(deque.pop_max(), Some(9));
+/// assert_eq!(deque.pop_min(), Some(1));
+/// assert_eq!(deque.len(), 2);
+/// ```
+#[derive(Debug, Clone)]
+pub struct PriorityDeque<T> {
+    data: Vec<T>,
+    bound: Option<usize>,
+}
+
+impl<T> PriorityDeque<T> {
+    /// 创建没有容量上限的空队列
+    pub fn new() -> Self {
+        Self {
+            data: Vec::new(),
+            bound: None,
+        }
+    }
+
+    /// 创建容量上限为 bound 的空队列
+    pub fn with_bound(bound: usize) -> Self {
+        Self {
+            data: Vec::with_capacity(bound),
+            bound: Some(bound),
+        }
+    }
+
+    /// 容量上限
+    pub fn bound(&self) -> Option<usize> {
+        self.bound
+    }
+
+    /// 元素数量
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    /// 判断队列是否为空
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// 最小元素
+    pub fn peek_min(&self) -> Option<&T> {
+        self.data.first()
+    }
+
+    /// 最大元素，只有一个元素时与最小元素相同
+    pub fn peek_max(&self) -> Option<&T> {
+        self.data.get(1).or(self.data.first())
+    }
+
+    /// 以任意顺序遍历所有元素
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.data.iter()
+    }
+}
+
+impl<T: Ord> PriorityDeque<T> {
+    /// 元素入队，返回因超出容量上限被淘汰的元素
+    ///
+    /// 队列已满时，若新元素不大于最小元素则直接淘汰新元素，否则淘汰原来的最小元素
+    pub fn push(&mut self, val: T) -> Option<T> {
+        if let Some(bound) = self.bound
+            && self.len() >= bound
+        {
+            match self.peek_min() {
+                Some(min) if *min < val => {}
+                // 队列为空说明容量上限为 0
+                _ => return Some(val),
+            }
+            self.push_unbounded(val);
+            return self.pop_min();
+        }
+
+        self.push_unbounded(val);
+        None
+    }
+
+    /// 弹出最小元素
+    pub fn pop_min(&mut self) -> Option<T> {
+        let last = self.data.pop()?;
+        if self.data.is_empty() {
+            return Some(last);
+        }
+
+        let min = mem::replace(&mut self.data[0], last);
+        self.sift_down_min();
+        Some(min)
+    }
+
+    /// 弹出最大元素
+    pub fn pop_max(&mut self) -> Option<T> {
+        let last = self.data.pop()?;
+        // 只剩一个或两个元素时，最后一个元素就是最大元素
+        if self.data.len() <= 1 {
+            return Some(last);
+        }
+
+        let max = mem::replace(&mut self.data[1], last);
+        self.sift_down_max();
+        Some(max)
+    }
+
+    /// 只保留满足条件的元素，时间复杂度：O(nlogn)
+    pub fn retain<F: FnMut(&T) -> bool>(&mut self, f: F) {
+        let mut data = mem::take(&mut self.data);
+        data.retain(f);
+
+        for val in data {
+            self.push_unbounded(val);
+        }
+    }
+
+    /// 转换为升序数组
+    pub fn into_sorted_vec(mut self) -> Vec<T> {
+        let mut v = Vec::with_capacity(self.len());
+        while let Some(val) = self.pop_min() {
+            v.push(val);
+        }
+
+        v
+    }
+
+    fn push_unbounded(&mut self, val: T) {
+        self.data.push(val);
+
+        let mut k = self.data.len() - 1;
+        // 新元素补全了一个结点，先保证结点内 lo <= hi
+        if k % 2 == 1 && self.data[k - 1] > self.data[k] {
+            self.data.swap(k - 1, k);
+            k -= 1;
+        }
+
+        let node = k / 2;
+        if node == 0 {
+            return;
+        }
+        let parent = (node - 1) / 2;
+        if self.data[k] < self.data[2 * parent] {
+            self.sift_up_min(k);
+        } else if self.data[k] > self.data[2 * parent + 1] {
+            self.sift_up_max(k);
+        }
+    }
+
+    /// 位置 k 的元素沿 lo 构成的小顶堆向上调整
+    fn sift_up_min(&mut self, mut k: usize) {
+        while k / 2 > 0 {
+            let parent = 2 * ((k / 2 - 1) / 2);
+            if self.data[k] >= self.data[parent] {
+                break;
+            }
+            self.data.swap(k, parent);
+            k = parent;
+        }
+    }
+
+    /// 位置 k 的元素沿 hi 构成的大顶堆向上调整
+    fn sift_up_max(&mut self, mut k: usize) {
+        while k / 2 > 0 {
+            let parent = 2 * ((k / 2 - 1) / 2) + 1;
+            if self.data[k] <= self.data[parent] {
+                break;
+            }
+            self.data.swap(k, parent);
+            k = parent;
+        }
+    }
+
+    /// 根结点的 lo 被替换后，沿 lo 构成的小顶堆向下调整
+    fn sift_down_min(&mut self) {
+        let n = self.data.len();
+        let mut node = 0;
+
+        loop {
+            let lo = 2 * node;
+            if lo + 1 < n && self.data[lo] > self.data[lo + 1] {
+                self.data.swap(lo, lo + 1);
+            }
+
+            // 在两个子结点的 lo 中找最小的
+            let Some(child) = [2 * lo + 2, 2 * lo + 4]
+                .into_iter()
+                .filter(|&c| c < n)
+                .min_by(|&a, &b| self.data[a].cmp(&self.data[b]))
+            else {
+                break;
+            };
+            if self.data[child] >= self.data[lo] {
+                break;
+            }
+
+            self.data.swap(lo, child);
+            node = child / 2;
+        }
+    }
+
+    /// 根结点的 hi 被替换后，沿 hi 构成的大顶堆向下调整
+    fn sift_down_max(&mut self) {
+        let n = self.data.len();
+        let mut node = 0;
+
+        loop {
+            let (lo, hi) = (2 * node, 2 * node + 1);
+            // 只有一个元素的结点一定是叶结点
+            if hi >= n {
+                break;
+            }
+            if self.data[lo] > self.data[hi] {
+                self.data.swap(lo, hi);
+            }
+
+            // 在两个子结点的 hi 中找最大的，只有一个元素的子结点用它的 lo
+            let Some(child) = [2 * lo + 2, 2 * lo + 4]
+                .into_iter()
+                .filter(|&c| c < n)
+                .map(|c| if c + 1 < n { c + 1 } else { c })
+                .max_by(|&a, &b| self.data[a].cmp(&self.data[b]))
+            else {
+                break;
+            };
+            if self.data[child] <= self.data[hi] {
+                break;
+            }
+
+            self.data.swap(hi, child);
+            node = child / 2;
+        }
+    }
+}
+
+impl<T> Default for PriorityDeque<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Ord> FromIterator<T> for PriorityDeque<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut deque = PriorityDeque::new();
+
+        iter.into_iter().for_each(|val| {
+            deque.push(val);
+        });
+
+        deque
+    }
+}
+
+impl<T: Ord> Extend<T> for PriorityDeque<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        iter.into_iter().for_each(|val| {
+            self.push(val);
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generate::{self, Rng};
+
+    #[test]
+    fn priority_deque_should_match_sorted_vec() {
+        let mut deque = PriorityDeque::new();
+        let mut expected = Vec::new();
+        let mut rng = Rng::new(11);
+
+        for _ in 0..5000 {
+            match rng.below(4) {
+                0 | 1 => {
+                    let val = rng.below(100);
+                    deque.push(val);
+                    let pos = expected.partition_point(|&x| x < val);
+                    expected.insert(pos, val);
+                }
+                2 => assert_eq!(
+                    deque.pop_min(),
+                    (!expected.is_empty()).then(|| expected.remove(0))
+                ),
+                _ => assert_eq!(deque.pop_max(), expected.pop()),
+            }
+            assert_eq!(deque.peek_min(), expected.first());
+            assert_eq!(deque.peek_max(), expected.last());
+        }
+
+        assert_eq!(deque.into_sorted_vec(), expected);
+    }
+
+    #[test]
+    fn priority_deque_bound_should_keep_largest() {
+        let mut deque = PriorityDeque::with_bound(3);
+
+        assert_eq!(deque.push(5), None);
+        assert_eq!(deque.push(1), None);
+        assert_eq!(deque.push(7), None);
+        assert_eq!(deque.push(0), Some(0));
+        assert_eq!(deque.push(6), Some(1));
+        assert_eq!(deque.len(), 3);
+        assert_eq!(deque.clone().into_sorted_vec(), [5, 6, 7]);
+
+        let mut top = PriorityDeque::with_bound(10);
+        top.extend(generate::uniform(1000, 3));
+        let mut expected = generate::uniform(1000, 3);
+        expected.sort();
+        assert_eq!(top.into_sorted_vec(), expected[990..]);
+
+        let mut empty = PriorityDeque::with_bound(0);
+        assert_eq!(empty.push(1), Some(1));
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn priority_deque_retain_should_work() {
+        let mut deque = (0..20).collect::<PriorityDeque<_>>();
+
+        deque.retain(|x| x % 3 == 0);
+        assert_eq!(deque.peek_min(), Some(&0));
+        assert_eq!(deque.peek_max(), Some(&18));
+        assert_eq!(deque.into_sorted_vec(), [0, 3, 6, 9, 12, 15, 18]);
+    }
+}
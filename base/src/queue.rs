@@ -43,6 +43,33 @@ impl<T> QueueWithList<T> {
     }
 }
 
+/// 数组的第一个元素位于队首
+impl<T, const N: usize> From<[T; N]> for QueueWithList<T> {
+    fn from(v: [T; N]) -> Self {
+        QueueWithList(LinkedList::from(v))
+    }
+}
+
+/// 用字面量构建 [`QueueWithList`]，依次入队，第一个元素位于队首
+///
+/// # Examples
+///
+/// ```rust
+/// use base::queue;
+///
+/// let mut queue = queue![1, 2, 3];
+///
+/// assert_eq!(queue.peek(), Some(&1));
+/// assert_eq!(queue.tail(), Some(&3));
+/// assert_eq!(queue.pop(), Some(1));
+/// ```
+#[macro_export]
+macro_rules! queue {
+    ($($x:expr),* $(,)?) => {
+        $crate::QueueWithList::from([$($x),*])
+    };
+}
+
 impl<T: Clone> QueueWithList<T> {
     pub fn to_vec(&self) -> Vec<T> {
         self.0.iter().cloned().collect()
@@ -145,6 +172,20 @@ impl<T: Copy, const N: usize> QueueWithArray<T, N> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn queue_macro_should_match_pushes() {
+        let mut pushed = QueueWithList::new();
+        (1..=3).for_each(|i| pushed.push(i));
+
+        let mut queue = queue![1, 2, 3];
+        assert_eq!(queue.to_vec(), pushed.to_vec());
+        assert_eq!(queue.pop(), pushed.pop());
+        assert_eq!(queue.to_vec(), [2, 3]);
+
+        let empty: QueueWithList<i32> = queue![];
+        assert!(empty.is_empty());
+    }
+
     #[test]
     fn queue_with_list_basics() {
         let mut queue = QueueWithList::new();
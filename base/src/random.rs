@@ -0,0 +1,231 @@
+//! 随机化算法与可复现的伪随机数生成器
+//!
+//! 随机化的数据结构和算法（随机基准的快速排序、跳表的层数等）都从 [`RandomSource`] 获取随机数，
+//! 相同的种子总是得到相同的结果，测试失败可以复现。生成器都在本模块中实现，不依赖外部库：
+//! * [`Xorshift64`]：三次移位异或，速度快、实现简单，状态不能为 0
+//! * [`SplitMix64`]：每次把状态加上一个固定的奇数，再对其做混合，任意种子（包括 0）都能使用，
+//!   常用于为其他生成器产生种子
+//!
+//! 两者都不适合密码学用途。
+
+/// 伪随机数来源
+pub trait RandomSource {
+    /// 下一个随机数
+    fn next_u64(&mut self) -> u64;
+
+    /// [0, n) 内的随机数，存在可以忽略的取模偏差
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n == 0`.
+    fn below(&mut self, n: u64) -> u64 {
+        assert!(n > 0, "Upper bound must be positive");
+
+        self.next_u64() % n
+    }
+}
+
+/// xorshift64 默认种子
+const DEFAULT_SEED: u64 = 0x9e37_79b9_7f4a_7c15;
+
+/// xorshift64 伪随机数生成器
+#[derive(Debug, Clone)]
+pub struct Xorshift64 {
+    state: u64,
+}
+
+impl Xorshift64 {
+    /// 使用给定的种子创建生成器，种子为 0 时使用默认种子（xorshift 的状态不能为 0）
+    pub fn new(seed: u64) -> Self {
+        Self {
+            state: if seed == 0 { DEFAULT_SEED } else { seed },
+        }
+    }
+
+    /// 下一个随机数
+    pub fn next_u64(&mut self) -> u64 {
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 7;
+        self.state ^= self.state << 17;
+        self.state
+    }
+
+    /// [0, n) 内的随机数，存在可以忽略的取模偏差
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n == 0`.
+    pub fn below(&mut self, n: u64) -> u64 {
+        RandomSource::below(self, n)
+    }
+
+    /// 随机打乱切片，见 [`fisher_yates_shuffle`]
+    pub fn shuffle<T>(&mut self, v: &mut [T]) {
+        fisher_yates_shuffle(v, self);
+    }
+}
+
+impl Default for Xorshift64 {
+    fn default() -> Self {
+        Self::new(DEFAULT_SEED)
+    }
+}
+
+impl RandomSource for Xorshift64 {
+    fn next_u64(&mut self) -> u64 {
+        Xorshift64::next_u64(self)
+    }
+}
+
+impl Iterator for Xorshift64 {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(self.next_u64())
+    }
+}
+
+/// SplitMix64 伪随机数生成器
+#[derive(Debug, Clone, Default)]
+pub struct SplitMix64 {
+    state: u64,
+}
+
+impl SplitMix64 {
+    /// 使用给定的种子创建生成器
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+}
+
+impl RandomSource for SplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+}
+
+impl Iterator for SplitMix64 {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(RandomSource::next_u64(self))
+    }
+}
+
+/// Fisher-Yates 洗牌：从后往前，把每个位置与它及它之前的一个随机位置交换，
+/// n! 种排列出现的概率相同，时间复杂度：O(n)
+///
+/// # Examples
+///
+/// ```rust
+/// use base::random::{self, SplitMix64};
+///
+/// let mut v = [1, 2, 3, 4, 5];
+/// random::fisher_yates_shuffle(&mut v, &mut SplitMix64::new(42));
+///
+/// v.sort();
+/// assert_eq!(v, [1, 2, 3, 4, 5]);
+/// ```
+pub fn fisher_yates_shuffle<T, R: RandomSource + ?Sized>(v: &mut [T], rng: &mut R) {
+    for i in (1..v.len()).rev() {
+        let j = rng.below(i as u64 + 1) as usize;
+        v.swap(i, j);
+    }
+}
+
+/// 从序列中等概率地不放回抽取 k 个元素（蓄水池抽样），元素不足 k 个时全部返回
+///
+/// 保留前 k 个元素，之后的第 i 个元素（从 0 开始）以 k / (i + 1) 的概率替换蓄水池中的随机一个，
+/// 只需遍历一次序列，适用于长度未知的数据流。返回的元素顺序不保证与原序列一致。
+///
+/// # Examples
+///
+/// ```rust
+/// use base::random::{self, Xorshift64};
+///
+/// let sample = random::sample_without_replacement(0..100, 10, &mut Xorshift64::new(7));
+///
+/// assert_eq!(sample.len(), 10);
+/// assert!(sample.iter().all(|&x| x < 100));
+/// ```
+pub fn sample_without_replacement<I, R>(iter: I, k: usize, rng: &mut R) -> Vec<I::Item>
+where
+    I: IntoIterator,
+    R: RandomSource + ?Sized,
+{
+    let mut iter = iter.into_iter();
+    let mut reservoir = iter.by_ref().take(k).collect::<Vec<_>>();
+    if reservoir.len() < k {
+        return reservoir;
+    }
+
+    for (i, item) in iter.enumerate() {
+        let j = rng.below((k + i + 1) as u64) as usize;
+        if j < k {
+            reservoir[j] = item;
+        }
+    }
+
+    reservoir
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generators_should_be_reproducible() {
+        let a = SplitMix64::new(0).take(100).collect::<Vec<_>>();
+        assert_eq!(a, SplitMix64::new(0).take(100).collect::<Vec<_>>());
+        assert_ne!(a, SplitMix64::new(1).take(100).collect::<Vec<_>>());
+        // SplitMix64 的参考输出
+        assert_eq!(a[0], 0xe220_a839_7b1d_cdaf);
+
+        assert_eq!(
+            Xorshift64::new(0).next_u64(),
+            Xorshift64::default().next_u64()
+        );
+    }
+
+    #[test]
+    fn shuffle_should_be_uniform() {
+        // 3 个元素的 6 种排列出现的次数应大致相同
+        let mut rng = SplitMix64::new(3);
+        let mut counts = std::collections::HashMap::new();
+        for _ in 0..6000 {
+            let mut v = [0, 1, 2];
+            fisher_yates_shuffle(&mut v, &mut rng);
+            *counts.entry(v).or_insert(0) += 1;
+        }
+
+        assert_eq!(counts.len(), 6);
+        assert!(counts.values().all(|&c| (800..1200).contains(&c)));
+    }
+
+    #[test]
+    fn sample_without_replacement_should_work() {
+        let mut rng = Xorshift64::new(5);
+
+        let mut sample = sample_without_replacement(0..50, 20, &mut rng);
+        sample.sort();
+        sample.dedup();
+        assert_eq!(sample.len(), 20);
+
+        assert_eq!(sample_without_replacement(0..3, 5, &mut rng), [0, 1, 2]);
+        assert!(sample_without_replacement(0..3, 0, &mut rng).is_empty());
+
+        // 每个元素被选中的概率都应接近 k / n = 1 / 4
+        let mut hits = [0; 8];
+        for _ in 0..8000 {
+            for x in sample_without_replacement(0..8, 2, &mut rng) {
+                hits[x] += 1;
+            }
+        }
+        assert!(hits.iter().all(|&h| (1700..2300).contains(&h)));
+    }
+}
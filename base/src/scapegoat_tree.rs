@@ -0,0 +1,369 @@
+//! 替罪羊树（scapegoat tree）：通过局部重建保持平衡的二叉搜索树
+//!
+//! AVL 树在每个结点上保存高度，每次插入、删除后立即旋转恢复平衡；替罪羊树的结点不保存任何平衡信息，
+//! 只在整棵树记录元素数量 n 和自上次完全重建以来的最大元素数量 max_n：
+//! * 插入：新结点的深度超过 h(n) = ⌊log_{1/α} n⌋ 时，沿着插入路径向上寻找第一个不满足
+//!   size(child) <= α · size(node) 的祖先结点，称为替罪羊，把以它为根的子树重建为完全平衡的子树
+//! * 删除：按普通二叉搜索树删除，n < α · max_n 时重建整棵树
+//!
+//! 重建一棵大小为 m 的子树需要 O(m) 时间，但要让它再次失衡至少需要 Ω(m) 次插入，
+//! 因此插入和删除的均摊时间复杂度为 O(logn)，查找的最坏时间复杂度为 O(logn)。
+//! 这里取 α = 2/3，重建时先中序遍历得到有序序列，再由有序序列构建平衡的二叉搜索树。
+
+use std::{cmp::Ordering, rc::Rc};
+
+use crate::{
+    BinaryTree, OrderedSet,
+    binary_tree::{self, NodeRc, OptionNodeRc, TreeNode},
+    bt,
+};
+
+/// 替罪羊树
+///
+/// # Examples
+///
+/// ```rust
+/// use base::{ScapegoatTree, bt};
+///
+/// // 有序插入时会不断重建子树，树高保持在 O(logn)
+/// let mut tree = (1..=7).collect::<ScapegoatTree<_>>();
+///
+/// assert!(tree.remove(&4));
+/// assert!(!tree.insert(5));
+/// assert_eq!(tree.to_vec(), [1, 2, 3, 5, 6, 7]);
+/// // 插入 5 时深度超过 h(5) = 3，以 2 为根的子树被重建
+/// assert_eq!(bt::pre_order(&tree.to_tree().root), [1, 5, 3, 2, 6, 7]);
+/// ```
+pub struct ScapegoatTree<T> {
+    root: OptionNodeRc<T>,
+    len: usize,
+    /// 自上次重建整棵树以来的最大元素数量
+    max_len: usize,
+}
+
+impl<T> ScapegoatTree<T> {
+    /// 创建空树
+    pub fn new() -> Self {
+        Self {
+            root: None,
+            len: 0,
+            max_len: 0,
+        }
+    }
+
+    /// 元素数量
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// 判断树是否为空
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+impl<T: Clone> ScapegoatTree<T> {
+    /// 中序遍历，转换为升序的 Vec
+    pub fn to_vec(&self) -> Vec<T> {
+        bt::in_order(&self.root)
+    }
+
+    /// 转换为普通二叉树，复制所有结点
+    pub fn to_tree(&self) -> BinaryTree<T> {
+        BinaryTree {
+            root: clone_tree(&self.root),
+        }
+    }
+}
+
+impl<T: Clone + Ord> ScapegoatTree<T> {
+    /// 判断树中是否包含元素，时间复杂度：O(logn)
+    pub fn contains(&self, value: &T) -> bool {
+        let mut current = self.root.clone();
+
+        while let Some(node) = current {
+            current = match value.cmp(&node.borrow().value) {
+                Ordering::Equal => return true,
+                Ordering::Less => node.borrow().left.clone(),
+                Ordering::Greater => node.borrow().right.clone(),
+            };
+        }
+
+        false
+    }
+
+    /// 插入元素，树中已存在相同的元素时不插入并返回 false，均摊时间复杂度：O(logn)
+    pub fn insert(&mut self, value: T) -> bool {
+        // 记录从根结点到插入位置的路径，用于寻找替罪羊
+        let mut path: Vec<NodeRc<T>> = Vec::new();
+        let mut current = self.root.clone();
+
+        while let Some(node) = current {
+            current = match value.cmp(&node.borrow().value) {
+                Ordering::Equal => return false,
+                Ordering::Less => node.borrow().left.clone(),
+                Ordering::Greater => node.borrow().right.clone(),
+            };
+            path.push(node);
+        }
+
+        let new_node = TreeNode::new_node_rc(value);
+        match path.last() {
+            None => self.root = Some(new_node.clone()),
+            Some(parent) => {
+                let mut parent = parent.borrow_mut();
+                if new_node.borrow().value < parent.value {
+                    parent.left = Some(new_node.clone());
+                } else {
+                    parent.right = Some(new_node.clone());
+                }
+            }
+        }
+        self.len += 1;
+        self.max_len = self.max_len.max(self.len);
+
+        // 新结点的深度等于路径长度
+        if path.len() > alpha_height(self.len) {
+            self.rebuild_scapegoat(path, new_node);
+        }
+
+        true
+    }
+
+    /// 删除元素，元素不存在时返回 false，均摊时间复杂度：O(logn)
+    pub fn remove(&mut self, value: &T) -> bool {
+        let mut removed = false;
+        self.root = remove_recursive(self.root.take(), value, &mut removed);
+        if !removed {
+            return false;
+        }
+
+        self.len -= 1;
+        // 删除过多时重建整棵树，即 len < α · max_len
+        if 3 * self.len < 2 * self.max_len {
+            self.root = rebuild(&self.root);
+            self.max_len = self.len;
+        }
+
+        true
+    }
+
+    /// 沿着路径自底向上寻找替罪羊并重建以它为根的子树
+    ///
+    /// 结点不保存子树大小，只在这里计算兄弟子树的大小，替罪羊以下的部分总共只需 O(size(替罪羊)) 时间
+    fn rebuild_scapegoat(&mut self, path: Vec<NodeRc<T>>, new_node: NodeRc<T>) {
+        let (mut child, mut child_size) = (new_node, 1);
+
+        for (i, node) in path.iter().enumerate().rev() {
+            let sibling = {
+                let node = node.borrow();
+                let is_left = node
+                    .left
+                    .as_ref()
+                    .is_some_and(|left| Rc::ptr_eq(left, &child));
+                if is_left {
+                    subtree_size(&node.right)
+                } else {
+                    subtree_size(&node.left)
+                }
+            };
+            let size = child_size + sibling + 1;
+
+            // size(child) > α · size(node)，node 是替罪羊
+            if 3 * child_size > 2 * size {
+                let subtree = rebuild(&Some(node.clone()));
+                match i.checked_sub(1).map(|parent| &path[parent]) {
+                    None => self.root = subtree,
+                    Some(parent) => {
+                        let mut parent = parent.borrow_mut();
+                        let is_left = parent
+                            .left
+                            .as_ref()
+                            .is_some_and(|left| Rc::ptr_eq(left, node));
+                        if is_left {
+                            parent.left = subtree;
+                        } else {
+                            parent.right = subtree;
+                        }
+                    }
+                }
+                return;
+            }
+
+            (child, child_size) = (node.clone(), size);
+        }
+    }
+}
+
+impl<T> Default for ScapegoatTree<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Clone + Ord> Extend<T> for ScapegoatTree<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for value in iter {
+            self.insert(value);
+        }
+    }
+}
+
+impl<T: Clone + Ord> FromIterator<T> for ScapegoatTree<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut tree = Self::new();
+
+        tree.extend(iter);
+        tree
+    }
+}
+
+impl<T: Clone + Ord> OrderedSet<T> for ScapegoatTree<T> {
+    fn insert(&mut self, value: T) -> bool {
+        ScapegoatTree::insert(self, value)
+    }
+
+    fn remove(&mut self, value: &T) -> bool {
+        ScapegoatTree::remove(self, value)
+    }
+
+    fn contains(&self, value: &T) -> bool {
+        ScapegoatTree::contains(self, value)
+    }
+}
+
+/// 允许的最大深度 h(n) = ⌊log_{3/2} n⌋
+fn alpha_height(len: usize) -> usize {
+    ((len as f64).ln() / 1.5_f64.ln()).floor() as usize
+}
+
+/// 子树中结点的数量
+fn subtree_size<T>(node: &OptionNodeRc<T>) -> usize {
+    node.as_ref().map_or(0, |node| {
+        let node = node.borrow();
+        subtree_size(&node.left) + subtree_size(&node.right) + 1
+    })
+}
+
+/// 把子树重建为完全平衡的二叉搜索树，返回新的根结点
+fn rebuild<T: Clone>(node: &OptionNodeRc<T>) -> OptionNodeRc<T> {
+    let values = bt::in_order(node);
+    let len = values.len();
+
+    binary_tree::build_balanced(&mut values.into_iter(), len)
+}
+
+fn remove_recursive<T: Clone + Ord>(
+    node: OptionNodeRc<T>,
+    value: &T,
+    removed: &mut bool,
+) -> OptionNodeRc<T> {
+    let node = node?;
+
+    let ordering = value.cmp(&node.borrow().value);
+    match ordering {
+        Ordering::Less => {
+            let left = node.borrow_mut().left.take();
+            node.borrow_mut().left = remove_recursive(left, value, removed);
+        }
+        Ordering::Greater => {
+            let right = node.borrow_mut().right.take();
+            node.borrow_mut().right = remove_recursive(right, value, removed);
+        }
+        Ordering::Equal => {
+            *removed = true;
+            let (left, right) = {
+                let mut node = node.borrow_mut();
+                (node.left.take(), node.right.take())
+            };
+            match (left, right) {
+                // 子节点数量为0或1，用子节点替换当前节点
+                (None, child) | (child, None) => return child,
+                // 子节点数量为2，用右子树的最小值替换当前节点的值
+                (left, Some(right)) => {
+                    let mut min = right.clone();
+                    while let Some(next) = min.clone().borrow().left.clone() {
+                        min = next;
+                    }
+                    let min_value = min.borrow().value.clone();
+
+                    let right = remove_recursive(Some(right), &min_value, removed);
+                    let mut node = node.borrow_mut();
+                    node.value = min_value;
+                    node.left = left;
+                    node.right = right;
+                }
+            }
+        }
+    }
+
+    Some(node)
+}
+
+fn clone_tree<T: Clone>(node: &OptionNodeRc<T>) -> OptionNodeRc<T> {
+    node.as_ref().map(|node| {
+        let node = node.borrow();
+        let copy = TreeNode::new_node_rc(node.value.clone());
+        {
+            let mut copy = copy.borrow_mut();
+            copy.left = clone_tree(&node.left);
+            copy.right = clone_tree(&node.right);
+        }
+
+        copy
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use super::*;
+    use crate::generate::{self, Operation};
+
+    fn height<T>(node: &OptionNodeRc<T>) -> usize {
+        node.as_ref().map_or(0, |node| {
+            let node = node.borrow();
+            height(&node.left).max(height(&node.right)) + 1
+        })
+    }
+
+    #[test]
+    fn scapegoat_tree_should_match_btree_set() {
+        let mut tree = ScapegoatTree::new();
+        let mut expected = BTreeSet::new();
+
+        for op in generate::operations(5000, 500, 8) {
+            match op {
+                Operation::Insert(key) => assert_eq!(tree.insert(key), expected.insert(key)),
+                Operation::Remove(key) => assert_eq!(tree.remove(&key), expected.remove(&key)),
+                Operation::Get(key) => assert_eq!(tree.contains(&key), expected.contains(&key)),
+            }
+
+            assert_eq!(tree.len(), expected.len());
+            // 树高（边数）不超过 h(max_len) + 1
+            assert!(height(&tree.root) <= alpha_height(tree.max_len) + 2);
+        }
+
+        assert_eq!(tree.to_vec(), expected.into_iter().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn scapegoat_tree_should_stay_balanced_on_sorted_input() {
+        let mut tree = ScapegoatTree::new();
+
+        for key in 0..4096 {
+            assert!(tree.insert(key));
+            // 每次插入后所有结点的深度都不超过 h(n)
+            assert!(height(&tree.root) <= alpha_height(tree.len()) + 1);
+        }
+        for key in (0..4096).rev().step_by(2) {
+            assert!(tree.remove(&key));
+        }
+
+        assert_eq!(tree.len(), 2048);
+        assert!(height(&tree.root) <= alpha_height(tree.max_len) + 1);
+        assert!(tree.to_vec().into_iter().eq((0..4096).step_by(2)));
+        assert!(!tree.contains(&1));
+    }
+}
@@ -0,0 +1,605 @@
+//! 搜索
+//!
+//! 二分答案：很多最优化问题可以转化为判定问题：“答案不超过 x 是否可行？”。
+//! 若判定结果关于 x 单调（x 越大越容易满足），就可以在答案的取值范围上二分，
+//! 找出判定结果由 false 变为 true 的位置，即最优答案。
+//!
+//! 束搜索（beam search）：逐层扩展状态，每层只保留得分最高的若干个状态，
+//! 用有限的内存近似地搜索巨大的状态空间，但不保证找到最优解。
+//!
+//! 迭代加深：反复进行深度受限的深度优先搜索，每次把深度上限加一（IDDFS），
+//! 或把代价上限提高到上一轮超出上限的最小估计代价（IDA*）。
+//! 只需保存当前路径，内存为 O(d)，又能像广度优先搜索和 A* 一样找到最优解。
+//! 浅层结点会被重复访问，但状态数随深度指数增长，重复访问的开销只占常数倍。
+
+use std::cmp::{Ordering, Reverse};
+
+use crate::PriorityDeque;
+
+/// 在整数区间 [lo, hi) 上二分，返回第一个使 pred 为 true 的值，不存在时返回 hi
+///
+/// pred 必须单调：存在某个分界点，分界点之前均为 false，之后（含）均为 true。
+/// 调用 pred 的次数为 O(log(hi - lo))。
+///
+/// # Examples
+///
+/// 最小化最大负载：将任务按顺序分给 k 个工人，每人负责连续的一段，求最大负载的最小值。
+///
+/// ```rust
+/// use base::search;
+///
+/// let tasks = [7, 2, 5, 10, 8];
+/// let k = 2;
+///
+/// // 最大负载不超过 limit 时，贪心地计算至少需要多少个工人
+/// let workers = |limit: i64| {
+///     let (mut count, mut load) = (1, 0);
+///     for &task in &tasks {
+///         if load + task > limit {
+///             count += 1;
+///             load = 0;
+///         }
+///         load += task;
+///     }
+///     count
+/// };
+///
+/// let lo = *tasks.iter().max().unwrap();
+/// let hi = tasks.iter().sum::<i64>() + 1;
+/// let answer = search::binary_search_predicate(lo, hi, |limit| workers(limit) <= k);
+///
+/// assert_eq!(answer, 18);
+/// ```
+pub fn binary_search_predicate<F>(mut lo: i64, mut hi: i64, mut pred: F) -> i64
+where
+    F: FnMut(i64) -> bool,
+{
+    while lo < hi {
+        // 先转换为 i128 再求和，避免溢出；结果满足 lo <= mid < hi
+        let mid = ((lo as i128 + hi as i128) >> 1) as i64;
+        if pred(mid) {
+            hi = mid;
+        } else {
+            lo = mid + 1;
+        }
+    }
+
+    lo
+}
+
+/// 在实数区间 [lo, hi] 上二分，返回 pred 由 false 变为 true 的分界点的近似值
+///
+/// 区间长度不超过 eps 时停止，返回值处 pred 为 true（除非整个区间都不满足，此时返回 hi）。
+/// 浮点数的精度有限，eps 相对于 lo、hi 过小时区间无法继续缩小，因此最多迭代 200 次。
+///
+/// # Panics
+///
+/// Panics if `eps` is not positive.
+///
+/// # Examples
+///
+/// ```rust
+/// use base::search;
+///
+/// // 求 2 的平方根
+/// let root = search::bisect(0.0, 2.0, 1e-9, |x| x * x >= 2.0);
+///
+/// assert!((root - 2f64.sqrt()).abs() <= 1e-9);
+/// ```
+pub fn bisect<F>(mut lo: f64, mut hi: f64, eps: f64, mut pred: F) -> f64
+where
+    F: FnMut(f64) -> bool,
+{
+    assert!(eps > 0.0, "Epsilon must be positive: {eps}");
+
+    for _ in 0..200 {
+        if hi - lo <= eps {
+            break;
+        }
+
+        let mid = lo + (hi - lo) / 2.0;
+        if pred(mid) {
+            hi = mid;
+        } else {
+            lo = mid;
+        }
+    }
+
+    hi
+}
+
+/// 带得分的状态，只按得分比较，得分相同时先产生的状态更优
+struct Scored<K, S> {
+    score: K,
+    seq: Reverse<usize>,
+    state: S,
+}
+
+impl<K: Ord, S> Ord for Scored<K, S> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (&self.score, self.seq).cmp(&(&other.score, other.seq))
+    }
+}
+
+impl<K: Ord, S> PartialOrd for Scored<K, S> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<K: Ord, S> PartialEq for Scored<K, S> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<K: Ord, S> Eq for Scored<K, S> {}
+
+/// 束搜索，返回搜索过程中得分最高的状态
+///
+/// 从 start 开始，每一轮用 expand 扩展当前层的所有状态，
+/// 在新产生的状态中用容量为 beam_width 的 [`PriorityDeque`] 保留得分最高的若干个作为下一层，
+/// 没有新状态时停止。expand 必须保证搜索会终止，例如在状态中记录深度或已访问的位置。
+///
+/// beam_width 为 1 时退化为贪心搜索，beam_width 足够大时等价于广度优先搜索。
+///
+/// # Panics
+///
+/// Panics if `beam_width == 0`.
+///
+/// # Examples
+///
+/// 0-1 背包：依次决定每件物品放或不放，状态为（下一件物品的下标，已用容量，总价值）
+///
+/// ```rust
+/// use base::search;
+///
+/// let (weights, values, capacity) = ([3, 4, 5, 2], [4, 5, 6, 3], 7);
+///
+/// let (_, _, best) = search::beam_search(
+///     (0, 0, 0),
+///     |&(i, weight, value)| {
+///         let mut next = Vec::new();
+///         if i < weights.len() {
+///             next.push((i + 1, weight, value));
+///             if weight + weights[i] <= capacity {
+///                 next.push((i + 1, weight + weights[i], value + values[i]));
+///             }
+///         }
+///         next
+///     },
+///     |&(_, _, value)| value,
+///     4,
+/// );
+///
+/// assert_eq!(best, 9);
+/// ```
+pub fn beam_search<S, I, E, F, K>(start: S, mut expand: E, mut score: F, beam_width: usize) -> S
+where
+    I: IntoIterator<Item = S>,
+    E: FnMut(&S) -> I,
+    F: FnMut(&S) -> K,
+    K: Ord,
+{
+    assert!(beam_width > 0, "Beam width must be positive");
+
+    let mut seq = 0;
+    let mut scored = |state: S| {
+        seq += 1;
+        Scored {
+            score: score(&state),
+            seq: Reverse(seq),
+            state,
+        }
+    };
+
+    let mut best = scored(start);
+    let mut beam = Vec::new();
+    // 当前层只有 start，扩展它时需要借用 best 中的状态
+    let mut candidates = PriorityDeque::with_bound(beam_width);
+    for next in expand(&best.state) {
+        candidates.push(scored(next));
+    }
+
+    while !candidates.is_empty() {
+        beam.clear();
+        beam.extend(candidates.into_sorted_vec());
+        candidates = PriorityDeque::with_bound(beam_width);
+
+        // 从得分最高的状态开始扩展，得分相同时它的后继更优
+        for current in beam.iter().rev() {
+            for next in expand(&current.state) {
+                candidates.push(scored(next));
+            }
+        }
+
+        // Safety: beam 不为空，最后一个元素得分最高
+        let top = beam.pop().unwrap();
+        if top > best {
+            best = top;
+        }
+    }
+
+    best.state
+}
+
+/// 深度受限搜索的结果
+enum Depth {
+    Found,
+    /// 有分支因达到深度上限而被截断，加深后可能找到目标
+    Cutoff,
+    /// 所有分支都已搜索完毕
+    Exhausted,
+}
+
+/// 迭代加深深度优先搜索（IDDFS），返回从 start 到第一个满足 goal 的状态的最短路径
+///
+/// 路径包含起点和终点，长度为步数加一。为避免走回头路，不会扩展已在当前路径上的状态。
+/// 深度上限达到 max_depth 仍未找到，或状态空间已搜索完毕时返回 None。
+///
+/// # Examples
+///
+/// ```rust
+/// use base::search;
+///
+/// // 每步加 3 或乘 2，从 1 到 11 最少需要几步
+/// let path = search::iddfs(1, |&x| x == 11, |&x| [x + 3, x * 2], 10).unwrap();
+///
+/// assert_eq!(path, [1, 4, 8, 11]);
+/// ```
+pub fn iddfs<S, I, G, E>(start: S, mut goal: G, mut expand: E, max_depth: usize) -> Option<Vec<S>>
+where
+    S: PartialEq,
+    I: IntoIterator<Item = S>,
+    G: FnMut(&S) -> bool,
+    E: FnMut(&S) -> I,
+{
+    let mut path = vec![start];
+
+    for limit in 0..=max_depth {
+        match depth_limited(&mut path, limit, &mut goal, &mut expand) {
+            Depth::Found => return Some(path),
+            Depth::Cutoff => {}
+            Depth::Exhausted => return None,
+        }
+    }
+
+    None
+}
+
+fn depth_limited<S, I, G, E>(path: &mut Vec<S>, depth: usize, goal: &mut G, expand: &mut E) -> Depth
+where
+    S: PartialEq,
+    I: IntoIterator<Item = S>,
+    G: FnMut(&S) -> bool,
+    E: FnMut(&S) -> I,
+{
+    // Safety: 路径中至少有起点
+    let current = path.last().unwrap();
+    if goal(current) {
+        return Depth::Found;
+    }
+    if depth == 0 {
+        return Depth::Cutoff;
+    }
+
+    let mut cutoff = false;
+    for next in expand(current) {
+        if path.contains(&next) {
+            continue;
+        }
+
+        path.push(next);
+        match depth_limited(path, depth - 1, goal, expand) {
+            Depth::Found => return Depth::Found,
+            Depth::Cutoff => cutoff = true,
+            Depth::Exhausted => {}
+        }
+        path.pop();
+    }
+
+    if cutoff {
+        Depth::Cutoff
+    } else {
+        Depth::Exhausted
+    }
+}
+
+/// 迭代加深 A*（IDA*），返回从 start 到第一个满足 goal 的状态的最优路径及其代价
+///
+/// expand 返回后继状态及转移的代价，heuristic 估计到达目标的剩余代价。
+/// heuristic 不高估剩余代价（可采纳）时，返回的路径代价最小。
+/// 与 [`iddfs`] 一样不会扩展已在当前路径上的状态，状态空间搜索完毕仍未找到时返回 None，
+/// 状态空间无限且目标不可达时不会终止。
+///
+/// # Examples
+///
+/// ```rust
+/// use base::search;
+///
+/// // 数轴上每步可以走 1（代价 1）或走 5（代价 3），从 0 走到 13
+/// let (path, cost) = search::ida_star(
+///     0u64,
+///     |&x| x == 13,
+///     |&x| [(x + 1, 1), (x + 5, 3)].into_iter().filter(|&(x, _)| x <= 13),
+///     |&x| (13 - x).div_ceil(5),
+/// )
+/// .unwrap();
+///
+/// assert_eq!(cost, 9);
+/// assert_eq!(path.last(), Some(&13));
+/// ```
+pub fn ida_star<S, I, G, E, H>(
+    start: S,
+    mut goal: G,
+    mut expand: E,
+    mut heuristic: H,
+) -> Option<(Vec<S>, u64)>
+where
+    S: PartialEq,
+    I: IntoIterator<Item = (S, u64)>,
+    G: FnMut(&S) -> bool,
+    E: FnMut(&S) -> I,
+    H: FnMut(&S) -> u64,
+{
+    let mut bound = heuristic(&start);
+    let mut path = vec![start];
+
+    loop {
+        match cost_limited(&mut path, 0, bound, &mut goal, &mut expand, &mut heuristic) {
+            Ok(cost) => return Some((path, cost)),
+            Err(Some(next)) => bound = next,
+            Err(None) => return None,
+        }
+    }
+}
+
+/// 代价受限搜索，找到目标时返回路径代价，否则返回超出上限的最小估计代价
+fn cost_limited<S, I, G, E, H>(
+    path: &mut Vec<S>,
+    cost: u64,
+    bound: u64,
+    goal: &mut G,
+    expand: &mut E,
+    heuristic: &mut H,
+) -> Result<u64, Option<u64>>
+where
+    S: PartialEq,
+    I: IntoIterator<Item = (S, u64)>,
+    G: FnMut(&S) -> bool,
+    E: FnMut(&S) -> I,
+    H: FnMut(&S) -> u64,
+{
+    // Safety: 路径中至少有起点
+    let current = path.last().unwrap();
+    let estimate = cost + heuristic(current);
+    if estimate > bound {
+        return Err(Some(estimate));
+    }
+    if goal(current) {
+        return Ok(cost);
+    }
+
+    let mut min_exceeded: Option<u64> = None;
+    for (next, step) in expand(current) {
+        if path.contains(&next) {
+            continue;
+        }
+
+        path.push(next);
+        match cost_limited(path, cost + step, bound, goal, expand, heuristic) {
+            Ok(cost) => return Ok(cost),
+            Err(Some(exceeded)) => {
+                min_exceeded = Some(min_exceeded.map_or(exceeded, |min| min.min(exceeded)));
+            }
+            Err(None) => {}
+        }
+        path.pop();
+    }
+
+    Err(min_exceeded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn binary_search_predicate_should_work() {
+        let v = [1, 3, 3, 5, 8, 13];
+        // 下界：第一个不小于 x 的下标
+        let lower_bound = |x| binary_search_predicate(0, v.len() as i64, |i| v[i as usize] >= x);
+
+        assert_eq!(lower_bound(0), 0);
+        assert_eq!(lower_bound(3), 1);
+        assert_eq!(lower_bound(4), 3);
+        assert_eq!(lower_bound(14), 6);
+
+        // 空区间
+        assert_eq!(binary_search_predicate(5, 5, |_| true), 5);
+        // 整数平方根
+        assert_eq!(
+            binary_search_predicate(0, 1 << 32, |x| x * x > 1_000_000) - 1,
+            1000
+        );
+        // 极端区间不溢出
+        assert_eq!(binary_search_predicate(i64::MIN, i64::MAX, |x| x >= -7), -7);
+    }
+
+    #[test]
+    fn bisect_should_work() {
+        let cube_root = bisect(0.0, 10.0, 1e-12, |x| x * x * x >= 27.0);
+        assert!((cube_root - 3.0).abs() <= 1e-12);
+
+        // eps 远小于浮点数精度时也能结束
+        let root = bisect(0.0, 1e10, f64::MIN_POSITIVE, |x| x >= 12345.678);
+        assert!((root - 12345.678).abs() <= 1e-8);
+
+        // 整个区间都不满足时返回 hi
+        assert_eq!(bisect(0.0, 1.0, 1e-6, |_| false), 1.0);
+    }
+
+    #[test]
+    fn beam_search_should_trade_width_for_quality() {
+        // 第一步选 a 得 5 分，之后每步 0 分；选 b 得 1 分，之后每步 10 分
+        let expand = |path: &String| {
+            if path.len() < 3 {
+                vec![format!("{path}a"), format!("{path}b")]
+            } else {
+                vec![]
+            }
+        };
+        let score = |path: &String| match path.as_bytes().first() {
+            Some(b'a') => 5,
+            Some(b'b') => 1 + 10 * (path.len() as i32 - 1),
+            _ => 0,
+        };
+
+        // 贪心地选择了 a
+        assert_eq!(beam_search(String::new(), expand, score, 1), "a");
+        // 得分相同时保留先产生的状态
+        assert_eq!(beam_search(String::new(), expand, score, 2), "baa");
+    }
+
+    #[test]
+    fn beam_search_should_find_path_in_grid() {
+        let grid = ["S..#....", ".#.#.##.", ".#...#..", ".####.#.", "......#G"];
+        let cell = |(r, c): (usize, usize)| grid[r].as_bytes()[c];
+        let goal = (4, 7);
+
+        // 状态为从起点出发的路径，不重复经过同一个格子
+        let expand = |path: &Vec<(usize, usize)>| {
+            let &(r, c) = path.last().unwrap();
+            if (r, c) == goal {
+                return vec![];
+            }
+            [
+                (r.wrapping_sub(1), c),
+                (r + 1, c),
+                (r, c.wrapping_sub(1)),
+                (r, c + 1),
+            ]
+            .into_iter()
+            .filter(|&(r, c)| r < grid.len() && c < grid[0].len())
+            .filter(|&next| cell(next) != b'#' && !path.contains(&next))
+            .map(|next| {
+                let mut path = path.clone();
+                path.push(next);
+                path
+            })
+            .collect()
+        };
+        // 离终点越近越好，距离相同时路径越短越好
+        let score = |path: &Vec<(usize, usize)>| {
+            let &(r, c) = path.last().unwrap();
+            let dist = r.abs_diff(goal.0) + c.abs_diff(goal.1);
+            Reverse((dist, path.len()))
+        };
+
+        let path = beam_search(vec![(0, 0)], expand, score, 8);
+        assert_eq!(path.last(), Some(&goal));
+        assert_eq!(path.len(), 16);
+    }
+
+    /// 八数码：3x3 的棋盘上有 1~8 和一个空格（0），每次把空格与相邻的数字交换
+    type Puzzle = [u8; 9];
+
+    const SOLVED: Puzzle = [1, 2, 3, 4, 5, 6, 7, 8, 0];
+
+    fn puzzle_moves(p: &Puzzle) -> Vec<Puzzle> {
+        // Safety: 棋盘上一定有空格
+        let blank = p.iter().position(|&x| x == 0).unwrap();
+        let (r, c) = (blank / 3, blank % 3);
+
+        [
+            (r > 0, blank.wrapping_sub(3)),
+            (r < 2, blank + 3),
+            (c > 0, blank.wrapping_sub(1)),
+            (c < 2, blank + 1),
+        ]
+        .into_iter()
+        .filter(|&(valid, _)| valid)
+        .map(|(_, next)| {
+            let mut p = *p;
+            p.swap(blank, next);
+            p
+        })
+        .collect()
+    }
+
+    /// 各数字到目标位置的曼哈顿距离之和，不会高估剩余步数
+    fn manhattan(p: &Puzzle) -> u64 {
+        p.iter()
+            .enumerate()
+            .filter(|&(_, &x)| x != 0)
+            .map(|(i, &x)| {
+                let target = x as usize - 1;
+                ((i / 3).abs_diff(target / 3) + (i % 3).abs_diff(target % 3)) as u64
+            })
+            .sum()
+    }
+
+    fn assert_valid_solution(path: &[Puzzle], start: Puzzle) {
+        assert_eq!(path.first(), Some(&start));
+        assert_eq!(path.last(), Some(&SOLVED));
+        assert!(path.windows(2).all(|w| puzzle_moves(&w[0]).contains(&w[1])));
+    }
+
+    #[test]
+    fn iddfs_should_solve_sliding_puzzle() {
+        let start = [5, 1, 2, 6, 3, 0, 4, 7, 8];
+        let path = iddfs(start, |p| *p == SOLVED, puzzle_moves, 20).unwrap();
+
+        // 最少需要 11 步
+        assert_eq!(path.len(), 12);
+        assert_valid_solution(&path, start);
+
+        assert!(iddfs(start, |p| *p == SOLVED, puzzle_moves, 10).is_none());
+        assert_eq!(
+            iddfs(SOLVED, |p| *p == SOLVED, puzzle_moves, 0),
+            Some(vec![SOLVED])
+        );
+    }
+
+    #[test]
+    fn ida_star_should_solve_sliding_puzzle() {
+        let start = [8, 1, 3, 4, 0, 2, 7, 6, 5];
+        let unit_cost = |p: &Puzzle| puzzle_moves(p).into_iter().map(|p| (p, 1));
+        let (path, cost) = ida_star(start, |p| *p == SOLVED, unit_cost, manhattan).unwrap();
+
+        // 最少需要 14 步
+        assert_eq!(cost, 14);
+        assert_eq!(path.len(), 15);
+        assert_valid_solution(&path, start);
+    }
+
+    #[test]
+    fn iterative_deepening_should_stop_when_exhausted() {
+        let expand = |&x: &u32| (x < 5).then_some(x + 1);
+
+        assert_eq!(iddfs(0, |&x| x == 10, expand, usize::MAX), None);
+        assert_eq!(
+            iddfs(0, |&x| x == 3, expand, usize::MAX),
+            Some(vec![0, 1, 2, 3])
+        );
+
+        let weighted = |&x: &u32| expand(&x).map(|x| (x, 2));
+        assert_eq!(ida_star(0, |&x| x == 10, weighted, |_| 0), None);
+        assert_eq!(
+            ida_star(0, |&x| x == 2, weighted, |_| 0),
+            Some((vec![0, 1, 2], 4))
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn beam_search_zero_width_should_panic() {
+        beam_search(0, |_| [1], |&x| x, 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn bisect_non_positive_eps_should_panic() {
+        bisect(0.0, 1.0, 0.0, |_| true);
+    }
+}
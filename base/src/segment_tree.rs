@@ -0,0 +1,408 @@
+//! 线段树（segment tree）
+//!
+//! 线段树的每个节点保存一段区间的聚合值（如区间和、区间最值），
+//! 父节点的值由两个子节点的值合并得到。这里采用自底向上的非递归实现：
+//! * 使用长度为 2n 的数组存储，叶节点位于 [n, 2n)，节点 i 的子节点为 2i 和 2i+1
+//! * 单点修改：修改叶节点后沿父节点向上更新，时间复杂度 O(logn)
+//! * 区间查询：左右边界同时向上收缩，时间复杂度 O(logn)
+//!
+//! 合并函数 op 需要满足结合律，identity 为其单位元（如求和时为 0）。
+//!
+//! [`LazySegmentTree`] 在此基础上支持区间修改：整段落在修改区间内的节点只记录一个
+//! 尚未下传给子节点的修改（懒标记），等到之后需要访问子节点时再下传。
+//! 区间修改和区间查询都只访问 O(logn) 个节点。
+
+use std::ops::Range;
+
+/// 支持单点修改、区间查询的线段树
+#[derive(Debug, Clone)]
+pub struct SegmentTree<T> {
+    tree: Vec<T>,
+    len: usize,
+    identity: T,
+    op: fn(&T, &T) -> T,
+}
+
+impl<T: Clone> SegmentTree<T> {
+    /// 由初始值构建线段树，时间复杂度：O(n)
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use base::SegmentTree;
+    ///
+    /// let mut tree = SegmentTree::new(vec![1, 3, 5, 7, 9], 0, |a, b| a + b);
+    ///
+    /// assert_eq!(tree.query(1..4), 15);
+    /// tree.set(2, 0);
+    /// assert_eq!(tree.query(1..4), 10);
+    /// ```
+    pub fn new(values: Vec<T>, identity: T, op: fn(&T, &T) -> T) -> Self {
+        let len = values.len();
+        let mut tree = vec![identity.clone(); len];
+
+        tree.extend(values);
+        // 自底向上计算所有非叶节点
+        for i in (1..len).rev() {
+            tree[i] = op(&tree[2 * i], &tree[2 * i + 1]);
+        }
+
+        Self {
+            tree,
+            len,
+            identity,
+            op,
+        }
+    }
+
+    /// 将位置 i 的值修改为 val
+    ///
+    /// # Panics
+    ///
+    /// Panics if `i >= len`.
+    pub fn set(&mut self, i: usize, val: T) {
+        let len = self.len;
+        assert!(i < len, "Cannot set at index: {i}, len: {len}");
+
+        let mut i = i + len;
+        self.tree[i] = val;
+        // 沿父节点向上更新
+        while i > 1 {
+            i /= 2;
+            self.tree[i] = (self.op)(&self.tree[2 * i], &self.tree[2 * i + 1]);
+        }
+    }
+
+    /// 将位置 i 的值与 val 合并，即 a[i] = op(a[i], val)
+    ///
+    /// # Panics
+    ///
+    /// Panics if `i >= len`.
+    pub fn update(&mut self, i: usize, val: T) {
+        let len = self.len;
+        assert!(i < len, "Cannot update at index: {i}, len: {len}");
+
+        let merged = (self.op)(&self.tree[i + len], &val);
+        self.set(i, merged);
+    }
+
+    /// 查询区间 [range.start, range.end) 的聚合值，空区间返回单位元
+    ///
+    /// # Panics
+    ///
+    /// Panics if `range.end > len`.
+    pub fn query(&self, range: Range<usize>) -> T {
+        let len = self.len;
+        assert!(
+            range.end <= len,
+            "Range end out of bounds: {}, len: {len}",
+            range.end
+        );
+
+        // 左右两侧分别累积，保证 op 不满足交换律时结果依然正确
+        let (mut left, mut right) = (self.identity.clone(), self.identity.clone());
+        let (mut l, mut r) = (range.start + len, range.end + len);
+
+        while l < r {
+            if l % 2 == 1 {
+                left = (self.op)(&left, &self.tree[l]);
+                l += 1;
+            }
+            if r % 2 == 1 {
+                r -= 1;
+                right = (self.op)(&self.tree[r], &right);
+            }
+            l /= 2;
+            r /= 2;
+        }
+
+        (self.op)(&left, &right)
+    }
+}
+
+impl<T> SegmentTree<T> {
+    /// 获取位置 i 的值，越界返回 None
+    pub fn get(&self, i: usize) -> Option<&T> {
+        if i >= self.len {
+            return None;
+        }
+
+        self.tree.get(i + self.len)
+    }
+
+    /// 元素数量
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// 判断线段树是否为空
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+/// 支持区间修改、区间查询的懒标记线段树
+///
+/// 除了合并函数 op 和单位元 identity，还需要描述“修改”F：
+/// * noop：恒等修改，作用后聚合值不变
+/// * mapping(f, x, count)：将修改 f 作用于 count 个元素的聚合值 x
+/// * composition(f, g)：先执行 g 再执行 f 的复合修改
+#[derive(Debug, Clone)]
+pub struct LazySegmentTree<T, F> {
+    len: usize,
+    tree: Vec<T>,
+    /// 懒标记：已作用于该节点、尚未下传给子节点的修改
+    lazy: Vec<F>,
+    identity: T,
+    op: fn(&T, &T) -> T,
+    noop: F,
+    mapping: fn(&F, &T, usize) -> T,
+    composition: fn(&F, &F) -> F,
+}
+
+impl<T: Clone, F: Clone> LazySegmentTree<T, F> {
+    /// 由初始值构建线段树，时间复杂度：O(n)
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use base::LazySegmentTree;
+    ///
+    /// // 区间加、区间求和
+    /// let mut tree = LazySegmentTree::new(
+    ///     vec![1, 3, 5, 7, 9],
+    ///     0,
+    ///     |a, b| a + b,
+    ///     0,
+    ///     |delta, sum, count| sum + delta * count as i64,
+    ///     |f, g| f + g,
+    /// );
+    ///
+    /// assert_eq!(tree.query(1..4), 15);
+    /// tree.apply(0..3, 10);
+    /// assert_eq!(tree.query(1..4), 35);
+    /// assert_eq!(tree.query(4..5), 9);
+    /// ```
+    pub fn new(
+        values: Vec<T>,
+        identity: T,
+        op: fn(&T, &T) -> T,
+        noop: F,
+        mapping: fn(&F, &T, usize) -> T,
+        composition: fn(&F, &F) -> F,
+    ) -> Self {
+        let len = values.len();
+        let mut tree = Self {
+            len,
+            tree: vec![identity.clone(); 4 * len.max(1)],
+            lazy: vec![noop.clone(); 4 * len.max(1)],
+            identity,
+            op,
+            noop,
+            mapping,
+            composition,
+        };
+        if len > 0 {
+            tree.build(1, 0, len, &values);
+        }
+
+        tree
+    }
+
+    fn build(&mut self, node: usize, l: usize, r: usize, values: &[T]) {
+        if r - l == 1 {
+            self.tree[node] = values[l].clone();
+            return;
+        }
+        let mid = (l + r) / 2;
+        self.build(2 * node, l, mid, values);
+        self.build(2 * node + 1, mid, r, values);
+        self.pull_up(node);
+    }
+
+    fn pull_up(&mut self, node: usize) {
+        self.tree[node] = (self.op)(&self.tree[2 * node], &self.tree[2 * node + 1]);
+    }
+
+    /// 将修改 f 作用于节点 node（对应 count 个元素），并记入懒标记
+    fn apply_node(&mut self, node: usize, count: usize, f: &F) {
+        self.tree[node] = (self.mapping)(f, &self.tree[node], count);
+        self.lazy[node] = (self.composition)(f, &self.lazy[node]);
+    }
+
+    /// 将懒标记下传给两个子节点
+    fn push_down(&mut self, node: usize, l: usize, mid: usize, r: usize) {
+        let f = std::mem::replace(&mut self.lazy[node], self.noop.clone());
+        self.apply_node(2 * node, mid - l, &f);
+        self.apply_node(2 * node + 1, r - mid, &f);
+    }
+
+    /// 将修改 f 作用于区间 [range.start, range.end) 内的每个元素，时间复杂度：O(logn)
+    ///
+    /// # Panics
+    ///
+    /// Panics if `range.end > len`.
+    pub fn apply(&mut self, range: Range<usize>, f: F) {
+        let len = self.len;
+        assert!(
+            range.end <= len,
+            "Range end out of bounds: {}, len: {len}",
+            range.end
+        );
+
+        if !range.is_empty() {
+            self.apply_recursive(1, 0, len, &range, &f);
+        }
+    }
+
+    fn apply_recursive(&mut self, node: usize, l: usize, r: usize, range: &Range<usize>, f: &F) {
+        if range.end <= l || r <= range.start {
+            return;
+        }
+        if range.start <= l && r <= range.end {
+            self.apply_node(node, r - l, f);
+            return;
+        }
+        let mid = (l + r) / 2;
+        self.push_down(node, l, mid, r);
+        self.apply_recursive(2 * node, l, mid, range, f);
+        self.apply_recursive(2 * node + 1, mid, r, range, f);
+        self.pull_up(node);
+    }
+
+    /// 查询区间 [range.start, range.end) 的聚合值，空区间返回单位元，时间复杂度：O(logn)
+    ///
+    /// # Panics
+    ///
+    /// Panics if `range.end > len`.
+    pub fn query(&self, range: Range<usize>) -> T {
+        let len = self.len;
+        assert!(
+            range.end <= len,
+            "Range end out of bounds: {}, len: {len}",
+            range.end
+        );
+
+        if range.is_empty() {
+            return self.identity.clone();
+        }
+        self.query_recursive(1, 0, len, &range, &self.noop)
+    }
+
+    /// 查询时不修改树，而是将沿途的懒标记复合到 pending 上
+    fn query_recursive(
+        &self,
+        node: usize,
+        l: usize,
+        r: usize,
+        range: &Range<usize>,
+        pending: &F,
+    ) -> T {
+        if range.start <= l && r <= range.end {
+            return (self.mapping)(pending, &self.tree[node], r - l);
+        }
+        let mid = (l + r) / 2;
+        // 祖先节点上的懒标记总是比子孙节点上的更晚作用
+        let pending = (self.composition)(pending, &self.lazy[node]);
+        match (range.start < mid, range.end > mid) {
+            (true, true) => (self.op)(
+                &self.query_recursive(2 * node, l, mid, range, &pending),
+                &self.query_recursive(2 * node + 1, mid, r, range, &pending),
+            ),
+            (true, false) => self.query_recursive(2 * node, l, mid, range, &pending),
+            _ => self.query_recursive(2 * node + 1, mid, r, range, &pending),
+        }
+    }
+}
+
+impl<T, F> LazySegmentTree<T, F> {
+    /// 元素数量
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// 判断线段树是否为空
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generate::Rng;
+
+    #[test]
+    fn segment_tree_sum_should_work() {
+        let mut tree = SegmentTree::new(vec![2, 1, 4, 3, 6, 5], 0, |a, b| a + b);
+
+        assert_eq!(tree.query(0..6), 21);
+        assert_eq!(tree.query(2..5), 13);
+        assert_eq!(tree.query(3..3), 0);
+
+        tree.set(3, 10);
+        assert_eq!(tree.get(3), Some(&10));
+        assert_eq!(tree.query(2..5), 20);
+
+        tree.update(0, 5);
+        assert_eq!(tree.query(0..2), 8);
+        assert_eq!(tree.get(6), None);
+    }
+
+    #[test]
+    fn segment_tree_min_should_work() {
+        let tree = SegmentTree::new(vec![5, 2, 8, 1, 9], i32::MAX, |a, b| *a.min(b));
+
+        assert_eq!(tree.query(0..5), 1);
+        assert_eq!(tree.query(0..3), 2);
+        assert_eq!(tree.query(4..5), 9);
+    }
+
+    #[test]
+    fn segment_tree_non_commutative_should_work() {
+        // 字符串拼接满足结合律但不满足交换律
+        let words = ["a", "b", "c", "d", "e"].map(String::from).to_vec();
+        let tree = SegmentTree::new(words, String::new(), |a, b| format!("{a}{b}"));
+
+        assert_eq!(tree.query(0..5), "abcde");
+        assert_eq!(tree.query(1..4), "bcd");
+    }
+
+    #[test]
+    fn lazy_segment_tree_should_match_vec() {
+        // 区间乘 a 再加 b，维护区间和与区间最大值；修改的复合不满足交换律
+        type Agg = (i64, i64);
+        let mut rng = Rng::new(41);
+        let mut expected = (0..50).map(|_| rng.below(20) as i64).collect::<Vec<_>>();
+        let mut tree = LazySegmentTree::new(
+            expected.iter().map(|&x| (x, x)).collect(),
+            (0, i64::MIN),
+            |a: &Agg, b: &Agg| (a.0 + b.0, a.1.max(b.1)),
+            (1, 0),
+            |&(a, b): &(i64, i64), &(sum, max): &Agg, count| {
+                (a * sum + b * count as i64, a * max + b)
+            },
+            |&(a1, b1), &(a2, b2)| (a1 * a2, a1 * b2 + b1),
+        );
+
+        for _ in 0..500 {
+            let l = rng.below(51) as usize;
+            let r = l + rng.below(51 - l as u64) as usize;
+            if rng.below(2) == 0 {
+                let (a, b) = (rng.below(2) as i64 + 1, rng.below(7) as i64 - 3);
+                tree.apply(l..r, (a, b));
+                expected[l..r].iter_mut().for_each(|x| *x = a * *x + b);
+                // 避免溢出
+                if expected.iter().any(|x| x.abs() > 1 << 40) {
+                    tree.apply(0..50, (0, 0));
+                    expected.fill(0);
+                }
+            } else {
+                let slice = &expected[l..r];
+                let max = slice.iter().copied().max().unwrap_or(i64::MIN);
+                assert_eq!(tree.query(l..r), (slice.iter().sum(), max));
+            }
+        }
+        assert_eq!(tree.len(), 50);
+    }
+}
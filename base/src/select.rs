@@ -0,0 +1,210 @@
+//! 选择问题：在无序数组中查找第 k 小（从 0 开始计数）的元素
+//!
+//! * 快速选择（quickselect）：与快速排序相同地划分数组，但只需继续处理 k 所在的一侧。
+//!   随机选择基准值时期望时间复杂度为 O(n)，但最坏情况下仍会退化为 O(n²)
+//! * 中位数的中位数（median of medians，BFPRT）：将数组每 5 个元素分为一组，
+//!   取各组中位数的中位数作为基准值，保证每次划分至少排除约 30% 的元素，
+//!   最坏时间复杂度为 O(n)，代价是常数因子明显大于随机快速选择
+//!
+//! 两个函数返回后，数组被重新排列：下标 k 处为第 k 小的元素，
+//! 其左侧的元素均不大于它，右侧的元素均不小于它。
+
+use std::cmp::Ordering;
+
+/// 随机快速选择，返回第 k 小的元素，期望时间复杂度：O(n)
+///
+/// # Panics
+///
+/// Panics if `k >= v.len()`.
+///
+/// # Examples
+///
+/// ```rust
+/// use base::select;
+///
+/// let mut v = [7, 2, 9, 4, 1];
+///
+/// assert_eq!(*select::quick_select(&mut v, 2), 4);
+/// ```
+pub fn quick_select<T: Ord>(v: &mut [T], k: usize) -> &T {
+    let len = v.len();
+    assert!(k < len, "Cannot select at index: {k}, len: {len}");
+
+    // xorshift 伪随机数生成器
+    let mut seed = 0x9e37_79b9_7f4a_7c15_u64 ^ len as u64;
+    let (mut lo, mut hi) = (0, len);
+
+    loop {
+        seed ^= seed << 13;
+        seed ^= seed >> 7;
+        seed ^= seed << 17;
+        let pivot = lo + (seed % (hi - lo) as u64) as usize;
+
+        let (lt, gt) = partition3(&mut v[lo..hi], pivot - lo);
+        // 只需继续处理 k 所在的一侧
+        if k < lo + lt {
+            hi = lo + lt;
+        } else if k >= lo + gt {
+            lo += gt;
+        } else {
+            return &v[k];
+        }
+    }
+}
+
+/// 中位数的中位数算法，返回第 k 小的元素，最坏时间复杂度：O(n)
+///
+/// # Panics
+///
+/// Panics if `k >= v.len()`.
+///
+/// # Examples
+///
+/// ```rust
+/// use base::select;
+///
+/// let mut v = [7, 2, 9, 4, 1, 8];
+///
+/// assert_eq!(*select::median_of_medians(&mut v, 4), 8);
+/// ```
+pub fn median_of_medians<T: Ord>(v: &mut [T], k: usize) -> &T {
+    let len = v.len();
+    assert!(k < len, "Cannot select at index: {k}, len: {len}");
+
+    select_recursive(v, k);
+    &v[k]
+}
+
+/// 将第 k 小的元素放到下标 k 处
+fn select_recursive<T: Ord>(v: &mut [T], k: usize) {
+    let (mut lo, mut hi) = (0, v.len());
+
+    loop {
+        let slice = &mut v[lo..hi];
+        if slice.len() <= 5 {
+            insertion_sort(slice);
+            return;
+        }
+
+        let pivot = pivot_of_medians(slice);
+        let (lt, gt) = partition3(slice, pivot);
+        if k < lo + lt {
+            hi = lo + lt;
+        } else if k >= lo + gt {
+            lo += gt;
+        } else {
+            return;
+        }
+    }
+}
+
+/// 求各组中位数的中位数，返回其下标
+fn pivot_of_medians<T: Ord>(v: &mut [T]) -> usize {
+    let groups = v.len().div_ceil(5);
+
+    // 将第 i 组的中位数交换到下标 i 处
+    for i in 0..groups {
+        let start = i * 5;
+        let end = (start + 5).min(v.len());
+        insertion_sort(&mut v[start..end]);
+        v.swap(i, start + (end - start - 1) / 2);
+    }
+
+    // 递归求出这 groups 个中位数的中位数
+    let mid = (groups - 1) / 2;
+    select_recursive(&mut v[..groups], mid);
+
+    mid
+}
+
+/// 三路划分，返回 (lt, gt)：划分完成后 [0, lt) 小于基准值，[lt, gt) 等于基准值，[gt, len) 大于基准值
+///
+/// 等于基准值的元素被集中到中间，避免大量重复元素时划分不均衡
+fn partition3<T: Ord>(v: &mut [T], pivot: usize) -> (usize, usize) {
+    // 将基准值交换到首位
+    v.swap(0, pivot);
+    let (head, rest) = v.split_at_mut(1);
+    let pivot = &head[0];
+
+    // 荷兰国旗问题
+    let (mut lt, mut i, mut gt) = (0, 0, rest.len());
+    while i < gt {
+        match rest[i].cmp(pivot) {
+            Ordering::Less => {
+                rest.swap(lt, i);
+                lt += 1;
+                i += 1;
+            }
+            Ordering::Greater => {
+                gt -= 1;
+                rest.swap(i, gt);
+            }
+            Ordering::Equal => i += 1,
+        }
+    }
+
+    // 将首位的基准值交换到小于部分的末尾，使其并入等于部分
+    v.swap(0, lt);
+    (lt, gt + 1)
+}
+
+fn insertion_sort<T: Ord>(v: &mut [T]) {
+    for i in 1..v.len() {
+        let mut j = i;
+        while j > 0 && v[j] < v[j - 1] {
+            v.swap(j, j - 1);
+            j -= 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn adversarial_inputs() -> Vec<Vec<i32>> {
+        vec![
+            vec![42],
+            (0..200).collect(),
+            (0..200).rev().collect(),
+            vec![7; 200],
+            // 先升后降的“管风琴”序列
+            (0..100).chain((0..100).rev()).collect(),
+            // 交错序列
+            (0..200)
+                .map(|i| if i % 2 == 0 { i } else { 200 - i })
+                .collect(),
+        ]
+    }
+
+    fn check_select(select: fn(&mut [i32], usize) -> &i32) {
+        for input in adversarial_inputs() {
+            let mut sorted = input.clone();
+            sorted.sort();
+
+            for k in 0..input.len() {
+                let mut v = input.clone();
+                assert_eq!(*select(&mut v, k), sorted[k]);
+                // 左侧不大于第 k 小的元素，右侧不小于第 k 小的元素
+                assert!(v[..k].iter().all(|x| *x <= sorted[k]));
+                assert!(v[k + 1..].iter().all(|x| *x >= sorted[k]));
+            }
+        }
+    }
+
+    #[test]
+    fn quick_select_should_work() {
+        check_select(quick_select);
+    }
+
+    #[test]
+    fn median_of_medians_should_work() {
+        check_select(median_of_medians);
+    }
+
+    #[test]
+    #[should_panic]
+    fn select_out_of_range_should_panic() {
+        median_of_medians(&mut [1, 2, 3], 3);
+    }
+}
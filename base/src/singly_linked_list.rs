@@ -0,0 +1,304 @@
+//! 单向链表，结点的 next 可以指回链表中已有的结点，从而形成环
+//!
+//! [`LinkedList`](crate::LinkedList) 是双向链表，每个结点的前驱唯一，无法表示“尾结点指回中间”的链表。
+//! 这里把结点保存在 Vec 中，用下标（结点编号）代替指针，next 可以指向任意结点，
+//! 有环时也不会出现循环引用导致的内存泄漏。
+//!
+//! Floyd 判圈算法（龟兔赛跑）只需 O(1) 额外空间：
+//! 1. 慢指针每次走一步，快指针每次走两步，有环时两者必然在环内相遇，快指针走到尾部则无环
+//! 2. 设环外部分长为 μ，相遇时慢指针走了 k 步，快指针走了 2k 步，两者之差 k 是环长 λ 的整数倍。
+//!    让一个指针从头结点出发、另一个从相遇点出发，同时每次走一步，走 μ 步后前者到达环的入口，
+//!    后者走了 k + μ 步，也恰好在环的入口，两者第一次相遇的位置就是环的入口
+//! 3. 从相遇点出发绕环一周即可得到环长
+
+/// 结点
+#[derive(Debug, Clone)]
+struct SinglyNode<T> {
+    value: T,
+    next: Option<usize>,
+}
+
+/// 可以有环的单向链表，结点用创建时的编号表示
+///
+/// # Examples
+///
+/// ```rust
+/// use base::SinglyLinkedList;
+///
+/// // 1 -> 2 -> 3 -> 4 -> 5
+/// //           ^         |
+/// //           +---------+
+/// let mut list = SinglyLinkedList::from_iter([1, 2, 3, 4, 5]);
+/// list.set_next(4, Some(2));
+///
+/// assert!(list.has_cycle());
+/// assert_eq!(list.cycle_start().map(|node| list.value(node)), Some(&3));
+/// assert_eq!(list.cycle_len(), 3);
+/// assert_eq!(list.len(), 5);
+/// assert!(list.iter().take(8).eq(&[1, 2, 3, 4, 5, 3, 4, 5]));
+/// ```
+#[derive(Debug, Clone)]
+pub struct SinglyLinkedList<T> {
+    nodes: Vec<SinglyNode<T>>,
+    head: Option<usize>,
+}
+
+impl<T> SinglyLinkedList<T> {
+    /// 创建空链表
+    pub fn new() -> Self {
+        Self {
+            nodes: Vec::new(),
+            head: None,
+        }
+    }
+
+    /// 头结点
+    pub fn head(&self) -> Option<usize> {
+        self.head
+    }
+
+    /// 在头部插入元素，返回新结点的编号
+    pub fn push_front(&mut self, value: T) -> usize {
+        self.nodes.push(SinglyNode {
+            value,
+            next: self.head,
+        });
+        self.head = Some(self.nodes.len() - 1);
+
+        self.nodes.len() - 1
+    }
+
+    /// 结点的值
+    ///
+    /// # Panics
+    ///
+    /// Panics if `node` is not a node of this list.
+    pub fn value(&self, node: usize) -> &T {
+        &self.nodes[node].value
+    }
+
+    /// 结点的值的可变借用
+    ///
+    /// # Panics
+    ///
+    /// Panics if `node` is not a node of this list.
+    pub fn value_mut(&mut self, node: usize) -> &mut T {
+        &mut self.nodes[node].value
+    }
+
+    /// 结点的后继
+    ///
+    /// # Panics
+    ///
+    /// Panics if `node` is not a node of this list.
+    pub fn next(&self, node: usize) -> Option<usize> {
+        self.nodes[node].next
+    }
+
+    /// 修改结点的后继，可以指向任意结点（包括自身）以形成环，
+    /// 不再能从头结点到达的结点仍然保留，但遍历时不会访问
+    ///
+    /// # Panics
+    ///
+    /// Panics if `node` or `next` is not a node of this list.
+    pub fn set_next(&mut self, node: usize, next: Option<usize>) {
+        let count = self.nodes.len();
+        assert!(node < count, "Invalid node: {node}, nodes: {count}");
+        if let Some(next) = next {
+            assert!(next < count, "Invalid node: {next}, nodes: {count}");
+        }
+
+        self.nodes[node].next = next;
+    }
+
+    /// 从头结点开始依次访问每个值，有环时永远不会结束，可以配合 `take(len)` 使用
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        let mut current = self.head;
+
+        std::iter::from_fn(move || {
+            let node = &self.nodes[current?];
+            current = node.next;
+
+            Some(&node.value)
+        })
+    }
+
+    /// 从头结点可以到达的不同结点的数量，即环外部分的长度加上环长
+    pub fn len(&self) -> usize {
+        match self.cycle_start() {
+            None => self.iter().count(),
+            Some(start) => self.distance(self.head, start) + self.cycle_len(),
+        }
+    }
+
+    /// 判断链表是否为空
+    pub fn is_empty(&self) -> bool {
+        self.head.is_none()
+    }
+
+    /// 判断链表是否有环，时间复杂度：O(n)，空间复杂度：O(1)
+    pub fn has_cycle(&self) -> bool {
+        self.meeting_point().is_some()
+    }
+
+    /// 环的入口结点，无环时返回 None
+    pub fn cycle_start(&self) -> Option<usize> {
+        let mut fast = self.meeting_point()?;
+        // Safety: 有环时头结点一定存在
+        let mut slow = self.head.unwrap();
+
+        // 同时每次走一步，在环的入口相遇
+        while slow != fast {
+            slow = self.step(slow);
+            fast = self.step(fast);
+        }
+
+        Some(slow)
+    }
+
+    /// 环长，无环时返回 0
+    pub fn cycle_len(&self) -> usize {
+        let Some(meeting) = self.meeting_point() else {
+            return 0;
+        };
+
+        let mut len = 1;
+        let mut current = self.step(meeting);
+        while current != meeting {
+            current = self.step(current);
+            len += 1;
+        }
+
+        len
+    }
+
+    /// 快慢指针在环内的相遇点，无环时返回 None
+    fn meeting_point(&self) -> Option<usize> {
+        let (mut slow, mut fast) = (self.head?, self.head?);
+
+        loop {
+            fast = self.next(self.next(fast)?)?;
+            slow = self.step(slow);
+            if slow == fast {
+                return Some(slow);
+            }
+        }
+    }
+
+    /// 走一步，只用于已知在环上或后面还有结点的情况
+    fn step(&self, node: usize) -> usize {
+        // Safety: 调用者保证 node 有后继
+        self.next(node).unwrap()
+    }
+
+    /// 从 from 走到 to 的步数，调用者保证可以到达
+    fn distance(&self, mut from: Option<usize>, to: usize) -> usize {
+        let mut steps = 0;
+        while from != Some(to) {
+            from = from.and_then(|node| self.next(node));
+            steps += 1;
+        }
+
+        steps
+    }
+}
+
+impl<T> Default for SinglyLinkedList<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> FromIterator<T> for SinglyLinkedList<T> {
+    /// 按顺序构建无环的链表，第 i 个元素的结点编号为 i
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut nodes = iter
+            .into_iter()
+            .enumerate()
+            .map(|(i, value)| SinglyNode {
+                value,
+                next: Some(i + 1),
+            })
+            .collect::<Vec<_>>();
+        if let Some(last) = nodes.last_mut() {
+            last.next = None;
+        }
+
+        Self {
+            head: (!nodes.is_empty()).then_some(0),
+            nodes,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::generate::Rng;
+
+    /// 记录每个结点第一次被访问的位置，返回 (环的入口, 环长, 可到达的结点数)
+    fn brute_force<T>(list: &SinglyLinkedList<T>) -> (Option<usize>, usize, usize) {
+        let mut visited = HashMap::new();
+        let mut current = list.head();
+
+        while let Some(node) = current {
+            if let Some(&first) = visited.get(&node) {
+                return (Some(node), visited.len() - first, visited.len());
+            }
+            visited.insert(node, visited.len());
+            current = list.next(node);
+        }
+
+        (None, 0, visited.len())
+    }
+
+    #[test]
+    fn floyd_should_match_brute_force() {
+        let mut rng = Rng::new(5);
+
+        for len in 0..60 {
+            let mut list = (0..len).collect::<SinglyLinkedList<_>>();
+            // 约三分之二的用例让尾结点指回随机的结点
+            if len > 0 && rng.below(3) > 0 {
+                let target = rng.below(len as u64) as usize;
+                list.set_next(len - 1, Some(target));
+            }
+
+            let (start, cycle_len, reachable) = brute_force(&list);
+            assert_eq!(list.has_cycle(), start.is_some());
+            assert_eq!(list.cycle_start(), start);
+            assert_eq!(list.cycle_len(), cycle_len);
+            assert_eq!(list.len(), reachable);
+        }
+    }
+
+    #[test]
+    fn singly_linked_list_should_support_push_front() {
+        let mut list = SinglyLinkedList::new();
+        assert!(list.is_empty());
+        assert!(!list.has_cycle());
+
+        let last = list.push_front('c');
+        list.push_front('b');
+        let first = list.push_front('a');
+        assert!(list.iter().eq(&['a', 'b', 'c']));
+
+        // 自环
+        list.set_next(first, Some(first));
+        assert_eq!(list.cycle_start(), Some(first));
+        assert_eq!((list.cycle_len(), list.len()), (1, 1));
+
+        list.set_next(first, Some(last));
+        *list.value_mut(last) = 'z';
+        assert!(list.iter().eq(&['a', 'z']));
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid node: 3, nodes: 3")]
+    fn set_next_should_panic_on_invalid_node() {
+        let mut list = SinglyLinkedList::from_iter([1, 2, 3]);
+        list.set_next(0, Some(3));
+    }
+}
@@ -0,0 +1,453 @@
+//! 跳表（skip list）
+//!
+//! 跳表在有序链表的基础上增加多层“快速通道”：每个结点以 1/2 的概率晋升到上一层，
+//! 查找时从最高层开始向右前进，遇到比目标大的结点时下降一层，
+//! 查找、插入、删除的期望时间复杂度均为 O(logn)。
+//!
+//! SkipListMap 额外支持带过期时间的键值对：过期时间被记录在一个小顶堆中，
+//! 每次修改操作前先弹出所有已到期的键并将其删除。
+
+use std::{
+    cmp::Reverse,
+    collections::BinaryHeap,
+    ops::{Bound, RangeBounds},
+    time::{Duration, Instant},
+};
+
+use crate::random::Xorshift64;
+
+/// 跳表的最大层数
+const MAX_LEVEL: usize = 16;
+
+#[derive(Debug)]
+struct SkipNode<K, V> {
+    /// 哨兵结点的 entry 为 None
+    entry: Option<Entry<K, V>>,
+    /// next[i] 为该结点在第 i 层的后继
+    next: Vec<Option<usize>>,
+}
+
+#[derive(Debug)]
+struct Entry<K, V> {
+    key: K,
+    value: V,
+    deadline: Option<Instant>,
+}
+
+impl<K, V> Entry<K, V> {
+    fn is_expired(&self, now: Instant) -> bool {
+        self.deadline.is_some_and(|deadline| deadline <= now)
+    }
+}
+
+/// 基于跳表实现的有序映射，支持为键值对设置过期时间
+#[derive(Debug)]
+pub struct SkipListMap<K, V> {
+    /// 下标 0 为哨兵结点
+    nodes: Vec<SkipNode<K, V>>,
+    /// 已删除、可复用的结点下标
+    free: Vec<usize>,
+    /// 当前使用的最高层数
+    level: usize,
+    len: usize,
+    /// 随机数生成器，用于决定新结点的层数
+    rng: Xorshift64,
+    /// 过期时间小顶堆
+    deadlines: BinaryHeap<Reverse<(Instant, K)>>,
+}
+
+impl<K, V> SkipListMap<K, V> {
+    /// 创建空的跳表
+    pub fn new() -> Self {
+        Self::with_seed(0x2545_f491_4f6c_dd1d)
+    }
+
+    /// 创建空的跳表，使用给定的种子生成结点层数，相同的种子和操作序列得到相同的结构
+    pub fn with_seed(seed: u64) -> Self {
+        Self {
+            nodes: vec![SkipNode {
+                entry: None,
+                next: vec![None; MAX_LEVEL],
+            }],
+            free: Vec::new(),
+            level: 1,
+            len: 0,
+            rng: Xorshift64::new(seed),
+            deadlines: BinaryHeap::new(),
+        }
+    }
+
+    /// 键值对数量（包括已过期但尚未清理的键值对）
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// 判断跳表是否为空
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// 按键升序遍历所有未过期的键值对
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> {
+        let now = Instant::now();
+
+        self.iter_from(self.nodes[0].next[0], now)
+            .map(|entry| (&entry.key, &entry.value))
+    }
+
+    /// 从结点 start 开始沿第 0 层遍历，跳过已过期的键值对
+    fn iter_from(&self, start: Option<usize>, now: Instant) -> impl Iterator<Item = &Entry<K, V>> {
+        let mut current = start;
+
+        std::iter::from_fn(move || {
+            let node = &self.nodes[current?];
+            current = node.next[0];
+            node.entry.as_ref()
+        })
+        .filter(move |entry| !entry.is_expired(now))
+    }
+
+    fn entry(&self, index: usize) -> &Entry<K, V> {
+        // Safety: 除哨兵结点外，链表中的结点一定保存了键值对
+        self.nodes[index].entry.as_ref().unwrap()
+    }
+
+    /// 以 1/2 的概率逐层晋升，生成新结点的层数
+    fn random_level(&mut self) -> usize {
+        (self.rng.next_u64().trailing_ones() as usize + 1).min(MAX_LEVEL)
+    }
+}
+
+impl<K: Ord, V> SkipListMap<K, V> {
+    /// 返回每一层中最后一个键小于 key 的结点
+    fn predecessors(&self, key: &K) -> [usize; MAX_LEVEL] {
+        let mut update = [0; MAX_LEVEL];
+        let mut current = 0;
+
+        for i in (0..self.level).rev() {
+            while let Some(next) = self.nodes[current].next[i]
+                && self.entry(next).key < *key
+            {
+                current = next;
+            }
+            update[i] = current;
+        }
+
+        update
+    }
+
+    /// 查找键为 key 的结点
+    fn find(&self, key: &K) -> Option<usize> {
+        let update = self.predecessors(key);
+
+        self.nodes[update[0]].next[0].filter(|&next| self.entry(next).key == *key)
+    }
+
+    /// 获取键对应的值，键不存在或已过期时返回 None
+    pub fn get(&self, key: &K) -> Option<&V> {
+        let entry = self.entry(self.find(key)?);
+
+        (!entry.is_expired(Instant::now())).then_some(&entry.value)
+    }
+
+    /// 获取键对应值的可变借用，键不存在或已过期时返回 None
+    pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        let index = self.find(key)?;
+        let entry = self.nodes[index].entry.as_mut().unwrap();
+
+        (!entry.is_expired(Instant::now())).then_some(&mut entry.value)
+    }
+
+    /// 判断是否包含指定的键
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// 返回最小的键值对
+    pub fn first_key_value(&self) -> Option<(&K, &V)> {
+        self.iter().next()
+    }
+
+    /// 按键升序遍历落在指定范围内的未过期键值对
+    pub fn range<R: RangeBounds<K>>(&self, range: R) -> impl Iterator<Item = (&K, &V)> {
+        let start = match range.start_bound() {
+            Bound::Unbounded => self.nodes[0].next[0],
+            Bound::Included(key) => self.nodes[self.predecessors(key)[0]].next[0],
+            Bound::Excluded(key) => {
+                let next = self.nodes[self.predecessors(key)[0]].next[0];
+                match next {
+                    Some(index) if self.entry(index).key == *key => self.nodes[index].next[0],
+                    _ => next,
+                }
+            }
+        };
+
+        self.iter_from(start, Instant::now())
+            .take_while(move |entry| match range.end_bound() {
+                Bound::Unbounded => true,
+                Bound::Included(key) => entry.key <= *key,
+                Bound::Excluded(key) => entry.key < *key,
+            })
+            .map(|entry| (&entry.key, &entry.value))
+    }
+
+    fn insert_entry(&mut self, key: K, value: V, deadline: Option<Instant>) -> Option<V> {
+        let update = self.predecessors(&key);
+
+        // 键已存在，替换值
+        if let Some(index) = self.nodes[update[0]].next[0]
+            && self.entry(index).key == key
+        {
+            let entry = self.nodes[index].entry.as_mut().unwrap();
+            entry.deadline = deadline;
+            return Some(std::mem::replace(&mut entry.value, value));
+        }
+
+        let level = self.random_level();
+        // 新增的层以哨兵结点作为前驱
+        self.level = self.level.max(level);
+
+        let node = SkipNode {
+            entry: Some(Entry {
+                key,
+                value,
+                deadline,
+            }),
+            next: vec![None; level],
+        };
+        let index = match self.free.pop() {
+            Some(free) => {
+                self.nodes[free] = node;
+                free
+            }
+            None => {
+                self.nodes.push(node);
+                self.nodes.len() - 1
+            }
+        };
+
+        for (i, &prev) in update.iter().enumerate().take(level) {
+            self.nodes[index].next[i] = self.nodes[prev].next[i];
+            self.nodes[prev].next[i] = Some(index);
+        }
+        self.len += 1;
+
+        None
+    }
+
+    /// 删除键对应的键值对，返回被删除的值
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        let update = self.predecessors(key);
+        let index = self.nodes[update[0]].next[0].filter(|&next| self.entry(next).key == *key)?;
+
+        for (i, &prev) in update.iter().enumerate().take(self.nodes[index].next.len()) {
+            self.nodes[prev].next[i] = self.nodes[index].next[i];
+        }
+        // 降低空的最高层
+        while self.level > 1 && self.nodes[0].next[self.level - 1].is_none() {
+            self.level -= 1;
+        }
+
+        let node = std::mem::replace(
+            &mut self.nodes[index],
+            SkipNode {
+                entry: None,
+                next: Vec::new(),
+            },
+        );
+        self.free.push(index);
+        self.len -= 1;
+
+        node.entry.map(|entry| entry.value)
+    }
+}
+
+impl<K: Ord + Clone, V> SkipListMap<K, V> {
+    /// 插入键值对，键已存在时替换其值并返回旧值
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        self.purge_expired();
+        self.insert_entry(key, value, None)
+    }
+
+    /// 插入一个在 ttl 之后过期的键值对，键已存在时替换其值和过期时间并返回旧值
+    ///
+    /// 过期时刻超出 Instant 的表示范围时（如 Duration::MAX），视为永不过期
+    pub fn insert_with_ttl(&mut self, key: K, value: V, ttl: Duration) -> Option<V> {
+        self.purge_expired();
+
+        let deadline = Instant::now().checked_add(ttl);
+        if let Some(deadline) = deadline {
+            self.deadlines.push(Reverse((deadline, key.clone())));
+        }
+        self.insert_entry(key, value, deadline)
+    }
+
+    /// 删除当前时刻所有已过期的键值对，返回删除的数量
+    pub fn purge_expired(&mut self) -> usize {
+        self.purge_expired_at(Instant::now())
+    }
+
+    /// 删除在 now 时刻已过期的键值对，返回删除的数量
+    pub fn purge_expired_at(&mut self, now: Instant) -> usize {
+        let mut purged = 0;
+
+        while let Some(Reverse((deadline, _))) = self.deadlines.peek()
+            && *deadline <= now
+        {
+            // Safety: peek() 已经确认堆不为空
+            let Reverse((deadline, key)) = self.deadlines.pop().unwrap();
+            // 键可能已被删除或以新的过期时间重新插入，只有过期时间一致时才删除
+            if let Some(index) = self.find(&key)
+                && self.entry(index).deadline == Some(deadline)
+            {
+                self.remove(&key);
+                purged += 1;
+            }
+        }
+
+        purged
+    }
+}
+
+impl<K, V> Default for SkipListMap<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Ord + Clone, V> FromIterator<(K, V)> for SkipListMap<K, V> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut map = SkipListMap::new();
+
+        iter.into_iter().for_each(|(key, value)| {
+            map.insert(key, value);
+        });
+
+        map
+    }
+}
+
+impl<K: PartialEq, V: PartialEq> PartialEq for SkipListMap<K, V> {
+    fn eq(&self, other: &Self) -> bool {
+        self.iter().eq(other.iter())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::*;
+    use crate::generate::{self, Operation};
+
+    #[test]
+    fn skip_list_map_should_match_btree_map() {
+        let mut map = SkipListMap::new();
+        let mut expected = BTreeMap::new();
+
+        for (i, op) in generate::operations(5000, 200, 1).into_iter().enumerate() {
+            match op {
+                Operation::Insert(key) => assert_eq!(map.insert(key, i), expected.insert(key, i)),
+                Operation::Remove(key) => assert_eq!(map.remove(&key), expected.remove(&key)),
+                Operation::Get(key) => assert_eq!(map.get(&key), expected.get(&key)),
+            }
+        }
+
+        assert_eq!(map.len(), expected.len());
+        assert!(map.iter().eq(expected.iter()));
+    }
+
+    #[test]
+    fn skip_list_map_basics_should_work() {
+        let mut map = SkipListMap::new();
+
+        assert!(map.is_empty());
+        for key in [5, 1, 9, 3, 7, 2, 8, 4, 6, 0] {
+            assert_eq!(map.insert(key, key * 10), None);
+        }
+        assert_eq!(map.insert(3, 33), Some(30));
+        assert_eq!(map.len(), 10);
+
+        assert_eq!(map.get(&3), Some(&33));
+        assert_eq!(map.get(&10), None);
+        *map.get_mut(&4).unwrap() += 1;
+        assert_eq!(map.get(&4), Some(&41));
+
+        assert_eq!(map.remove(&5), Some(50));
+        assert_eq!(map.remove(&5), None);
+        assert!(!map.contains_key(&5));
+        assert_eq!(map.first_key_value(), Some((&0, &0)));
+
+        let keys = map.iter().map(|(k, _)| *k).collect::<Vec<_>>();
+        assert_eq!(keys, vec![0, 1, 2, 3, 4, 6, 7, 8, 9]);
+    }
+
+    #[test]
+    fn skip_list_map_range_should_work() {
+        let map = (0..100).map(|i| (i * 2, i)).collect::<SkipListMap<_, _>>();
+
+        let keys = |iter: &mut dyn Iterator<Item = (&i32, &i32)>| {
+            iter.map(|(k, _)| *k).collect::<Vec<_>>()
+        };
+        assert_eq!(keys(&mut map.range(10..16)), vec![10, 12, 14]);
+        assert_eq!(keys(&mut map.range(9..=16)), vec![10, 12, 14, 16]);
+        assert_eq!(keys(&mut map.range(195..)), vec![196, 198]);
+        assert_eq!(keys(&mut map.range(..3)), vec![0, 2]);
+        assert_eq!(
+            keys(&mut map.range((Bound::Excluded(10), Bound::Included(14)))),
+            vec![12, 14]
+        );
+        assert_eq!(map.range(300..).count(), 0);
+    }
+
+    #[test]
+    fn skip_list_levels_should_be_geometric() {
+        let mut map = SkipListMap::<u64, ()>::with_seed(42);
+        let n = 1 << 14;
+        let levels = (0..n).map(|_| map.random_level()).collect::<Vec<_>>();
+
+        // 层数不小于 l 的结点约占 1 / 2^(l - 1)
+        for l in 1..=6 {
+            let count = levels.iter().filter(|&&level| level >= l).count();
+            let expected = n >> (l - 1);
+            assert!(count.abs_diff(expected) < expected / 10 + 20);
+        }
+        assert!(levels.iter().all(|&level| (1..=MAX_LEVEL).contains(&level)));
+
+        // 相同的种子生成相同的层数序列
+        let mut same = SkipListMap::<u64, ()>::with_seed(42);
+        assert!(levels.iter().all(|&level| level == same.random_level()));
+        let mut other = SkipListMap::<u64, ()>::with_seed(7);
+        assert!(levels.iter().any(|&level| level != other.random_level()));
+    }
+
+    #[test]
+    fn skip_list_map_ttl_should_work() {
+        let mut map = SkipListMap::new();
+
+        map.insert(1, "forever");
+        map.insert_with_ttl(2, "short", Duration::ZERO);
+        map.insert_with_ttl(3, "long", Duration::from_secs(3600));
+        // 重新插入后旧的过期时间不再生效
+        map.insert_with_ttl(4, "renewed", Duration::ZERO);
+        map.insert_with_ttl(4, "renewed", Duration::from_secs(3600));
+
+        assert_eq!(map.get(&2), None);
+        assert_eq!(map.iter().count(), 3);
+
+        assert_eq!(map.purge_expired(), 0);
+        assert_eq!(map.len(), 3);
+        assert_eq!(map.get(&4), Some(&"renewed"));
+
+        // 过期时刻溢出时永不过期，并覆盖原来的过期时间
+        map.insert_with_ttl(5, "unbounded", Duration::MAX);
+        map.insert_with_ttl(3, "long", Duration::MAX);
+
+        let later = Instant::now() + Duration::from_secs(7200);
+        assert_eq!(map.purge_expired_at(later), 1);
+        assert_eq!(
+            map.iter().collect::<Vec<_>>(),
+            vec![(&1, &"forever"), (&3, &"long"), (&5, &"unbounded")]
+        );
+    }
+}
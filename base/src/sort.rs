@@ -0,0 +1,1052 @@
+//! 排序算法
+//!
+//! 朴素快速排序在最坏情况下（如对已有序的数组总是选择末尾元素为基准）会退化为 O(n²)，
+//! 并且递归深度达到 O(n)。生产环境中的排序通过以下手段防御这类“对抗性输入”：
+//! * 内省排序（introsort）：限制快速排序的递归深度为 2logn，超过后改用堆排序，
+//!   从而保证最坏时间复杂度为 O(nlogn)；区间较短时改用插入排序以减少常数开销
+//! * 模式消除快速排序（pattern-defeating quicksort，pdqsort）：在内省排序的基础上，
+//!   - 划分极不均衡时打乱部分元素，破坏可能导致退化的输入模式
+//!   - 基准值与前驱相等时，一次性跳过所有与基准值相等的元素，大量重复元素时为 O(n)
+//!   - 划分前区间已有序时，尝试用有限步数的插入排序直接完成排序，有序输入时为 O(n)
+//!
+//! 启用 `sorting-network` 特性后，内省排序和模式消除快速排序的基本情形改用排序网络，见 [`network`]。
+
+pub mod network;
+
+use std::cmp::Ordering;
+
+use crate::random::{RandomSource, Xorshift64};
+
+/// 区间长度不超过该值时使用插入排序（或排序网络）
+const INSERTION_SORT_THRESHOLD: usize = network::MAX_LEN;
+
+/// 朴素快速排序，总是选择区间末尾的元素作为基准，仅用于对比
+///
+/// 平均时间复杂度：O(nlogn)，最坏时间复杂度：O(n²)
+pub fn quick_sort<T: Ord>(v: &mut [T]) {
+    quick_sort_by(v, T::cmp);
+}
+
+/// 使用比较函数进行朴素快速排序
+pub fn quick_sort_by<T, F>(v: &mut [T], mut compare: F)
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    quick_sort_recursive(v, &mut |a, b| compare(a, b) == Ordering::Less);
+}
+
+fn quick_sort_recursive<T, F>(v: &mut [T], is_less: &mut F)
+where
+    F: FnMut(&T, &T) -> bool,
+{
+    if v.len() <= 1 {
+        return;
+    }
+
+    // Lomuto 划分，以末尾元素为基准
+    let last = v.len() - 1;
+    let mut store = 0;
+    for i in 0..last {
+        if is_less(&v[i], &v[last]) {
+            v.swap(i, store);
+            store += 1;
+        }
+    }
+    v.swap(store, last);
+
+    quick_sort_recursive(&mut v[..store], is_less);
+    quick_sort_recursive(&mut v[store + 1..], is_less);
+}
+
+/// 随机基准快速排序：每次在区间中随机选择基准，对任何输入的期望时间复杂度均为 O(nlogn)
+///
+/// 退化不再取决于输入，而只取决于随机数，传入相同种子的生成器可以复现同一次排序过程。
+///
+/// # Examples
+///
+/// ```rust
+/// use base::{random::SplitMix64, sort};
+///
+/// let mut v = (0..1000).rev().collect::<Vec<_>>();
+/// sort::quick_sort_randomized(&mut v, &mut SplitMix64::new(1));
+///
+/// assert!(v.is_sorted());
+/// ```
+pub fn quick_sort_randomized<T: Ord, R: RandomSource + ?Sized>(v: &mut [T], rng: &mut R) {
+    quick_sort_randomized_recursive(v, rng);
+}
+
+fn quick_sort_randomized_recursive<T: Ord, R: RandomSource + ?Sized>(v: &mut [T], rng: &mut R) {
+    if v.len() <= 1 {
+        return;
+    }
+
+    // 把随机选出的基准交换到末尾，之后与朴素快速排序相同
+    let last = v.len() - 1;
+    v.swap(rng.below(v.len() as u64) as usize, last);
+    let mut store = 0;
+    for i in 0..last {
+        if v[i] < v[last] {
+            v.swap(i, store);
+            store += 1;
+        }
+    }
+    v.swap(store, last);
+
+    quick_sort_randomized_recursive(&mut v[..store], rng);
+    quick_sort_randomized_recursive(&mut v[store + 1..], rng);
+}
+
+/// 内省排序，最坏时间复杂度：O(nlogn)，不稳定
+///
+/// # Examples
+///
+/// ```rust
+/// use base::sort;
+///
+/// let mut v = [5, 2, 9, 1, 5, 6];
+/// sort::intro_sort(&mut v);
+///
+/// assert_eq!(v, [1, 2, 5, 5, 6, 9]);
+/// ```
+pub fn intro_sort<T: Ord>(v: &mut [T]) {
+    intro_sort_by(v, T::cmp);
+}
+
+/// 使用比较函数进行内省排序
+pub fn intro_sort_by<T, F>(v: &mut [T], mut compare: F)
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    let limit = 2 * log2(v.len());
+    intro_sort_recursive(v, &mut |a, b| compare(a, b) == Ordering::Less, limit);
+}
+
+fn intro_sort_recursive<T, F>(mut v: &mut [T], is_less: &mut F, mut limit: usize)
+where
+    F: FnMut(&T, &T) -> bool,
+{
+    loop {
+        if v.len() <= INSERTION_SORT_THRESHOLD {
+            small_sort(v, is_less);
+            return;
+        }
+        // 递归过深，说明划分持续不均衡，改用堆排序
+        if limit == 0 {
+            heap_sort(v, is_less);
+            return;
+        }
+        limit -= 1;
+
+        let pivot = choose_pivot(v, is_less);
+        let (mid, _) = partition(v, pivot, is_less);
+
+        // 递归处理较短的一侧，循环处理较长的一侧，保证递归深度为 O(logn)
+        let (left, right) = v.split_at_mut(mid);
+        let right = &mut right[1..];
+        if left.len() < right.len() {
+            intro_sort_recursive(left, is_less, limit);
+            v = right;
+        } else {
+            intro_sort_recursive(right, is_less, limit);
+            v = left;
+        }
+    }
+}
+
+/// 模式消除快速排序，最坏时间复杂度：O(nlogn)，不稳定
+///
+/// 对有序、逆序或包含大量重复元素的输入，时间复杂度接近 O(n)
+///
+/// # Examples
+///
+/// ```rust
+/// use base::sort;
+///
+/// let mut v = [3, 1, 3, 3, 2, 1, 3];
+/// sort::pdq_sort(&mut v);
+///
+/// assert_eq!(v, [1, 1, 2, 3, 3, 3, 3]);
+/// ```
+pub fn pdq_sort<T: Ord>(v: &mut [T]) {
+    pdq_sort_by(v, T::cmp);
+}
+
+/// 使用比较函数进行模式消除快速排序
+pub fn pdq_sort_by<T, F>(v: &mut [T], mut compare: F)
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    let limit = log2(v.len());
+    pdq_sort_recursive(v, &mut |a, b| compare(a, b) == Ordering::Less, None, limit);
+}
+
+fn pdq_sort_recursive<'a, T, F>(
+    mut v: &'a mut [T],
+    is_less: &mut F,
+    mut pred: Option<&'a T>,
+    mut limit: usize,
+) where
+    F: FnMut(&T, &T) -> bool,
+{
+    // 上一次划分是否均衡、是否未交换任何元素
+    let mut was_balanced = true;
+    let mut was_partitioned = true;
+
+    loop {
+        let len = v.len();
+        if len <= INSERTION_SORT_THRESHOLD {
+            small_sort(v, is_less);
+            return;
+        }
+        // 不均衡的划分次数过多，改用堆排序
+        if limit == 0 {
+            heap_sort(v, is_less);
+            return;
+        }
+
+        // 上一次划分不均衡，打乱部分元素以破坏输入模式
+        if !was_balanced {
+            break_patterns(v);
+            limit -= 1;
+        }
+
+        let pivot = choose_pivot(v, is_less);
+
+        // 上一次划分均衡且没有交换元素，区间很可能已经有序，尝试直接完成排序
+        if was_balanced && was_partitioned && partial_insertion_sort(v, is_less) {
+            return;
+        }
+
+        // 前驱元素（左侧已排好的基准值）不小于当前基准值，说明两者相等，
+        // 将所有与基准值相等的元素移到左侧并跳过
+        if let Some(p) = pred
+            && !is_less(p, &v[pivot])
+        {
+            let mid = partition_equal(v, pivot, is_less);
+            v = &mut v[mid..];
+            continue;
+        }
+
+        let (mid, partitioned) = partition(v, pivot, is_less);
+        was_balanced = mid.min(len - mid) >= len / 8;
+        was_partitioned = partitioned;
+
+        let (left, right) = v.split_at_mut(mid);
+        let (pivot, right) = right.split_at_mut(1);
+        let pivot = &pivot[0];
+
+        if left.len() < right.len() {
+            pdq_sort_recursive(left, is_less, pred, limit);
+            v = right;
+            pred = Some(pivot);
+        } else {
+            pdq_sort_recursive(right, is_less, Some(pivot), limit);
+            v = left;
+        }
+    }
+}
+
+/// 归并排序，时间复杂度：O(nlogn)，稳定
+///
+/// 自底向上归并：先将长度为 1 的子数组两两归并，再归并长度为 2、4、8…… 的子数组
+pub fn merge_sort<T: Ord + Clone>(v: &mut [T]) {
+    merge_sort_by(v, T::cmp);
+}
+
+/// 使用比较函数进行归并排序
+pub fn merge_sort_by<T, F>(v: &mut [T], mut compare: F)
+where
+    T: Clone,
+    F: FnMut(&T, &T) -> Ordering,
+{
+    let runs = (0..=v.len()).collect::<Vec<_>>();
+    merge_runs(v, runs, &mut |a, b| compare(a, b) == Ordering::Less);
+}
+
+/// 自然归并排序，时间复杂度：O(nlogr)，r 为数组中有序段的数量，稳定
+///
+/// 先找出数组中已有的有序段（run）：非递减段直接使用，严格递减段原地反转
+/// （要求严格递减是为了保证反转后依然稳定），再将相邻的有序段两两归并。
+/// 对于已经基本有序的数据，有序段很少，排序接近 O(n)。
+///
+/// # Examples
+///
+/// ```rust
+/// use base::sort;
+///
+/// let mut v = [1, 2, 3, 9, 8, 7, 4, 5, 6];
+/// sort::natural_merge_sort(&mut v);
+///
+/// assert_eq!(v, [1, 2, 3, 4, 5, 6, 7, 8, 9]);
+/// ```
+pub fn natural_merge_sort<T: Ord + Clone>(v: &mut [T]) {
+    natural_merge_sort_by(v, T::cmp);
+}
+
+/// 使用比较函数进行自然归并排序
+pub fn natural_merge_sort_by<T, F>(v: &mut [T], mut compare: F)
+where
+    T: Clone,
+    F: FnMut(&T, &T) -> Ordering,
+{
+    let is_less = &mut |a: &T, b: &T| compare(a, b) == Ordering::Less;
+
+    // runs 记录各有序段的边界，第 i 段为 [runs[i], runs[i + 1])
+    let mut runs = vec![0];
+    let mut start = 0;
+    while start < v.len() {
+        let mut end = start + 1;
+        if end < v.len() {
+            if is_less(&v[end], &v[start]) {
+                // 严格递减段
+                end += 1;
+                while end < v.len() && is_less(&v[end], &v[end - 1]) {
+                    end += 1;
+                }
+                v[start..end].reverse();
+            } else {
+                // 非递减段
+                end += 1;
+                while end < v.len() && !is_less(&v[end], &v[end - 1]) {
+                    end += 1;
+                }
+            }
+        }
+        runs.push(end);
+        start = end;
+    }
+
+    merge_runs(v, runs, is_less);
+}
+
+/// 反复将相邻的有序段两两归并，直到只剩一个有序段
+fn merge_runs<T, F>(v: &mut [T], mut runs: Vec<usize>, is_less: &mut F)
+where
+    T: Clone,
+    F: FnMut(&T, &T) -> bool,
+{
+    let mut buf = Vec::with_capacity(v.len() / 2 + 1);
+
+    while runs.len() > 2 {
+        let mut merged = vec![0];
+        let mut i = 0;
+        while i + 2 < runs.len() {
+            let (start, mid, end) = (runs[i], runs[i + 1], runs[i + 2]);
+            merge(&mut v[start..end], mid - start, &mut buf, is_less);
+            merged.push(end);
+            i += 2;
+        }
+        // 剩余一个落单的有序段，留到下一轮归并
+        if i + 1 < runs.len() {
+            merged.push(runs[i + 1]);
+        }
+        runs = merged;
+    }
+}
+
+/// 归并 v[..mid] 和 v[mid..] 两个有序段
+fn merge<T, F>(v: &mut [T], mid: usize, buf: &mut Vec<T>, is_less: &mut F)
+where
+    T: Clone,
+    F: FnMut(&T, &T) -> bool,
+{
+    // 将左半部分复制到辅助数组中，再从前往后写回
+    buf.clear();
+    buf.extend_from_slice(&v[..mid]);
+
+    let (mut i, mut j, mut k) = (0, mid, 0);
+    while i < buf.len() && j < v.len() {
+        // 右侧严格更小时才取右侧元素，保证稳定性
+        if is_less(&v[j], &buf[i]) {
+            v[k] = v[j].clone();
+            j += 1;
+        } else {
+            v[k] = buf[i].clone();
+            i += 1;
+        }
+        k += 1;
+    }
+    // 右半部分剩余的元素已经在正确的位置上
+    while i < buf.len() {
+        v[k] = buf[i].clone();
+        i += 1;
+        k += 1;
+    }
+}
+
+/// 圈排序，返回写入次数，时间复杂度：O(n²)，不稳定
+///
+/// 将数组看作若干个置换环，每个元素通过统计比它小的元素数量直接计算出最终位置，
+/// 沿着环依次把元素放到最终位置。每个元素最多被写入一次，已在正确位置的元素不会被写入，
+/// 写入次数是所有排序算法中最少的，适用于写入代价很高的存储介质。
+///
+/// # Examples
+///
+/// ```rust
+/// use base::sort;
+///
+/// let mut v = [1, 3, 2, 4];
+///
+/// assert_eq!(sort::cycle_sort(&mut v), 2);
+/// assert_eq!(v, [1, 2, 3, 4]);
+/// ```
+pub fn cycle_sort<T: Ord>(v: &mut [T]) -> usize {
+    let mut writes = 0;
+
+    for start in 0..v.len().saturating_sub(1) {
+        // 当前“手中”的元素始终保存在 v[start] 处
+        let mut pos = cycle_position(v, start);
+        if pos == start {
+            continue;
+        }
+
+        while pos != start {
+            // 跳过与手中元素相等的元素，它们已经在正确的位置上
+            while v[pos] == v[start] {
+                pos += 1;
+            }
+            // 将手中元素放到最终位置，同时拿起原来位于该位置的元素
+            v.swap(start, pos);
+            writes += 1;
+            pos = cycle_position(v, start);
+        }
+        // 环的最后一个元素恰好属于 start，计入它的写入
+        writes += 1;
+    }
+
+    writes
+}
+
+/// 计算 v[start] 在 v[start..] 中的最终位置
+fn cycle_position<T: Ord>(v: &[T], start: usize) -> usize {
+    start + v[start + 1..].iter().filter(|x| **x < v[start]).count()
+}
+
+/// 煎饼排序，返回翻转序列，时间复杂度：O(n²)，不稳定
+///
+/// 唯一允许的操作是“翻转”：将前 k 个元素整体反转，就像用铲子翻转一摞煎饼的最上面 k 张。
+/// 每轮先将未排序部分的最大值翻到最前面，再将其翻到未排序部分的末尾，最多 2n 次翻转。
+/// 返回值中的每个元素 k 表示一次对前 k 个元素的翻转。
+///
+/// # Examples
+///
+/// ```rust
+/// use base::sort;
+///
+/// let mut v = [3, 2, 4, 1];
+/// let flips = sort::pancake_sort(&mut v);
+///
+/// assert_eq!(v, [1, 2, 3, 4]);
+/// assert_eq!(flips, vec![3, 4, 2, 3, 2]);
+/// ```
+pub fn pancake_sort<T: Ord>(v: &mut [T]) -> Vec<usize> {
+    let mut flips = Vec::new();
+
+    for size in (2..=v.len()).rev() {
+        // 找到未排序部分中最大元素的下标
+        let max = (0..size).max_by(|&a, &b| v[a].cmp(&v[b])).unwrap_or(0);
+        if max == size - 1 {
+            continue;
+        }
+        // 先将最大元素翻到最前面
+        if max != 0 {
+            v[..=max].reverse();
+            flips.push(max + 1);
+        }
+        // 再将其翻到未排序部分的末尾
+        v[..size].reverse();
+        flips.push(size);
+    }
+
+    flips
+}
+
+/// 字符串 MSD 基数排序，时间复杂度：O(n + 总比较字节数)，不稳定
+///
+/// 从首个字节开始，按当前字节将字符串分入 257 个桶（桶 0 存放在此处结束的字符串），
+/// 再对每个桶按下一个字节继续分桶。分桶采用原地置换（American flag sort），不需要额外的缓冲区。
+/// 长公共前缀较少时效率很高；公共前缀很长时每层都要扫描 257 个桶，常数开销较大。
+///
+/// # Examples
+///
+/// ```rust
+/// use base::sort;
+///
+/// let mut v = ["she", "sells", "sea", "shells", "by", "the", "sea"];
+/// sort::msd_radix_sort(&mut v);
+///
+/// assert_eq!(v, ["by", "sea", "sea", "sells", "she", "shells", "the"]);
+/// ```
+pub fn msd_radix_sort<T: AsRef<[u8]>>(v: &mut [T]) {
+    // 待处理的区间 (lo, hi, depth)，区间内的字符串前 depth 个字节相同。
+    // 使用显式栈代替递归，避免公共前缀很长时栈溢出
+    let mut stack = vec![(0, v.len(), 0)];
+
+    while let Some((lo, hi, depth)) = stack.pop() {
+        let v = &mut v[lo..hi];
+        if v.len() <= INSERTION_SORT_THRESHOLD {
+            string_insertion_sort(v, depth);
+            continue;
+        }
+
+        // 统计每个桶的大小，并计算每个桶的起始位置
+        let mut counts = [0; 257];
+        for s in v.iter() {
+            counts[byte_at(s, depth)] += 1;
+        }
+        let mut starts = [0; 257];
+        for b in 1..257 {
+            starts[b] = starts[b - 1] + counts[b - 1];
+        }
+
+        // 原地置换：把每个桶中不属于它的元素交换到所属桶的下一个空位
+        let mut next = starts;
+        for b in 0..257 {
+            while next[b] < starts[b] + counts[b] {
+                let target = byte_at(&v[next[b]], depth);
+                if target == b {
+                    next[b] += 1;
+                } else {
+                    v.swap(next[b], next[target]);
+                    next[target] += 1;
+                }
+            }
+        }
+
+        // 桶 0 中的字符串都已结束，彼此相等，无需继续处理
+        for b in 1..257 {
+            if counts[b] > 1 {
+                stack.push((lo + starts[b], lo + starts[b] + counts[b], depth + 1));
+            }
+        }
+    }
+}
+
+/// 三路字符串快速排序，时间复杂度：O(nlogn + 总比较字节数)，不稳定
+///
+/// 按当前字节的基准值将字符串三路划分：小于和大于的部分继续比较当前字节，
+/// 等于的部分才比较下一个字节。与 MSD 基数排序相比不需要遍历 257 个桶，
+/// 对于公共前缀很长的字符串（如后缀排序）更有优势。
+///
+/// # Examples
+///
+/// ```rust
+/// use base::sort;
+///
+/// let mut v = vec![String::from("banana"), String::from("apple"), String::from("band")];
+/// sort::three_way_string_quicksort(&mut v);
+///
+/// assert_eq!(v, ["apple", "banana", "band"]);
+/// ```
+pub fn three_way_string_quicksort<T: AsRef<[u8]>>(v: &mut [T]) {
+    // 与 MSD 基数排序相同，使用显式栈代替递归
+    let mut stack = vec![(0, v.len(), 0)];
+
+    while let Some((lo, hi, depth)) = stack.pop() {
+        let v = &mut v[lo..hi];
+        let len = v.len();
+        if len <= INSERTION_SORT_THRESHOLD {
+            string_insertion_sort(v, depth);
+            continue;
+        }
+
+        // 以首、中、尾三个字节的中位数作为基准值
+        let (a, b, c) = (
+            byte_at(&v[0], depth),
+            byte_at(&v[len / 2], depth),
+            byte_at(&v[len - 1], depth),
+        );
+        let pivot = a.max(b).min(a.min(b).max(c));
+
+        // 荷兰国旗问题
+        let (mut lt, mut i, mut gt) = (0, 0, len);
+        while i < gt {
+            match byte_at(&v[i], depth).cmp(&pivot) {
+                Ordering::Less => {
+                    v.swap(lt, i);
+                    lt += 1;
+                    i += 1;
+                }
+                Ordering::Greater => {
+                    gt -= 1;
+                    v.swap(i, gt);
+                }
+                Ordering::Equal => i += 1,
+            }
+        }
+
+        if lt > 1 {
+            stack.push((lo, lo + lt, depth));
+        }
+        if len - gt > 1 {
+            stack.push((lo + gt, hi, depth));
+        }
+        // 基准值为 0 时，等于部分的字符串都已结束，彼此相等
+        if pivot != 0 && gt - lt > 1 {
+            stack.push((lo + lt, lo + gt, depth + 1));
+        }
+    }
+}
+
+/// 字符串第 depth 个字节加 1，字符串已结束时返回 0，使较短的字符串排在前面
+fn byte_at<T: AsRef<[u8]>>(s: &T, depth: usize) -> usize {
+    s.as_ref().get(depth).map_or(0, |&b| b as usize + 1)
+}
+
+/// 字符串插入排序，假设所有字符串的前 depth 个字节相同
+fn string_insertion_sort<T: AsRef<[u8]>>(v: &mut [T], depth: usize) {
+    for i in 1..v.len() {
+        let mut j = i;
+        while j > 0 && v[j].as_ref()[depth..] < v[j - 1].as_ref()[depth..] {
+            v.swap(j, j - 1);
+            j -= 1;
+        }
+    }
+}
+
+/// n 的二进制位数，即 floor(log2(n)) + 1，n 为 0 时返回 0
+fn log2(n: usize) -> usize {
+    (usize::BITS - n.leading_zeros()) as usize
+}
+
+/// 快速排序的基本情形，对长度不超过 INSERTION_SORT_THRESHOLD 的区间排序
+fn small_sort<T, F>(v: &mut [T], is_less: &mut F)
+where
+    F: FnMut(&T, &T) -> bool,
+{
+    if cfg!(feature = "sorting-network") {
+        network::sort_by(v, is_less);
+    } else {
+        insertion_sort(v, is_less);
+    }
+}
+
+/// 插入排序，时间复杂度：O(n²)，稳定
+fn insertion_sort<T, F>(v: &mut [T], is_less: &mut F)
+where
+    F: FnMut(&T, &T) -> bool,
+{
+    for i in 1..v.len() {
+        shift_tail(&mut v[..=i], is_less);
+    }
+}
+
+/// 将末尾元素向左移动到正确位置，假设其余元素已有序
+fn shift_tail<T, F>(v: &mut [T], is_less: &mut F)
+where
+    F: FnMut(&T, &T) -> bool,
+{
+    let mut j = v.len();
+    while j > 1 && is_less(&v[j - 1], &v[j - 2]) {
+        v.swap(j - 1, j - 2);
+        j -= 1;
+    }
+}
+
+/// 将首元素向右移动到正确位置，假设其余元素已有序
+fn shift_head<T, F>(v: &mut [T], is_less: &mut F)
+where
+    F: FnMut(&T, &T) -> bool,
+{
+    let mut j = 0;
+    while j + 1 < v.len() && is_less(&v[j + 1], &v[j]) {
+        v.swap(j, j + 1);
+        j += 1;
+    }
+}
+
+/// 堆排序，时间复杂度：O(nlogn)，不稳定
+fn heap_sort<T, F>(v: &mut [T], is_less: &mut F)
+where
+    F: FnMut(&T, &T) -> bool,
+{
+    // 从顶至底堆化
+    let mut sift_down = |v: &mut [T], mut i: usize| {
+        loop {
+            let (l, r) = (2 * i + 1, 2 * i + 2);
+            let mut max = i;
+            if l < v.len() && is_less(&v[max], &v[l]) {
+                max = l;
+            }
+            if r < v.len() && is_less(&v[max], &v[r]) {
+                max = r;
+            }
+            if max == i {
+                break;
+            }
+            v.swap(i, max);
+            i = max;
+        }
+    };
+
+    // 建立大顶堆
+    for i in (0..v.len() / 2).rev() {
+        sift_down(v, i);
+    }
+    // 依次将堆顶元素交换到末尾
+    for end in (1..v.len()).rev() {
+        v.swap(0, end);
+        sift_down(&mut v[..end], 0);
+    }
+}
+
+/// 三数取中选择基准值的下标，长区间使用“九数取中”
+fn choose_pivot<T, F>(v: &[T], is_less: &mut F) -> usize
+where
+    F: FnMut(&T, &T) -> bool,
+{
+    let len = v.len();
+    let (a, b, c) = (len / 4, len / 2, len / 4 * 3);
+
+    let mut median_of_three = |a: usize, b: usize, c: usize| {
+        let (a, b) = if is_less(&v[b], &v[a]) {
+            (b, a)
+        } else {
+            (a, b)
+        };
+        if is_less(&v[c], &v[a]) {
+            a
+        } else if is_less(&v[c], &v[b]) {
+            c
+        } else {
+            b
+        }
+    };
+
+    if len >= 128 {
+        let a = median_of_three(a - 1, a, a + 1);
+        let b = median_of_three(b - 1, b, b + 1);
+        let c = median_of_three(c - 1, c, c + 1);
+        median_of_three(a, b, c)
+    } else {
+        median_of_three(a, b, c)
+    }
+}
+
+/// 以 v[pivot] 为基准划分区间，返回基准值最终所在的下标，以及划分前区间是否已经满足划分要求
+///
+/// 划分完成后，基准值左侧的元素均小于基准值，右侧的元素均不小于基准值
+fn partition<T, F>(v: &mut [T], pivot: usize, is_less: &mut F) -> (usize, bool)
+where
+    F: FnMut(&T, &T) -> bool,
+{
+    // 将基准值交换到首位
+    v.swap(0, pivot);
+    let (head, rest) = v.split_at_mut(1);
+    let pivot = &head[0];
+
+    let (mut l, mut r) = (0, rest.len());
+    while l < r && is_less(&rest[l], pivot) {
+        l += 1;
+    }
+    while l < r && !is_less(&rest[r - 1], pivot) {
+        r -= 1;
+    }
+    let was_partitioned = l >= r;
+
+    // 哨兵划分：左右指针相向而行，交换两侧不满足条件的元素
+    loop {
+        while l < r && is_less(&rest[l], pivot) {
+            l += 1;
+        }
+        while l < r && !is_less(&rest[r - 1], pivot) {
+            r -= 1;
+        }
+        if l >= r {
+            break;
+        }
+        r -= 1;
+        rest.swap(l, r);
+        l += 1;
+    }
+
+    // 将基准值交换到两部分的分界处
+    v.swap(0, l);
+    (l, was_partitioned)
+}
+
+/// 将与基准值相等的元素全部移到区间左侧，返回这部分元素的数量
+///
+/// 要求区间中不存在小于基准值的元素
+fn partition_equal<T, F>(v: &mut [T], pivot: usize, is_less: &mut F) -> usize
+where
+    F: FnMut(&T, &T) -> bool,
+{
+    v.swap(0, pivot);
+    let (head, rest) = v.split_at_mut(1);
+    let pivot = &head[0];
+
+    let (mut l, mut r) = (0, rest.len());
+    loop {
+        while l < r && !is_less(pivot, &rest[l]) {
+            l += 1;
+        }
+        while l < r && is_less(pivot, &rest[r - 1]) {
+            r -= 1;
+        }
+        if l >= r {
+            break;
+        }
+        r -= 1;
+        rest.swap(l, r);
+        l += 1;
+    }
+
+    // 加上位于首位的基准值
+    l + 1
+}
+
+/// 尝试通过少量的相邻交换完成排序，成功返回 true
+fn partial_insertion_sort<T, F>(v: &mut [T], is_less: &mut F) -> bool
+where
+    F: FnMut(&T, &T) -> bool,
+{
+    // 最多修正的逆序对数量
+    const MAX_STEPS: usize = 5;
+    // 区间过短时直接交给快速排序，不尝试修正
+    const SHORTEST_SHIFTING: usize = 50;
+
+    let len = v.len();
+    let mut i = 1;
+
+    for _ in 0..MAX_STEPS {
+        // 找到下一对逆序的相邻元素
+        while i < len && !is_less(&v[i], &v[i - 1]) {
+            i += 1;
+        }
+        if i == len {
+            return true;
+        }
+        if len < SHORTEST_SHIFTING {
+            return false;
+        }
+
+        // 交换这对元素，并将它们分别移动到正确的位置
+        v.swap(i - 1, i);
+        shift_tail(&mut v[..i], is_less);
+        shift_head(&mut v[i..], is_less);
+    }
+
+    false
+}
+
+/// 用伪随机数交换区间中部的几个元素，破坏可能导致划分不均衡的模式
+fn break_patterns<T>(v: &mut [T]) {
+    let len = v.len();
+    // 以长度为种子保证结果可复现
+    let mut rng = Xorshift64::new(len as u64 | 1);
+
+    let pos = len / 4 * 2;
+    for i in 0..3 {
+        let other = rng.below(len as u64) as usize;
+        v.swap(pos - 1 + i, other);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use super::*;
+    use crate::generate;
+
+    /// 生成可复现的伪随机数组
+    fn random_vec(len: usize, modulo: u64) -> Vec<u64> {
+        generate::few_distinct(len, modulo, 0)
+    }
+
+    fn check_sort(sort: fn(&mut [u64])) {
+        let mut inputs = [
+            vec![],
+            vec![1],
+            random_vec(10, 100),
+            random_vec(1000, 1_000_000),
+            random_vec(1000, 3),
+            (0..1000).collect(),
+            (0..1000).rev().collect(),
+            generate::organ_pipe(1000),
+        ];
+
+        for input in inputs.iter_mut() {
+            let mut expected = input.clone();
+            expected.sort();
+            sort(input);
+            assert_eq!(input, &expected);
+        }
+    }
+
+    type SortBy = fn(&mut [u64], &mut dyn FnMut(&u64, &u64) -> Ordering);
+
+    /// 统计排序过程中的比较次数
+    fn count_comparisons(sort: SortBy, v: &[u64]) -> usize {
+        let count = Cell::new(0);
+        let mut v = v.to_vec();
+
+        sort(&mut v, &mut |a, b| {
+            count.set(count.get() + 1);
+            a.cmp(b)
+        });
+        assert!(v.is_sorted());
+
+        count.get()
+    }
+
+    #[test]
+    fn quick_sort_should_work() {
+        check_sort(quick_sort);
+        check_sort(|v| quick_sort_randomized(v, &mut crate::random::SplitMix64::new(1)));
+    }
+
+    #[test]
+    fn intro_sort_should_work() {
+        check_sort(intro_sort);
+    }
+
+    #[test]
+    fn pdq_sort_should_work() {
+        check_sort(pdq_sort);
+    }
+
+    #[test]
+    fn merge_sort_should_work() {
+        check_sort(merge_sort);
+        check_sort(natural_merge_sort);
+    }
+
+    #[test]
+    fn merge_sort_should_be_stable() {
+        let pairs = random_vec(500, 10)
+            .into_iter()
+            .enumerate()
+            .map(|(i, key)| (key, i))
+            .collect::<Vec<_>>();
+        let mut expected = pairs.clone();
+        expected.sort_by_key(|p| p.0);
+
+        let mut v = pairs.clone();
+        merge_sort_by(&mut v, |a, b| a.0.cmp(&b.0));
+        assert_eq!(v, expected);
+
+        let mut v = pairs.clone();
+        natural_merge_sort_by(&mut v, |a, b| a.0.cmp(&b.0));
+        assert_eq!(v, expected);
+    }
+
+    #[test]
+    fn natural_merge_sort_should_exploit_runs() {
+        let n = 1000;
+        let sorted = (0..n as u64).collect::<Vec<_>>();
+        let reversed = (0..n as u64).rev().collect::<Vec<_>>();
+
+        // 整体有序或严格递减时，只需一次扫描
+        assert_eq!(
+            count_comparisons(|v, f| natural_merge_sort_by(v, f), &sorted),
+            n - 1
+        );
+        assert_eq!(
+            count_comparisons(|v, f| natural_merge_sort_by(v, f), &reversed),
+            n - 1
+        );
+
+        // 两个有序段只需一次归并
+        let two_runs = (0..n as u64 / 2).chain(0..n as u64 / 2).collect::<Vec<_>>();
+        assert!(count_comparisons(|v, f| natural_merge_sort_by(v, f), &two_runs) < 2 * n);
+        assert!(count_comparisons(|v, f| merge_sort_by(v, f), &two_runs) > 4 * n);
+    }
+
+    #[test]
+    fn cycle_sort_should_work() {
+        check_sort(|v| {
+            cycle_sort(v);
+        });
+
+        // 写入次数等于不在正确位置上的元素数量
+        let mut v = [5, 1, 2, 3, 4, 6];
+        assert_eq!(cycle_sort(&mut v), 5);
+        let mut v = [1, 2, 2, 3];
+        assert_eq!(cycle_sort(&mut v), 0);
+        let mut v = [2, 1, 2, 1];
+        assert_eq!(cycle_sort(&mut v), 2);
+        assert_eq!(v, [1, 1, 2, 2]);
+    }
+
+    #[test]
+    fn pancake_sort_should_work() {
+        check_sort(|v| {
+            pancake_sort(v);
+        });
+
+        // 按返回的翻转序列重放，应得到相同的结果
+        let original = random_vec(100, 50);
+        let mut v = original.clone();
+        let flips = pancake_sort(&mut v);
+        let mut replay = original.clone();
+        for k in flips.iter() {
+            replay[..*k].reverse();
+        }
+        assert_eq!(replay, v);
+        assert!(flips.len() <= 2 * original.len());
+        assert!(pancake_sort(&mut [1, 2, 3]).is_empty());
+    }
+
+    /// 生成共享前缀长度不同的字符串
+    fn random_strings(len: usize, prefix: &str) -> Vec<String> {
+        random_vec(len, 1 << 20)
+            .into_iter()
+            .map(|x| {
+                let suffix = (0..x % 6).map(|i| (b'a' + (x >> (i * 3)) as u8 % 4) as char);
+                prefix
+                    .chars()
+                    .take(x as usize % (prefix.len() + 1))
+                    .chain(suffix)
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn string_sorts_should_work() {
+        let sorts: [fn(&mut [String]); 2] = [msd_radix_sort, three_way_string_quicksort];
+
+        for sort in sorts {
+            for prefix in ["", "prefix", "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa"] {
+                let mut v = random_strings(2000, prefix);
+                let mut expected = v.clone();
+                expected.sort();
+                sort(&mut v);
+                assert_eq!(v, expected);
+            }
+
+            let mut v = vec![String::new(), String::from("a"), String::new()];
+            sort(&mut v);
+            assert_eq!(v, ["", "", "a"]);
+        }
+    }
+
+    #[test]
+    fn adversarial_input_should_not_go_quadratic() {
+        let n = 1000;
+        let sorted = (0..n as u64).collect::<Vec<_>>();
+
+        // 对有序数组，朴素快速排序每次都只能划分出一个元素，比较次数为 n(n-1)/2
+        let naive = count_comparisons(|v, f| quick_sort_by(v, f), &sorted);
+        assert_eq!(naive, n * (n - 1) / 2);
+
+        // 内省排序和 pdqsort 的比较次数均为 O(nlogn)
+        let bound = 4 * n * log2(n);
+        assert!(count_comparisons(|v, f| intro_sort_by(v, f), &sorted) < bound);
+        assert!(count_comparisons(|v, f| pdq_sort_by(v, f), &sorted) < bound);
+
+        // pdqsort 能识别有序输入和大量重复元素，比较次数接近线性
+        assert!(count_comparisons(|v, f| pdq_sort_by(v, f), &sorted) < 4 * n);
+        let duplicated = random_vec(n, 2);
+        assert!(count_comparisons(|v, f| pdq_sort_by(v, f), &duplicated) < 8 * n);
+
+        // 内省排序的深度限制保证即使划分持续不均衡，最终也会退回堆排序
+        let organ_pipe = (0..n as u64 / 2)
+            .chain((0..n as u64 / 2).rev())
+            .collect::<Vec<_>>();
+        assert!(count_comparisons(|v, f| intro_sort_by(v, f), &organ_pipe) < bound);
+    }
+}
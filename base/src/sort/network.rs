@@ -0,0 +1,227 @@
+//! 排序网络（sorting network）
+//!
+//! 排序网络由一组固定的比较器 (i, j)（i < j）组成：依次对每个比较器，若 v[j] < v[i] 则交换二者。
+//! 比较的位置与输入无关，没有数据相关的分支，适合对长度固定的小数组排序，
+//! 也可以作为快速排序的基本情形代替插入排序。
+//!
+//! 下表中 n <= 12 的网络比较器数量已被证明最少，n = 13..=16 为目前已知最少。
+//! 根据 0-1 原理，一个网络能对所有 0/1 序列排序，就能对任意序列排序，测试即据此穷举验证。
+//!
+//! 注意：排序网络不稳定，因此不能作为归并排序的基本情形。
+
+/// 可以使用排序网络的最大长度
+pub const MAX_LEN: usize = 16;
+
+// 以下比较器表由脚本生成：n = 15 的网络由 n = 16 的网络删去所有涉及下标 15 的比较器得到
+
+#[rustfmt::skip]
+const NETWORK_2: [(usize, usize); 1] = [
+    (0, 1),
+];
+
+#[rustfmt::skip]
+const NETWORK_3: [(usize, usize); 3] = [
+    (0, 2), (0, 1), (1, 2),
+];
+
+#[rustfmt::skip]
+const NETWORK_4: [(usize, usize); 5] = [
+    (0, 2), (1, 3), (0, 1), (2, 3), (1, 2),
+];
+
+#[rustfmt::skip]
+const NETWORK_5: [(usize, usize); 9] = [
+    (0, 3), (1, 4), (0, 2), (1, 3), (0, 1), (2, 4), (1, 2), (3, 4),
+    (2, 3),
+];
+
+#[rustfmt::skip]
+const NETWORK_6: [(usize, usize); 12] = [
+    (0, 5), (1, 3), (2, 4), (1, 2), (3, 4), (0, 3), (2, 5), (0, 1),
+    (2, 3), (4, 5), (1, 2), (3, 4),
+];
+
+#[rustfmt::skip]
+const NETWORK_7: [(usize, usize); 16] = [
+    (0, 6), (2, 3), (4, 5), (0, 2), (1, 4), (3, 6), (0, 1), (2, 5),
+    (3, 4), (1, 2), (4, 6), (2, 3), (4, 5), (1, 2), (3, 4), (5, 6),
+];
+
+#[rustfmt::skip]
+const NETWORK_8: [(usize, usize); 19] = [
+    (0, 2), (1, 3), (4, 6), (5, 7), (0, 4), (1, 5), (2, 6), (3, 7),
+    (0, 1), (2, 3), (4, 5), (6, 7), (2, 4), (3, 5), (1, 4), (3, 6),
+    (1, 2), (3, 4), (5, 6),
+];
+
+#[rustfmt::skip]
+const NETWORK_9: [(usize, usize); 25] = [
+    (0, 3), (1, 7), (2, 5), (4, 8), (0, 7), (2, 4), (3, 8), (5, 6),
+    (0, 2), (1, 3), (4, 5), (7, 8), (1, 4), (3, 6), (5, 7), (0, 1),
+    (2, 4), (3, 5), (6, 8), (2, 3), (4, 5), (6, 7), (1, 2), (3, 4),
+    (5, 6),
+];
+
+#[rustfmt::skip]
+const NETWORK_10: [(usize, usize); 29] = [
+    (0, 8), (1, 9), (2, 7), (3, 5), (4, 6), (0, 2), (1, 4), (5, 8),
+    (7, 9), (0, 3), (2, 4), (5, 7), (6, 9), (0, 1), (3, 6), (8, 9),
+    (1, 5), (2, 3), (4, 8), (6, 7), (1, 2), (3, 5), (4, 6), (7, 8),
+    (2, 3), (4, 5), (6, 7), (3, 4), (5, 6),
+];
+
+#[rustfmt::skip]
+const NETWORK_11: [(usize, usize); 35] = [
+    (0, 9), (1, 6), (2, 4), (3, 7), (5, 8), (0, 1), (3, 5), (4, 10),
+    (6, 9), (7, 8), (1, 3), (2, 5), (4, 7), (8, 10), (0, 4), (1, 2),
+    (3, 7), (5, 9), (6, 8), (0, 1), (2, 6), (4, 5), (7, 8), (9, 10),
+    (2, 4), (3, 6), (5, 7), (8, 9), (1, 2), (3, 4), (5, 6), (7, 8),
+    (2, 3), (4, 5), (6, 7),
+];
+
+#[rustfmt::skip]
+const NETWORK_12: [(usize, usize); 39] = [
+    (0, 8), (1, 7), (2, 6), (3, 11), (4, 10), (5, 9), (0, 1), (2, 5),
+    (3, 4), (6, 9), (7, 8), (10, 11), (0, 2), (1, 6), (5, 10), (9, 11),
+    (0, 3), (1, 2), (4, 6), (5, 7), (8, 11), (9, 10), (1, 4), (3, 5),
+    (6, 8), (7, 10), (1, 3), (2, 5), (6, 9), (8, 10), (2, 3), (4, 5),
+    (6, 7), (8, 9), (4, 6), (5, 7), (3, 4), (5, 6), (7, 8),
+];
+
+#[rustfmt::skip]
+const NETWORK_13: [(usize, usize); 45] = [
+    (0, 12), (1, 10), (2, 9), (3, 7), (5, 11), (6, 8), (1, 6), (2, 3),
+    (4, 11), (7, 9), (8, 10), (0, 4), (1, 2), (3, 6), (7, 8), (9, 10),
+    (11, 12), (4, 6), (5, 9), (8, 11), (10, 12), (0, 5), (3, 8), (4, 7),
+    (6, 11), (9, 10), (0, 1), (2, 5), (6, 9), (7, 8), (10, 11), (1, 3),
+    (2, 4), (5, 6), (9, 10), (1, 2), (3, 4), (5, 7), (6, 8), (2, 3),
+    (4, 5), (6, 7), (8, 9), (3, 4), (5, 6),
+];
+
+#[rustfmt::skip]
+const NETWORK_14: [(usize, usize); 51] = [
+    (0, 1), (2, 3), (4, 5), (6, 7), (8, 9), (10, 11), (12, 13), (0, 2),
+    (1, 3), (4, 8), (5, 9), (10, 12), (11, 13), (0, 4), (1, 2), (3, 7),
+    (5, 8), (6, 10), (9, 13), (11, 12), (0, 6), (1, 5), (3, 9), (4, 10),
+    (7, 13), (8, 12), (2, 10), (3, 11), (4, 6), (7, 9), (1, 3), (2, 8),
+    (5, 11), (6, 7), (10, 12), (1, 4), (2, 6), (3, 5), (7, 11), (8, 10),
+    (9, 12), (2, 4), (3, 6), (5, 8), (7, 10), (9, 11), (3, 4), (5, 6),
+    (7, 8), (9, 10), (6, 7),
+];
+
+#[rustfmt::skip]
+const NETWORK_15: [(usize, usize); 56] = [
+    (0, 13), (1, 12), (3, 14), (4, 8), (5, 6), (7, 11), (9, 10), (0, 5),
+    (1, 7), (2, 9), (3, 4), (6, 13), (8, 14), (11, 12), (0, 1), (2, 3),
+    (4, 5), (6, 8), (7, 9), (10, 11), (12, 13), (0, 2), (1, 3), (4, 10),
+    (5, 11), (6, 7), (8, 9), (12, 14), (1, 2), (3, 12), (4, 6), (5, 7),
+    (8, 10), (9, 11), (13, 14), (1, 4), (2, 6), (5, 8), (7, 10), (9, 13),
+    (11, 14), (2, 4), (3, 6), (9, 12), (11, 13), (3, 5), (6, 8), (7, 9),
+    (10, 12), (3, 4), (5, 6), (7, 8), (9, 10), (11, 12), (6, 7), (8, 9),
+];
+
+#[rustfmt::skip]
+const NETWORK_16: [(usize, usize); 60] = [
+    (0, 13), (1, 12), (2, 15), (3, 14), (4, 8), (5, 6), (7, 11), (9, 10),
+    (0, 5), (1, 7), (2, 9), (3, 4), (6, 13), (8, 14), (10, 15), (11, 12),
+    (0, 1), (2, 3), (4, 5), (6, 8), (7, 9), (10, 11), (12, 13), (14, 15),
+    (0, 2), (1, 3), (4, 10), (5, 11), (6, 7), (8, 9), (12, 14), (13, 15),
+    (1, 2), (3, 12), (4, 6), (5, 7), (8, 10), (9, 11), (13, 14), (1, 4),
+    (2, 6), (5, 8), (7, 10), (9, 13), (11, 14), (2, 4), (3, 6), (9, 12),
+    (11, 13), (3, 5), (6, 8), (7, 9), (10, 12), (3, 4), (5, 6), (7, 8),
+    (9, 10), (11, 12), (6, 7), (8, 9),
+];
+
+/// 长度为 n 的比较器序列
+fn network(n: usize) -> &'static [(usize, usize)] {
+    match n {
+        0 | 1 => &[],
+        2 => &NETWORK_2,
+        3 => &NETWORK_3,
+        4 => &NETWORK_4,
+        5 => &NETWORK_5,
+        6 => &NETWORK_6,
+        7 => &NETWORK_7,
+        8 => &NETWORK_8,
+        9 => &NETWORK_9,
+        10 => &NETWORK_10,
+        11 => &NETWORK_11,
+        12 => &NETWORK_12,
+        13 => &NETWORK_13,
+        14 => &NETWORK_14,
+        15 => &NETWORK_15,
+        16 => &NETWORK_16,
+        _ => panic!("No sorting network for len: {n}"),
+    }
+}
+
+/// 使用排序网络对长度为 N 的数组排序，N 不能超过 16，不稳定
+///
+/// # Examples
+///
+/// ```rust
+/// use base::sort::network;
+///
+/// let mut v = [5, 2, 9, 1, 5, 6];
+/// network::sort_n(&mut v);
+///
+/// assert_eq!(v, [1, 2, 5, 5, 6, 9]);
+/// ```
+pub fn sort_n<T: Ord, const N: usize>(v: &mut [T; N]) {
+    const { assert!(N <= MAX_LEN, "Sorting network supports at most 16 elements") };
+
+    sort_by(v, &mut |a, b| a < b);
+}
+
+/// 使用排序网络对长度不超过 16 的切片排序
+///
+/// # Panics
+///
+/// Panics if `v.len() > MAX_LEN`.
+pub(super) fn sort_by<T, F>(v: &mut [T], is_less: &mut F)
+where
+    F: FnMut(&T, &T) -> bool,
+{
+    for &(i, j) in network(v.len()) {
+        if is_less(&v[j], &v[i]) {
+            v.swap(i, j);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn networks_should_sort_all_binary_inputs() {
+        // 0-1 原理：穷举长度为 n 的所有 0/1 序列
+        for n in 0..=MAX_LEN {
+            for mask in 0..1u32 << n {
+                let mut v = (0..n).map(|i| mask >> i & 1).collect::<Vec<_>>();
+                sort_by(&mut v, &mut |a, b| a < b);
+                assert!(v.is_sorted(), "len: {n}, mask: {mask:b}");
+            }
+        }
+    }
+
+    #[test]
+    fn sort_n_should_work() {
+        let mut v = [3, 1, 2];
+        sort_n(&mut v);
+        assert_eq!(v, [1, 2, 3]);
+
+        let mut v = [0u8; 0];
+        sort_n(&mut v);
+
+        let mut v: [i32; 16] = std::array::from_fn(|i| (i as i32 * 7) % 16 - 8);
+        sort_n(&mut v);
+        assert_eq!(v, std::array::from_fn(|i| i as i32 - 8));
+    }
+
+    #[test]
+    #[should_panic]
+    fn sort_by_too_long_should_panic() {
+        sort_by(&mut [0; MAX_LEN + 1], &mut |a, b| a < b);
+    }
+}
@@ -39,6 +39,13 @@ impl<T> StackWithList<T> {
     }
 }
 
+/// 数组的最后一个元素位于栈顶
+impl<T, const N: usize> From<[T; N]> for StackWithList<T> {
+    fn from(v: [T; N]) -> Self {
+        StackWithList(LinkedList::from(v))
+    }
+}
+
 impl<T: Clone> StackWithList<T> {
     pub fn to_vec(&self) -> Vec<T> {
         self.iter().cloned().collect()
@@ -73,6 +80,33 @@ impl<T> StackWithVec<T> {
     }
 }
 
+/// 数组的最后一个元素位于栈顶
+impl<T, const N: usize> From<[T; N]> for StackWithVec<T> {
+    fn from(v: [T; N]) -> Self {
+        StackWithVec(Vec::from(v))
+    }
+}
+
+/// 用字面量构建 [`StackWithVec`]，依次入栈，最后一个元素位于栈顶
+///
+/// # Examples
+///
+/// ```rust
+/// use base::stack;
+///
+/// let mut stack = stack![1, 2, 3];
+///
+/// assert_eq!(stack.peek(), Some(&3));
+/// assert_eq!(stack.pop(), Some(3));
+/// assert_eq!(stack.to_vec(), [1, 2]);
+/// ```
+#[macro_export]
+macro_rules! stack {
+    ($($x:expr),* $(,)?) => {
+        $crate::StackWithVec::from([$($x),*])
+    };
+}
+
 impl<T: Clone> StackWithVec<T> {
     pub fn to_vec(&self) -> Vec<T> {
         self.0.clone()
@@ -105,6 +139,23 @@ mod tests {
         assert_eq!(stack.pop(), None);
     }
 
+    #[test]
+    fn stack_from_array_should_match_pushes() {
+        let mut pushed = StackWithList::new();
+        (1..=3).for_each(|i| pushed.push(i));
+
+        let mut from = StackWithList::from([1, 2, 3]);
+        assert_eq!(from.to_vec(), pushed.to_vec());
+        assert_eq!(from.pop(), Some(3));
+
+        let mut stack = stack![1, 2, 3,];
+        assert_eq!(stack.pop(), pushed.pop());
+        assert_eq!(stack.to_vec(), pushed.to_vec());
+
+        let empty: StackWithVec<i32> = stack![];
+        assert!(empty.is_empty());
+    }
+
     #[test]
     fn stack_with_vec_basics() {
         let mut stack = StackWithVec::new();
@@ -0,0 +1,411 @@
+//! 流式统计：只扫描一遍数据、使用常数内存的在线算法
+//!
+//! * [`Quantile`]：P² 算法（Jain & Chlamtac, 1985）估计分位数。只维护 5 个标记，
+//!   分别跟踪最小值、p/2 分位数、p 分位数、(1 + p)/2 分位数和最大值。每个新数据使位于它之后的标记的位置加一，
+//!   标记偏离期望位置超过 1 时，用相邻标记的抛物线插值（必要时退化为线性插值）调整高度并移动一格
+//! * [`MovingAverage`] / [`MovingStd`]：最近 N 个数据的均值和标准差。窗口保存在环形队列
+//!   [`QueueWithArray`] 中，新数据替换最旧的数据时增量更新统计量，每次 O(1)
+//!
+//! 增量更新的浮点误差会随数据量缓慢累积，对精度要求很高时应定期重新计算。
+
+use crate::QueueWithArray;
+
+/// P² 算法的分位数估计器
+///
+/// # Examples
+///
+/// ```rust
+/// use base::Quantile;
+///
+/// let mut median = Quantile::new(0.5);
+/// assert_eq!(median.estimate(), None);
+///
+/// for x in 1..=1001 {
+///     median.push(x as f64);
+/// }
+/// assert!((median.estimate().unwrap() - 501.0).abs() < 1.0);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Quantile {
+    p: f64,
+    count: u64,
+    /// 标记的高度，即对应分位数的估计值；不足 5 个数据时保存全部数据
+    heights: [f64; 5],
+    /// 标记的实际位置（从 0 开始）
+    positions: [i64; 5],
+    /// 标记的期望位置
+    desired: [f64; 5],
+    /// 每个新数据使期望位置增加的量
+    increments: [f64; 5],
+}
+
+impl Quantile {
+    /// 创建估计 p 分位数的估计器
+    ///
+    /// # Panics
+    ///
+    /// Panics if `p` is not in `(0, 1)`.
+    pub fn new(p: f64) -> Self {
+        assert!(p > 0.0 && p < 1.0, "Quantile must be in (0, 1): {p}");
+
+        Self {
+            p,
+            count: 0,
+            heights: [0.0; 5],
+            positions: [0, 1, 2, 3, 4],
+            desired: [0.0, 2.0 * p, 4.0 * p, 2.0 + 2.0 * p, 4.0],
+            increments: [0.0, p / 2.0, p, (1.0 + p) / 2.0, 1.0],
+        }
+    }
+
+    /// 估计的分位数
+    pub fn p(&self) -> f64 {
+        self.p
+    }
+
+    /// 已加入的数据个数
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// 加入一个数据，NaN 无法参与比较，会被忽略，时间复杂度：O(1)
+    pub fn push(&mut self, x: f64) {
+        if x.is_nan() {
+            return;
+        }
+        if self.count < 5 {
+            self.heights[self.count as usize] = x;
+            self.count += 1;
+            if self.count == 5 {
+                self.heights.sort_by(f64::total_cmp);
+            }
+            return;
+        }
+        self.count += 1;
+
+        // 找到 x 所在的区间 [heights[k], heights[k + 1])，超出范围时更新最小值或最大值
+        let h = &mut self.heights;
+        let k = if x < h[0] {
+            h[0] = x;
+            0
+        } else if x >= h[4] {
+            h[4] = x;
+            3
+        } else {
+            // Safety: h[0] <= x < h[4]，一定能找到
+            (0..4).find(|&i| x < h[i + 1]).unwrap()
+        };
+
+        for position in &mut self.positions[k + 1..] {
+            *position += 1;
+        }
+        for (desired, increment) in self.desired.iter_mut().zip(self.increments) {
+            *desired += increment;
+        }
+
+        // 调整中间的 3 个标记
+        for i in 1..4 {
+            let d = self.desired[i] - self.positions[i] as f64;
+            let (n, h) = (&mut self.positions, &mut self.heights);
+            if (d >= 1.0 && n[i + 1] - n[i] > 1) || (d <= -1.0 && n[i - 1] - n[i] < -1) {
+                let d = d.signum() as i64;
+                let parabolic = parabolic(h, n, i, d);
+                h[i] = if h[i - 1] < parabolic && parabolic < h[i + 1] {
+                    parabolic
+                } else {
+                    // 抛物线插值破坏了单调性，改用向 d 方向的线性插值
+                    let j = (i as i64 + d) as usize;
+                    h[i] + d as f64 * (h[j] - h[i]) / (n[j] - n[i]) as f64
+                };
+                n[i] += d;
+            }
+        }
+    }
+
+    /// 分位数的估计值，没有数据时返回 None；不足 5 个数据时返回精确的分位数（取最近的秩）
+    pub fn estimate(&self) -> Option<f64> {
+        match self.count {
+            0 => None,
+            1..5 => {
+                let mut v = self.heights[..self.count as usize].to_vec();
+                v.sort_by(f64::total_cmp);
+                let rank = ((v.len() - 1) as f64 * self.p).round() as usize;
+
+                Some(v[rank])
+            }
+            _ => Some(self.heights[2]),
+        }
+    }
+}
+
+/// 由相邻标记 i - 1、i、i + 1 做抛物线插值，求标记 i 移动 d 格后的高度
+fn parabolic(h: &[f64; 5], n: &[i64; 5], i: usize, d: i64) -> f64 {
+    let (d, n0, n1, n2) = (d as f64, n[i - 1] as f64, n[i] as f64, n[i + 1] as f64);
+
+    h[i] + d / (n2 - n0)
+        * ((n1 - n0 + d) * (h[i + 1] - h[i]) / (n2 - n1)
+            + (n2 - n1 - d) * (h[i] - h[i - 1]) / (n1 - n0))
+}
+
+/// 最近 N 个数据的滑动平均值
+///
+/// # Examples
+///
+/// ```rust
+/// use base::MovingAverage;
+///
+/// let mut avg = MovingAverage::<3>::new();
+/// assert_eq!(avg.mean(), None);
+///
+/// avg.push(1.0);
+/// avg.push(2.0);
+/// assert_eq!(avg.mean(), Some(1.5));
+///
+/// avg.push(3.0);
+/// avg.push(10.0);
+/// // 窗口中为 [2, 3, 10]
+/// assert_eq!(avg.mean(), Some(5.0));
+/// ```
+#[derive(Debug, Clone)]
+pub struct MovingAverage<const N: usize> {
+    window: QueueWithArray<f64, N>,
+    sum: f64,
+}
+
+impl<const N: usize> MovingAverage<N> {
+    /// 创建空的滑动窗口
+    ///
+    /// # Panics
+    ///
+    /// Panics if `N == 0`.
+    pub fn new() -> Self {
+        assert!(N > 0, "Window size must be positive");
+
+        Self {
+            window: QueueWithArray::new(),
+            sum: 0.0,
+        }
+    }
+
+    /// 加入一个数据，窗口已满时移除最旧的数据，时间复杂度：O(1)
+    pub fn push(&mut self, x: f64) {
+        if self.window.len() == N {
+            // Safety: 窗口已满，一定有数据
+            self.sum -= self.window.pop().unwrap();
+        }
+        self.window.push(x);
+        self.sum += x;
+    }
+
+    /// 窗口中数据的平均值，窗口为空时返回 None
+    pub fn mean(&self) -> Option<f64> {
+        (!self.window.is_empty()).then(|| self.sum / self.window.len() as f64)
+    }
+
+    /// 窗口中的数据个数
+    pub fn len(&self) -> usize {
+        self.window.len()
+    }
+
+    /// 判断窗口是否为空
+    pub fn is_empty(&self) -> bool {
+        self.window.is_empty()
+    }
+}
+
+impl<const N: usize> Default for MovingAverage<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 最近 N 个数据的滑动均值和标准差，用 Welford 算法增量更新，
+/// 比维护平方和再相减的做法更不容易损失精度
+///
+/// # Examples
+///
+/// ```rust
+/// use base::MovingStd;
+///
+/// let mut std = MovingStd::<4>::new();
+/// for x in [100.0, 2.0, 4.0, 4.0, 4.0, 5.0] {
+///     std.push(x);
+/// }
+///
+/// // 窗口中为 [4, 4, 4, 5]
+/// assert_eq!(std.mean(), Some(4.25));
+/// assert!((std.std_dev().unwrap() - 0.1875f64.sqrt()).abs() < 1e-12);
+/// ```
+#[derive(Debug, Clone)]
+pub struct MovingStd<const N: usize> {
+    window: QueueWithArray<f64, N>,
+    mean: f64,
+    /// 窗口中数据与均值之差的平方和
+    m2: f64,
+}
+
+impl<const N: usize> MovingStd<N> {
+    /// 创建空的滑动窗口
+    ///
+    /// # Panics
+    ///
+    /// Panics if `N == 0`.
+    pub fn new() -> Self {
+        assert!(N > 0, "Window size must be positive");
+
+        Self {
+            window: QueueWithArray::new(),
+            mean: 0.0,
+            m2: 0.0,
+        }
+    }
+
+    /// 加入一个数据，窗口已满时移除最旧的数据，时间复杂度：O(1)
+    pub fn push(&mut self, x: f64) {
+        if self.window.len() < N {
+            self.window.push(x);
+            let delta = x - self.mean;
+            self.mean += delta / self.window.len() as f64;
+            self.m2 += delta * (x - self.mean);
+            return;
+        }
+
+        // 用 x 替换最旧的数据 old，长度不变
+        // Safety: 窗口已满，一定有数据
+        let old = self.window.pop().unwrap();
+        self.window.push(x);
+        let mean = self.mean + (x - old) / N as f64;
+        self.m2 += (x - old) * (x - mean + old - self.mean);
+        // 抵消舍入误差导致的微小负数
+        self.m2 = self.m2.max(0.0);
+        self.mean = mean;
+    }
+
+    /// 窗口中数据的平均值，窗口为空时返回 None
+    pub fn mean(&self) -> Option<f64> {
+        (!self.window.is_empty()).then_some(self.mean)
+    }
+
+    /// 窗口中数据的总体方差，窗口为空时返回 None
+    pub fn variance(&self) -> Option<f64> {
+        (!self.window.is_empty()).then(|| self.m2 / self.window.len() as f64)
+    }
+
+    /// 窗口中数据的总体标准差，窗口为空时返回 None
+    pub fn std_dev(&self) -> Option<f64> {
+        self.variance().map(f64::sqrt)
+    }
+
+    /// 窗口中的数据个数
+    pub fn len(&self) -> usize {
+        self.window.len()
+    }
+
+    /// 判断窗口是否为空
+    pub fn is_empty(&self) -> bool {
+        self.window.is_empty()
+    }
+}
+
+impl<const N: usize> Default for MovingStd<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generate::Rng;
+
+    /// [0, 1) 内均匀分布的随机数
+    fn unit_floats(len: usize, seed: u64) -> Vec<f64> {
+        let mut rng = Rng::new(seed);
+
+        (0..len)
+            .map(|_| rng.below(1 << 53) as f64 / (1u64 << 53) as f64)
+            .collect()
+    }
+
+    #[test]
+    fn quantile_should_approximate_sorted_rank() {
+        let data = unit_floats(100_000, 3);
+        let mut sorted = data.clone();
+        sorted.sort_by(f64::total_cmp);
+
+        for p in [0.1, 0.5, 0.9, 0.99] {
+            let mut quantile = Quantile::new(p);
+            for &x in &data {
+                quantile.push(x);
+            }
+            let exact = sorted[(p * (sorted.len() - 1) as f64) as usize];
+            assert!(
+                (quantile.estimate().unwrap() - exact).abs() < 0.01,
+                "p = {p}"
+            );
+            assert_eq!(quantile.count(), data.len() as u64);
+        }
+
+        // 有序输入是 P² 算法较难的情形
+        let mut median = Quantile::new(0.5);
+        for x in 0..10_000 {
+            median.push(x as f64);
+        }
+        assert!((median.estimate().unwrap() - 5000.0).abs() < 50.0);
+    }
+
+    #[test]
+    fn quantile_should_be_exact_for_few_samples() {
+        let mut quantile = Quantile::new(0.75);
+        for (x, expected) in [(3.0, 3.0), (1.0, 3.0), (2.0, 3.0), (4.0, 3.0)] {
+            quantile.push(x);
+            assert_eq!(quantile.estimate(), Some(expected));
+        }
+        quantile.push(0.0);
+        assert_eq!(quantile.estimate(), Some(2.0));
+    }
+
+    #[test]
+    fn quantile_should_ignore_nan() {
+        let mut quantile = Quantile::new(0.5);
+        quantile.push(f64::NAN);
+        assert_eq!((quantile.count(), quantile.estimate()), (0, None));
+
+        for x in [5.0, 1.0, f64::NAN, 4.0, 2.0, 3.0, f64::NAN, 6.0] {
+            quantile.push(x);
+        }
+        assert_eq!(quantile.count(), 6);
+        assert!(
+            quantile
+                .estimate()
+                .is_some_and(|q| (1.0..=6.0).contains(&q))
+        );
+    }
+
+    #[test]
+    fn moving_statistics_should_match_naive_window() {
+        const N: usize = 16;
+        let data = unit_floats(5000, 7)
+            .into_iter()
+            .map(|x| 1000.0 + x)
+            .collect::<Vec<_>>();
+        let mut avg = MovingAverage::<N>::new();
+        let mut std = MovingStd::<N>::new();
+
+        for (i, &x) in data.iter().enumerate() {
+            avg.push(x);
+            std.push(x);
+
+            let window = &data[(i + 1).saturating_sub(N)..=i];
+            let mean = window.iter().sum::<f64>() / window.len() as f64;
+            let variance =
+                window.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / window.len() as f64;
+            assert_eq!(avg.len(), window.len());
+            assert!((avg.mean().unwrap() - mean).abs() < 1e-9);
+            assert!((std.mean().unwrap() - mean).abs() < 1e-9);
+            assert!((std.variance().unwrap() - variance).abs() < 1e-9);
+        }
+
+        assert!(MovingStd::<3>::new().std_dev().is_none());
+    }
+}
@@ -0,0 +1,662 @@
+//! 字符串算法：回文问题和常用的字符串工具
+//!
+//! 回文问题可以用不同的技巧解决：
+//! * Manacher 算法：利用已知回文串的对称性，在 O(n) 时间内求出以每个位置为中心的最长回文半径
+//! * 字符串哈希：预处理正反两个方向的多项式哈希，O(1) 判断任意区间是否为回文（存在极小的误判概率）
+//! * 回溯：枚举所有把字符串分割为回文子串的方案
+//!
+//! 回文算法都作用于切片，处理 &str 时可以使用 `as_bytes()`（仅限 ASCII）或先收集为 `Vec<char>`。
+//!
+//! 字符串工具直接处理 &str：最长公共前缀、旋转判断、十进制字符串相乘以及罗马数字的转换。
+
+use std::ops::Range;
+
+use crate::math::fft;
+
+/// 哈希取模使用的梅森素数 2^61 - 1
+const MOD: u64 = (1 << 61) - 1;
+/// 哈希的基数
+const BASE: u64 = 0x1000_0000_01b3;
+
+fn mul_mod(a: u64, b: u64) -> u64 {
+    ((a as u128 * b as u128) % MOD as u128) as u64
+}
+
+/// Manacher 算法求出的回文半径
+struct Radii {
+    /// odd[i]：以 i 为中心的最长奇数长度回文串为 [i - odd[i] + 1, i + odd[i])
+    odd: Vec<usize>,
+    /// even[i]：以 i - 1 和 i 之间为中心的最长偶数长度回文串为 [i - even[i], i + even[i])
+    even: Vec<usize>,
+}
+
+impl Radii {
+    fn new<T: Eq>(s: &[T]) -> Self {
+        let n = s.len();
+        let (mut odd, mut even) = (vec![0; n], vec![0; n]);
+
+        // [l, r) 为目前右端点最靠右的回文串，i 在其中时，关于中心对称的位置的半径可以直接复用
+        let (mut l, mut r) = (0, 0);
+        for i in 0..n {
+            let mut k = if i < r {
+                odd[l + r - 1 - i].min(r - i)
+            } else {
+                1
+            };
+            while i + k < n && i >= k && s[i + k] == s[i - k] {
+                k += 1;
+            }
+            odd[i] = k;
+            if i + k > r {
+                (l, r) = (i + 1 - k, i + k);
+            }
+        }
+
+        let (mut l, mut r) = (0, 0);
+        for i in 0..n {
+            let mut k = if i < r { even[l + r - i].min(r - i) } else { 0 };
+            while i + k < n && i > k && s[i + k] == s[i - k - 1] {
+                k += 1;
+            }
+            even[i] = k;
+            if i + k > r {
+                (l, r) = (i - k, i + k);
+            }
+        }
+
+        Self { odd, even }
+    }
+
+    /// 判断非空区间 [l, r) 是否为回文，时间复杂度：O(1)
+    fn is_palindrome(&self, l: usize, r: usize) -> bool {
+        let (len, center) = (r - l, l + (r - l) / 2);
+
+        if len % 2 == 1 {
+            self.odd[center] > len / 2
+        } else {
+            self.even[center] >= len / 2
+        }
+    }
+}
+
+/// 最长回文子串（Manacher 算法），有多个时返回最靠前的，时间复杂度：O(n)
+///
+/// # Examples
+///
+/// ```rust
+/// use base::strings;
+///
+/// assert_eq!(strings::longest_palindromic_substring(b"babad"), b"bab");
+/// assert_eq!(strings::longest_palindromic_substring(b"cbbd"), b"bb");
+///
+/// let chars = "上海自来水来自海上".chars().collect::<Vec<_>>();
+/// assert_eq!(strings::longest_palindromic_substring(&chars).len(), 9);
+/// ```
+pub fn longest_palindromic_substring<T: Eq>(s: &[T]) -> &[T] {
+    let radii = Radii::new(s);
+
+    let odd = radii
+        .odd
+        .iter()
+        .enumerate()
+        .map(|(i, &k)| (i + 1 - k, 2 * k - 1));
+    let even = radii.even.iter().enumerate().map(|(i, &k)| (i - k, 2 * k));
+    // 长度相同时取起点最小的
+    let (start, len) = odd
+        .chain(even)
+        .max_by(|a, b| a.1.cmp(&b.1).then(b.0.cmp(&a.0)))
+        .unwrap_or((0, 0));
+
+    &s[start..start + len]
+}
+
+/// 把 s 分割为若干回文子串的所有方案（回溯），按字典序枚举切分位置
+///
+/// 借助 Manacher 算法 O(1) 判断区间是否为回文，方案数最多为 2^(n-1)。
+/// 空串只有一种方案，即不包含任何子串。
+///
+/// # Examples
+///
+/// ```rust
+/// use base::strings;
+///
+/// let partitions = strings::palindromic_partitions(b"aab");
+///
+/// assert_eq!(partitions, [vec![&b"a"[..], b"a", b"b"], vec![b"aa", b"b"]]);
+/// ```
+pub fn palindromic_partitions<T: Eq>(s: &[T]) -> Vec<Vec<&[T]>> {
+    let radii = Radii::new(s);
+    let mut partitions = Vec::new();
+
+    backtrack(s, 0, &radii, &mut Vec::new(), &mut partitions);
+
+    partitions
+}
+
+fn backtrack<'a, T>(
+    s: &'a [T],
+    start: usize,
+    radii: &Radii,
+    current: &mut Vec<&'a [T]>,
+    partitions: &mut Vec<Vec<&'a [T]>>,
+) {
+    if start == s.len() {
+        partitions.push(current.clone());
+        return;
+    }
+
+    for end in start + 1..=s.len() {
+        // 剪枝：s[start..end] 不是回文时不必继续
+        if radii.is_palindrome(start, end) {
+            current.push(&s[start..end]);
+            backtrack(s, end, radii, current, partitions);
+            current.pop();
+        }
+    }
+}
+
+/// 基于字符串哈希的回文区间查询
+///
+/// 预处理正序和逆序的前缀哈希，区间正读与反读的哈希值相等即认为是回文。
+/// 不同的串哈希值相同的概率约为 n / 2^61，可以忽略。
+///
+/// # Examples
+///
+/// ```rust
+/// use base::strings::PalindromeHash;
+///
+/// let hash = PalindromeHash::new(b"abacaba");
+///
+/// assert!(hash.is_palindrome_range(0..7));
+/// assert!(hash.is_palindrome_range(2..5));
+/// assert!(!hash.is_palindrome_range(0..2));
+/// ```
+#[derive(Debug, Clone)]
+pub struct PalindromeHash {
+    /// forward[i] 为 s[..i] 的哈希值
+    forward: Vec<u64>,
+    /// backward[i] 为逆序串前 i 个字符的哈希值
+    backward: Vec<u64>,
+    powers: Vec<u64>,
+}
+
+impl PalindromeHash {
+    /// 预处理，时间复杂度：O(n)
+    pub fn new<T: Copy + Into<u64>>(s: &[T]) -> Self {
+        let n = s.len();
+        let prefix = |iter: &mut dyn Iterator<Item = &T>| {
+            let mut hashes = Vec::with_capacity(n + 1);
+            hashes.push(0);
+            for &c in iter {
+                // 加一避免字符 0 与空串的哈希值相同
+                let value = (c.into() % MOD + 1) % MOD;
+                let last = *hashes.last().unwrap();
+                hashes.push((mul_mod(last, BASE) + value) % MOD);
+            }
+            hashes
+        };
+
+        let mut powers = vec![1; n + 1];
+        for i in 1..=n {
+            powers[i] = mul_mod(powers[i - 1], BASE);
+        }
+
+        Self {
+            forward: prefix(&mut s.iter()),
+            backward: prefix(&mut s.iter().rev()),
+            powers,
+        }
+    }
+
+    /// 字符串长度
+    pub fn len(&self) -> usize {
+        self.powers.len() - 1
+    }
+
+    /// 判断字符串是否为空
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// 前缀哈希数组中区间 [l, r) 的哈希值
+    fn range_hash(&self, hashes: &[u64], l: usize, r: usize) -> u64 {
+        (hashes[r] + MOD - mul_mod(hashes[l], self.powers[r - l])) % MOD
+    }
+
+    /// 判断区间 [range.start, range.end) 是否为回文，空区间视为回文，时间复杂度：O(1)
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is out of bounds.
+    pub fn is_palindrome_range(&self, range: Range<usize>) -> bool {
+        let n = self.len();
+        assert!(
+            range.start <= range.end && range.end <= n,
+            "Range {}..{} out of bounds, len: {n}",
+            range.start,
+            range.end
+        );
+
+        // 原串的 [l, r) 对应逆序串的 [n - r, n - l)
+        let (l, r) = (range.start, range.end);
+        self.range_hash(&self.forward, l, r) == self.range_hash(&self.backward, n - r, n - l)
+    }
+}
+
+/// 所有字符串的最长公共前缀，没有字符串时返回空串
+///
+/// 逐字节比较，UTF-8 中多字节字符的首字节与后续字节不同，公共前缀总在字符边界上结束，
+/// 时间复杂度：O(所有字符串的总长度)
+///
+/// # Examples
+///
+/// ```rust
+/// use base::strings;
+///
+/// assert_eq!(strings::longest_common_prefix(&["flower", "flow", "flight"]), "fl");
+/// assert_eq!(strings::longest_common_prefix(&["上海", "上饶"]), "上");
+/// assert_eq!(strings::longest_common_prefix(&["dog", "car"]), "");
+/// ```
+pub fn longest_common_prefix<'a>(strs: &[&'a str]) -> &'a str {
+    let Some((&first, rest)) = strs.split_first() else {
+        return "";
+    };
+
+    let len = rest.iter().fold(first.len(), |len, s| {
+        first
+            .bytes()
+            .zip(s.bytes())
+            .take(len)
+            .take_while(|(a, b)| a == b)
+            .count()
+    });
+    // 公共前缀可能结束在某个字符的中间，例如 "上"（E4 B8 8A）和 "中"（E4 B8 AD）
+    let len = (0..=len)
+        .rev()
+        .find(|&i| first.is_char_boundary(i))
+        .unwrap_or(0);
+
+    &first[..len]
+}
+
+/// 判断 b 是否由 a 旋转得到，即 b = a[i..] + a[..i]
+///
+/// b 是 a 的旋转当且仅当两者长度相同且 b 是 a + a 的子串，时间复杂度：O(n)
+///
+/// # Examples
+///
+/// ```rust
+/// use base::strings;
+///
+/// assert!(strings::is_rotation("waterbottle", "erbottlewat"));
+/// assert!(strings::is_rotation("上海自来水", "来水上海自"));
+/// assert!(!strings::is_rotation("abc", "acb"));
+/// ```
+pub fn is_rotation(a: &str, b: &str) -> bool {
+    a.len() == b.len() && a.repeat(2).contains(b)
+}
+
+/// 两个用十进制字符串表示的非负整数相乘，结果不含前导零
+///
+/// 把数字看作以 10 为自变量的多项式，先求出各位乘积之和（即系数的卷积），最后统一进位。
+/// 卷积由 [`fft::multiply_polynomials`] 计算：较短的数不超过 32 位时是 O(nm) 的竖式乘法，
+/// 否则用数论变换，时间复杂度为 O((n + m)log(n + m))
+///
+/// # Panics
+///
+/// Panics if either number is empty or contains a non-digit character,
+/// or if both numbers are longer than 32 digits and their total length exceeds 2^23.
+///
+/// # Examples
+///
+/// ```rust
+/// use base::strings;
+///
+/// assert_eq!(strings::string_multiply("123", "456"), "56088");
+/// assert_eq!(strings::string_multiply("99999999999999999999", "99"), "9899999999999999999901");
+/// assert_eq!(strings::string_multiply("0", "12345"), "0");
+/// ```
+pub fn string_multiply(a: &str, b: &str) -> String {
+    let digits = |s: &str| {
+        assert!(
+            !s.is_empty() && s.bytes().all(|c| c.is_ascii_digit()),
+            "Invalid number: {s:?}"
+        );
+        s.bytes().map(|c| (c - b'0') as u64).collect::<Vec<_>>()
+    };
+    let (a, b) = (digits(a), digits(b));
+
+    // a[i] * b[j] 累加到结果的第 i + j + 1 位，最高位留给进位。
+    // 每一位最多累加 min(n, m) 个 81，长度受限于变换的最大长度，总小于 NTT 的模数，卷积是精确的
+    let mut product = vec![0];
+    product.extend(fft::multiply_polynomials(&a, &b));
+    // 从低位向高位进位
+    for i in (1..product.len()).rev() {
+        product[i - 1] += product[i] / 10;
+        product[i] %= 10;
+    }
+
+    let start = product
+        .iter()
+        .position(|&d| d != 0)
+        .unwrap_or(product.len() - 1);
+    product[start..]
+        .iter()
+        .map(|&d| char::from(b'0' + d as u8))
+        .collect()
+}
+
+/// 带通配符的字符串匹配：返回 pattern 在 text 中所有匹配的起始位置，
+/// 两者中的 wildcard 字节都可以匹配任意字节
+///
+/// 把字节 c 映射为 c + 1、通配符映射为 0，则位置 i 匹配当且仅当
+/// Σ P[j]·T[i+j]·(P[j] - T[i+j])² = Σ P³T - 2P²T² + PT³ = 0（每一项都非负）。
+/// 三个求和都是反转的 pattern 与 text 的卷积，各用一次多项式乘法求出。
+/// 卷积在模 [`fft::MODULUS`]（约 2^30）意义下计算，而每个位置的和最大约为 2^55，
+/// 构造的输入可以使不匹配的位置的和恰好是模数的倍数，因此模意义下为 0 的位置只是候选，
+/// 还要逐个字节比较确认，结果总是精确的。
+/// 时间复杂度：O((n + m)log(n + m) + km)，k 为候选位置的数量。
+///
+/// # Panics
+///
+/// Panics if `text` and `pattern` are both longer than 32 and their total length exceeds 2^23.
+///
+/// # Examples
+///
+/// ```rust
+/// use base::strings;
+///
+/// assert_eq!(strings::wildcard_matches(b"abcabd", b"ab?", b'?'), [0, 3]);
+/// assert_eq!(strings::wildcard_matches(b"a?cxbc", b"bc", b'?'), [1, 4]);
+/// ```
+pub fn wildcard_matches(text: &[u8], pattern: &[u8], wildcard: u8) -> Vec<usize> {
+    let (n, m) = (text.len(), pattern.len());
+    if m > n {
+        return Vec::new();
+    }
+    if m == 0 {
+        return (0..=n).collect();
+    }
+
+    let encode = |c: &u8| if *c == wildcard { 0 } else { *c as u64 + 1 };
+    let t = text.iter().map(encode).collect::<Vec<_>>();
+    let p = pattern.iter().rev().map(encode).collect::<Vec<_>>();
+    let powers = |v: &[u64], k: u32| v.iter().map(|x| x.pow(k)).collect::<Vec<_>>();
+    let (t2, t3) = (powers(&t, 2), powers(&t, 3));
+    let (p2, p3) = (powers(&p, 2), powers(&p, 3));
+
+    // 卷积的第 m - 1 + i 项是 Σ P[j]^a·T[i+j]^b
+    let p3t = fft::multiply_polynomials(&p3, &t);
+    let p2t2 = fft::multiply_polynomials(&p2, &t2);
+    let pt3 = fft::multiply_polynomials(&p, &t3);
+
+    let matches_at = |i: usize| {
+        text[i..i + m]
+            .iter()
+            .zip(pattern)
+            .all(|(&t, &p)| t == p || t == wildcard || p == wildcard)
+    };
+
+    (0..=n - m)
+        .filter(|&i| {
+            let k = m - 1 + i;
+            (p3t[k] + pt3[k] + 2 * (fft::MODULUS - p2t2[k])).is_multiple_of(fft::MODULUS)
+        })
+        .filter(|&i| matches_at(i))
+        .collect()
+}
+
+/// 罗马数字的符号及其数值，包括减法记法的 6 种组合，按数值从大到小排列
+const ROMAN: [(&str, u32); 13] = [
+    ("M", 1000),
+    ("CM", 900),
+    ("D", 500),
+    ("CD", 400),
+    ("C", 100),
+    ("XC", 90),
+    ("L", 50),
+    ("XL", 40),
+    ("X", 10),
+    ("IX", 9),
+    ("V", 5),
+    ("IV", 4),
+    ("I", 1),
+];
+
+/// 整数转换为罗马数字，只能表示 1 到 3999，超出范围时返回 None
+///
+/// 贪心：每次减去不超过剩余数值的最大符号
+///
+/// # Examples
+///
+/// ```rust
+/// use base::strings;
+///
+/// assert_eq!(strings::to_roman(1994).as_deref(), Some("MCMXCIV"));
+/// assert_eq!(strings::to_roman(0), None);
+/// ```
+pub fn to_roman(mut n: u32) -> Option<String> {
+    if !(1..=3999).contains(&n) {
+        return None;
+    }
+
+    let mut roman = String::new();
+    for (symbol, value) in ROMAN {
+        while n >= value {
+            roman.push_str(symbol);
+            n -= value;
+        }
+    }
+
+    Some(roman)
+}
+
+/// 罗马数字转换为整数，不是规范写法（如 "IIII"、"IC"）时返回 None
+///
+/// 从左到右按符号表贪心匹配，最后把结果转换回罗马数字，与输入相同才是规范写法
+///
+/// # Examples
+///
+/// ```rust
+/// use base::strings;
+///
+/// assert_eq!(strings::from_roman("MCMXCIV"), Some(1994));
+/// assert_eq!(strings::from_roman("IIII"), None);
+/// assert_eq!(strings::from_roman("ABC"), None);
+/// ```
+pub fn from_roman(s: &str) -> Option<u32> {
+    let mut rest = s;
+    let mut n = 0u32;
+    while !rest.is_empty() {
+        let (symbol, value) = ROMAN.iter().find(|(symbol, _)| rest.starts_with(symbol))?;
+        n = n.checked_add(*value)?;
+        rest = &rest[symbol.len()..];
+    }
+
+    (to_roman(n)? == s).then_some(n)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generate;
+
+    fn random_text(len: usize, seed: u64) -> Vec<u8> {
+        generate::few_distinct(len, 2, seed)
+            .into_iter()
+            .map(|x| b'a' + x as u8)
+            .collect()
+    }
+
+    fn is_palindrome(s: &[u8]) -> bool {
+        s.iter().eq(s.iter().rev())
+    }
+
+    #[test]
+    fn palindrome_queries_should_match_brute_force() {
+        for seed in 1..=20 {
+            let s = random_text(30, seed);
+            let radii = Radii::new(&s);
+            let hash = PalindromeHash::new(&s);
+
+            for l in 0..s.len() {
+                for r in l + 1..=s.len() {
+                    let expected = is_palindrome(&s[l..r]);
+                    assert_eq!(radii.is_palindrome(l, r), expected);
+                    assert_eq!(hash.is_palindrome_range(l..r), expected);
+                }
+            }
+            assert!(hash.is_palindrome_range(3..3));
+        }
+    }
+
+    #[test]
+    fn longest_palindromic_substring_should_work() {
+        for seed in 1..=20 {
+            let s = random_text(40, seed);
+            let longest = longest_palindromic_substring(&s);
+
+            let expected = (0..s.len())
+                .flat_map(|l| (l + 1..=s.len()).map(move |r| (l, r)))
+                .filter(|&(l, r)| is_palindrome(&s[l..r]))
+                .map(|(l, r)| r - l)
+                .max();
+            assert_eq!(Some(longest.len()), expected);
+            assert!(is_palindrome(longest));
+        }
+
+        assert_eq!(longest_palindromic_substring(b"abc"), b"a");
+        assert_eq!(longest_palindromic_substring(b"aaaa"), b"aaaa");
+        assert!(longest_palindromic_substring::<u8>(&[]).is_empty());
+    }
+
+    #[test]
+    fn palindromic_partitions_should_work() {
+        let partitions = palindromic_partitions(b"aabaa");
+        // 每个方案都由回文串组成，且拼接后是原串
+        assert!(
+            partitions
+                .iter()
+                .all(|p| p.iter().all(|s| is_palindrome(s)))
+        );
+        assert!(partitions.iter().all(|p| p.concat() == b"aabaa"));
+        assert_eq!(partitions.len(), 6);
+        assert_eq!(partitions.last().unwrap(), &[b"aabaa"]);
+
+        // 所有字符都不同时只有一种方案
+        assert_eq!(palindromic_partitions(b"abcd").len(), 1);
+        assert_eq!(palindromic_partitions::<u8>(&[]), [Vec::<&[u8]>::new()]);
+    }
+
+    #[test]
+    fn palindrome_hash_should_support_chars() {
+        let chars = "上海自来水来自海上".chars().collect::<Vec<_>>();
+        let hash = PalindromeHash::new(&chars);
+
+        assert!(hash.is_palindrome_range(0..9));
+        assert!(!hash.is_palindrome_range(0..8));
+    }
+
+    #[test]
+    fn longest_common_prefix_should_stop_at_char_boundary() {
+        assert_eq!(longest_common_prefix(&[]), "");
+        assert_eq!(longest_common_prefix(&["alone"]), "alone");
+        assert_eq!(longest_common_prefix(&["abc", "abc", ""]), "");
+        assert_eq!(
+            longest_common_prefix(&["interview", "internet", "interval"]),
+            "inter"
+        );
+        // "上" 和 "中" 的前两个字节相同
+        assert_eq!(longest_common_prefix(&["a上", "a中"]), "a");
+    }
+
+    #[test]
+    fn is_rotation_should_match_brute_force() {
+        for seed in 1..=20 {
+            let a = String::from_utf8(random_text(8, seed)).unwrap();
+            let b = String::from_utf8(random_text(8, seed + 100)).unwrap();
+            let expected = (0..a.len()).any(|i| format!("{}{}", &a[i..], &a[..i]) == b);
+            assert_eq!(is_rotation(&a, &b), expected);
+            assert!(is_rotation(&a, &format!("{}{}", &a[3..], &a[..3])));
+        }
+
+        assert!(is_rotation("", ""));
+        assert!(!is_rotation("ab", "aba"));
+    }
+
+    #[test]
+    fn string_multiply_should_match_u128() {
+        let mut rng = generate::Rng::new(13);
+        for _ in 0..200 {
+            let (a, b) = (rng.next_u64(), rng.below(1 << 40));
+            let expected = (a as u128 * b as u128).to_string();
+            assert_eq!(string_multiply(&a.to_string(), &b.to_string()), expected);
+        }
+
+        assert_eq!(string_multiply("000", "0"), "0");
+        assert_eq!(string_multiply("007", "6"), "42");
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid number")]
+    fn string_multiply_should_reject_non_digits() {
+        string_multiply("12a", "3");
+    }
+
+    #[test]
+    fn string_multiply_should_handle_long_numbers() {
+        // (10^n - 1)² = 99...9800...01
+        let n = 1000;
+        let nines = "9".repeat(n);
+        let expected = format!("{}8{}1", "9".repeat(n - 1), "0".repeat(n - 1));
+        assert_eq!(string_multiply(&nines, &nines), expected);
+    }
+
+    #[test]
+    fn wildcard_matches_should_match_brute_force() {
+        let mut rng = generate::Rng::new(37);
+        let symbols = b"ab?";
+        for (n, m) in [(10usize, 3), (200, 5), (300, 40), (50, 50), (5, 8)] {
+            let mut random = |len| {
+                (0..len)
+                    .map(|_| symbols[rng.below(3) as usize])
+                    .collect::<Vec<_>>()
+            };
+            let (text, pattern) = (random(n), random(m));
+
+            let expected = (0..(n + 1).saturating_sub(m))
+                .filter(|&i| {
+                    text[i..i + m]
+                        .iter()
+                        .zip(&pattern)
+                        .all(|(&t, &p)| t == p || t == b'?' || p == b'?')
+                })
+                .collect::<Vec<_>>();
+            assert_eq!(wildcard_matches(&text, &pattern, b'?'), expected);
+        }
+
+        // 这组输入的和恰好是 2 * fft::MODULUS，只靠卷积会误判为匹配
+        let (text, pattern) = ([245, 247, 254, 250], [131, 135, 57, 96]);
+        assert!(wildcard_matches(&text, &pattern, b'?').is_empty());
+
+        assert_eq!(wildcard_matches(b"abc", b"", b'?'), [0, 1, 2, 3]);
+        assert_eq!(
+            wildcard_matches("上海".as_bytes(), "海".as_bytes(), b'?'),
+            [3]
+        );
+    }
+
+    #[test]
+    fn roman_conversions_should_round_trip() {
+        for n in 1..=3999 {
+            let roman = to_roman(n).unwrap();
+            assert_eq!(from_roman(&roman), Some(n));
+        }
+
+        assert_eq!(to_roman(4000), None);
+        assert_eq!(to_roman(3888).as_deref(), Some("MMMDCCCLXXXVIII"));
+        for invalid in ["", "IIII", "VV", "IC", "MMMM", "XM", "iv"] {
+            assert_eq!(from_roman(invalid), None, "{invalid}");
+        }
+    }
+}
@@ -0,0 +1,186 @@
+//! 欧拉序（Euler tour）+ 线段树实现子树查询
+//!
+//! 对树做一次 DFS，记录每个节点的进入时间 tin 和离开时间 tout，
+//! 则节点 v 的子树恰好对应 DFS 序中的连续区间 [tin[v], tout[v])。
+//! 这样就把“子树上的操作”转化成了“数组区间上的操作”，可以直接交给线段树处理：
+//! * 修改节点的值：线段树单点修改，O(logn)
+//! * 查询子树聚合值：线段树区间查询，O(logn)
+
+use std::ops::Range;
+
+use crate::SegmentTree;
+
+/// 基于欧拉序展开的子树聚合查询结构，节点编号为 0..n
+#[derive(Debug, Clone)]
+pub struct SubtreeQuery<T> {
+    tin: Vec<usize>,
+    tout: Vec<usize>,
+    tree: SegmentTree<T>,
+}
+
+impl<T: Clone> SubtreeQuery<T> {
+    /// 从以 root 为根的多叉树构建，children[v] 为节点 v 的所有子节点，values[v] 为节点 v 的值
+    ///
+    /// # Panics
+    ///
+    /// Panics if `values.len() != children.len()`, or some nodes are unreachable from `root`.
+    pub fn from_children(
+        root: usize,
+        children: &[Vec<usize>],
+        values: &[T],
+        identity: T,
+        op: fn(&T, &T) -> T,
+    ) -> Self {
+        let n = children.len();
+        assert_eq!(values.len(), n, "Each node should have exactly one value");
+
+        let (mut tin, mut tout) = (vec![usize::MAX; n], vec![0; n]);
+        let mut order = Vec::with_capacity(n);
+
+        // 使用显式栈模拟 DFS，避免深树导致的递归栈溢出
+        // 栈中元素为 (节点, 下一个待访问的子节点下标)
+        let mut stack = vec![(root, 0)];
+        tin[root] = order.len();
+        order.push(root);
+        while let Some((v, i)) = stack.last_mut() {
+            let v = *v;
+            match children[v].get(*i) {
+                Some(&c) => {
+                    *i += 1;
+                    tin[c] = order.len();
+                    order.push(c);
+                    stack.push((c, 0));
+                }
+                None => {
+                    tout[v] = order.len();
+                    stack.pop();
+                }
+            }
+        }
+        assert_eq!(order.len(), n, "All nodes should be reachable from root");
+
+        // 按 DFS 序排列节点的值
+        let flattened = order.iter().map(|&v| values[v].clone()).collect();
+
+        Self {
+            tin,
+            tout,
+            tree: SegmentTree::new(flattened, identity, op),
+        }
+    }
+
+    /// 从父节点数组构建，parents[v] 为节点 v 的父节点，根节点为 None
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is not exactly one root.
+    pub fn from_parents(
+        parents: &[Option<usize>],
+        values: &[T],
+        identity: T,
+        op: fn(&T, &T) -> T,
+    ) -> Self {
+        let mut children = vec![Vec::new(); parents.len()];
+        let mut roots = Vec::new();
+
+        for (v, parent) in parents.iter().enumerate() {
+            match *parent {
+                Some(p) => children[p].push(v),
+                None => roots.push(v),
+            }
+        }
+        assert_eq!(roots.len(), 1, "Tree should have exactly one root");
+
+        Self::from_children(roots[0], &children, values, identity, op)
+    }
+
+    /// 查询以 v 为根的子树的聚合值
+    pub fn subtree(&self, v: usize) -> T {
+        self.tree.query(self.range(v))
+    }
+
+    /// 将节点 v 的值修改为 val
+    pub fn set(&mut self, v: usize, val: T) {
+        self.tree.set(self.tin[v], val);
+    }
+
+    /// 将节点 v 的值与 val 合并
+    pub fn update(&mut self, v: usize, val: T) {
+        self.tree.update(self.tin[v], val);
+    }
+}
+
+impl<T> SubtreeQuery<T> {
+    /// 获取节点 v 的值
+    pub fn get(&self, v: usize) -> Option<&T> {
+        self.tin.get(v).and_then(|&i| self.tree.get(i))
+    }
+
+    /// 节点 v 的子树在 DFS 序中对应的区间
+    pub fn range(&self, v: usize) -> Range<usize> {
+        self.tin[v]..self.tout[v]
+    }
+
+    /// 判断 u 是否为 v 的祖先（节点也视为自身的祖先）
+    pub fn is_ancestor(&self, u: usize, v: usize) -> bool {
+        self.tin[u] <= self.tin[v] && self.tout[v] <= self.tout[u]
+    }
+
+    /// 节点数量
+    pub fn len(&self) -> usize {
+        self.tin.len()
+    }
+
+    /// 判断是否为空
+    pub fn is_empty(&self) -> bool {
+        self.tin.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    //         0
+    //       / | \
+    //      1  2  3
+    //     / \     \
+    //    4   5     6
+    fn new_query() -> SubtreeQuery<i64> {
+        SubtreeQuery::from_parents(
+            &[None, Some(0), Some(0), Some(0), Some(1), Some(1), Some(3)],
+            &[1, 2, 3, 4, 5, 6, 7],
+            0,
+            |a, b| a + b,
+        )
+    }
+
+    #[test]
+    fn subtree_sum_should_work() {
+        let mut query = new_query();
+
+        assert_eq!(query.subtree(0), 28);
+        assert_eq!(query.subtree(1), 13);
+        assert_eq!(query.subtree(3), 11);
+        assert_eq!(query.subtree(5), 6);
+
+        query.set(4, 10);
+        assert_eq!(query.get(4), Some(&10));
+        assert_eq!(query.subtree(1), 18);
+        assert_eq!(query.subtree(0), 33);
+
+        query.update(6, -7);
+        assert_eq!(query.subtree(3), 4);
+    }
+
+    #[test]
+    fn subtree_ancestor_should_work() {
+        let query = new_query();
+
+        assert!(query.is_ancestor(0, 5));
+        assert!(query.is_ancestor(1, 4));
+        assert!(query.is_ancestor(2, 2));
+        assert!(!query.is_ancestor(1, 6));
+        assert_eq!(query.range(1).len(), 3);
+    }
+}
@@ -0,0 +1,513 @@
+//! 简洁数据结构：支持 rank / select 的位向量和小波树
+//!
+//! * rank(i)：前 i 位中 1 的个数；select(k)：第 k 个 1 的位置，两者互为逆运算
+//! * 位向量每 512 位（8 个字）记录一次此前 1 的个数，rank 只需再对至多 8 个字做 popcount，
+//!   是 O(1) 的，额外空间为 1/8；select 先在这些计数上二分查找，再在块内逐字查找，O(logn)
+//! * 小波树自高位到低位逐位划分序列：每层用一个位向量记录每个元素的当前位，
+//!   再把当前位为 0 的元素稳定地排在为 1 的元素之前，作为下一层的序列（小波矩阵的存储方式）。
+//!   区间 [l, r) 中的元素在下一层仍然各自连续，位置可以由 rank 直接算出，
+//!   因此区间内某个值的出现次数、第 k 小的值、小于某个值的元素个数都只需 O(logσ) 次 rank，σ 为值域大小
+//!
+//! 值域很大而不同值较少时，可以先用 [`crate::discretize`] 把值映射到 [0, 不同值的个数)。
+
+use std::ops::Range;
+
+/// 每个字的位数
+const WORD_BITS: usize = u64::BITS as usize;
+/// 每个块的字数，每块记录一次此前 1 的个数
+const BLOCK_WORDS: usize = 8;
+/// 每个块的位数
+const BLOCK_BITS: usize = WORD_BITS * BLOCK_WORDS;
+
+/// 支持 O(1) rank 和 O(logn) select 的不可变位向量
+///
+/// # Examples
+///
+/// ```rust
+/// use base::RankSelectBitVec;
+///
+/// let bits = [true, false, true, true, false].into_iter().collect::<RankSelectBitVec>();
+///
+/// assert_eq!(bits.rank1(3), 2);
+/// assert_eq!(bits.rank0(5), 2);
+/// assert_eq!(bits.select1(2), Some(3));
+/// assert_eq!(bits.select0(1), Some(4));
+/// assert_eq!(bits.select1(3), None);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RankSelectBitVec {
+    words: Vec<u64>,
+    len: usize,
+    /// blocks[b] 为前 b 个块中 1 的个数，最后一项为全部 1 的个数
+    blocks: Vec<usize>,
+}
+
+impl RankSelectBitVec {
+    /// 位数
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// 判断是否为空
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// 1 的个数
+    pub fn count_ones(&self) -> usize {
+        // Safety: blocks 至少有一项
+        *self.blocks.last().unwrap()
+    }
+
+    /// 第 i 位，越界时返回 None
+    pub fn get(&self, i: usize) -> Option<bool> {
+        (i < self.len).then(|| self.words[i / WORD_BITS] >> (i % WORD_BITS) & 1 == 1)
+    }
+
+    /// 前 i 位中 1 的个数，时间复杂度：O(1)
+    ///
+    /// # Panics
+    ///
+    /// Panics if `i > len`.
+    pub fn rank1(&self, i: usize) -> usize {
+        assert!(i <= self.len, "Index out of bounds: {i}, len: {}", self.len);
+
+        let (word, bit) = (i / WORD_BITS, i % WORD_BITS);
+        let block_start = i / BLOCK_BITS * BLOCK_WORDS;
+        let mut ones = self.blocks[i / BLOCK_BITS];
+        for &w in &self.words[block_start..word] {
+            ones += w.count_ones() as usize;
+        }
+        if bit > 0 {
+            ones += (self.words[word] & ((1 << bit) - 1)).count_ones() as usize;
+        }
+
+        ones
+    }
+
+    /// 前 i 位中 0 的个数，时间复杂度：O(1)
+    ///
+    /// # Panics
+    ///
+    /// Panics if `i > len`.
+    pub fn rank0(&self, i: usize) -> usize {
+        i - self.rank1(i)
+    }
+
+    /// 第 k 个（从 0 开始）1 的位置，不存在时返回 None，时间复杂度：O(logn)
+    pub fn select1(&self, k: usize) -> Option<usize> {
+        self.select(k, true)
+    }
+
+    /// 第 k 个（从 0 开始）0 的位置，不存在时返回 None，时间复杂度：O(logn)
+    pub fn select0(&self, k: usize) -> Option<usize> {
+        self.select(k, false)
+    }
+
+    fn select(&self, mut k: usize, bit: bool) -> Option<usize> {
+        // 前 b 个块中值为 bit 的位数，b 小于块数时这些块都不含末尾的填充位
+        let before = |b: usize| {
+            let ones = self.blocks[b];
+            if bit { ones } else { b * BLOCK_BITS - ones }
+        };
+        let total = if bit {
+            self.count_ones()
+        } else {
+            self.len - self.count_ones()
+        };
+        if k >= total {
+            return None;
+        }
+
+        // 二分查找最后一个 before(b) <= k 的块，before(0) = 0，一定存在
+        let (mut lo, mut hi) = (0, self.blocks.len() - 1);
+        while hi - lo > 1 {
+            let mid = lo + (hi - lo) / 2;
+            if before(mid) <= k {
+                lo = mid;
+            } else {
+                hi = mid;
+            }
+        }
+        k -= before(lo);
+
+        for (i, &w) in self.words.iter().enumerate().skip(lo * BLOCK_WORDS) {
+            let mut w = if bit { w } else { !w };
+            let count = w.count_ones() as usize;
+            if k < count {
+                // 清除最低的 k 个 1，剩下最低位的 1 即为所求
+                for _ in 0..k {
+                    w &= w - 1;
+                }
+                return Some(i * WORD_BITS + w.trailing_zeros() as usize);
+            }
+            k -= count;
+        }
+
+        // Safety: k < total，一定能在上面的循环中找到
+        unreachable!()
+    }
+}
+
+impl Default for RankSelectBitVec {
+    /// 空的位向量，blocks 中仍然有表示总数的一项
+    fn default() -> Self {
+        Self {
+            words: Vec::new(),
+            len: 0,
+            blocks: vec![0],
+        }
+    }
+}
+
+impl FromIterator<bool> for RankSelectBitVec {
+    fn from_iter<I: IntoIterator<Item = bool>>(iter: I) -> Self {
+        let mut words = Vec::<u64>::new();
+        let mut len = 0;
+        for bit in iter {
+            if len % WORD_BITS == 0 {
+                words.push(0);
+            }
+            if bit {
+                // Safety: 上面保证了当前字存在
+                *words.last_mut().unwrap() |= 1 << (len % WORD_BITS);
+            }
+            len += 1;
+        }
+
+        let mut blocks = vec![0];
+        for chunk in words.chunks(BLOCK_WORDS) {
+            let ones = chunk.iter().map(|w| w.count_ones() as usize).sum::<usize>();
+            blocks.push(blocks[blocks.len() - 1] + ones);
+        }
+
+        Self { words, len, blocks }
+    }
+}
+
+/// 小波树：对 u64 序列支持区间内的计数和第 k 小查询
+///
+/// # Examples
+///
+/// ```rust
+/// use base::WaveletTree;
+///
+/// let tree = WaveletTree::from_slice(&[5, 1, 4, 1, 5, 9, 2, 6]);
+///
+/// assert_eq!(tree.get(4), Some(5));
+/// // [1, 4, 1, 5, 9] 中 1 出现 2 次，第 2 小（从 0 开始）为 4，小于 5 的有 3 个
+/// assert_eq!(tree.count(1..6, 1), 2);
+/// assert_eq!(tree.quantile(1..6, 2), Some(4));
+/// assert_eq!(tree.count_less(1..6, 5), 3);
+/// // 第 1 个（从 0 开始）5 位于下标 4
+/// assert_eq!(tree.select(5, 1), Some(4));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct WaveletTree {
+    len: usize,
+    /// 自高位到低位每一层的位向量
+    levels: Vec<RankSelectBitVec>,
+    /// 每一层中 0 的个数，即下一层中当前位为 1 的元素的起始位置
+    zeros: Vec<usize>,
+}
+
+impl WaveletTree {
+    /// 由序列构建小波树，时间复杂度：O(nlogσ)
+    pub fn from_slice(v: &[u64]) -> Self {
+        let max = v.iter().copied().max().unwrap_or(0);
+        let bits = u64::BITS - max.leading_zeros();
+        let mut cur = v.to_vec();
+        let mut levels = Vec::with_capacity(bits as usize);
+        let mut zeros = Vec::with_capacity(bits as usize);
+
+        for bit in (0..bits).rev() {
+            let level = cur
+                .iter()
+                .map(|&x| x >> bit & 1 == 1)
+                .collect::<RankSelectBitVec>();
+            zeros.push(level.len() - level.count_ones());
+            levels.push(level);
+
+            // 稳定地把当前位为 0 的元素排在前面
+            let (mut next, ones): (Vec<_>, Vec<_>) =
+                cur.into_iter().partition(|&x| x >> bit & 1 == 0);
+            next.extend(ones);
+            cur = next;
+        }
+
+        Self {
+            len: v.len(),
+            levels,
+            zeros,
+        }
+    }
+
+    /// 元素个数
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// 判断是否为空
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// 第 i 个元素，越界时返回 None，时间复杂度：O(logσ)
+    pub fn get(&self, mut i: usize) -> Option<u64> {
+        if i >= self.len {
+            return None;
+        }
+
+        let mut value = 0;
+        for (level, &zeros) in self.levels.iter().zip(&self.zeros) {
+            // Safety: i 始终小于 len
+            let bit = level.get(i).unwrap();
+            value = value << 1 | bit as u64;
+            i = if bit {
+                zeros + level.rank1(i)
+            } else {
+                level.rank0(i)
+            };
+        }
+
+        Some(value)
+    }
+
+    /// 区间 [range.start, range.end) 中 value 出现的次数，时间复杂度：O(logσ)
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is out of bounds or decreasing.
+    pub fn count(&self, range: Range<usize>, value: u64) -> usize {
+        self.check_range(&range);
+        if !self.in_domain(value) {
+            return 0;
+        }
+
+        let (start, end) = self.descend(range, value);
+        end - start
+    }
+
+    /// 区间 [range.start, range.end) 中第 k 小（从 0 开始）的元素，k 超出区间长度时返回 None，
+    /// 时间复杂度：O(logσ)
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is out of bounds or decreasing.
+    pub fn quantile(&self, range: Range<usize>, mut k: usize) -> Option<u64> {
+        self.check_range(&range);
+        if k >= range.len() {
+            return None;
+        }
+
+        let (mut start, mut end) = (range.start, range.end);
+        let mut value = 0;
+        for (level, &zeros) in self.levels.iter().zip(&self.zeros) {
+            let (start0, end0) = (level.rank0(start), level.rank0(end));
+            if k < end0 - start0 {
+                (start, end) = (start0, end0);
+                value <<= 1;
+            } else {
+                k -= end0 - start0;
+                (start, end) = (zeros + start - start0, zeros + end - end0);
+                value = value << 1 | 1;
+            }
+        }
+
+        Some(value)
+    }
+
+    /// 区间 [range.start, range.end) 中小于 value 的元素个数，时间复杂度：O(logσ)
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is out of bounds or decreasing.
+    pub fn count_less(&self, range: Range<usize>, value: u64) -> usize {
+        self.check_range(&range);
+        if !self.in_domain(value) {
+            return range.len();
+        }
+
+        let (mut start, mut end) = (range.start, range.end);
+        let mut less = 0;
+        for (bit, (level, &zeros)) in self.bits().zip(self.levels.iter().zip(&self.zeros)) {
+            let (start0, end0) = (level.rank0(start), level.rank0(end));
+            if value >> bit & 1 == 1 {
+                // 当前位为 0 的元素都小于 value
+                less += end0 - start0;
+                (start, end) = (zeros + start - start0, zeros + end - end0);
+            } else {
+                (start, end) = (start0, end0);
+            }
+        }
+
+        less
+    }
+
+    /// 第 k 个（从 0 开始）value 的下标，不存在时返回 None，时间复杂度：O(logσ·logn)
+    pub fn select(&self, value: u64, k: usize) -> Option<usize> {
+        if !self.in_domain(value) {
+            return None;
+        }
+
+        // 先向下找到最后一层中 value 所在的区间，再用 select 逐层向上还原位置
+        let (start, end) = self.descend(0..self.len, value);
+        if k >= end - start {
+            return None;
+        }
+
+        let mut i = start + k;
+        for (bit, (level, &zeros)) in self.bits().zip(self.levels.iter().zip(&self.zeros)).rev() {
+            i = if value >> bit & 1 == 1 {
+                level.select1(i - zeros)?
+            } else {
+                level.select0(i)?
+            };
+        }
+
+        Some(i)
+    }
+
+    /// 自高位到低位，每一层对应的位
+    fn bits(&self) -> impl DoubleEndedIterator<Item = usize> + ExactSizeIterator {
+        (0..self.levels.len()).rev()
+    }
+
+    /// value 能否用 levels.len() 位表示
+    fn in_domain(&self, value: u64) -> bool {
+        value.checked_shr(self.levels.len() as u32).unwrap_or(0) == 0
+    }
+
+    /// 区间 range 中值为 value 的元素在最后一层中的区间
+    fn descend(&self, range: Range<usize>, value: u64) -> (usize, usize) {
+        let (mut start, mut end) = (range.start, range.end);
+        for (bit, (level, &zeros)) in self.bits().zip(self.levels.iter().zip(&self.zeros)) {
+            (start, end) = if value >> bit & 1 == 1 {
+                (zeros + level.rank1(start), zeros + level.rank1(end))
+            } else {
+                (level.rank0(start), level.rank0(end))
+            };
+        }
+
+        (start, end)
+    }
+
+    fn check_range(&self, range: &Range<usize>) {
+        assert!(
+            range.start <= range.end && range.end <= self.len,
+            "Range {}..{} out of bounds, len: {}",
+            range.start,
+            range.end,
+            self.len
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generate::{self, Rng};
+
+    #[test]
+    fn rank_select_should_match_naive() {
+        let mut rng = Rng::new(7);
+        for len in [0, 1, 63, 64, 65, 511, 512, 513, 3000] {
+            // 稀疏、均匀和稠密三种密度
+            for density in [1, 50, 99] {
+                let bits = (0..len)
+                    .map(|_| rng.below(100) < density)
+                    .collect::<Vec<_>>();
+                let v = bits.iter().copied().collect::<RankSelectBitVec>();
+                assert_eq!(v.len(), len);
+                assert_eq!(v.get(len), None);
+
+                let mut ones = 0;
+                for (i, &bit) in bits.iter().enumerate() {
+                    assert_eq!(v.get(i), Some(bit));
+                    assert_eq!(v.rank1(i), ones);
+                    assert_eq!(v.rank0(i), i - ones);
+                    if bit {
+                        assert_eq!(v.select1(ones), Some(i));
+                    } else {
+                        assert_eq!(v.select0(i - ones), Some(i));
+                    }
+                    ones += bit as usize;
+                }
+                assert_eq!(v.rank1(len), ones);
+                assert_eq!(v.count_ones(), ones);
+                assert_eq!(v.select1(ones), None);
+                assert_eq!(v.select0(len - ones), None);
+            }
+        }
+    }
+
+    #[test]
+    fn default_bit_vec_should_be_empty() {
+        let v = RankSelectBitVec::default();
+        assert_eq!(v, std::iter::empty().collect());
+        assert_eq!((v.len(), v.count_ones(), v.rank1(0)), (0, 0, 0));
+        assert_eq!((v.select1(0), v.select0(0)), (None, None));
+
+        // WaveletTree::default 不含任何层，也不会用到位向量
+        let tree = WaveletTree::default();
+        assert_eq!((tree.count(0..0, 0), tree.get(0)), (0, None));
+    }
+
+    #[test]
+    fn wavelet_tree_should_match_naive() {
+        let mut rng = Rng::new(11);
+        for (distinct, len) in [(1, 50), (2, 300), (37, 500), (1 << 40, 300)] {
+            let v = generate::few_distinct(len, distinct, distinct);
+            let tree = WaveletTree::from_slice(&v);
+            assert_eq!(tree.len(), len);
+            for (i, &x) in v.iter().enumerate() {
+                assert_eq!(tree.get(i), Some(x));
+            }
+            assert_eq!(tree.get(len), None);
+
+            for _ in 0..200 {
+                let (a, b) = (rng.below(len as u64 + 1), rng.below(len as u64 + 1));
+                let range = a.min(b) as usize..a.max(b) as usize;
+                let mut sorted = v[range.clone()].to_vec();
+                sorted.sort_unstable();
+                let probe = match rng.below(3) {
+                    0 => rng.below(distinct + 1),
+                    _ => v[rng.below(len as u64) as usize],
+                };
+
+                let count = sorted.iter().filter(|&&x| x == probe).count();
+                assert_eq!(tree.count(range.clone(), probe), count);
+                let less = sorted.partition_point(|&x| x < probe);
+                assert_eq!(tree.count_less(range.clone(), probe), less);
+
+                let k = rng.below(range.len() as u64 + 1) as usize;
+                assert_eq!(tree.quantile(range.clone(), k), sorted.get(k).copied());
+
+                let k = rng.below(4) as usize;
+                let nth = v.iter().enumerate().filter(|&(_, &x)| x == probe).nth(k);
+                assert_eq!(tree.select(probe, k), nth.map(|(i, _)| i));
+            }
+        }
+    }
+
+    #[test]
+    fn wavelet_tree_should_handle_edge_values() {
+        let v = [0, u64::MAX, 0, 1 << 63];
+        let tree = WaveletTree::from_slice(&v);
+        assert_eq!(tree.quantile(0..4, 3), Some(u64::MAX));
+        assert_eq!(tree.count_less(0..4, u64::MAX), 3);
+        assert_eq!(tree.select(0, 1), Some(2));
+
+        let empty = WaveletTree::from_slice(&[]);
+        assert!(empty.is_empty());
+        assert_eq!(empty.count(0..0, 0), 0);
+        assert_eq!(empty.quantile(0..0, 0), None);
+
+        // 全为 0 时没有任何层
+        let zeros = WaveletTree::from_slice(&[0; 5]);
+        assert_eq!(zeros.count(1..4, 0), 3);
+        assert_eq!(zeros.count_less(0..5, 1), 5);
+        assert_eq!(zeros.select(0, 4), Some(4));
+        assert_eq!(zeros.select(1, 0), None);
+    }
+}
@@ -0,0 +1,156 @@
+//! 后缀数组（suffix array）
+//!
+//! 将文本的所有后缀按字典序排序，记录各后缀的起始下标。
+//! 所有以模式串开头的后缀在后缀数组中是连续的一段，二分查找即可找出模式串的全部出现位置。
+//!
+//! 构建后缀数组本质上是对 n 个字符串排序，这里直接复用字符串排序算法，可选的后端见 [`SuffixSort`]。
+//! 后缀之间往往共享很长的前缀（如重复度高的文本），不同后端的性能差异明显。
+
+use std::ops::Range;
+
+use crate::sort;
+
+/// 构建后缀数组时使用的排序算法
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SuffixSort {
+    /// 基于比较的模式消除快速排序，每次比较的时间复杂度为 O(n)
+    #[default]
+    Comparison,
+    /// MSD 基数排序
+    MsdRadix,
+    /// 三路字符串快速排序
+    ThreeWayQuicksort,
+}
+
+/// 后缀数组
+#[derive(Debug, Clone)]
+pub struct SuffixArray {
+    text: Vec<u8>,
+    suffixes: Vec<usize>,
+}
+
+impl SuffixArray {
+    /// 使用默认的排序算法构建后缀数组
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use base::SuffixArray;
+    ///
+    /// let sa = SuffixArray::new("banana");
+    ///
+    /// assert_eq!(sa.as_slice(), [5, 3, 1, 0, 4, 2]);
+    /// ```
+    pub fn new(text: impl AsRef<[u8]>) -> Self {
+        Self::with_backend(text, SuffixSort::default())
+    }
+
+    /// 使用指定的排序算法构建后缀数组
+    pub fn with_backend(text: impl AsRef<[u8]>, backend: SuffixSort) -> Self {
+        let text = text.as_ref().to_vec();
+
+        let mut suffixes = (0..text.len()).map(|i| &text[i..]).collect::<Vec<_>>();
+        match backend {
+            SuffixSort::Comparison => sort::pdq_sort(&mut suffixes),
+            SuffixSort::MsdRadix => sort::msd_radix_sort(&mut suffixes),
+            SuffixSort::ThreeWayQuicksort => sort::three_way_string_quicksort(&mut suffixes),
+        }
+        // 后缀的起始下标 = 文本长度 - 后缀长度
+        let suffixes = suffixes.iter().map(|s| text.len() - s.len()).collect();
+
+        Self { text, suffixes }
+    }
+
+    /// 文本长度，即后缀的数量
+    pub fn len(&self) -> usize {
+        self.suffixes.len()
+    }
+
+    /// 判断文本是否为空
+    pub fn is_empty(&self) -> bool {
+        self.suffixes.is_empty()
+    }
+
+    /// 原始文本
+    pub fn text(&self) -> &[u8] {
+        &self.text
+    }
+
+    /// 按字典序排列的各后缀起始下标
+    pub fn as_slice(&self) -> &[usize] {
+        &self.suffixes
+    }
+
+    /// 查找模式串在文本中的全部出现位置（无序），时间复杂度：O(mlogn)
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use base::SuffixArray;
+    ///
+    /// let sa = SuffixArray::new("banana");
+    /// let mut found = sa.find("ana").to_vec();
+    /// found.sort();
+    ///
+    /// assert_eq!(found, [1, 3]);
+    /// assert!(sa.find("nab").is_empty());
+    /// ```
+    pub fn find(&self, pattern: impl AsRef<[u8]>) -> &[usize] {
+        &self.suffixes[self.find_range(pattern.as_ref())]
+    }
+
+    /// 以 pattern 开头的后缀在后缀数组中的区间
+    fn find_range(&self, pattern: &[u8]) -> Range<usize> {
+        // 截取后缀的前 m 个字节与模式串比较
+        let prefix = |i: usize| &self.text[i..(i + pattern.len()).min(self.text.len())];
+
+        let start = self.suffixes.partition_point(|&i| prefix(i) < pattern);
+        let end = start + self.suffixes[start..].partition_point(|&i| prefix(i) == pattern);
+
+        start..end
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BACKENDS: [SuffixSort; 3] = [
+        SuffixSort::Comparison,
+        SuffixSort::MsdRadix,
+        SuffixSort::ThreeWayQuicksort,
+    ];
+
+    #[test]
+    fn suffix_array_backends_should_agree() {
+        let texts = [
+            String::new(),
+            String::from("mississippi"),
+            "a".repeat(1000),
+            "abcab".repeat(200),
+        ];
+
+        for text in texts {
+            let mut expected = (0..text.len()).collect::<Vec<_>>();
+            expected.sort_by_key(|&i| &text[i..]);
+
+            for backend in BACKENDS {
+                let sa = SuffixArray::with_backend(&text, backend);
+                assert_eq!(sa.as_slice(), expected, "{backend:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn suffix_array_find_should_work() {
+        let sa = SuffixArray::new("mississippi");
+
+        let mut found = sa.find("ss").to_vec();
+        found.sort();
+        assert_eq!(found, [2, 5]);
+        assert_eq!(sa.find("i").len(), 4);
+        assert_eq!(sa.find("").len(), 11);
+        assert!(sa.find("pis").is_empty());
+        assert!(sa.find("mississippis").is_empty());
+    }
+}
@@ -0,0 +1,310 @@
+//! 后缀自动机（suffix automaton）
+//!
+//! 识别文本所有子串的最小确定有限自动机：从初始状态出发，沿转移边读入模式串，
+//! 不会走到空转移当且仅当模式串是文本的子串。
+//!
+//! 每个状态对应一组结束位置集合（endpos）相同的子串，这些子串互为后缀，长度恰好覆盖
+//! 区间 (len(link), len]，其中 link 为后缀链接，指向对应更短后缀的状态。
+//! 因此不同子串的数量为所有状态上 len - len(link) 之和。
+//!
+//! 构建是在线的：每次在文本末尾追加一个字符，均摊时间复杂度为 O(1)（字符集大小视为常数），
+//! 状态数不超过 2n - 1，转移数不超过 3n - 4。
+
+use std::collections::BTreeMap;
+
+#[derive(Debug, Clone, Default)]
+struct State {
+    /// 该状态对应的最长子串的长度
+    len: usize,
+    /// 后缀链接，初始状态为 None
+    link: Option<usize>,
+    next: BTreeMap<u8, usize>,
+}
+
+/// 后缀自动机
+///
+/// # Examples
+///
+/// ```rust
+/// use base::SuffixAutomaton;
+///
+/// let mut sam = SuffixAutomaton::from_text("abab");
+///
+/// assert!(sam.contains("bab"));
+/// assert!(!sam.contains("bb"));
+/// // a, b, ab, ba, aba, bab, abab
+/// assert_eq!(sam.count_distinct_substrings(), 7);
+///
+/// // 在线追加字符
+/// sam.push(b'b');
+/// assert!(sam.contains("bb"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct SuffixAutomaton {
+    states: Vec<State>,
+    /// 整个文本对应的状态
+    last: usize,
+    text: Vec<u8>,
+    /// 不同子串的数量，追加字符时增量维护
+    distinct: u64,
+}
+
+impl SuffixAutomaton {
+    /// 创建只包含初始状态的后缀自动机，对应空文本
+    pub fn new() -> Self {
+        Self {
+            states: vec![State::default()],
+            last: 0,
+            text: Vec::new(),
+            distinct: 0,
+        }
+    }
+
+    /// 为文本构建后缀自动机，时间复杂度：O(n)
+    pub fn from_text(text: impl AsRef<[u8]>) -> Self {
+        let mut sam = Self::new();
+        sam.extend(text.as_ref().iter().copied());
+
+        sam
+    }
+
+    /// 文本长度
+    pub fn len(&self) -> usize {
+        self.text.len()
+    }
+
+    /// 判断文本是否为空
+    pub fn is_empty(&self) -> bool {
+        self.text.is_empty()
+    }
+
+    /// 已读入的文本
+    pub fn text(&self) -> &[u8] {
+        &self.text
+    }
+
+    /// 状态数量（包括初始状态）
+    pub fn state_count(&self) -> usize {
+        self.states.len()
+    }
+
+    /// 在文本末尾追加一个字符
+    pub fn push(&mut self, c: u8) {
+        self.text.push(c);
+
+        let cur = self.states.len();
+        self.states.push(State {
+            len: self.states[self.last].len + 1,
+            link: None,
+            next: BTreeMap::new(),
+        });
+
+        // 沿后缀链接向上，为所有还没有 c 转移的状态添加指向 cur 的转移
+        let mut p = Some(self.last);
+        while let Some(state) = p
+            && !self.states[state].next.contains_key(&c)
+        {
+            self.states[state].next.insert(c, cur);
+            p = self.states[state].link;
+        }
+
+        let link = match p {
+            // 到达初始状态之上，cur 的所有后缀都是新出现的
+            None => 0,
+            Some(p) => {
+                let q = self.states[p].next[&c];
+                if self.states[p].len + 1 == self.states[q].len {
+                    q
+                } else {
+                    // q 对应的子串中只有较短的一部分是 cur 的后缀，把它们拆分到新的状态 clone 中
+                    let clone = self.states.len();
+                    self.states.push(State {
+                        len: self.states[p].len + 1,
+                        ..self.states[q].clone()
+                    });
+
+                    let mut p = Some(p);
+                    while let Some(state) = p
+                        && self.states[state].next.get(&c) == Some(&q)
+                    {
+                        self.states[state].next.insert(c, clone);
+                        p = self.states[state].link;
+                    }
+                    self.states[q].link = Some(clone);
+                    clone
+                }
+            }
+        };
+
+        self.states[cur].link = Some(link);
+        // 拆分状态不改变子串总数，新增的子串恰好是 cur 对应的那些
+        self.distinct += (self.states[cur].len - self.states[link].len) as u64;
+        self.last = cur;
+    }
+
+    /// 判断 pattern 是否为文本的子串，时间复杂度：O(m)
+    pub fn contains(&self, pattern: impl AsRef<[u8]>) -> bool {
+        pattern
+            .as_ref()
+            .iter()
+            .try_fold(0, |state, c| self.states[state].next.get(c).copied())
+            .is_some()
+    }
+
+    /// 文本中不同的非空子串的数量，时间复杂度：O(1)
+    pub fn count_distinct_substrings(&self) -> u64 {
+        self.distinct
+    }
+
+    /// 文本与 other 的最长公共子串，返回 other 中的对应片段，有多个时返回最靠前的
+    ///
+    /// 时间复杂度：O(m)，m 为 other 的长度
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use base::SuffixAutomaton;
+    ///
+    /// let sam = SuffixAutomaton::from_text("xabcdey");
+    ///
+    /// assert_eq!(sam.longest_common_substring(b"zzbcdzabc"), b"bcd");
+    /// assert_eq!(sam.longest_common_substring(b"qqq"), b"");
+    /// ```
+    pub fn longest_common_substring<'a>(&self, other: &'a [u8]) -> &'a [u8] {
+        let (mut state, mut len) = (0, 0);
+        let (mut best_len, mut best_end) = (0, 0);
+
+        for (i, c) in other.iter().enumerate() {
+            // 当前匹配无法用 c 延伸时，沿后缀链接缩短匹配
+            while state != 0 && !self.states[state].next.contains_key(c) {
+                // Safety: 除初始状态外所有状态都有后缀链接
+                state = self.states[state].link.unwrap();
+                len = self.states[state].len;
+            }
+
+            match self.states[state].next.get(c) {
+                Some(&next) => {
+                    state = next;
+                    len += 1;
+                }
+                None => len = 0,
+            }
+
+            if len > best_len {
+                best_len = len;
+                best_end = i + 1;
+            }
+        }
+
+        &other[best_end - best_len..best_end]
+    }
+}
+
+impl Default for SuffixAutomaton {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Extend<u8> for SuffixAutomaton {
+    fn extend<I: IntoIterator<Item = u8>>(&mut self, iter: I) {
+        iter.into_iter().for_each(|c| self.push(c));
+    }
+}
+
+impl FromIterator<u8> for SuffixAutomaton {
+    fn from_iter<I: IntoIterator<Item = u8>>(iter: I) -> Self {
+        let mut sam = Self::new();
+        sam.extend(iter);
+
+        sam
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+    use crate::generate;
+
+    /// 由 [0, alphabet) 组成的随机字节串
+    fn random_text(len: usize, alphabet: u64, seed: u64) -> Vec<u8> {
+        generate::few_distinct(len, alphabet, seed)
+            .into_iter()
+            .map(|x| b'a' + x as u8)
+            .collect()
+    }
+
+    fn brute_force_substrings(text: &[u8]) -> HashSet<&[u8]> {
+        (0..text.len())
+            .flat_map(|i| (i + 1..=text.len()).map(move |j| &text[i..j]))
+            .collect()
+    }
+
+    #[test]
+    fn suffix_automaton_should_recognize_substrings() {
+        for seed in 1..=20 {
+            let text = random_text(40, 3, seed);
+            let sam = SuffixAutomaton::from_text(&text);
+            let substrings = brute_force_substrings(&text);
+
+            assert_eq!(sam.count_distinct_substrings(), substrings.len() as u64);
+            assert!(substrings.iter().all(|s| sam.contains(s)));
+            assert!(sam.contains(""));
+            assert!(sam.state_count() < 2 * text.len());
+
+            // 长度为 4 的所有候选串中，不是子串的都应被拒绝
+            let probe = random_text(400, 3, seed + 100);
+            for window in probe.windows(4) {
+                assert_eq!(sam.contains(window), substrings.contains(window));
+            }
+        }
+    }
+
+    #[test]
+    fn suffix_automaton_should_extend_online() {
+        let text = b"mississippi";
+        let mut sam = SuffixAutomaton::new();
+        assert_eq!(sam.count_distinct_substrings(), 0);
+
+        for (i, &c) in text.iter().enumerate() {
+            sam.push(c);
+            let prefix = &text[..=i];
+            assert_eq!(
+                sam.count_distinct_substrings(),
+                brute_force_substrings(prefix).len() as u64
+            );
+        }
+
+        assert_eq!(sam.text(), text);
+        assert_eq!(sam.count_distinct_substrings(), 53);
+        assert!(sam.contains("ssippi"));
+        assert!(!sam.contains("spis"));
+
+        let collected = text.iter().copied().collect::<SuffixAutomaton>();
+        assert_eq!(collected.state_count(), sam.state_count());
+    }
+
+    #[test]
+    fn longest_common_substring_should_match_brute_force() {
+        for seed in 1..=20 {
+            let (a, b) = (random_text(30, 2, seed), random_text(30, 2, seed + 50));
+            let sam = SuffixAutomaton::from_text(&a);
+            let lcs = sam.longest_common_substring(&b);
+
+            let common = brute_force_substrings(&a);
+            let expected = (0..b.len())
+                .flat_map(|i| (i + 1..=b.len()).map(move |j| (i, j)))
+                .filter(|&(i, j)| common.contains(&b[i..j]))
+                .map(|(i, j)| j - i)
+                .max()
+                .unwrap_or(0);
+            assert_eq!(lcs.len(), expected);
+            assert!(common.contains(lcs) || lcs.is_empty());
+        }
+
+        let sam = SuffixAutomaton::from_text("");
+        assert_eq!(sam.longest_common_substring(b"abc"), b"");
+    }
+}
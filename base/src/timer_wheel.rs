@@ -0,0 +1,374 @@
+//! 分层时间轮（hierarchical timer wheel）
+//!
+//! 用堆实现定时器时，插入和取出最早到期的定时器都需要 O(logn)，取消定时器还需要额外的索引。
+//! 时间轮把时间划分为刻度（tick），每层轮子有 64 个槽，第 l 层的一个槽覆盖 64^l 个刻度：
+//! * 插入：找到到期时刻与当前时刻最高的不同 6 位组，放入对应层中到期时刻所在的槽，O(1)
+//! * 推进：当前时刻加一，第 0 层当前槽中的定时器全部到期；当前时刻的低 6l 位全为 0 时，
+//!   第 l 层当前槽中的定时器离到期已不足 64^l 个刻度，重新放入更低的层（级联）。
+//!   每层用一个 64 位的位图记录非空的槽，空槽对应的刻度不需要处理，直接跳到下一个非空槽的时刻
+//! * 取消：只作废定时器本身，槽中的记录在轮到该槽时跳过，O(1)
+//!
+//! 每个定时器最多级联 11 次（64^11 > 2^64），插入、取消和到期的均摊时间复杂度都是 O(1)，
+//! 操作系统内核和网络框架中管理大量超时的定时器通常都使用时间轮。
+
+/// 每层轮子的槽数的位数
+const SLOT_BITS: u32 = 6;
+/// 每层轮子的槽数
+const SLOTS: usize = 1 << SLOT_BITS;
+/// 层数，足以覆盖全部 u64 的刻度
+const LEVELS: usize = u64::BITS.div_ceil(SLOT_BITS) as usize;
+
+/// 定时器的句柄，定时器到期或被取消后句柄失效
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TimerHandle {
+    index: usize,
+    generation: u32,
+}
+
+#[derive(Debug)]
+struct Timer<T> {
+    deadline: u64,
+    generation: u32,
+    /// 定时器到期或被取消后为 None
+    item: Option<T>,
+}
+
+/// 分层时间轮
+///
+/// # Examples
+///
+/// ```rust
+/// use base::TimerWheel;
+///
+/// let mut wheel = TimerWheel::new();
+/// let a = wheel.schedule(3, "a");
+/// wheel.schedule(100, "b");
+/// wheel.schedule(5, "c");
+///
+/// assert_eq!(wheel.advance(4), ["a"]);
+/// assert_eq!(wheel.cancel(a), None);
+/// assert_eq!(wheel.advance(200), ["c", "b"]);
+/// assert_eq!(wheel.now(), 204);
+/// assert!(wheel.is_empty());
+/// ```
+#[derive(Debug)]
+pub struct TimerWheel<T> {
+    timers: Vec<Timer<T>>,
+    /// 已到期或被取消、可复用的定时器下标
+    free: Vec<usize>,
+    /// 各层轮子的槽，第 level 层的第 slot 个槽的下标为 level * SLOTS + slot
+    slots: Vec<Vec<TimerHandle>>,
+    /// 各层轮子中非空的槽，第 slot 位为 1 表示第 slot 个槽非空
+    occupied: [u64; LEVELS],
+    now: u64,
+    len: usize,
+}
+
+impl<T> TimerWheel<T> {
+    /// 创建空的时间轮，当前时刻为 0
+    pub fn new() -> Self {
+        Self {
+            timers: Vec::new(),
+            free: Vec::new(),
+            slots: (0..LEVELS * SLOTS).map(|_| Vec::new()).collect(),
+            occupied: [0; LEVELS],
+            now: 0,
+            len: 0,
+        }
+    }
+
+    /// 当前时刻
+    pub fn now(&self) -> u64 {
+        self.now
+    }
+
+    /// 尚未到期的定时器数量
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// 判断是否没有尚未到期的定时器
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// 添加在 after 个刻度之后到期的定时器，after 为 0 时视为 1，时间复杂度：O(1)
+    ///
+    /// 到期时刻超出 u64::MAX 时饱和为 u64::MAX；当前时刻已是 u64::MAX 时无法再推进，
+    /// 定时器在下一次调用 [`advance`](Self::advance) 时立即到期。
+    pub fn schedule(&mut self, after: u64, item: T) -> TimerHandle {
+        let deadline = self.now.saturating_add(after.max(1));
+        let handle = match self.free.pop() {
+            Some(index) => {
+                let timer = &mut self.timers[index];
+                timer.deadline = deadline;
+                timer.item = Some(item);
+
+                TimerHandle {
+                    index,
+                    generation: timer.generation,
+                }
+            }
+            None => {
+                self.timers.push(Timer {
+                    deadline,
+                    generation: 0,
+                    item: Some(item),
+                });
+
+                TimerHandle {
+                    index: self.timers.len() - 1,
+                    generation: 0,
+                }
+            }
+        };
+        self.len += 1;
+        self.place(handle, deadline);
+
+        handle
+    }
+
+    /// 定时器的到期时刻，句柄已失效时返回 None
+    pub fn deadline(&self, handle: TimerHandle) -> Option<u64> {
+        self.get(handle).map(|timer| timer.deadline)
+    }
+
+    /// 取消定时器并返回它的元素，句柄已失效时返回 None，时间复杂度：O(1)
+    pub fn cancel(&mut self, handle: TimerHandle) -> Option<T> {
+        self.get(handle)?;
+
+        Some(self.release(handle.index))
+    }
+
+    /// 向前推进 ticks 个刻度，按到期时刻的顺序返回期间到期的定时器的元素
+    ///
+    /// 跳过没有定时器的刻度，只在槽到期或级联的时刻停下，
+    /// 时间复杂度：O(经过的非空槽的数量 + 到期和级联的定时器数量)。
+    pub fn advance(&mut self, ticks: u64) -> Vec<T> {
+        let target = self.now.saturating_add(ticks);
+        let mut expired = Vec::new();
+
+        // 第 0 层当前的槽只会存放当前时刻为 u64::MAX 时添加的定时器
+        self.expire_current(&mut expired);
+        while let Some(next) = self.next_event().filter(|&next| next <= target) {
+            self.now = next;
+            // 先从高层向低层级联，级联下来的定时器可能落在更低层即将级联的槽中
+            for level in (1..LEVELS).rev() {
+                if self.now.trailing_zeros() >= SLOT_BITS * level as u32 {
+                    self.cascade(level, &mut expired);
+                }
+            }
+            self.expire_current(&mut expired);
+        }
+        self.now = target;
+
+        expired
+    }
+
+    /// 下一个需要处理的时刻，即第 0 层的槽到期或更高层的槽级联的最早时刻，所有槽都为空时返回 None
+    ///
+    /// 第 l 层的定时器与当前时刻最高的不同 6 位组是第 l 组，只会在当前槽之后的槽中，
+    /// 并且都在下一个 64^(l+1) 的整数倍之前处理，所以从低层往高层找到的第一个非空槽就是最早的。
+    fn next_event(&self) -> Option<u64> {
+        (0..LEVELS).find_map(|level| {
+            let shift = SLOT_BITS * level as u32;
+            let current = (self.now >> shift) as u32 & (SLOTS as u32 - 1);
+            let later = self.occupied[level].checked_shr(current + 1).unwrap_or(0);
+            if later == 0 {
+                return None;
+            }
+
+            let slot = (current + 1 + later.trailing_zeros()) as u64;
+            let block = self.now & u64::MAX.checked_shl(shift + SLOT_BITS).unwrap_or(0);
+            Some(block | slot << shift)
+        })
+    }
+
+    /// 第 0 层当前槽中的有效定时器全部到期
+    fn expire_current(&mut self, expired: &mut Vec<T>) {
+        for handle in self.take_slot(0) {
+            if self.get(handle).is_some() {
+                expired.push(self.release(handle.index));
+            }
+        }
+    }
+
+    /// 把第 level 层当前槽中的定时器重新放入更低的层，恰好在当前时刻到期的直接到期
+    fn cascade(&mut self, level: usize, expired: &mut Vec<T>) {
+        for handle in self.take_slot(level) {
+            let Some(deadline) = self.deadline(handle) else {
+                continue;
+            };
+            if deadline == self.now {
+                expired.push(self.release(handle.index));
+            } else {
+                self.place(handle, deadline);
+            }
+        }
+    }
+
+    /// 把定时器放入到期时刻与当前时刻最高的不同 6 位组对应的层
+    ///
+    /// deadline == now 只在当前时刻为 u64::MAX 时出现，放入第 0 层当前的槽，下次推进时到期
+    fn place(&mut self, handle: TimerHandle, deadline: u64) {
+        let level = match deadline ^ self.now {
+            0 => 0,
+            diff => (diff.ilog2() / SLOT_BITS) as usize,
+        };
+        let slot = slot_index(level, deadline);
+
+        self.slots[slot].push(handle);
+        self.occupied[level] |= 1 << (slot - level * SLOTS);
+    }
+
+    /// 取出第 level 层当前槽中的全部记录
+    fn take_slot(&mut self, level: usize) -> Vec<TimerHandle> {
+        let slot = slot_index(level, self.now);
+
+        self.occupied[level] &= !(1 << (slot - level * SLOTS));
+        std::mem::take(&mut self.slots[slot])
+    }
+
+    /// 句柄有效时返回它指向的定时器
+    fn get(&self, handle: TimerHandle) -> Option<&Timer<T>> {
+        self.timers
+            .get(handle.index)
+            .filter(|timer| timer.generation == handle.generation && timer.item.is_some())
+    }
+
+    /// 取出有效定时器的元素，使它的句柄失效并回收下标
+    fn release(&mut self, index: usize) -> T {
+        let timer = &mut self.timers[index];
+        timer.generation = timer.generation.wrapping_add(1);
+        self.free.push(index);
+        self.len -= 1;
+
+        // Safety: 调用者保证定时器有效
+        timer.item.take().unwrap()
+    }
+}
+
+impl<T> Default for TimerWheel<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 时刻 tick 在第 level 层所在的槽在 slots 中的下标
+fn slot_index(level: usize, tick: u64) -> usize {
+    let slot = (tick >> (SLOT_BITS as usize * level)) as usize & (SLOTS - 1);
+
+    level * SLOTS + slot
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::*;
+    use crate::generate::Rng;
+
+    #[test]
+    fn timer_wheel_should_match_sorted_map() {
+        let mut wheel = TimerWheel::new();
+        // (到期时刻, 定时器编号) -> 句柄
+        let mut expected = BTreeMap::new();
+        let mut rng = Rng::new(29);
+
+        for id in 0..5000 {
+            match rng.below(8) {
+                0..4 => {
+                    // 大部分定时器较近，少数跨越多层
+                    let after = match rng.below(4) {
+                        0 => rng.below(1 << 20),
+                        _ => rng.below(200),
+                    };
+                    let handle = wheel.schedule(after, id);
+                    let deadline = wheel.now() + after.max(1);
+                    assert_eq!(wheel.deadline(handle), Some(deadline));
+                    expected.insert((deadline, id), handle);
+                }
+                4 => {
+                    let Some((&key, &handle)) = expected.iter().nth(rng.below(8) as usize) else {
+                        continue;
+                    };
+                    assert_eq!(wheel.cancel(handle), Some(key.1));
+                    assert_eq!(wheel.cancel(handle), None);
+                    expected.remove(&key);
+                }
+                _ => {
+                    let target = wheel.now() + rng.below(100);
+                    let due = expected.range(..(target + 1, 0)).map(|(&key, _)| key);
+                    let due = due.collect::<Vec<_>>();
+
+                    // 同一时刻到期的定时器之间的顺序不确定，只比较时刻
+                    let mut fired = wheel.advance(target - wheel.now());
+                    fired.sort_unstable();
+                    let mut due_ids = due.iter().map(|&(_, id)| id).collect::<Vec<_>>();
+                    due_ids.sort_unstable();
+                    assert_eq!(fired, due_ids);
+
+                    for key in due {
+                        let handle = expected.remove(&key).unwrap();
+                        assert_eq!(wheel.deadline(handle), None);
+                    }
+                }
+            }
+
+            assert_eq!(wheel.len(), expected.len());
+        }
+    }
+
+    #[test]
+    fn timer_wheel_should_fire_in_deadline_order() {
+        let mut wheel = TimerWheel::new();
+        let mut rng = Rng::new(31);
+        let afters = (0..2000).map(|_| rng.below(100_000)).collect::<Vec<_>>();
+
+        for &after in &afters {
+            wheel.schedule(after, after.max(1));
+        }
+        let fired = wheel.advance(200_000);
+
+        assert!(fired.is_sorted());
+        assert_eq!(fired.len(), afters.len());
+        assert!(wheel.is_empty());
+    }
+
+    #[test]
+    fn timer_wheel_should_handle_far_deadlines() {
+        let mut wheel = TimerWheel::new();
+        wheel.advance(u64::MAX - 10);
+        assert_eq!(wheel.now(), u64::MAX - 10);
+
+        let far = wheel.schedule(u64::MAX, 'a');
+        wheel.schedule(3, 'b');
+        assert_eq!(wheel.deadline(far), Some(u64::MAX));
+
+        assert_eq!(wheel.advance(3), ['b']);
+        assert_eq!(wheel.advance(u64::MAX), ['a']);
+        assert_eq!(wheel.now(), u64::MAX);
+
+        // 时刻已无法推进，新的定时器在下一次推进时立即到期
+        let last = wheel.schedule(5, 'c');
+        assert_eq!(wheel.deadline(last), Some(u64::MAX));
+        assert_eq!(wheel.advance(0), ['c']);
+        assert!(wheel.is_empty());
+    }
+
+    #[test]
+    fn timer_wheel_should_skip_empty_spans() {
+        let mut wheel = TimerWheel::new();
+        // 逐个刻度推进需要 2^50 步
+        let far = wheel.schedule(1 << 50, 'a');
+        wheel.schedule((1 << 50) + 70, 'b');
+        let cancelled = wheel.schedule(1 << 40, 'x');
+        assert_eq!(wheel.cancel(cancelled), Some('x'));
+
+        assert_eq!(wheel.advance((1 << 50) - 1), []);
+        assert_eq!(wheel.deadline(far), Some(1 << 50));
+        assert_eq!(wheel.advance(1), ['a']);
+        assert_eq!(wheel.advance(u64::MAX), ['b']);
+        assert_eq!(wheel.now(), u64::MAX);
+    }
+}
@@ -0,0 +1,84 @@
+//! 蹦床（trampoline）：不消耗调用栈的递归
+//!
+//! 递归函数不直接调用自身，而是返回“下一步要做的调用” [`Bounce::Call`]，
+//! 由 [`Bounce::run`] 在一个循环中依次执行，调用栈的深度始终为常数，递归深度只受堆内存限制。
+//!
+//! 尾递归可以直接改写；非尾递归（如二叉树的遍历）需要先改写为续延传递风格（CPS）：
+//! 把“递归返回后还要做的事”打包成闭包作为参数传下去，见 [`crate::bt::in_order_trampolined`]。
+//! 代价是每一步都要在堆上分配一个闭包，比普通递归慢，只在递归可能很深时使用。
+
+/// 一步计算：要么已经得到结果，要么还需要执行一次调用
+pub enum Bounce<T> {
+    /// 计算完成
+    Done(T),
+    /// 下一步要执行的调用
+    Call(Box<dyn FnOnce() -> Bounce<T>>),
+}
+
+impl<T> Bounce<T> {
+    /// 把 f 包装为下一步要执行的调用
+    pub fn call(f: impl FnOnce() -> Bounce<T> + 'static) -> Self {
+        Bounce::Call(Box::new(f))
+    }
+
+    /// 循环执行调用直到得到结果
+    ///
+    /// # Examples
+    ///
+    /// 互相递归的奇偶判断，递归深度为一百万：
+    ///
+    /// ```rust
+    /// use base::trampoline::Bounce;
+    ///
+    /// fn is_even(n: u64) -> Bounce<bool> {
+    ///     if n == 0 {
+    ///         Bounce::Done(true)
+    ///     } else {
+    ///         Bounce::call(move || is_odd(n - 1))
+    ///     }
+    /// }
+    ///
+    /// fn is_odd(n: u64) -> Bounce<bool> {
+    ///     if n == 0 {
+    ///         Bounce::Done(false)
+    ///     } else {
+    ///         Bounce::call(move || is_even(n - 1))
+    ///     }
+    /// }
+    ///
+    /// assert!(is_even(1_000_000).run());
+    /// assert!(is_odd(999_999).run());
+    /// ```
+    pub fn run(self) -> T {
+        let mut current = self;
+
+        loop {
+            match current {
+                Bounce::Done(value) => return value,
+                Bounce::Call(f) => current = f(),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 非尾递归的求和，改写为续延传递风格
+    fn sum(n: u64, k: Box<dyn FnOnce(u64) -> Bounce<u64>>) -> Bounce<u64> {
+        if n == 0 {
+            return Bounce::call(move || k(0));
+        }
+
+        Bounce::call(move || sum(n - 1, Box::new(move |s| Bounce::call(move || k(s + n)))))
+    }
+
+    #[test]
+    fn trampoline_should_not_overflow_stack() {
+        let n = 1_000_000;
+
+        assert_eq!(sum(n, Box::new(Bounce::Done)).run(), n * (n + 1) / 2);
+        assert_eq!(Bounce::Done(1).run(), 1);
+    }
+}
@@ -0,0 +1,264 @@
+//! 二叉树的 serde 序列化，需要启用 `serde` 特性
+//!
+//! 支持两种格式，由 [`TreeFormat`] 选择：
+//! * 层序数组（默认）：与 LeetCode 相同，按层序列出结点，空位用 null 表示，省略末尾的 null，
+//!   如 `[1, null, 2, 3]`
+//! * 嵌套对象：每个结点为 `{"value": 1, "left": ..., "right": ...}`，空子树为 null，
+//!   便于 JavaScript 的树可视化工具直接使用
+//!
+//! 反序列化时自动识别格式：数组按层序解析，对象或 null 按嵌套结构解析，嵌套对象中可以省略空子树。
+//! 嵌套格式的深度等于树高，serde_json 默认限制嵌套深度为 128，很深的树应使用层序数组。
+//!
+//! 二叉搜索树反序列化时按层序依次插入结点的值：对于合法的二叉搜索树，按层序插入得到的形状与原树相同。
+
+use std::collections::VecDeque;
+
+use serde::{
+    Deserialize, Deserializer, Serialize, Serializer,
+    ser::{SerializeSeq, SerializeStruct},
+};
+
+use crate::{
+    BinarySearchTree, BinaryTree,
+    binary_tree::{NodeRc, OptionNodeRc, TreeNode, level_slots},
+};
+
+/// 二叉树的序列化格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TreeFormat {
+    /// 层序数组，空位为 null
+    #[default]
+    LevelOrder,
+    /// 嵌套的 `{value, left, right}` 对象
+    Nested,
+}
+
+/// 按指定格式序列化二叉树，由 `serialize_as` 创建
+///
+/// # Examples
+///
+/// ```rust
+/// use base::{BinaryTree, tree_serde::TreeFormat};
+///
+/// let tree = BinaryTree::from([1, 2, 3]);
+///
+/// assert_eq!(serde_json::to_string(&tree).unwrap(), "[1,2,3]");
+/// assert_eq!(
+///     serde_json::to_string(&tree.serialize_as(TreeFormat::Nested)).unwrap(),
+///     r#"{"value":1,"left":{"value":2,"left":null,"right":null},"right":{"value":3,"left":null,"right":null}}"#
+/// );
+///
+/// // 两种格式都可以反序列化
+/// let tree: BinaryTree<i32> = serde_json::from_str(r#"{"value":1,"right":{"value":2}}"#).unwrap();
+/// assert_eq!(serde_json::to_string(&tree).unwrap(), "[1,null,2]");
+/// ```
+pub struct TreeSerializer<'a, T> {
+    root: &'a OptionNodeRc<T>,
+    format: TreeFormat,
+}
+
+impl<T: Serialize> Serialize for TreeSerializer<'_, T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.format {
+            TreeFormat::LevelOrder => serialize_level_order(self.root, serializer),
+            TreeFormat::Nested => Nested(self.root).serialize(serializer),
+        }
+    }
+}
+
+fn serialize_level_order<T, S>(root: &OptionNodeRc<T>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: Serialize,
+    S: Serializer,
+{
+    let slots = level_slots(root);
+
+    let mut seq = serializer.serialize_seq(Some(slots.len()))?;
+    for slot in &slots {
+        match slot {
+            Some(node) => seq.serialize_element(&Some(&node.borrow().value))?,
+            None => seq.serialize_element(&None::<&T>)?,
+        }
+    }
+    seq.end()
+}
+
+/// 以 root 为根的子树的嵌套格式
+struct Nested<'a, T>(&'a OptionNodeRc<T>);
+
+impl<T: Serialize> Serialize for Nested<'_, T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let Some(node) = self.0 else {
+            return serializer.serialize_none();
+        };
+
+        let node = node.borrow();
+        let mut state = serializer.serialize_struct("TreeNode", 3)?;
+        state.serialize_field("value", &node.value)?;
+        state.serialize_field("left", &Nested(&node.left))?;
+        state.serialize_field("right", &Nested(&node.right))?;
+        state.end()
+    }
+}
+
+/// 反序列化的中间表示，根据 JSON 的形状选择格式
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Repr<T> {
+    LevelOrder(Vec<Option<T>>),
+    Nested(Option<NestedNode<T>>),
+}
+
+#[derive(Deserialize)]
+struct NestedNode<T> {
+    value: T,
+    #[serde(default = "none")]
+    left: Option<Box<NestedNode<T>>>,
+    #[serde(default = "none")]
+    right: Option<Box<NestedNode<T>>>,
+}
+
+/// serde 的 `default` 属性会要求 `T: Default`，这里只需要 None
+fn none<T>() -> Option<T> {
+    None
+}
+
+impl<T> Repr<T> {
+    fn into_root(self) -> OptionNodeRc<T> {
+        match self {
+            Repr::LevelOrder(slots) => BinaryTree::from_level_vec(slots).root.take(),
+            Repr::Nested(root) => root.map(build_nested),
+        }
+    }
+}
+
+fn build_nested<T>(node: NestedNode<T>) -> NodeRc<T> {
+    let NestedNode { value, left, right } = node;
+    let rc = TreeNode::new_node_rc(value);
+    {
+        let mut rc = rc.borrow_mut();
+        rc.left = left.map(|left| build_nested(*left));
+        rc.right = right.map(|right| build_nested(*right));
+    }
+
+    rc
+}
+
+impl<T> BinaryTree<T> {
+    /// 按指定格式序列化
+    pub fn serialize_as(&self, format: TreeFormat) -> TreeSerializer<'_, T> {
+        TreeSerializer {
+            root: &self.root,
+            format,
+        }
+    }
+}
+
+impl<T> BinarySearchTree<T> {
+    /// 按指定格式序列化
+    pub fn serialize_as(&self, format: TreeFormat) -> TreeSerializer<'_, T> {
+        TreeSerializer {
+            root: &self.root,
+            format,
+        }
+    }
+}
+
+impl<T: Serialize> Serialize for BinaryTree<T> {
+    /// 序列化为层序数组
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.serialize_as(TreeFormat::LevelOrder)
+            .serialize(serializer)
+    }
+}
+
+impl<T: Serialize> Serialize for BinarySearchTree<T> {
+    /// 序列化为层序数组
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.serialize_as(TreeFormat::LevelOrder)
+            .serialize(serializer)
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for BinaryTree<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let root = Repr::deserialize(deserializer)?.into_root();
+
+        Ok(Self { root })
+    }
+}
+
+impl<'de, T: Deserialize<'de> + Ord + Clone> Deserialize<'de> for BinarySearchTree<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let root = Repr::<T>::deserialize(deserializer)?.into_root();
+
+        let mut tree = Self::new();
+        let mut queue = VecDeque::from_iter(root);
+        while let Some(node) = queue.pop_front() {
+            let node = node.borrow();
+            tree.insert(node.value.clone());
+            queue.extend(node.left.clone());
+            queue.extend(node.right.clone());
+        }
+
+        Ok(tree)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generate;
+
+    fn to_json<T: Serialize>(value: &T) -> String {
+        serde_json::to_string(value).unwrap()
+    }
+
+    #[test]
+    fn level_order_should_round_trip() {
+        for json in [
+            "[]",
+            "[1]",
+            "[1,2,3,4,5,6,7]",
+            "[1,null,2,3]",
+            "[5,4,8,11,null,13,4,7,2,null,null,null,1]",
+        ] {
+            let tree: BinaryTree<i32> = serde_json::from_str(json).unwrap();
+            assert_eq!(to_json(&tree), json);
+
+            // 转换为嵌套格式后再转换回来，结构不变
+            let nested = to_json(&tree.serialize_as(TreeFormat::Nested));
+            let tree: BinaryTree<i32> = serde_json::from_str(&nested).unwrap();
+            assert_eq!(to_json(&tree), json);
+        }
+
+        let empty: BinaryTree<i32> = serde_json::from_str("null").unwrap();
+        assert!(empty.root.is_none());
+        assert_eq!(to_json(&empty.serialize_as(TreeFormat::Nested)), "null");
+    }
+
+    #[test]
+    fn nested_format_should_match_tree_shape() {
+        let tree: BinaryTree<&str> = serde_json::from_str(r#"["a",null,"b","c"]"#).unwrap();
+
+        assert_eq!(
+            to_json(&tree.serialize_as(TreeFormat::Nested)),
+            r#"{"value":"a","left":null,"right":{"value":"b","left":{"value":"c","left":null,"right":null},"right":null}}"#
+        );
+        assert!(serde_json::from_str::<BinaryTree<i32>>(r#"{"left":null}"#).is_err());
+    }
+
+    #[test]
+    fn binary_search_tree_should_keep_shape() {
+        let values = generate::insertion_order(200, 3);
+        let (tree, _) = BinarySearchTree::from_iter_dedup(values);
+
+        for format in [TreeFormat::LevelOrder, TreeFormat::Nested] {
+            let json = to_json(&tree.serialize_as(format));
+            let restored: BinarySearchTree<u64> = serde_json::from_str(&json).unwrap();
+
+            assert_eq!(to_json(&restored), to_json(&tree));
+            assert_eq!(restored.to_vec(), (0..200).collect::<Vec<_>>());
+        }
+    }
+}
@@ -0,0 +1,283 @@
+//! 字典树（trie），又称前缀树
+//!
+//! 每条边表示一个字符，从根结点到某个结点的路径即为一个前缀，
+//! 结点上的标记表示该前缀是否为一个完整的单词。
+//! 插入和查找的时间复杂度均为 O(m)，m 为单词长度，与单词数量无关。
+//!
+//! 子结点使用 BTreeMap 保存，按字典序遍历即可得到有序的单词列表。
+//!
+//! 模糊查找（Levenshtein 自动机的思路）：在字典树上深度优先遍历，每个结点维护当前前缀与查询词
+//! 各前缀之间的编辑距离（动态规划表中的一行），子结点的行可由父结点的行在 O(m) 时间内算出，
+//! 共享前缀的单词只需计算一次。行中的最小值超过编辑距离上限时，整棵子树都可以剪枝。
+
+use std::collections::BTreeMap;
+
+#[derive(Debug, Default, Clone)]
+struct TrieNode {
+    children: BTreeMap<char, TrieNode>,
+    is_end: bool,
+}
+
+/// 字典树
+///
+/// # Examples
+///
+/// ```rust
+/// use base::Trie;
+///
+/// let trie = Trie::from_iter(["apple", "app", "apply", "bat"]);
+///
+/// assert!(trie.contains("app"));
+/// assert!(!trie.contains("ap"));
+/// assert!(trie.starts_with("ap"));
+/// assert_eq!(trie.words_with_prefix("appl"), ["apple", "apply"]);
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct Trie {
+    root: TrieNode,
+    len: usize,
+}
+
+impl Trie {
+    /// 创建空的字典树
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 单词数量
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// 判断是否为空
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// 插入单词，单词已存在时返回 false
+    pub fn insert(&mut self, word: &str) -> bool {
+        let node = word.chars().fold(&mut self.root, |node, c| {
+            node.children.entry(c).or_default()
+        });
+        if node.is_end {
+            return false;
+        }
+
+        node.is_end = true;
+        self.len += 1;
+        true
+    }
+
+    /// 判断是否包含单词
+    pub fn contains(&self, word: &str) -> bool {
+        self.find(word).is_some_and(|node| node.is_end)
+    }
+
+    /// 判断是否有以 prefix 为前缀的单词
+    pub fn starts_with(&self, prefix: &str) -> bool {
+        self.find(prefix).is_some()
+    }
+
+    /// 按字典序返回所有以 prefix 为前缀的单词
+    pub fn words_with_prefix(&self, prefix: &str) -> Vec<String> {
+        let mut words = Vec::new();
+        if let Some(node) = self.find(prefix) {
+            let mut word = prefix.to_string();
+            collect(node, &mut word, &mut words);
+        }
+
+        words
+    }
+
+    /// 查找与 word 的编辑距离不超过 max_edits 的所有单词，按字典序返回单词及其编辑距离
+    ///
+    /// 编辑操作为插入、删除和替换一个字符。深度为 i 的结点只需计算行中 [i - k, i + k] 的部分，
+    /// 其余位置的编辑距离一定超过上限 k（带状动态规划）。
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use base::Trie;
+    ///
+    /// let trie = Trie::from_iter(["hello", "help", "hell", "shell", "world"]);
+    ///
+    /// assert_eq!(
+    ///     trie.fuzzy_search("helo", 1),
+    ///     [("hell".to_string(), 1), ("hello".to_string(), 1), ("help".to_string(), 1)]
+    /// );
+    /// ```
+    pub fn fuzzy_search(&self, word: &str, max_edits: usize) -> Vec<(String, usize)> {
+        let word = word.chars().collect::<Vec<_>>();
+        // 空前缀与 word[..j] 的编辑距离为 j，超过上限的距离统一记为 max_edits + 1
+        let row = (0..=word.len())
+            .map(|j| j.min(max_edits + 1))
+            .collect::<Vec<_>>();
+
+        let mut search = FuzzySearch {
+            word: &word,
+            max_edits,
+            prefix: String::new(),
+            matches: Vec::new(),
+        };
+        search.visit(&self.root, &row);
+
+        search.matches
+    }
+
+    /// 沿 prefix 向下查找结点
+    fn find(&self, prefix: &str) -> Option<&TrieNode> {
+        prefix
+            .chars()
+            .try_fold(&self.root, |node, c| node.children.get(&c))
+    }
+}
+
+/// 深度优先遍历，收集 node 子树中的所有单词，word 为根结点到 node 的路径
+fn collect(node: &TrieNode, word: &mut String, words: &mut Vec<String>) {
+    if node.is_end {
+        words.push(word.clone());
+    }
+
+    for (&c, child) in &node.children {
+        word.push(c);
+        collect(child, word, words);
+        word.pop();
+    }
+}
+
+/// 模糊查找的状态
+struct FuzzySearch<'a> {
+    word: &'a [char],
+    max_edits: usize,
+    /// 根结点到当前结点的路径
+    prefix: String,
+    matches: Vec<(String, usize)>,
+}
+
+impl FuzzySearch<'_> {
+    /// row[j] 为当前前缀与 word[..j] 的编辑距离
+    fn visit(&mut self, node: &TrieNode, row: &[usize]) {
+        let (m, k) = (self.word.len(), self.max_edits);
+
+        if node.is_end && row[m] <= k {
+            self.matches.push((self.prefix.clone(), row[m]));
+        }
+
+        // 子结点的深度
+        let depth = self.prefix.chars().count() + 1;
+        let band = depth.saturating_sub(k).max(1)..=(depth + k).min(m);
+
+        for (&c, child) in &node.children {
+            let mut next = vec![k + 1; m + 1];
+            next[0] = depth.min(k + 1);
+            for j in band.clone() {
+                let substitute = row[j - 1] + usize::from(self.word[j - 1] != c);
+                next[j] = substitute.min(row[j] + 1).min(next[j - 1] + 1).min(k + 1);
+            }
+
+            // 剪枝：这一行的最小值已超过上限，继续向下只会更大
+            if next.iter().all(|&d| d > k) {
+                continue;
+            }
+
+            self.prefix.push(c);
+            self.visit(child, &next);
+            self.prefix.pop();
+        }
+    }
+}
+
+impl<'a> FromIterator<&'a str> for Trie {
+    fn from_iter<I: IntoIterator<Item = &'a str>>(iter: I) -> Self {
+        let mut trie = Trie::new();
+
+        iter.into_iter().for_each(|word| {
+            trie.insert(word);
+        });
+
+        trie
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn trie_basics_should_work() {
+        let mut trie = Trie::new();
+
+        assert!(trie.insert("tea"));
+        assert!(trie.insert("ten"));
+        assert!(trie.insert("to"));
+        assert!(trie.insert("茶叶"));
+        assert!(!trie.insert("tea"));
+        assert_eq!(trie.len(), 4);
+
+        assert!(trie.contains("ten"));
+        assert!(!trie.contains("te"));
+        assert!(trie.starts_with("te"));
+        assert!(trie.starts_with("茶"));
+        assert!(!trie.starts_with("x"));
+
+        assert_eq!(trie.words_with_prefix("t"), ["tea", "ten", "to"]);
+        assert_eq!(trie.words_with_prefix(""), ["tea", "ten", "to", "茶叶"]);
+        assert!(trie.words_with_prefix("z").is_empty());
+
+        assert_eq!(
+            trie.fuzzy_search("tex", 1),
+            [("tea".to_string(), 1), ("ten".to_string(), 1)]
+        );
+
+        // 空字符串也可以作为单词
+        assert!(!trie.contains(""));
+        assert!(trie.insert(""));
+        assert!(trie.contains(""));
+    }
+
+    fn levenshtein(a: &str, b: &str) -> usize {
+        let b = b.chars().collect::<Vec<_>>();
+        let mut row = (0..=b.len()).collect::<Vec<_>>();
+
+        for (i, ca) in a.chars().enumerate() {
+            let mut next = vec![i + 1; b.len() + 1];
+            for j in 1..=b.len() {
+                next[j] = (row[j - 1] + usize::from(ca != b[j - 1]))
+                    .min(row[j] + 1)
+                    .min(next[j - 1] + 1);
+            }
+            row = next;
+        }
+
+        row[b.len()]
+    }
+
+    #[test]
+    fn fuzzy_search_should_match_brute_force() {
+        let words = crate::generate::uniform(300, 8)
+            .into_iter()
+            .map(|x| {
+                let len = 1 + x % 6;
+                (0..len)
+                    .map(|i| (b'a' + (x >> (8 * i) & 3) as u8) as char)
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>();
+        let trie = words.iter().map(String::as_str).collect::<Trie>();
+
+        let mut dictionary = words.clone();
+        dictionary.sort();
+        dictionary.dedup();
+
+        for query in ["", "a", "abc", "dddd", "abcdab", "bad"] {
+            for max_edits in 0..=3 {
+                let expected = dictionary
+                    .iter()
+                    .map(|w| (w.clone(), levenshtein(w, query)))
+                    .filter(|&(_, d)| d <= max_edits)
+                    .collect::<Vec<_>>();
+                assert_eq!(trie.fuzzy_search(query, max_edits), expected);
+            }
+        }
+    }
+}
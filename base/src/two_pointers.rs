@@ -0,0 +1,243 @@
+//! 双指针与滑动窗口
+//!
+//! * 对撞指针：两个指针分别从两端向中间移动，每步根据当前结果排除一端，
+//!   将 O(n²) 的枚举降为 O(n)，通常要求数组有序或具有单调性
+//! * 滑动窗口：两个指针同向移动，右指针扩张窗口，左指针在窗口不满足条件（或已满足条件）时收缩，
+//!   每个元素最多进出窗口各一次，时间复杂度 O(n)
+
+use std::{cmp::Ordering, collections::HashMap};
+
+/// 在升序数组中查找和为 target 的两个元素，返回其下标 (i, j)，i < j，时间复杂度：O(n)
+///
+/// # Examples
+///
+/// ```rust
+/// use base::two_pointers;
+///
+/// assert_eq!(two_pointers::two_sum_sorted(&[2, 7, 11, 15], 9), Some((0, 1)));
+/// assert_eq!(two_pointers::two_sum_sorted(&[2, 7, 11, 15], 10), None);
+/// ```
+pub fn two_sum_sorted(v: &[i64], target: i64) -> Option<(usize, usize)> {
+    let (mut i, mut j) = (0, v.len().checked_sub(1)?);
+
+    while i < j {
+        match (v[i] + v[j]).cmp(&target) {
+            // 和偏小，v[i] 与任何元素的和都不会等于 target
+            Ordering::Less => i += 1,
+            // 和偏大，v[j] 与任何元素的和都不会等于 target
+            Ordering::Greater => j -= 1,
+            Ordering::Equal => return Some((i, j)),
+        }
+    }
+
+    None
+}
+
+/// 找出所有和为 0 且互不重复的三元组，每个三元组升序排列，时间复杂度：O(n²)
+///
+/// # Examples
+///
+/// ```rust
+/// use base::two_pointers;
+///
+/// let triples = two_pointers::three_sum(&[-1, 0, 1, 2, -1, -4]);
+///
+/// assert_eq!(triples, vec![[-1, -1, 2], [-1, 0, 1]]);
+/// ```
+pub fn three_sum(v: &[i64]) -> Vec<[i64; 3]> {
+    let mut v = v.to_vec();
+    v.sort();
+    let mut triples = Vec::new();
+
+    for i in 0..v.len() {
+        // 跳过重复的第一个元素
+        if i > 0 && v[i] == v[i - 1] {
+            continue;
+        }
+
+        // 在 v[i] 之后使用对撞指针查找和为 -v[i] 的两个元素
+        let (mut lo, mut hi) = (i + 1, v.len());
+        while lo + 1 < hi {
+            match (v[i] + v[lo] + v[hi - 1]).cmp(&0) {
+                Ordering::Less => lo += 1,
+                Ordering::Greater => hi -= 1,
+                Ordering::Equal => {
+                    triples.push([v[i], v[lo], v[hi - 1]]);
+                    // 跳过重复的第二个元素
+                    lo += 1;
+                    while lo + 1 < hi && v[lo] == v[lo - 1] {
+                        lo += 1;
+                    }
+                }
+            }
+        }
+    }
+
+    triples
+}
+
+/// 盛最多水的容器：任选两条竖线与 x 轴构成容器，返回最大容量，时间复杂度：O(n)
+///
+/// 容量由较短的一侧决定，移动较长的一侧只会让宽度变小而高度不变大，因此每步移动较短的一侧。
+///
+/// # Examples
+///
+/// ```rust
+/// use base::two_pointers;
+///
+/// assert_eq!(two_pointers::container_with_most_water(&[1, 8, 6, 2, 5, 4, 8, 3, 7]), 49);
+/// ```
+pub fn container_with_most_water(heights: &[u64]) -> u64 {
+    let (mut i, mut j) = (0, heights.len().saturating_sub(1));
+    let mut max = 0;
+
+    while i < j {
+        let width = (j - i) as u64;
+        max = max.max(width * heights[i].min(heights[j]));
+        if heights[i] < heights[j] {
+            i += 1;
+        } else {
+            j -= 1;
+        }
+    }
+
+    max
+}
+
+/// 最小覆盖子串：返回 s 中包含 t 的所有字符（含重复）的最短子串，不存在时返回 None，
+/// 时间复杂度：O(|s| + |t|)
+///
+/// # Examples
+///
+/// ```rust
+/// use base::two_pointers;
+///
+/// assert_eq!(two_pointers::min_window_substring("ADOBECODEBANC", "ABC"), Some("BANC"));
+/// assert_eq!(two_pointers::min_window_substring("a", "aa"), None);
+/// ```
+pub fn min_window_substring<'a>(s: &'a str, t: &str) -> Option<&'a str> {
+    // 窗口内还缺少的各字符数量，负数表示多余
+    let mut need = HashMap::new();
+    for c in t.chars() {
+        *need.entry(c).or_insert(0) += 1;
+    }
+    let mut missing = t.chars().count();
+    if missing == 0 {
+        return Some("");
+    }
+
+    let chars = s.char_indices().collect::<Vec<_>>();
+    let mut best: Option<(usize, usize)> = None;
+    let mut left = 0;
+
+    for &(i, c) in &chars {
+        // 右指针扩张窗口
+        if let Some(count) = need.get_mut(&c) {
+            if *count > 0 {
+                missing -= 1;
+            }
+            *count -= 1;
+        }
+
+        // 窗口已覆盖 t，左指针收缩到不能再收缩为止
+        while missing == 0 {
+            let start = chars[left].0;
+            let end = i + c.len_utf8();
+            if best.is_none_or(|(lo, hi)| end - start < hi - lo) {
+                best = Some((start, end));
+            }
+
+            if let Some(count) = need.get_mut(&chars[left].1) {
+                *count += 1;
+                if *count > 0 {
+                    missing += 1;
+                }
+            }
+            left += 1;
+        }
+    }
+
+    best.map(|(start, end)| &s[start..end])
+}
+
+/// 无重复字符的最长子串，有多个时返回最靠前的一个，时间复杂度：O(n)
+///
+/// # Examples
+///
+/// ```rust
+/// use base::two_pointers;
+///
+/// assert_eq!(two_pointers::longest_substring_without_repeat("abcabcbb"), "abc");
+/// assert_eq!(two_pointers::longest_substring_without_repeat("pwwkew"), "wke");
+/// ```
+pub fn longest_substring_without_repeat(s: &str) -> &str {
+    // 各字符最近一次出现之后的位置
+    let mut last = HashMap::new();
+    let (mut start, mut best) = (0, (0, 0));
+
+    for (i, c) in s.char_indices() {
+        let end = i + c.len_utf8();
+        // 字符在窗口内重复出现，左指针直接跳到上次出现之后
+        if let Some(&after) = last.get(&c) {
+            start = start.max(after);
+        }
+        last.insert(c, end);
+
+        if end - start > best.1 - best.0 {
+            best = (start, end);
+        }
+    }
+
+    &s[best.0..best.1]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn two_sum_sorted_should_work() {
+        assert_eq!(two_sum_sorted(&[], 0), None);
+        assert_eq!(two_sum_sorted(&[5], 10), None);
+        assert_eq!(two_sum_sorted(&[-3, -1, 0, 4, 9], 3), Some((1, 3)));
+        assert_eq!(two_sum_sorted(&[1, 2, 2, 3], 4), Some((0, 3)));
+    }
+
+    #[test]
+    fn three_sum_should_work() {
+        assert!(three_sum(&[]).is_empty());
+        assert_eq!(three_sum(&[0, 0, 0, 0]), vec![[0, 0, 0]]);
+        assert_eq!(
+            three_sum(&[-2, 0, 1, 1, 2, -1, -4, 3]),
+            vec![[-4, 1, 3], [-2, -1, 3], [-2, 0, 2], [-2, 1, 1], [-1, 0, 1]]
+        );
+    }
+
+    #[test]
+    fn container_with_most_water_should_work() {
+        assert_eq!(container_with_most_water(&[]), 0);
+        assert_eq!(container_with_most_water(&[3]), 0);
+        assert_eq!(container_with_most_water(&[1, 1]), 1);
+        assert_eq!(container_with_most_water(&[4, 3, 2, 1, 4]), 16);
+    }
+
+    #[test]
+    fn min_window_substring_should_work() {
+        assert_eq!(min_window_substring("abc", ""), Some(""));
+        assert_eq!(min_window_substring("aa", "aa"), Some("aa"));
+        assert_eq!(min_window_substring("xyz", "a"), None);
+        assert_eq!(
+            min_window_substring("cabwefgewcwaefgcf", "cae"),
+            Some("cwae")
+        );
+        assert_eq!(min_window_substring("你好世界你好", "好你"), Some("你好"));
+    }
+
+    #[test]
+    fn longest_substring_without_repeat_should_work() {
+        assert_eq!(longest_substring_without_repeat(""), "");
+        assert_eq!(longest_substring_without_repeat("bbbbb"), "b");
+        assert_eq!(longest_substring_without_repeat("abba"), "ab");
+        assert_eq!(longest_substring_without_repeat("一二三一二四"), "三一二四");
+    }
+}
@@ -0,0 +1,505 @@
+//! van Emde Boas 树（vEB tree）：固定值域上的整数集合
+//!
+//! 比较排序的下界决定了基于比较的有序集合（如 AVL 树）的查找后继需要 O(logn)，
+//! vEB 树则直接利用键的二进制表示：把 w 位的键拆成高 w/2 位和低 w/2 位，
+//! 高位相同的键放在同一个簇（cluster）中，簇本身是值域为 2^(w/2) 的 vEB 树，
+//! 另用一棵同样大小的摘要树（summary）记录哪些簇非空。
+//!
+//! 查找后继时，先看当前簇中是否还有更大的键，只需比较簇的最大值；没有时再在摘要树中查找下一个非空簇，
+//! 每一步只递归进入一棵位数减半的子树，时间复杂度为 O(loglogU)，U = 2^w 为值域大小。
+//! 插入和删除同样只递归一次，关键在于每个结点的最小值不放入簇中：
+//! 向空簇插入时只需设置其最小值，是 O(1) 的，于是另一次递归（在摘要树中插入）不会继续向下。
+//!
+//! 两处实现上的取舍：
+//! * 簇保存在哈希表中，只为非空的簇分配空间，空间复杂度为 O(nlogw) 而不是 O(U)
+//! * 位数不超过 6 的子树直接用一个 u64 的位图表示，用 trailing_zeros / leading_zeros 查找后继和前驱
+
+use crate::ChainedHashMap;
+
+/// 位数不超过该值时使用位图
+const LEAF_BITS: u32 = 6;
+
+#[derive(Debug, Clone)]
+enum VebNode {
+    /// 值域为 2^bits（bits <= LEAF_BITS）的位图
+    Leaf(u64),
+    Branch(Box<Branch>),
+}
+
+#[derive(Debug, Clone)]
+struct Branch {
+    /// 低位的位数，簇的值域为 2^low_bits
+    low_bits: u32,
+    /// (最小值, 最大值)，最小值不放入簇中
+    min_max: Option<(u64, u64)>,
+    /// 记录非空簇的编号
+    summary: VebNode,
+    /// 高位 -> 簇，只保存非空的簇
+    clusters: ChainedHashMap<u64, VebNode>,
+}
+
+impl VebNode {
+    /// 值域为 2^bits 的空结点
+    fn new(bits: u32) -> Self {
+        if bits <= LEAF_BITS {
+            return Self::Leaf(0);
+        }
+
+        let low_bits = bits / 2;
+        Self::Branch(Box::new(Branch {
+            low_bits,
+            min_max: None,
+            summary: VebNode::new(bits - low_bits),
+            clusters: ChainedHashMap::new(),
+        }))
+    }
+
+    fn is_empty(&self) -> bool {
+        match self {
+            Self::Leaf(mask) => *mask == 0,
+            Self::Branch(branch) => branch.min_max.is_none(),
+        }
+    }
+
+    fn min(&self) -> Option<u64> {
+        match self {
+            Self::Leaf(0) => None,
+            Self::Leaf(mask) => Some(mask.trailing_zeros() as u64),
+            Self::Branch(branch) => branch.min_max.map(|(min, _)| min),
+        }
+    }
+
+    fn max(&self) -> Option<u64> {
+        match self {
+            Self::Leaf(0) => None,
+            Self::Leaf(mask) => Some(63 - mask.leading_zeros() as u64),
+            Self::Branch(branch) => branch.min_max.map(|(_, max)| max),
+        }
+    }
+
+    fn contains(&self, x: u64) -> bool {
+        match self {
+            Self::Leaf(mask) => mask >> x & 1 == 1,
+            Self::Branch(branch) => match branch.min_max {
+                None => false,
+                Some((min, max)) if x == min || x == max => true,
+                Some(_) => {
+                    let (high, low) = branch.split(x);
+                    branch
+                        .clusters
+                        .get(&high)
+                        .is_some_and(|cluster| cluster.contains(low))
+                }
+            },
+        }
+    }
+
+    /// 插入 x，已存在时返回 false
+    fn insert(&mut self, x: u64) -> bool {
+        let branch = match self {
+            Self::Leaf(mask) => {
+                let inserted = *mask >> x & 1 == 0;
+                *mask |= 1 << x;
+                return inserted;
+            }
+            Self::Branch(branch) => branch,
+        };
+
+        let Some((min, max)) = branch.min_max else {
+            branch.min_max = Some((x, x));
+            return true;
+        };
+        if x == min {
+            return false;
+        }
+
+        // 比最小值小时成为新的最小值，原来的最小值放入簇中
+        let (new_min, x) = if x < min { (x, min) } else { (min, x) };
+        let (high, low) = branch.split(x);
+        let low_bits = branch.low_bits;
+        let cluster = branch
+            .clusters
+            .entry(high)
+            .or_insert_with(|| VebNode::new(low_bits));
+        if cluster.is_empty() {
+            // 空簇的插入是 O(1) 的，递归只发生在摘要树中
+            branch.summary.insert(high);
+            cluster.insert(low);
+        } else if !cluster.insert(low) {
+            return false;
+        }
+
+        branch.min_max = Some((new_min, max.max(x)));
+        true
+    }
+
+    /// 删除 x，不存在时返回 false
+    fn remove(&mut self, x: u64) -> bool {
+        let branch = match self {
+            Self::Leaf(mask) => {
+                let removed = *mask >> x & 1 == 1;
+                *mask &= !(1 << x);
+                return removed;
+            }
+            Self::Branch(branch) => branch,
+        };
+
+        let Some((mut min, max)) = branch.min_max else {
+            return false;
+        };
+        if min == max {
+            if x != min {
+                return false;
+            }
+            branch.min_max = None;
+            return true;
+        }
+
+        let mut x = x;
+        if x == min {
+            // 删除最小值：把第一个簇中的最小值取出作为新的最小值，再将其从簇中删除
+            // Safety: 至少有两个元素，摘要树和对应的簇都非空
+            let high = branch.summary.min().unwrap();
+            let low = branch.clusters.get(&high).and_then(VebNode::min).unwrap();
+            x = branch.join(high, low);
+            min = x;
+        }
+
+        let (high, low) = branch.split(x);
+        let Some(cluster) = branch.clusters.get_mut(&high) else {
+            return false;
+        };
+        if !cluster.remove(low) {
+            return false;
+        }
+        if cluster.is_empty() {
+            branch.clusters.remove(&high);
+            branch.summary.remove(high);
+        }
+
+        let max = if x == max {
+            // 最大值在最后一个非空簇中，没有非空簇时只剩下最小值
+            match branch.summary.max() {
+                Some(high) => branch.join(high, branch.cluster_max(high)),
+                None => min,
+            }
+        } else {
+            max
+        };
+        branch.min_max = Some((min, max));
+
+        true
+    }
+
+    /// 大于 x 的最小值
+    fn successor(&self, x: u64) -> Option<u64> {
+        let branch = match self {
+            Self::Leaf(mask) => {
+                let above = if x >= 63 {
+                    0
+                } else {
+                    mask & (u64::MAX << (x + 1))
+                };
+                return (above != 0).then(|| above.trailing_zeros() as u64);
+            }
+            Self::Branch(branch) => branch,
+        };
+
+        let (min, max) = branch.min_max?;
+        if x < min {
+            return Some(min);
+        } else if x >= max {
+            return None;
+        }
+
+        let (high, low) = branch.split(x);
+        if let Some(cluster) = branch.clusters.get(&high)
+            && cluster.max().is_some_and(|cluster_max| low < cluster_max)
+        {
+            // Safety: 簇中存在比 low 大的值
+            return Some(branch.join(high, cluster.successor(low).unwrap()));
+        }
+
+        // Safety: x < max，后面一定还有非空簇
+        let high = branch.summary.successor(high).unwrap();
+        Some(branch.join(high, branch.cluster_min(high)))
+    }
+
+    /// 小于 x 的最大值
+    fn predecessor(&self, x: u64) -> Option<u64> {
+        let branch = match self {
+            Self::Leaf(mask) => {
+                let below = mask & ((1 << x) - 1);
+                return (below != 0).then(|| 63 - below.leading_zeros() as u64);
+            }
+            Self::Branch(branch) => branch,
+        };
+
+        let (min, max) = branch.min_max?;
+        if x > max {
+            return Some(max);
+        } else if x <= min {
+            return None;
+        }
+
+        let (high, low) = branch.split(x);
+        if let Some(cluster) = branch.clusters.get(&high)
+            && cluster.min().is_some_and(|cluster_min| cluster_min < low)
+        {
+            // Safety: 簇中存在比 low 小的值
+            return Some(branch.join(high, cluster.predecessor(low).unwrap()));
+        }
+
+        // 前面没有非空簇时，最小值就是前驱
+        match branch.summary.predecessor(high) {
+            Some(high) => Some(branch.join(high, branch.cluster_max(high))),
+            None => Some(min),
+        }
+    }
+}
+
+impl Branch {
+    /// 拆分为 (高位, 低位)
+    fn split(&self, x: u64) -> (u64, u64) {
+        (x >> self.low_bits, x & ((1 << self.low_bits) - 1))
+    }
+
+    /// 由高位和低位拼接
+    fn join(&self, high: u64, low: u64) -> u64 {
+        high << self.low_bits | low
+    }
+
+    /// 非空簇的最小值
+    fn cluster_min(&self, high: u64) -> u64 {
+        // Safety: 只对摘要树中记录的非空簇调用
+        self.clusters.get(&high).and_then(VebNode::min).unwrap()
+    }
+
+    /// 非空簇的最大值
+    fn cluster_max(&self, high: u64) -> u64 {
+        // Safety: 同上
+        self.clusters.get(&high).and_then(VebNode::max).unwrap()
+    }
+}
+
+/// 值域为 [0, 2^bits) 的整数集合，基于 van Emde Boas 树
+///
+/// # Examples
+///
+/// ```rust
+/// use base::VebSet;
+///
+/// let mut set = VebSet::new(16);
+/// for x in [3, 1000, 42, 65535, 7] {
+///     set.insert(x);
+/// }
+///
+/// assert_eq!(set.successor(42), Some(1000));
+/// assert_eq!(set.predecessor(42), Some(7));
+/// assert_eq!(set.successor(65535), None);
+/// assert_eq!((set.min(), set.max()), (Some(3), Some(65535)));
+///
+/// assert!(set.remove(1000));
+/// assert_eq!(set.successor(42), Some(65535));
+/// assert_eq!(set.iter().collect::<Vec<_>>(), [3, 7, 42, 65535]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct VebSet {
+    root: VebNode,
+    bits: u32,
+    len: usize,
+}
+
+impl VebSet {
+    /// 创建值域为 [0, 2^bits) 的空集合
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bits` is 0 or greater than 64.
+    pub fn new(bits: u32) -> Self {
+        assert!(
+            (1..=64).contains(&bits),
+            "Universe bits must be in 1..=64, got {bits}"
+        );
+
+        Self {
+            root: VebNode::new(bits),
+            bits,
+            len: 0,
+        }
+    }
+
+    /// 值域的位数
+    pub fn universe_bits(&self) -> u32 {
+        self.bits
+    }
+
+    /// 元素数量
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// 判断是否为空
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// 插入 x，已存在时返回 false，时间复杂度：O(loglogU)
+    ///
+    /// # Panics
+    ///
+    /// Panics if `x` is outside the universe.
+    pub fn insert(&mut self, x: u64) -> bool {
+        self.check(x);
+        let inserted = self.root.insert(x);
+        self.len += inserted as usize;
+
+        inserted
+    }
+
+    /// 删除 x，不存在时返回 false，时间复杂度：O(loglogU)
+    pub fn remove(&mut self, x: u64) -> bool {
+        if !self.in_universe(x) {
+            return false;
+        }
+
+        let removed = self.root.remove(x);
+        self.len -= removed as usize;
+
+        removed
+    }
+
+    /// 判断是否包含 x
+    pub fn contains(&self, x: u64) -> bool {
+        self.in_universe(x) && self.root.contains(x)
+    }
+
+    /// 最小值
+    pub fn min(&self) -> Option<u64> {
+        self.root.min()
+    }
+
+    /// 最大值
+    pub fn max(&self) -> Option<u64> {
+        self.root.max()
+    }
+
+    /// 大于 x 的最小值，时间复杂度：O(loglogU)
+    pub fn successor(&self, x: u64) -> Option<u64> {
+        if !self.in_universe(x) {
+            return None;
+        }
+
+        self.root.successor(x)
+    }
+
+    /// 小于 x 的最大值，时间复杂度：O(loglogU)
+    pub fn predecessor(&self, x: u64) -> Option<u64> {
+        if !self.in_universe(x) {
+            return self.max();
+        }
+
+        self.root.predecessor(x)
+    }
+
+    /// 按升序遍历所有元素
+    pub fn iter(&self) -> impl Iterator<Item = u64> + '_ {
+        std::iter::successors(self.min(), |&x| self.root.successor(x))
+    }
+
+    fn in_universe(&self, x: u64) -> bool {
+        self.bits == 64 || x >> self.bits == 0
+    }
+
+    fn check(&self, x: u64) {
+        assert!(
+            self.in_universe(x),
+            "Key {x} out of universe of {} bits",
+            self.bits
+        );
+    }
+}
+
+impl Extend<u64> for VebSet {
+    fn extend<I: IntoIterator<Item = u64>>(&mut self, iter: I) {
+        for x in iter {
+            self.insert(x);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use super::*;
+    use crate::generate::{self, Operation, Rng};
+
+    #[test]
+    fn veb_set_should_match_btree_set() {
+        for bits in [1, 5, 6, 7, 12, 20] {
+            let mut set = VebSet::new(bits);
+            let mut expected = BTreeSet::new();
+            let mut rng = Rng::new(bits as u64);
+            let keys = 1_u64 << bits.min(9);
+
+            for op in generate::operations(3000, keys, bits as u64) {
+                // 把键分散到整个值域
+                let spread = |key: u64| key << (bits - bits.min(9));
+                match op {
+                    Operation::Insert(key) => {
+                        assert_eq!(set.insert(spread(key)), expected.insert(spread(key)));
+                    }
+                    Operation::Remove(key) => {
+                        assert_eq!(set.remove(spread(key)), expected.remove(&spread(key)));
+                    }
+                    Operation::Get(key) => {
+                        assert_eq!(set.contains(spread(key)), expected.contains(&spread(key)));
+                    }
+                }
+
+                let x = rng.below(1 << bits);
+                assert_eq!(set.successor(x), expected.range(x + 1..).next().copied());
+                assert_eq!(set.predecessor(x), expected.range(..x).next_back().copied());
+                assert_eq!(set.min(), expected.first().copied());
+                assert_eq!(set.max(), expected.last().copied());
+                assert_eq!(set.len(), expected.len());
+            }
+
+            assert!(set.iter().eq(expected.iter().copied()));
+        }
+    }
+
+    #[test]
+    fn veb_set_should_handle_full_universe() {
+        let mut set = VebSet::new(64);
+        let keys = generate::uniform(2000, 3);
+        set.extend(keys.iter().copied());
+        set.extend([0, u64::MAX]);
+        let expected = keys
+            .into_iter()
+            .chain([0, u64::MAX])
+            .collect::<BTreeSet<_>>();
+
+        assert!(set.iter().eq(expected.iter().copied()));
+        let mut rng = Rng::new(8);
+        for _ in 0..500 {
+            let x = rng.below(u64::MAX);
+            assert_eq!(set.successor(x), expected.range(x + 1..).next().copied());
+            assert_eq!(set.predecessor(x), expected.range(..x).next_back().copied());
+        }
+
+        // 值域之外的键
+        let mut small = VebSet::new(8);
+        assert!(!small.contains(256) && !small.remove(256));
+        small.insert(255);
+        assert_eq!(small.predecessor(1000), Some(255));
+        assert_eq!(small.successor(1000), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "out of universe")]
+    fn veb_set_insert_should_panic_out_of_universe() {
+        VebSet::new(8).insert(256);
+    }
+}
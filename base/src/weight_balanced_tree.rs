@@ -0,0 +1,406 @@
+//! 重量平衡树（weight-balanced tree），又称 BB[α] 树
+//!
+//! AVL 树用子树高度判断是否失衡，重量平衡树则用子树的大小：记 w(t) = size(t) + 1 为子树的重量，
+//! 要求每个结点的两棵子树的重量之比不超过 Δ，即
+//!
+//! w(left) * Δ >= w(right) 且 w(right) * Δ >= w(left)
+//!
+//! 插入或删除后，沿着路径自底向上检查每个结点，失衡时通过单旋转或双旋转恢复平衡：
+//! 以右子树过重为例，若右子树的左右两侧重量之比小于 Γ 则单旋转（左旋），否则先右旋右子树再左旋。
+//! 这里取 (Δ, Γ) = (3, 2)，这是已证明一次插入或删除后单次旋转即可恢复平衡的唯一整数参数。
+//!
+//! 平衡条件保证树高为 O(logn)，并且每个结点本来就保存了子树大小，
+//! 因此可以在 O(logn) 时间内完成按排名查找（select）和求排名（rank）等顺序统计查询，
+//! 而 AVL 树需要额外维护这一信息。
+
+use std::cmp::Ordering;
+
+use crate::OrderedSet;
+
+/// 两棵子树的重量之比的上限
+const DELTA: usize = 3;
+/// 判断单旋转还是双旋转的阈值
+const GAMMA: usize = 2;
+
+type Link<T> = Option<Box<WbtNode<T>>>;
+
+#[derive(Debug, Clone)]
+struct WbtNode<T> {
+    value: T,
+    /// 子树中结点的数量
+    size: usize,
+    left: Link<T>,
+    right: Link<T>,
+}
+
+impl<T> WbtNode<T> {
+    fn new(value: T) -> Box<Self> {
+        Box::new(Self {
+            value,
+            size: 1,
+            left: None,
+            right: None,
+        })
+    }
+
+    fn update_size(&mut self) {
+        self.size = size(&self.left) + size(&self.right) + 1;
+    }
+}
+
+/// 重量平衡树
+///
+/// # Examples
+///
+/// ```rust
+/// use base::WeightBalancedTree;
+///
+/// let mut tree = WeightBalancedTree::from_iter([50, 10, 40, 20, 30]);
+///
+/// assert!(tree.insert(60));
+/// assert!(!tree.insert(10));
+/// assert!(tree.remove(&40));
+///
+/// assert!(tree.iter().eq(&[10, 20, 30, 50, 60]));
+/// // 第 2 小的元素（从 0 开始）和小于 55 的元素数量
+/// assert_eq!(tree.select(2), Some(&30));
+/// assert_eq!(tree.rank(&55), 4);
+/// ```
+#[derive(Debug, Clone)]
+pub struct WeightBalancedTree<T> {
+    root: Link<T>,
+}
+
+impl<T> WeightBalancedTree<T> {
+    /// 创建空树
+    pub fn new() -> Self {
+        Self { root: None }
+    }
+
+    /// 元素数量
+    pub fn len(&self) -> usize {
+        size(&self.root)
+    }
+
+    /// 判断树是否为空
+    pub fn is_empty(&self) -> bool {
+        self.root.is_none()
+    }
+
+    /// 第 k 小的元素（从 0 开始），k >= len 时返回 None，时间复杂度：O(logn)
+    pub fn select(&self, mut k: usize) -> Option<&T> {
+        let mut current = &self.root;
+
+        while let Some(node) = current {
+            let left = size(&node.left);
+            match k.cmp(&left) {
+                Ordering::Less => current = &node.left,
+                Ordering::Equal => return Some(&node.value),
+                Ordering::Greater => {
+                    k -= left + 1;
+                    current = &node.right;
+                }
+            }
+        }
+
+        None
+    }
+
+    /// 按升序遍历
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        let mut stack = Vec::new();
+        let mut current = self.root.as_deref();
+
+        std::iter::from_fn(move || {
+            // 先一路向左，再访问结点，最后转向右子树
+            while let Some(node) = current {
+                stack.push(node);
+                current = node.left.as_deref();
+            }
+
+            let node = stack.pop()?;
+            current = node.right.as_deref();
+
+            Some(&node.value)
+        })
+    }
+}
+
+impl<T: Ord> WeightBalancedTree<T> {
+    /// 插入元素，树中已存在相同的元素时不插入并返回 false，时间复杂度：O(logn)
+    pub fn insert(&mut self, value: T) -> bool {
+        insert(&mut self.root, value)
+    }
+
+    /// 删除元素，元素不存在时返回 false，时间复杂度：O(logn)
+    pub fn remove(&mut self, value: &T) -> bool {
+        remove(&mut self.root, value)
+    }
+
+    /// 判断树中是否包含元素
+    pub fn contains(&self, value: &T) -> bool {
+        let mut current = &self.root;
+
+        while let Some(node) = current {
+            match value.cmp(&node.value) {
+                Ordering::Less => current = &node.left,
+                Ordering::Equal => return true,
+                Ordering::Greater => current = &node.right,
+            }
+        }
+
+        false
+    }
+
+    /// 小于 value 的元素数量，时间复杂度：O(logn)
+    pub fn rank(&self, value: &T) -> usize {
+        let mut current = &self.root;
+        let mut rank = 0;
+
+        while let Some(node) = current {
+            match value.cmp(&node.value) {
+                Ordering::Less => current = &node.left,
+                Ordering::Equal => return rank + size(&node.left),
+                Ordering::Greater => {
+                    rank += size(&node.left) + 1;
+                    current = &node.right;
+                }
+            }
+        }
+
+        rank
+    }
+}
+
+impl<T> Default for WeightBalancedTree<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Ord> Extend<T> for WeightBalancedTree<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for value in iter {
+            self.insert(value);
+        }
+    }
+}
+
+impl<T: Ord> FromIterator<T> for WeightBalancedTree<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut tree = Self::new();
+
+        tree.extend(iter);
+        tree
+    }
+}
+
+impl<T: Ord> OrderedSet<T> for WeightBalancedTree<T> {
+    fn insert(&mut self, value: T) -> bool {
+        WeightBalancedTree::insert(self, value)
+    }
+
+    fn remove(&mut self, value: &T) -> bool {
+        WeightBalancedTree::remove(self, value)
+    }
+
+    fn contains(&self, value: &T) -> bool {
+        WeightBalancedTree::contains(self, value)
+    }
+}
+
+fn size<T>(link: &Link<T>) -> usize {
+    link.as_ref().map_or(0, |node| node.size)
+}
+
+/// 子树的重量，空子树的重量为 1
+fn weight<T>(link: &Link<T>) -> usize {
+    size(link) + 1
+}
+
+// 左旋操作
+fn rotate_left<T>(mut node: Box<WbtNode<T>>) -> Box<WbtNode<T>> {
+    // Safety: 只在右子树过重时左旋，右子树一定不为空
+    let mut child = node.right.take().unwrap();
+
+    node.right = child.left.take();
+    node.update_size();
+    child.left = Some(node);
+    child.update_size();
+
+    child
+}
+
+// 右旋操作
+fn rotate_right<T>(mut node: Box<WbtNode<T>>) -> Box<WbtNode<T>> {
+    // Safety: 只在左子树过重时右旋，左子树一定不为空
+    let mut child = node.left.take().unwrap();
+
+    node.left = child.right.take();
+    node.update_size();
+    child.right = Some(node);
+    child.update_size();
+
+    child
+}
+
+/// 更新子树大小，失衡时通过旋转恢复平衡，返回新的子树根结点
+fn balance<T>(mut node: Box<WbtNode<T>>) -> Box<WbtNode<T>> {
+    node.update_size();
+    let (left, right) = (weight(&node.left), weight(&node.right));
+
+    if right > DELTA * left {
+        // Safety: 右子树的重量大于 Δ，一定不为空
+        let child = node.right.as_ref().unwrap();
+        if weight(&child.left) >= GAMMA * weight(&child.right) {
+            // 右子树的左侧过重，先右旋右子树
+            node.right = node.right.take().map(rotate_right);
+        }
+        rotate_left(node)
+    } else if left > DELTA * right {
+        // Safety: 同上
+        let child = node.left.as_ref().unwrap();
+        if weight(&child.right) >= GAMMA * weight(&child.left) {
+            node.left = node.left.take().map(rotate_left);
+        }
+        rotate_right(node)
+    } else {
+        node
+    }
+}
+
+fn rebalance<T>(link: &mut Link<T>) {
+    *link = link.take().map(balance);
+}
+
+fn insert<T: Ord>(link: &mut Link<T>, value: T) -> bool {
+    let Some(node) = link else {
+        *link = Some(WbtNode::new(value));
+        return true;
+    };
+
+    let inserted = match value.cmp(&node.value) {
+        Ordering::Less => insert(&mut node.left, value),
+        Ordering::Equal => false,
+        Ordering::Greater => insert(&mut node.right, value),
+    };
+    if inserted {
+        rebalance(link);
+    }
+
+    inserted
+}
+
+fn remove<T: Ord>(link: &mut Link<T>, value: &T) -> bool {
+    let Some(node) = link else {
+        return false;
+    };
+
+    let removed = match value.cmp(&node.value) {
+        Ordering::Less => remove(&mut node.left, value),
+        Ordering::Greater => remove(&mut node.right, value),
+        Ordering::Equal => {
+            *link = match (node.left.take(), node.right.take()) {
+                (None, child) | (child, None) => child,
+                // 用右子树的最小结点替换被删除的结点
+                (left, Some(right)) => {
+                    let (mut min, rest) = remove_min(right);
+                    min.left = left;
+                    min.right = rest;
+                    Some(balance(min))
+                }
+            };
+            return true;
+        }
+    };
+    if removed {
+        rebalance(link);
+    }
+
+    removed
+}
+
+/// 从子树中取出最小的结点，返回 (最小结点, 剩余的子树)
+fn remove_min<T>(mut node: Box<WbtNode<T>>) -> (Box<WbtNode<T>>, Link<T>) {
+    match node.left.take() {
+        None => {
+            let rest = node.right.take();
+            (node, rest)
+        }
+        Some(left) => {
+            let (min, rest) = remove_min(left);
+            node.left = rest;
+            (min, Some(balance(node)))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use super::*;
+    use crate::generate::{self, Operation};
+
+    /// 检查每个结点的子树大小和平衡条件，返回子树的高度
+    fn check<T: Ord>(link: &Link<T>) -> usize {
+        let Some(node) = link else {
+            return 0;
+        };
+
+        let (left, right) = (weight(&node.left), weight(&node.right));
+        assert!(left * DELTA >= right && right * DELTA >= left);
+        assert_eq!(node.size, left + right - 1);
+        assert!(node.left.as_ref().is_none_or(|l| l.value < node.value));
+        assert!(node.right.as_ref().is_none_or(|r| r.value > node.value));
+
+        check(&node.left).max(check(&node.right)) + 1
+    }
+
+    #[test]
+    fn weight_balanced_tree_should_match_btree_set() {
+        let mut tree = WeightBalancedTree::new();
+        let mut expected = BTreeSet::new();
+
+        for op in generate::operations(5000, 500, 3) {
+            match op {
+                Operation::Insert(key) => assert_eq!(tree.insert(key), expected.insert(key)),
+                Operation::Remove(key) => assert_eq!(tree.remove(&key), expected.remove(&key)),
+                Operation::Get(key) => {
+                    assert_eq!(tree.contains(&key), expected.contains(&key));
+                    assert_eq!(tree.rank(&key), expected.range(..key).count());
+                }
+            }
+            assert_eq!(tree.len(), expected.len());
+        }
+
+        check(&tree.root);
+        assert!(tree.iter().eq(expected.iter()));
+        for (k, value) in expected.iter().enumerate() {
+            assert_eq!(tree.select(k), Some(value));
+        }
+        assert_eq!(tree.select(expected.len()), None);
+    }
+
+    #[test]
+    fn weight_balanced_tree_should_stay_balanced() {
+        // 有序插入会使普通二叉搜索树退化为链表
+        let mut tree = (0..4096).collect::<WeightBalancedTree<_>>();
+        let height = check(&tree.root);
+        // 重量比不超过 3，高度不超过 log_{4/3}(n + 1)
+        assert!(height <= 29, "height: {height}");
+
+        // 删除一侧的所有元素
+        for key in 0..3000 {
+            assert!(tree.remove(&key));
+        }
+        check(&tree.root);
+        assert!(tree.iter().copied().eq(3000..4096));
+        assert!(!tree.remove(&0));
+
+        let tree = WeightBalancedTree::<i32>::new();
+        assert!(tree.is_empty());
+        assert_eq!(tree.iter().next(), None);
+        assert_eq!(tree.select(0), None);
+    }
+}